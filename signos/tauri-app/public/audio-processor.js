@@ -2,16 +2,39 @@
 // Runs in a separate thread for better performance
 
 class PCMProcessor extends AudioWorkletProcessor {
-    constructor() {
+    constructor(options) {
         super();
+        // Raw mode forwards interleaved Float32 frames of every channel and
+        // leaves downmixing and resampling to the backend (`convert_audio`)
+        this.raw = options?.processorOptions?.raw ?? false;
+        this.channels = options?.processorOptions?.channels ?? 1;
         this.bufferSize = 4096; // Process in chunks
-        this.buffer = new Float32Array(this.bufferSize);
+        if (!this.raw) {
+            this.bufferSize = options?.processorOptions?.chunkSamples ?? this.bufferSize;
+        }
+        this.buffer = new Float32Array(this.bufferSize * (this.raw ? this.channels : 1));
         this.bufferIndex = 0;
+
+        // Chunk size follows the inference parameters; raw mode is chunked by the backend
+        this.port.onmessage = (event) => {
+            const chunkSamples = event.data?.chunkSamples;
+            if (!this.raw && chunkSamples && chunkSamples !== this.bufferSize) {
+                this.sendPCMData(this.bufferIndex);
+                this.bufferSize = chunkSamples;
+                this.buffer = new Float32Array(this.bufferSize);
+                this.bufferIndex = 0;
+            }
+        };
     }
 
     process(inputs, outputs, parameters) {
         const input = inputs[0];
 
+        if (this.raw) {
+            this.bufferRaw(input);
+            return true;
+        }
+
         if (input.length > 0) {
             const channelData = input[0]; // Mono channel
 
@@ -30,11 +53,36 @@ class PCMProcessor extends AudioWorkletProcessor {
         return true;
     }
 
-    sendPCMData() {
+    bufferRaw(input) {
+        if (input.length === 0) {
+            return;
+        }
+
+        const frames = input[0].length;
+        for (let i = 0; i < frames; i++) {
+            for (let channel = 0; channel < this.channels; channel++) {
+                // Missing channels are sent as silence to keep the layout stable
+                const channelData = input[channel];
+                this.buffer[this.bufferIndex++] = channelData ? channelData[i] : 0;
+            }
+
+            if (this.bufferIndex >= this.buffer.length) {
+                const frameData = this.buffer.slice();
+                this.port.postMessage(frameData.buffer, [frameData.buffer]);
+                this.bufferIndex = 0;
+            }
+        }
+    }
+
+    sendPCMData(length = this.bufferSize) {
+        if (length === 0) {
+            return;
+        }
+
         // Convert Float32 samples (-1.0 to 1.0) to Int16 PCM
-        const pcmData = new Int16Array(this.bufferSize);
+        const pcmData = new Int16Array(length);
 
-        for (let i = 0; i < this.bufferSize; i++) {
+        for (let i = 0; i < length; i++) {
             // Clamp to -1.0 to 1.0 range and convert to 16-bit integer
             const sample = Math.max(-1, Math.min(1, this.buffer[i]));
             pcmData[i] = sample < 0 ? sample * 0x8000 : sample * 0x7FFF;
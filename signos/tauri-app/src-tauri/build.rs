@@ -1,3 +1,12 @@
 fn main() {
+    // Lets the Android overlay draw over other apps (see `overlay.rs`). Only
+    // runs when the Android project is being built.
+    tauri_utils::build::update_android_manifest(
+        "signos-overlay",
+        "manifest",
+        r#"<uses-permission android:name="android.permission.SYSTEM_ALERT_WINDOW" />"#.into(),
+    )
+    .expect("failed to update the Android manifest");
+
     tauri_build::build()
 }
@@ -0,0 +1,203 @@
+//! System accessibility preferences the overlay adapts to.
+//!
+//! The webview only exposes some of these through media queries, and not
+//! consistently across platforms, so the backend reads them from the OS and
+//! emits `accessibility:changed` when the user flips one.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityPrefs {
+    pub increase_contrast: bool,
+    pub reduce_motion: bool,
+    pub reduce_transparency: bool,
+    /// System text size relative to the default, e.g. 1.5 for 150%.
+    pub text_scale: f64,
+}
+
+impl Default for AccessibilityPrefs {
+    fn default() -> Self {
+        Self {
+            increase_contrast: false,
+            reduce_motion: false,
+            reduce_transparency: false,
+            text_scale: 1.0,
+        }
+    }
+}
+
+/// Last preferences seen by the watcher.
+#[derive(Default)]
+pub struct AccessibilityState(Mutex<AccessibilityPrefs>);
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::NSWorkspace;
+
+    use super::AccessibilityPrefs;
+
+    /// macOS has no system-wide text size, so `text_scale` stays at 1.0.
+    pub fn read() -> AccessibilityPrefs {
+        let workspace = NSWorkspace::sharedWorkspace();
+        AccessibilityPrefs {
+            increase_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+            reduce_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+            reduce_transparency: workspace.accessibilityDisplayShouldReduceTransparency(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::BOOL;
+    use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+    use windows::UI::ViewManagement::UISettings;
+
+    use super::AccessibilityPrefs;
+
+    fn high_contrast() -> bool {
+        let mut contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        let result = unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                contrast.cbSize,
+                Some(&mut contrast as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        };
+        result.is_ok() && contrast.dwFlags.contains(HCF_HIGHCONTRASTON)
+    }
+
+    /// "Show animations in Windows" in Settings > Accessibility > Visual effects.
+    fn animations() -> bool {
+        let mut enabled = BOOL(1);
+        let result = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut enabled as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        };
+        result.is_err() || enabled.as_bool()
+    }
+
+    pub fn read() -> AccessibilityPrefs {
+        let settings = UISettings::new().ok();
+        AccessibilityPrefs {
+            increase_contrast: high_contrast(),
+            reduce_motion: !animations(),
+            reduce_transparency: settings
+                .as_ref()
+                .and_then(|s| s.AdvancedEffectsEnabled().ok())
+                .is_some_and(|enabled| !enabled),
+            text_scale: settings
+                .as_ref()
+                .and_then(|s| s.TextScaleFactor().ok())
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+    use objc2_ui_kit::{
+        UIAccessibilityDarkerSystemColorsEnabled, UIAccessibilityIsReduceMotionEnabled,
+        UIAccessibilityIsReduceTransparencyEnabled,
+    };
+
+    use super::AccessibilityPrefs;
+
+    /// Dynamic Type is applied by WKWebView itself, so `text_scale` stays at 1.0.
+    pub fn read() -> AccessibilityPrefs {
+        AccessibilityPrefs {
+            increase_contrast: UIAccessibilityDarkerSystemColorsEnabled(),
+            reduce_motion: UIAccessibilityIsReduceMotionEnabled(),
+            reduce_transparency: UIAccessibilityIsReduceTransparencyEnabled(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::process::Command;
+
+    use super::AccessibilityPrefs;
+
+    fn gsettings(schema: &str, key: &str) -> Option<String> {
+        let output = Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// GNOME settings; other desktops report the defaults.
+    pub fn read() -> AccessibilityPrefs {
+        AccessibilityPrefs {
+            increase_contrast: gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+                .is_some_and(|value| value == "true"),
+            reduce_motion: gsettings("org.gnome.desktop.interface", "enable-animations")
+                .is_some_and(|value| value == "false"),
+            reduce_transparency: false,
+            text_scale: gsettings("org.gnome.desktop.interface", "text-scaling-factor")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+    use super::AccessibilityPrefs;
+
+    pub fn read() -> AccessibilityPrefs {
+        AccessibilityPrefs::default()
+    }
+}
+
+/// Starts the watcher that emits `accessibility:changed` when a preference changes.
+pub fn init(app: &AppHandle) {
+    app.manage(AccessibilityState(Mutex::new(platform::read())));
+    let app = app.clone();
+
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let prefs = platform::read();
+        let changed = {
+            let state = app.state::<AccessibilityState>();
+            let mut current = state.0.lock().unwrap();
+            std::mem::replace(&mut *current, prefs) != prefs
+        };
+        if changed {
+            println!("[Accessibility] Preferences changed: {:?}", prefs);
+            let _ = app.emit("accessibility:changed", prefs);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_accessibility_prefs(app: AppHandle) -> AccessibilityPrefs {
+    *app.state::<AccessibilityState>().0.lock().unwrap()
+}
@@ -0,0 +1,153 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::captions::Caption;
+use crate::recording::{self, Segment};
+
+/// Phrases that usually open a commitment or a request, in English and Spanish.
+const CUES: [&str; 28] = [
+    "i will ",
+    "i'll ",
+    "we will ",
+    "we'll ",
+    "need to ",
+    "let's ",
+    "can you ",
+    "could you ",
+    "please ",
+    "make sure ",
+    "don't forget ",
+    "action item",
+    "todo",
+    "to do:",
+    "follow up",
+    "voy a ",
+    "vamos a ",
+    "hay que ",
+    "tengo que ",
+    "tenemos que ",
+    "necesito ",
+    "necesitamos ",
+    "puedes ",
+    "podrías ",
+    "por favor ",
+    "no olvides ",
+    "acuérdate de ",
+    "pendiente",
+];
+
+/// Deadlines mentioned alongside an item ("by Friday", "para el lunes").
+const DUE_MARKERS: [&str; 6] = [
+    "by ",
+    "before ",
+    "until ",
+    "para el ",
+    "para ",
+    "antes del ",
+];
+const DUE_WORDS: [&str; 24] = [
+    "today",
+    "tonight",
+    "tomorrow",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+    "next week",
+    "end of day",
+    "eod",
+    "hoy",
+    "mañana",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+    "domingo",
+    "la próxima semana",
+    "fin de mes",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionItem {
+    pub text: String,
+    /// Position in the session audio, when taken from a stored session.
+    pub offset_ms: Option<u64>,
+    /// Unix timestamp (ms) of the caption.
+    pub timestamp: u64,
+    pub speaker: Option<u32>,
+    pub source: Option<String>,
+    /// Deadline as spoken, e.g. "friday".
+    pub due: Option<String>,
+}
+
+fn due_date(text: &str) -> Option<String> {
+    DUE_MARKERS.iter().find_map(|marker| {
+        text.match_indices(marker).find_map(|(index, _)| {
+            let rest = &text[index + marker.len()..];
+            DUE_WORDS
+                .iter()
+                .find(|word| rest.starts_with(*word))
+                .map(|word| word.to_string())
+        })
+    })
+}
+
+/// The action item in `text`, if it has one.
+fn extract(text: &str) -> Option<(String, Option<String>)> {
+    let lower = format!("{} ", text.trim().to_lowercase());
+    // Filler such as "let's see" is not a task.
+    if lower.starts_with("let's see") || lower.starts_with("vamos a ver") {
+        return None;
+    }
+    if !CUES.iter().any(|cue| lower.contains(cue)) {
+        return None;
+    }
+    Some((text.trim().to_string(), due_date(&lower)))
+}
+
+fn from_segment(segment: &Segment) -> Option<ActionItem> {
+    let (text, due) = extract(&segment.text)?;
+    Some(ActionItem {
+        text,
+        offset_ms: Some(segment.offset_ms),
+        timestamp: segment.timestamp,
+        speaker: segment.speaker,
+        source: segment.source.clone(),
+        due,
+    })
+}
+
+/// Emits `actions:item` for a final caption that reads like an action item.
+pub fn check(app: &AppHandle, caption: &Caption) {
+    if !caption.is_final {
+        return;
+    }
+    let Some((text, due)) = extract(&caption.text) else {
+        return;
+    };
+
+    let _ = app.emit(
+        "actions:item",
+        ActionItem {
+            text,
+            offset_ms: None,
+            timestamp: caption.timestamp,
+            speaker: caption.speaker,
+            source: caption.source.clone(),
+            due,
+        },
+    );
+}
+
+/// Action items found in a stored session's transcript, in spoken order.
+#[tauri::command]
+pub fn get_action_items(app: AppHandle, session_id: String) -> Result<Vec<ActionItem>, String> {
+    let session = recording::load_session(&app, &session_id)?;
+    Ok(session.segments.iter().filter_map(from_segment).collect())
+}
@@ -0,0 +1,216 @@
+//! Screen reader announcements (VoiceOver, NVDA, Narrator) for important events.
+//!
+//! The panel never takes focus, so screen readers don't notice changes in it
+//! on their own. Announcements are posted against the app instead and spoken
+//! without moving the user's focus.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener};
+
+use crate::capture::CaptureStatus;
+use crate::keywords::KeywordHit;
+use crate::settings;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AnnouncementSettings {
+    pub enabled: bool,
+    /// Announce when capture starts and stops.
+    pub capture: bool,
+    pub keywords: bool,
+}
+
+impl Default for AnnouncementSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capture: true,
+            keywords: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    #[default]
+    Normal,
+    /// Interrupts whatever the screen reader is currently saying.
+    High,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::runtime::AnyObject;
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::{
+        NSAccessibilityAnnouncementKey, NSAccessibilityAnnouncementRequestedNotification,
+        NSAccessibilityPostNotificationWithUserInfo, NSAccessibilityPriorityKey,
+        NSAccessibilityPriorityLevel, NSApplication,
+    };
+    use objc2_foundation::{NSDictionary, NSNumber, NSString};
+    use tauri::AppHandle;
+
+    use super::Priority;
+
+    pub fn announce(app: &AppHandle, message: String, priority: Priority) -> Result<(), String> {
+        app.run_on_main_thread(move || {
+            let Some(mtm) = MainThreadMarker::new() else {
+                return;
+            };
+            let level = match priority {
+                Priority::Normal => NSAccessibilityPriorityLevel::Medium,
+                Priority::High => NSAccessibilityPriorityLevel::High,
+            };
+
+            let text = NSString::from_str(&message);
+            let level = NSNumber::new_isize(level.0);
+            let objects: [&AnyObject; 2] = [&text, &level];
+            let info = unsafe {
+                NSDictionary::from_slices(
+                    &[NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey],
+                    &objects,
+                )
+            };
+
+            let application = NSApplication::sharedApplication(mtm);
+            unsafe {
+                NSAccessibilityPostNotificationWithUserInfo(
+                    &application,
+                    NSAccessibilityAnnouncementRequestedNotification,
+                    Some(&info),
+                )
+            };
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use tauri::{AppHandle, Manager};
+    use windows::core::BSTR;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Accessibility::{
+        NotificationKind_Other, NotificationProcessing_ImportantMostRecent,
+        NotificationProcessing_MostRecent, UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+    };
+
+    use super::Priority;
+    use crate::panel::MAIN_WINDOW;
+
+    /// Raised on the panel's host provider; NVDA and Narrator speak UIA
+    /// notifications from any window, focused or not.
+    pub fn announce(app: &AppHandle, message: String, priority: Priority) -> Result<(), String> {
+        let window = app
+            .get_webview_window(MAIN_WINDOW)
+            .ok_or("Main window not found")?;
+        let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+        let processing = match priority {
+            Priority::Normal => NotificationProcessing_MostRecent,
+            Priority::High => NotificationProcessing_ImportantMostRecent,
+        };
+
+        unsafe {
+            let provider = UiaHostProviderFromHwnd(hwnd).map_err(|e| e.to_string())?;
+            UiaRaiseNotificationEvent(
+                &provider,
+                NotificationKind_Other,
+                processing,
+                &BSTR::from(message),
+                &BSTR::from("Signos"),
+            )
+            .map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+    use objc2_foundation::NSString;
+    use objc2_ui_kit::{UIAccessibilityAnnouncementNotification, UIAccessibilityPostNotification};
+    use tauri::AppHandle;
+
+    use super::Priority;
+
+    pub fn announce(app: &AppHandle, message: String, _priority: Priority) -> Result<(), String> {
+        app.run_on_main_thread(move || {
+            let text = NSString::from_str(&message);
+            unsafe {
+                UIAccessibilityPostNotification(
+                    UIAccessibilityAnnouncementNotification,
+                    Some(&text),
+                )
+            };
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "ios")))]
+mod platform {
+    use tauri::AppHandle;
+
+    use super::Priority;
+
+    /// No screen reader bridge here; announcements are dropped.
+    pub fn announce(_app: &AppHandle, _message: String, _priority: Priority) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Speaks `message` through the active screen reader, if announcements are on.
+pub fn announce(app: &AppHandle, message: &str, priority: Priority) {
+    if !settings::get(app).announcements.enabled {
+        return;
+    }
+    if let Err(e) = platform::announce(app, message.to_string(), priority) {
+        eprintln!("[Announce] Failed to announce '{}': {}", message, e);
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    // Only transitions are announced, not every pause/resume.
+    let was_active = Mutex::new(false);
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let Ok(status) = serde_json::from_str::<CaptureStatus>(event.payload()) else {
+            return;
+        };
+        let mut was_active = was_active.lock().unwrap();
+        if status.active == *was_active {
+            return;
+        }
+        *was_active = status.active;
+
+        if settings::get(&handle).announcements.capture {
+            let message = match status.active {
+                true => "Captions started",
+                false => "Captions stopped",
+            };
+            announce(&handle, message, Priority::Normal);
+        }
+    });
+
+    let handle = app.clone();
+    app.listen_any("keyword:hit", move |event| {
+        let Ok(hit) = serde_json::from_str::<KeywordHit>(event.payload()) else {
+            return;
+        };
+        if settings::get(&handle).announcements.keywords {
+            announce(
+                &handle,
+                &format!("Keyword {}: {}", hit.keyword, hit.text),
+                Priority::High,
+            );
+        }
+    });
+}
+
+/// Lets the frontend announce its own events, e.g. errors shown in the panel.
+#[tauri::command]
+pub fn announce_message(app: AppHandle, message: String, priority: Option<Priority>) {
+    announce(&app, &message, priority.unwrap_or_default());
+}
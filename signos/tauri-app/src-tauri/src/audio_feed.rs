@@ -0,0 +1,260 @@
+//! A WAV or FLAC file played through the pipeline in place of the microphone.
+//!
+//! `feed_audio_file` queues the file and starts capture; the frontend sees the
+//! pending feed and calls `start_audio_feed` instead of opening a device. The
+//! file is paced like a live input (optionally sped up) so the worker and the
+//! overlay behave as they would in a real session.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use hound::{SampleFormat, WavReader};
+use serde::Serialize;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::capture_format::{Resampler, TARGET_SAMPLE_RATE};
+use crate::{capture, security, transcription};
+
+/// Matches the webview's audio worklet, so chunks arrive at the same cadence.
+const BUFFER_FRAMES: usize = 1024;
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 8.0;
+
+/// Decoded file, interleaved in -1.0..1.0.
+pub struct Decoded {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioFileInfo {
+    pub path: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_ms: u64,
+    pub speed: f64,
+}
+
+struct Feed {
+    info: AudioFileInfo,
+    audio: Decoded,
+}
+
+#[derive(Default)]
+pub struct AudioFeedState {
+    pending: Mutex<Option<Feed>>,
+    /// Set to stop the feed that is playing.
+    playing: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl Decoded {
+    fn duration_ms(&self) -> u64 {
+        let frames = self.samples.len() as u64 / self.channels.max(1) as u64;
+        frames * 1000 / self.sample_rate.max(1) as u64
+    }
+}
+
+fn decode_wav(path: &Path) -> Result<Decoded, String> {
+    let mut reader = WavReader::open(path).map_err(|e| e.to_string())?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?,
+        SampleFormat::Int => {
+            let scale = (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    Ok(Decoded {
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+        samples,
+    })
+}
+
+fn decode_flac(path: &Path) -> Result<Decoded, String> {
+    let mut reader = claxon::FlacReader::open(path).map_err(|e| e.to_string())?;
+    let info = reader.streaminfo();
+    let scale = (1u64 << (info.bits_per_sample - 1)) as f32;
+
+    let samples = reader
+        .samples()
+        .map(|sample| sample.map(|s| s as f32 / scale))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(Decoded {
+        sample_rate: info.sample_rate,
+        channels: info.channels as u16,
+        samples,
+    })
+}
+
+/// Reads a whole WAV or FLAC file, picked by extension.
+pub fn decode(path: &Path) -> Result<Decoded, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase);
+
+    let decoded = match extension.as_deref() {
+        Some("wav") | Some("wave") => decode_wav(path),
+        Some("flac") => decode_flac(path),
+        _ => return Err("Only WAV and FLAC files are supported".into()),
+    }
+    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    if decoded.samples.is_empty() {
+        return Err(format!("{} has no audio", path.display()));
+    }
+    Ok(decoded)
+}
+
+fn play(
+    app: &AppHandle,
+    feed: Feed,
+    channel: Channel<InvokeResponseBody>,
+    stopped: &AtomicBool,
+) -> Result<(), String> {
+    let chunk_samples = transcription::inference(app).chunk_samples();
+    let channels = feed.audio.channels.max(1);
+    let mut resampler = Resampler::new(
+        feed.audio.sample_rate as f64,
+        channels,
+        u32::MAX,
+        chunk_samples,
+    );
+
+    // Output chunks are due at fixed offsets from the start, so sleep jitter
+    // doesn't accumulate over a long file.
+    let chunk_duration =
+        Duration::from_secs_f64(chunk_samples as f64 / TARGET_SAMPLE_RATE as f64 / feed.info.speed);
+    let started = Instant::now();
+    let mut sent = 0u32;
+
+    for buffer in feed.audio.samples.chunks(BUFFER_FRAMES * channels as usize) {
+        for chunk in resampler.push(buffer) {
+            if stopped.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            if let Some(wait) = (chunk_duration * sent).checked_sub(started.elapsed()) {
+                thread::sleep(wait);
+            }
+            channel
+                .send(InvokeResponseBody::Raw(chunk))
+                .map_err(|e| e.to_string())?;
+            sent += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Queues `path` as the capture input and starts capture. `speed` defaults to real time.
+#[tauri::command]
+pub fn feed_audio_file(
+    app: AppHandle,
+    webview: Webview,
+    path: String,
+    speed: Option<f64>,
+) -> Result<AudioFileInfo, String> {
+    security::guard(&webview, "feed_audio_file", Some(&path))?;
+    if capture::status(&app).active {
+        return Err("Stop capture before feeding an audio file".into());
+    }
+
+    let audio = decode(&PathBuf::from(&path))?;
+    let info = AudioFileInfo {
+        path,
+        sample_rate: audio.sample_rate,
+        channels: audio.channels,
+        duration_ms: audio.duration_ms(),
+        speed: speed.unwrap_or(1.0).clamp(MIN_SPEED, MAX_SPEED),
+    };
+
+    *app.state::<AudioFeedState>().pending.lock().unwrap() = Some(Feed {
+        info: info.clone(),
+        audio,
+    });
+    capture::request_start(&app).map_err(|e| e.to_string())?;
+
+    println!(
+        "[AudioFeed] Queued {} ({} ms at {}x)",
+        info.path, info.duration_ms, info.speed
+    );
+    Ok(info)
+}
+
+/// Whether the next capture should read from a queued file instead of a device.
+#[tauri::command]
+pub fn audio_feed_pending(app: AppHandle) -> bool {
+    app.state::<AudioFeedState>()
+        .pending
+        .lock()
+        .unwrap()
+        .is_some()
+}
+
+/// Streams the queued file to `on_audio` as 16 kHz Int16 PCM chunks, then stops
+/// capture (`audio-feed:finished`).
+#[tauri::command]
+pub fn start_audio_feed(
+    app: AppHandle,
+    on_audio: Channel<InvokeResponseBody>,
+) -> Result<AudioFileInfo, String> {
+    let state = app.state::<AudioFeedState>();
+    let feed = state
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No audio file is queued")?;
+    let info = feed.info.clone();
+    let finished = info.clone();
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = state.playing.lock().unwrap().replace(stopped.clone()) {
+        previous.store(true, Ordering::Relaxed);
+    }
+
+    let handle = app.clone();
+    thread::spawn(move || {
+        if let Err(e) = play(&handle, feed, on_audio, &stopped) {
+            eprintln!("[AudioFeed] Playback failed: {}", e);
+        }
+        if stopped.load(Ordering::Relaxed) {
+            return;
+        }
+
+        println!("[AudioFeed] Finished {}", finished.path);
+        let _ = handle.emit("audio-feed:finished", &finished);
+        if let Err(e) = capture::request_stop(&handle) {
+            eprintln!("[AudioFeed] Failed to stop capture: {}", e);
+        }
+    });
+
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn stop_audio_feed(app: AppHandle) {
+    let state = app.state::<AudioFeedState>();
+    state.pending.lock().unwrap().take();
+    let playing = state.playing.lock().unwrap().take();
+    if let Some(stopped) = playing {
+        stopped.store(true, Ordering::Relaxed);
+    }
+}
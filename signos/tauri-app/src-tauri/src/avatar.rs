@@ -0,0 +1,253 @@
+//! Turns final captions into a gloss stream that drives the signing avatar.
+//!
+//! Text is translated by a [`GlossModel`] on a background thread and streamed
+//! to every frontend channel registered with `subscribe_avatar`, one
+//! [`AvatarFrame`] per sign.
+
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::llm::{self, Completion};
+use crate::usage::{self, Integration};
+use crate::{profiles, proxy, settings};
+
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(15);
+/// Function words dropped by the local model, as sign languages rarely sign them.
+const SKIPPED_WORDS: [&str; 24] = [
+    "the", "a", "an", "of", "to", "is", "are", "am", "be", "el", "la", "los", "las", "un", "una",
+    "unos", "unas", "de", "del", "al", "que", "es", "son", "y",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AvatarModel {
+    /// The worker's sign matcher (`POST /signs`), which also returns pose keyframes.
+    #[default]
+    Worker,
+    /// On-device glossing of content words, without keyframes.
+    Local,
+    /// Glosses from the LLM provider selected in `llm`, without keyframes.
+    Llm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AvatarSettings {
+    pub enabled: bool,
+    pub model: AvatarModel,
+    /// How long the avatar holds each sign.
+    pub ms_per_gloss: u64,
+}
+
+impl Default for AvatarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: AvatarModel::default(),
+            ms_per_gloss: 800,
+        }
+    }
+}
+
+/// One sign for the avatar to perform.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarFrame {
+    /// Timestamp of the caption the sign belongs to.
+    pub caption_timestamp: u64,
+    /// Position of the sign in the caption, and how many signs it has.
+    pub index: usize,
+    pub count: usize,
+    pub gloss: String,
+    /// Offset from the start of the caption's sequence.
+    pub start_ms: u64,
+    pub duration_ms: u64,
+    /// Pose reference images in order, relative to the bundled `signs/` folder.
+    pub keyframes: Vec<String>,
+}
+
+/// A gloss produced by a model, before timing is assigned.
+pub struct Gloss {
+    pub gloss: String,
+    pub keyframes: Vec<String>,
+}
+
+/// Translates text into a sequence of glosses.
+pub trait GlossModel {
+    fn translate(&self, app: &AppHandle, text: &str) -> Result<Vec<Gloss>, String>;
+}
+
+struct WorkerModel;
+
+struct LocalModel;
+
+struct LlmModel;
+
+#[derive(Deserialize)]
+struct SignsResponse {
+    signs: Vec<SignInfo>,
+}
+
+#[derive(Deserialize)]
+struct SignInfo {
+    glosa: String,
+    images: Vec<ImagePath>,
+}
+
+#[derive(Deserialize)]
+struct ImagePath {
+    path: String,
+    sequence: u32,
+}
+
+impl GlossModel for WorkerModel {
+    fn translate(&self, app: &AppHandle, text: &str) -> Result<Vec<Gloss>, String> {
+        let agent: ureq::Agent = proxy::agent_config(app)
+            .timeout_global(Some(TRANSLATE_TIMEOUT))
+            .build()
+            .into();
+
+        let response: SignsResponse = usage::post_json(
+            app,
+            Integration::Signs,
+            &agent,
+            &profiles::worker_endpoint(app, "signs"),
+            &serde_json::json!({ "text": text }),
+        )?;
+
+        Ok(response
+            .signs
+            .into_iter()
+            .map(|mut sign| {
+                sign.images.sort_by_key(|image| image.sequence);
+                Gloss {
+                    gloss: sign.glosa,
+                    keyframes: sign.images.into_iter().map(|image| image.path).collect(),
+                }
+            })
+            .collect())
+    }
+}
+
+impl GlossModel for LocalModel {
+    fn translate(&self, _app: &AppHandle, text: &str) -> Result<Vec<Gloss>, String> {
+        Ok(text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .filter(|word| !SKIPPED_WORDS.contains(&word.to_lowercase().as_str()))
+            .map(|word| Gloss {
+                gloss: word.to_uppercase(),
+                keyframes: Vec::new(),
+            })
+            .collect())
+    }
+}
+
+impl GlossModel for LlmModel {
+    fn translate(&self, app: &AppHandle, text: &str) -> Result<Vec<Gloss>, String> {
+        let request = Completion {
+            system: "Translate the text into sign language glosses: the signs in signing order, \
+                     each written as one uppercase word, separated by spaces. Answer with the \
+                     glosses only."
+                .into(),
+            prompt: text.into(),
+            max_tokens: 200,
+            temperature: 0.0,
+        };
+        let glosses = llm::complete(app, "avatar", Integration::Signs, &request)?;
+
+        Ok(glosses
+            .split_whitespace()
+            .map(|gloss| gloss.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|gloss| !gloss.is_empty())
+            .map(|gloss| Gloss {
+                gloss: gloss.to_uppercase(),
+                keyframes: Vec::new(),
+            })
+            .collect())
+    }
+}
+
+fn model(kind: AvatarModel) -> Box<dyn GlossModel> {
+    match kind {
+        AvatarModel::Worker => Box::new(WorkerModel),
+        AvatarModel::Local => Box::new(LocalModel),
+        AvatarModel::Llm => Box::new(LlmModel),
+    }
+}
+
+pub struct AvatarState {
+    queue: Mutex<Sender<Caption>>,
+    subscribers: Mutex<Vec<Channel<AvatarFrame>>>,
+}
+
+fn stream(app: &AppHandle, caption: &Caption) -> Result<(), String> {
+    let config = settings::get(app).avatar;
+    let glosses = model(config.model).translate(app, &caption.text)?;
+    let count = glosses.len();
+
+    let state = app.state::<AvatarState>();
+    let mut subscribers = state.subscribers.lock().unwrap();
+    for (index, gloss) in glosses.into_iter().enumerate() {
+        let frame = AvatarFrame {
+            caption_timestamp: caption.timestamp,
+            index,
+            count,
+            gloss: gloss.gloss,
+            start_ms: index as u64 * config.ms_per_gloss,
+            duration_ms: config.ms_per_gloss,
+            keyframes: gloss.keyframes,
+        };
+        subscribers.retain(|channel| channel.send(frame.clone()).is_ok());
+    }
+    Ok(())
+}
+
+/// Queues a final caption for glossing while the avatar is enabled.
+pub fn on_caption(app: &AppHandle, caption: &Caption) {
+    if !caption.is_final || !settings::get(app).avatar.enabled {
+        return;
+    }
+    let Some(state) = app.try_state::<AvatarState>() else {
+        return;
+    };
+    if state.subscribers.lock().unwrap().is_empty() {
+        return;
+    }
+    let _ = state.queue.lock().unwrap().send(caption.clone());
+}
+
+/// Starts the thread that glosses queued captions in order.
+pub fn init(app: &AppHandle) {
+    let (tx, rx) = mpsc::channel::<Caption>();
+    app.manage(AvatarState {
+        queue: Mutex::new(tx),
+        subscribers: Mutex::default(),
+    });
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for caption in rx {
+            if let Err(e) = stream(&app, &caption) {
+                eprintln!("[Avatar] Failed to gloss caption: {}", e);
+            }
+        }
+    });
+}
+
+/// Registers a channel that receives an [`AvatarFrame`] for every sign.
+#[tauri::command]
+pub fn subscribe_avatar(app: AppHandle, on_frame: Channel<AvatarFrame>) {
+    app.state::<AvatarState>()
+        .subscribers
+        .lock()
+        .unwrap()
+        .push(on_frame);
+}
@@ -0,0 +1,450 @@
+//! Single-file backups of everything worth migrating to another machine:
+//! recorded sessions and the settings, which hold the profiles and their
+//! glossaries.
+//!
+//! A backup is a zip archive with a `manifest.json` listing every file with
+//! its size and SHA-256; restoring verifies the whole archive before
+//! anything is written. Credentials and pairings are left out, since the
+//! archive is meant to be copied elsewhere.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Webview};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::settings::{self, Settings};
+use crate::{fs_access, journal, recording, security, storage, sync};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SETTINGS_FILE: &str = "settings.json";
+const RECORDINGS_DIR: &str = "recordings";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    version: u32,
+    app_version: String,
+    /// Unix timestamp (ms).
+    created_at: u64,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestFile {
+    /// `/`-separated, relative to the data directory.
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSummary {
+    pub sessions: usize,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Copies `reader` into `writer`, returning the byte count and SHA-256.
+fn copy_hashed(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut size = 0;
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        size += read as u64;
+    }
+    Ok((size, sync::to_hex(&hasher.finalize())))
+}
+
+/// Only settings and files inside recorded sessions are ever read or written.
+fn is_allowed(path: &str) -> bool {
+    let relative = Path::new(path);
+    let normal = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    normal && (path == SETTINGS_FILE || relative.starts_with(RECORDINGS_DIR))
+}
+
+fn session_files(app: &AppHandle) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut files = Vec::new();
+    let Ok(sessions) = fs::read_dir(recording::recordings_dir(app)?) else {
+        return Ok(files);
+    };
+
+    for session in sessions.flatten().filter(|entry| entry.path().is_dir()) {
+        let id = session.file_name().to_string_lossy().to_string();
+        for file in fs::read_dir(session.path())
+            .map_err(|e| e.to_string())?
+            .flatten()
+        {
+            if file.path().is_file() {
+                let name = file.file_name().to_string_lossy().to_string();
+                files.push((format!("{}/{}/{}", RECORDINGS_DIR, id, name), file.path()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Clears everything `keep_local` brings back on restore: the keys, passwords
+/// and pairings, and what identifies this machine.
+fn strip_local(settings: &mut Settings) {
+    settings.llm.api_key.clear();
+    settings.mqtt.password = None;
+    settings.sync.device_id.clear();
+    settings.sync.device_name.clear();
+    settings.sync.paired.clear();
+    settings.relay.interpreters.clear();
+    settings.relay.clients.clear();
+    settings.fs_access.granted_folders.clear();
+}
+
+fn create(app: &AppHandle, path: &Path) -> Result<BackupSummary, String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let manifest = write_archive(
+        file,
+        settings::get(app),
+        session_files(app)?,
+        app.package_info().version.to_string(),
+    )?;
+    Ok(summarize(&manifest))
+}
+
+fn write_archive(
+    writer: impl Write + Seek,
+    mut settings: Settings,
+    sessions: Vec<(String, PathBuf)>,
+    app_version: String,
+) -> Result<Manifest, String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(writer);
+    let mut manifest = Manifest {
+        version: FORMAT_VERSION,
+        app_version,
+        created_at: chrono::Utc::now().timestamp_millis().max(0) as u64,
+        files: Vec::new(),
+    };
+
+    strip_local(&mut settings);
+    let settings = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+    zip.start_file(SETTINGS_FILE, options)
+        .map_err(|e| e.to_string())?;
+    let (size, sha256) =
+        copy_hashed(&mut settings.as_slice(), &mut zip).map_err(|e| e.to_string())?;
+    manifest.files.push(ManifestFile {
+        path: SETTINGS_FILE.into(),
+        size,
+        sha256,
+    });
+
+    for (name, source) in sessions {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| e.to_string())?;
+        let mut file = File::open(&source).map_err(|e| e.to_string())?;
+        let (size, sha256) = copy_hashed(&mut file, &mut zip).map_err(|e| e.to_string())?;
+        manifest.files.push(ManifestFile {
+            path: name,
+            size,
+            sha256,
+        });
+    }
+
+    zip.start_file(MANIFEST_FILE, options)
+        .map_err(|e| e.to_string())?;
+    serde_json::to_writer_pretty(&mut zip, &manifest).map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(manifest)
+}
+
+fn summarize(manifest: &Manifest) -> BackupSummary {
+    let sessions = manifest
+        .files
+        .iter()
+        .filter(|file| file.path.ends_with("/session.json"))
+        .count();
+    BackupSummary {
+        sessions,
+        files: manifest.files.len(),
+        bytes: manifest.files.iter().map(|file| file.size).sum(),
+    }
+}
+
+/// Checks the manifest against every file it lists.
+fn verify(archive: &mut ZipArchive<impl Read + Seek>) -> Result<Manifest, String> {
+    let manifest: Manifest = {
+        let file = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| "Not a Signos backup: manifest missing")?;
+        serde_json::from_reader(file).map_err(|e| format!("Invalid manifest: {}", e))?
+    };
+    if manifest.version != FORMAT_VERSION {
+        return Err(format!("Unsupported backup version {}", manifest.version));
+    }
+
+    for entry in &manifest.files {
+        if !is_allowed(&entry.path) {
+            return Err(format!(
+                "Backup contains an unexpected path '{}'",
+                entry.path
+            ));
+        }
+        let file = archive
+            .by_name(&entry.path)
+            .map_err(|_| format!("Backup is missing '{}'", entry.path))?;
+        // One byte past the manifest size is enough to tell it doesn't match.
+        let (size, sha256) = copy_hashed(&mut file.take(entry.size + 1), &mut io::sink())
+            .map_err(|e| e.to_string())?;
+        if size != entry.size || sha256 != entry.sha256 {
+            return Err(format!(
+                "Backup is corrupted: '{}' does not match",
+                entry.path
+            ));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Keeps what belongs to this machine rather than the one backed up: its
+/// credentials, its sync identity, the devices paired with it and the folders
+/// granted on it. Backups never contain these; see `strip_local`.
+fn keep_local(restored: &mut Settings, current: &Settings) {
+    restored.llm.api_key = current.llm.api_key.clone();
+    restored.mqtt.password = current.mqtt.password.clone();
+    restored.sync.device_id = current.sync.device_id.clone();
+    restored.sync.device_name = current.sync.device_name.clone();
+    restored.sync.paired = current.sync.paired.clone();
+    restored.relay.interpreters = current.relay.interpreters.clone();
+    restored.relay.clients = current.relay.clients.clone();
+    restored.fs_access.granted_folders = current.fs_access.granted_folders.clone();
+}
+
+fn restore(app: &AppHandle, path: &Path) -> Result<BackupSummary, String> {
+    let mut archive =
+        ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let manifest = verify(&mut archive)?;
+    let data_dir = storage::data_dir(app).map_err(|e| e.to_string())?;
+
+    let mut restored_settings = None;
+    for entry in &manifest.files {
+        let mut file = archive
+            .by_name(&entry.path)
+            .map_err(|e| e.to_string())?
+            .take(entry.size);
+
+        if entry.path == SETTINGS_FILE {
+            let settings: Settings =
+                serde_json::from_reader(file).map_err(|e| format!("Invalid settings: {}", e))?;
+            restored_settings = Some(settings);
+            continue;
+        }
+
+        let target = data_dir.join(&entry.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut output = File::create(&target).map_err(|e| e.to_string())?;
+        io::copy(&mut file, &mut output).map_err(|e| e.to_string())?;
+    }
+
+    // Sessions are merged in; the settings replace the current ones, undoably.
+    if let Some(mut settings) = restored_settings {
+        let previous = settings::get(app);
+        let written = settings::update(app, |current| {
+            keep_local(&mut settings, current);
+            *current = settings;
+        })?;
+        journal::record_settings(app, previous, written);
+    }
+
+    Ok(summarize(&manifest))
+}
+
+/// Writes a backup archive to `path`, usually picked with the save dialog.
+#[tauri::command]
+pub async fn create_backup(
+    app: AppHandle,
+    webview: Webview,
+    path: String,
+) -> Result<BackupSummary, String> {
+    security::guard(&webview, "create_backup", Some(&path))?;
+
+    let handle = app.clone();
+    let target = PathBuf::from(&path);
+    fs_access::check(&app, &target)?;
+    let summary = tauri::async_runtime::spawn_blocking(move || create(&handle, &target))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    println!(
+        "[Backup] Wrote {} files ({} sessions) to {}",
+        summary.files, summary.sessions, path
+    );
+    let _ = app.emit("backup:created", &summary);
+    Ok(summary)
+}
+
+/// Verifies and restores a backup. Sessions with the same id are overwritten.
+#[tauri::command]
+pub async fn restore_backup(
+    app: AppHandle,
+    webview: Webview,
+    path: String,
+) -> Result<BackupSummary, String> {
+    security::guard(&webview, "restore_backup", Some(&path))?;
+    if recording::is_recording(&app) {
+        return Err("Stop the recording before restoring a backup".into());
+    }
+
+    let handle = app.clone();
+    let source = PathBuf::from(&path);
+    fs_access::check(&app, &source)?;
+    let summary = tauri::async_runtime::spawn_blocking(move || restore(&handle, &source))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    println!(
+        "[Backup] Restored {} files ({} sessions) from {}",
+        summary.files, summary.sessions, path
+    );
+    let _ = app.emit("backup:restored", &summary);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn listed(path: &str, contents: &[u8]) -> ManifestFile {
+        let (size, sha256) = copy_hashed(&mut &contents[..], &mut io::sink()).unwrap();
+        ManifestFile {
+            path: path.into(),
+            size,
+            sha256,
+        }
+    }
+
+    /// An archive holding `entries` and, if given, a manifest listing `files`.
+    fn archive(
+        entries: &[(&str, &[u8])],
+        files: Option<Vec<ManifestFile>>,
+    ) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        if let Some(files) = files {
+            let manifest = Manifest {
+                version: FORMAT_VERSION,
+                app_version: "1.0.0".into(),
+                created_at: 0,
+                files,
+            };
+            zip.start_file(MANIFEST_FILE, SimpleFileOptions::default())
+                .unwrap();
+            serde_json::to_writer(&mut zip, &manifest).unwrap();
+        }
+        ZipArchive::new(zip.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn allows_only_settings_and_session_files() {
+        assert!(is_allowed("settings.json"));
+        assert!(is_allowed("recordings/20260101-090000/audio.wav"));
+        for path in [
+            "config.json",
+            "recordings-old/a/session.json",
+            "recordings/../settings.json",
+            "../settings.json",
+            "/recordings/a/session.json",
+            "./settings.json",
+        ] {
+            assert!(!is_allowed(path), "{} is allowed", path);
+        }
+    }
+
+    #[test]
+    fn verifies_an_archive_with_sessions() {
+        let dir = std::env::temp_dir().join(format!("signos-backup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let session = dir.join("session.json");
+        fs::write(&session, "{}").unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        let sessions = vec![("recordings/a/session.json".to_string(), session)];
+        write_archive(&mut buffer, Settings::default(), sessions, "1.0.0".into()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let manifest = verify(&mut ZipArchive::new(buffer).unwrap()).unwrap();
+        let summary = summarize(&manifest);
+        assert_eq!((summary.sessions, summary.files), (1, 2));
+    }
+
+    #[test]
+    fn rejects_files_that_do_not_match_the_manifest() {
+        let path = "recordings/a/session.json";
+        let mut changed = archive(&[(path, b"changed")], Some(vec![listed(path, b"original")]));
+        assert!(verify(&mut changed).unwrap_err().contains("corrupted"));
+
+        let mut missing = archive(&[], Some(vec![listed(path, b"original")]));
+        assert!(verify(&mut missing).unwrap_err().contains("missing"));
+    }
+
+    #[test]
+    fn rejects_unexpected_paths_and_archives_without_a_manifest() {
+        let path = "../settings.json";
+        let mut escaping = archive(&[(path, b"{}")], Some(vec![listed(path, b"{}")]));
+        assert!(verify(&mut escaping)
+            .unwrap_err()
+            .contains("unexpected path"));
+
+        let mut unlisted = archive(&[(SETTINGS_FILE, b"{}")], None);
+        assert!(verify(&mut unlisted)
+            .unwrap_err()
+            .contains("manifest missing"));
+    }
+
+    #[test]
+    fn leaves_credentials_out_of_the_archive() {
+        let mut settings = Settings::default();
+        settings.llm.api_key = "sk-very-secret".into();
+        settings.mqtt.password = Some("hunter2".into());
+        settings.sync.paired.push(sync::PairedDevice {
+            id: "laptop".into(),
+            name: "Laptop".into(),
+            key: "0badc0ffee".into(),
+        });
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_archive(&mut buffer, settings, Vec::new(), "1.0.0".into()).unwrap();
+        let mut archive = ZipArchive::new(buffer).unwrap();
+        verify(&mut archive).unwrap();
+
+        let mut json = String::new();
+        archive
+            .by_name(SETTINGS_FILE)
+            .unwrap()
+            .read_to_string(&mut json)
+            .unwrap();
+        for secret in ["sk-very-secret", "hunter2", "0badc0ffee"] {
+            assert!(!json.contains(secret), "archive contains {}", secret);
+        }
+        let restored: Settings = serde_json::from_str(&json).unwrap();
+        assert!(restored.sync.paired.is_empty());
+    }
+}
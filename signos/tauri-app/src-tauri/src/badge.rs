@@ -0,0 +1,146 @@
+use std::sync::Mutex;
+
+#[cfg(desktop)]
+use tauri::image::Image;
+use tauri::{AppHandle, Listener, Manager};
+
+#[cfg(desktop)]
+use crate::capture;
+use crate::capture::CaptureStatus;
+use crate::panel;
+#[cfg(desktop)]
+use crate::tray::TRAY_ID;
+
+/// Unread keyword alerts, shown on the tray icon and the dock/taskbar.
+/// While capturing, the tray icon also carries a capture dot.
+#[derive(Default)]
+pub struct BadgeState(Mutex<u32>);
+
+#[cfg(desktop)]
+#[derive(Clone, Copy)]
+enum Corner {
+    /// Unread alerts.
+    TopRight,
+    /// Capture in progress.
+    BottomLeft,
+}
+
+/// Draws a red dot in a corner of the app icon.
+#[cfg(desktop)]
+fn with_dot(icon: &Image<'_>, corner: Corner) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+
+    let radius = width.min(height) as f32 / 4.0;
+    let (cx, cy) = match corner {
+        Corner::TopRight => (width as f32 - radius, radius),
+        Corner::BottomLeft => (radius, height as f32 - radius),
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[0xE5, 0x39, 0x35, 0xFF]);
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
+
+// Mobile has neither a tray nor a window badge.
+#[cfg_attr(mobile, allow(unused_variables))]
+fn apply(app: &AppHandle, count: u32) {
+    #[cfg(desktop)]
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let label = (count > 0).then(|| count.to_string());
+        let capturing = capture::status(app).active;
+        let icon = app.default_window_icon().map(|icon| {
+            let icon = match capturing {
+                true => with_dot(icon, Corner::BottomLeft),
+                false => icon.clone(),
+            };
+            match count {
+                0 => icon,
+                _ => with_dot(&icon, Corner::TopRight),
+            }
+        });
+        let _ = tray.set_icon(icon);
+        // Only macOS shows a title next to the tray icon.
+        let _ = tray.set_title(label.as_deref());
+        let _ = tray.set_tooltip(Some(match (&label, capturing) {
+            (Some(count), true) => format!("Signos (capturing, {} unread)", count),
+            (Some(count), false) => format!("Signos ({} unread)", count),
+            (None, true) => "Signos (capturing)".into(),
+            (None, false) => "Signos".into(),
+        }));
+    }
+
+    // The app runs in Accessory mode on macOS, so there is no dock tile to badge.
+    #[cfg(all(desktop, not(target_os = "macos")))]
+    if let Some(window) = app.get_webview_window(panel::MAIN_WINDOW) {
+        #[cfg(target_os = "windows")]
+        let _ = window.set_overlay_icon(
+            (count > 0)
+                .then(|| {
+                    app.default_window_icon()
+                        .map(|icon| with_dot(icon, Corner::TopRight))
+                })
+                .flatten(),
+        );
+        #[cfg(not(target_os = "windows"))]
+        let _ = window.set_badge_count((count > 0).then_some(count as i64));
+    }
+}
+
+pub fn set_count(app: &AppHandle, count: u32) {
+    *app.state::<BadgeState>().0.lock().unwrap() = count;
+    apply(app, count);
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(BadgeState::default());
+
+    let handle = app.clone();
+    app.listen_any("keyword:hit", move |_| {
+        if panel::is_visible(&handle) {
+            return;
+        }
+        let count = {
+            let state = handle.state::<BadgeState>();
+            let mut count = state.0.lock().unwrap();
+            *count += 1;
+            *count
+        };
+        apply(&handle, count);
+    });
+
+    // Redraw the tray icon for the capture dot, only on start/stop.
+    let was_active = Mutex::new(false);
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let Ok(status) = serde_json::from_str::<CaptureStatus>(event.payload()) else {
+            return;
+        };
+        let mut was_active = was_active.lock().unwrap();
+        if status.active != *was_active {
+            *was_active = status.active;
+            apply(&handle, *handle.state::<BadgeState>().0.lock().unwrap());
+        }
+    });
+
+    // Alerts count as read once the panel is visible.
+    let handle = app.clone();
+    app.listen_any("panel:visibility", move |event| {
+        if event.payload() == "true" {
+            set_count(&handle, 0);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn set_badge_count(app: AppHandle, count: u32) {
+    set_count(&app, count);
+}
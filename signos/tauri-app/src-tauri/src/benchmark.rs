@@ -0,0 +1,420 @@
+//! Measures which transcription model and backend suit this machine.
+//!
+//! The self-test sample is transcribed once per requested combination. Each
+//! run reports its real-time factor (time to the last final result over the
+//! length of the audio), how much the app's resident memory grew while it ran,
+//! and how many of the expected words came back. Results are ranked best first
+//! so the settings UI can offer the top one as the recommendation.
+
+use std::cmp::Ordering as Order;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use hound::{SampleFormat, WavSpec};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::capture_format::TARGET_SAMPLE_RATE;
+use crate::selftest::{self, SAMPLE_LANGUAGE};
+use crate::transcription::{self, SUPPORTED_MODELS};
+use crate::usage::{self, Integration};
+use crate::{profiles, retranscribe};
+
+/// Silence sent after the sample so the last segment is finalized.
+const TRAILING_SILENCE_MS: usize = 2000;
+/// A stream with no message for this long has nothing more to say.
+const STREAM_IDLE: Duration = Duration::from_secs(3);
+const STREAM_TIMEOUT: Duration = Duration::from_secs(60);
+const MEMORY_POLL: Duration = Duration::from_millis(50);
+/// Combinations at or above this can't keep up with live audio.
+const REAL_TIME: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Backend {
+    /// The worker's WebSocket route, as used for live captions.
+    Stream,
+    /// The worker's batch route, as used for re-transcription. It always uses
+    /// its own model, so it is run once whatever models are requested.
+    Batch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Combination {
+    /// `None` for the batch backend.
+    pub model: Option<String>,
+    pub backend: Backend,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResult {
+    #[serde(flatten)]
+    pub combination: Combination,
+    /// Processing time over audio length; below 1 keeps up with live audio.
+    pub real_time_factor: f64,
+    /// Peak growth of the app's resident memory during the run, where the
+    /// platform reports it.
+    pub memory_bytes: Option<u64>,
+    /// Share of the expected words in the transcript.
+    pub accuracy: f64,
+    pub transcript: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub audio_seconds: f64,
+    /// Best first; failed runs last.
+    pub results: Vec<BenchmarkResult>,
+    pub recommended: Option<Combination>,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+    const MACH_TASK_BASIC_INFO: u32 = 20;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct MachTaskBasicInfo {
+        virtual_size: u64,
+        resident_size: u64,
+        resident_size_max: u64,
+        user_time: [i32; 2],
+        system_time: [i32; 2],
+        policy: i32,
+        suspend_count: i32,
+    }
+
+    extern "C" {
+        static mach_task_self_: u32;
+        fn task_info(task: u32, flavor: u32, info: *mut MachTaskBasicInfo, count: *mut u32) -> i32;
+    }
+
+    pub fn resident_bytes() -> Option<u64> {
+        let mut info = MachTaskBasicInfo::default();
+        let mut count = (size_of::<MachTaskBasicInfo>() / size_of::<u32>()) as u32;
+        let status =
+            unsafe { task_info(mach_task_self_, MACH_TASK_BASIC_INFO, &mut info, &mut count) };
+        (status == 0).then_some(info.resident_size)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::GetCurrentProcess;
+
+    pub fn resident_bytes() -> Option<u64> {
+        let mut counters = PROCESS_MEMORY_COUNTERS::default();
+        unsafe {
+            GetProcessMemoryInfo(
+                GetCurrentProcess(),
+                &mut counters,
+                size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            )
+        }
+        .ok()
+        .map(|_| counters.WorkingSetSize as u64)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+    pub fn resident_bytes() -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+        let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kb * 1024)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android"
+)))]
+mod platform {
+    pub fn resident_bytes() -> Option<u64> {
+        None
+    }
+}
+
+/// Samples resident memory in the background and keeps the highest reading.
+struct MemoryProbe {
+    baseline: Option<u64>,
+    peak: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    thread: thread::JoinHandle<()>,
+}
+
+impl MemoryProbe {
+    fn start() -> Self {
+        let baseline = platform::resident_bytes();
+        let peak = Arc::new(AtomicU64::new(baseline.unwrap_or(0)));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let peak = peak.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    if let Some(bytes) = platform::resident_bytes() {
+                        peak.fetch_max(bytes, Ordering::SeqCst);
+                    }
+                    thread::sleep(MEMORY_POLL);
+                }
+            })
+        };
+        Self {
+            baseline,
+            peak,
+            stop,
+            thread,
+        }
+    }
+
+    /// Growth over the baseline, in bytes.
+    fn finish(self) -> Option<u64> {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.thread.join();
+        if let Some(bytes) = platform::resident_bytes() {
+            self.peak.fetch_max(bytes, Ordering::SeqCst);
+        }
+        self.baseline
+            .map(|baseline| self.peak.load(Ordering::SeqCst).saturating_sub(baseline))
+    }
+}
+
+fn set_read_timeout(socket: &WebSocket<MaybeTlsStream<TcpStream>>) -> std::io::Result<()> {
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream.set_read_timeout(Some(STREAM_IDLE)),
+        MaybeTlsStream::Rustls(stream) => stream.sock.set_read_timeout(Some(STREAM_IDLE)),
+        _ => Ok(()),
+    }
+}
+
+/// Streams `pcm` to the worker as fast as it takes it and collects the final
+/// transcripts. Returns them with the time until the last one arrived.
+fn stream(app: &AppHandle, model: &str, pcm: &[i16]) -> Result<(String, Duration), String> {
+    let params = transcription::inference(app);
+    let base = profiles::worker_url(app);
+    let url = format!(
+        "{}{}language={}&model={}&context_seconds={}&beam_size={}",
+        base,
+        if base.contains('?') { '&' } else { '?' },
+        SAMPLE_LANGUAGE,
+        model,
+        params.context_seconds,
+        params.beam_size
+    );
+    let (mut socket, _) = tungstenite::connect(url.as_str()).map_err(|e| e.to_string())?;
+    set_read_timeout(&socket).map_err(|e| e.to_string())?;
+
+    let silence = vec![0i16; TARGET_SAMPLE_RATE as usize * TRAILING_SILENCE_MS / 1000];
+    let started = Instant::now();
+    let mut sent = 0u64;
+    for chunk in pcm
+        .chunks(params.chunk_samples().max(1))
+        .chain([&silence[..]])
+    {
+        let bytes: Vec<u8> = chunk.iter().flat_map(|s| s.to_le_bytes()).collect();
+        sent += bytes.len() as u64;
+        socket
+            .send(Message::binary(bytes))
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut finals = Vec::new();
+    let mut last_final = started.elapsed();
+    let mut received = 0u64;
+    while started.elapsed() < STREAM_TIMEOUT {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                break
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        received += text.len() as u64;
+
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&text) else {
+            continue;
+        };
+        if message["type"] == "transcript" && message["is_final"] == true {
+            if let Some(text) = message["text"].as_str().filter(|t| !t.trim().is_empty()) {
+                finals.push(text.trim().to_string());
+                last_final = started.elapsed();
+            }
+        }
+    }
+    let _ = socket.close(None);
+    usage::record(app, Integration::CloudStt, sent, received);
+
+    Ok((finals.join(" "), last_final))
+}
+
+/// Sends `pcm` to the worker's batch route as one WAV file.
+fn batch(app: &AppHandle, pcm: &[i16]) -> Result<(String, Duration), String> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let wav = retranscribe::encode_chunk(spec, pcm)?;
+    let agent = retranscribe::agent(app);
+
+    let started = Instant::now();
+    let segments = retranscribe::transcribe(app, &agent, SAMPLE_LANGUAGE, &wav)?;
+    let transcript = segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok((transcript, started.elapsed()))
+}
+
+fn measure(
+    app: &AppHandle,
+    combination: Combination,
+    pcm: &[i16],
+    expected: &[String],
+) -> BenchmarkResult {
+    let audio_seconds = pcm.len() as f64 / TARGET_SAMPLE_RATE as f64;
+    let probe = MemoryProbe::start();
+    let result = match (&combination.model, combination.backend) {
+        (Some(model), Backend::Stream) => stream(app, model, pcm),
+        _ => batch(app, pcm),
+    };
+    let memory_bytes = probe.finish();
+
+    let (transcript, elapsed, error) = match result {
+        Ok((transcript, elapsed)) => (transcript, elapsed, None),
+        Err(e) => (String::new(), Duration::ZERO, Some(e)),
+    };
+    let heard = selftest::words(&transcript);
+    let found = expected.iter().filter(|word| heard.contains(word)).count();
+    BenchmarkResult {
+        combination,
+        real_time_factor: elapsed.as_secs_f64() / audio_seconds.max(f64::EPSILON),
+        memory_bytes,
+        accuracy: found as f64 / expected.len().max(1) as f64,
+        transcript,
+        error,
+    }
+}
+
+/// Working runs first, then those that keep up with live audio, then the most
+/// accurate, the fastest and the lightest.
+fn rank(a: &BenchmarkResult, b: &BenchmarkResult) -> Order {
+    a.error
+        .is_some()
+        .cmp(&b.error.is_some())
+        .then((a.real_time_factor >= REAL_TIME).cmp(&(b.real_time_factor >= REAL_TIME)))
+        .then(b.accuracy.total_cmp(&a.accuracy))
+        .then(a.real_time_factor.total_cmp(&b.real_time_factor))
+        .then(a.memory_bytes.cmp(&b.memory_bytes))
+}
+
+fn combinations(models: &[String], backends: &[Backend]) -> Vec<Combination> {
+    let mut combinations = Vec::new();
+    for backend in backends {
+        match backend {
+            Backend::Stream => combinations.extend(models.iter().map(|model| Combination {
+                model: Some(model.clone()),
+                backend: Backend::Stream,
+            })),
+            Backend::Batch => combinations.push(Combination {
+                model: None,
+                backend: Backend::Batch,
+            }),
+        }
+    }
+    combinations
+}
+
+fn run(
+    app: &AppHandle,
+    models: &[String],
+    backends: &[Backend],
+) -> Result<BenchmarkReport, String> {
+    let sample = selftest::load_sample(&selftest::sample_path(app)?)?;
+    let pcm = selftest::capture(app, &sample);
+    if pcm.is_empty() {
+        return Err("The converter produced no audio".into());
+    }
+    let expected = selftest::words(&sample.expected);
+
+    let mut results: Vec<BenchmarkResult> = combinations(models, backends)
+        .into_iter()
+        .map(|combination| {
+            let result = measure(app, combination, &pcm, &expected);
+            match &result.error {
+                Some(e) => eprintln!("[Benchmark] {:?} failed: {}", result.combination, e),
+                None => println!(
+                    "[Benchmark] {:?}: RTF {:.2}, {:.0}% of words",
+                    result.combination,
+                    result.real_time_factor,
+                    result.accuracy * 100.0
+                ),
+            }
+            result
+        })
+        .collect();
+    results.sort_by(rank);
+
+    Ok(BenchmarkReport {
+        audio_seconds: pcm.len() as f64 / TARGET_SAMPLE_RATE as f64,
+        recommended: results
+            .first()
+            .filter(|result| result.error.is_none())
+            .map(|result| result.combination.clone()),
+        results,
+    })
+}
+
+/// Transcribes the bundled sample with each model on each backend and ranks
+/// the results. Empty lists mean every supported model or backend.
+#[tauri::command]
+pub async fn run_benchmark(
+    app: AppHandle,
+    models: Vec<String>,
+    backends: Vec<Backend>,
+) -> Result<BenchmarkReport, String> {
+    if let Some(model) = models
+        .iter()
+        .find(|model| !SUPPORTED_MODELS.contains(&model.as_str()))
+    {
+        return Err(format!("Unsupported model '{}'", model));
+    }
+    let models = match models.is_empty() {
+        true => SUPPORTED_MODELS
+            .iter()
+            .map(|model| model.to_string())
+            .collect(),
+        false => models,
+    };
+    let backends = match backends.is_empty() {
+        true => vec![Backend::Stream, Backend::Batch],
+        false => backends,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || run(&app, &models, &backends))
+        .await
+        .map_err(|e| e.to_string())?
+}
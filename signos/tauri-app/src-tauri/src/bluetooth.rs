@@ -0,0 +1,120 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::capture::{self, AudioDevice, AudioDevices};
+use crate::settings;
+
+/// Label fragments of Bluetooth inputs. A Bluetooth headset mic only works in
+/// the hands-free profile (HFP), which carries 8-16 kHz narrowband audio.
+const BLUETOOTH_HINTS: [&str; 8] = [
+    "bluetooth",
+    "hands-free",
+    "handsfree",
+    "hfp",
+    "hsp",
+    "headset head unit",
+    "airpods",
+    "buds",
+];
+/// Label fragments of built-in microphones across macOS, Windows and Linux.
+const BUILTIN_HINTS: [&str; 5] = [
+    "built-in",
+    "internal",
+    "microphone array",
+    "macbook",
+    "analog stereo",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BluetoothSettings {
+    /// Switch to the built-in mic as soon as a low-quality input is selected.
+    pub auto_switch: bool,
+}
+
+/// Input already warned about, so each selection is only reported once.
+#[derive(Default)]
+pub struct BluetoothState(Mutex<Option<String>>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowQualityInput {
+    pub device: AudioDevice,
+    /// Built-in mic the user can switch to, if one was found.
+    pub fallback: Option<AudioDevice>,
+    pub switched: bool,
+}
+
+fn matches(device: &AudioDevice, hints: &[&str]) -> bool {
+    let label = device.label.to_lowercase();
+    hints.iter().any(|hint| label.contains(hint))
+}
+
+fn is_hands_free(device: &AudioDevice) -> bool {
+    matches(device, &BLUETOOTH_HINTS)
+}
+
+fn builtin_mic(devices: &AudioDevices) -> Option<AudioDevice> {
+    devices
+        .devices
+        .iter()
+        .find(|device| !is_hands_free(device) && matches(device, &BUILTIN_HINTS))
+        .cloned()
+}
+
+/// Warns (`capture:low-quality-input`) when the selected input is a Bluetooth
+/// headset in HFP mode. Called whenever the frontend reports its devices.
+pub fn check(app: &AppHandle, devices: &AudioDevices) {
+    let selected = devices
+        .selected
+        .as_ref()
+        .and_then(|id| devices.devices.iter().find(|device| &device.id == id));
+
+    let state = app.state::<BluetoothState>();
+    let mut warned = state.0.lock().unwrap();
+
+    let Some(device) = selected.filter(|device| is_hands_free(device)) else {
+        *warned = None;
+        return;
+    };
+    if warned.as_deref() == Some(device.id.as_str()) {
+        return;
+    }
+    *warned = Some(device.id.clone());
+    drop(warned);
+
+    let fallback = builtin_mic(devices);
+    let switched = settings::get(app).bluetooth.auto_switch
+        && fallback
+            .as_ref()
+            .is_some_and(|mic| capture::request_device(app, &mic.id).is_ok());
+
+    println!(
+        "[Bluetooth] '{}' is a hands-free input{}",
+        device.label,
+        if switched {
+            ", switching to the built-in mic"
+        } else {
+            ""
+        }
+    );
+
+    let _ = app.emit(
+        "capture:low-quality-input",
+        LowQualityInput {
+            device: device.clone(),
+            fallback,
+            switched,
+        },
+    );
+}
+
+/// Switches capture to the built-in mic, e.g. from the low-quality input warning.
+#[tauri::command]
+pub fn switch_to_builtin_mic(app: AppHandle) -> Result<AudioDevice, String> {
+    let mic = builtin_mic(&capture::devices(&app)).ok_or("No built-in microphone found")?;
+    capture::request_device(&app, &mic.id).map_err(|e| e.to_string())?;
+    Ok(mic)
+}
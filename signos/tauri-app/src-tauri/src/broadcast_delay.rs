@@ -0,0 +1,103 @@
+//! Holds captions back before they reach broadcast outputs.
+//!
+//! Streamers run their stream a few seconds behind; delaying the NDI output and
+//! the web viewer (which OBS shows as a browser source) by the same amount keeps
+//! captions in sync with what viewers see. The local panel stays real-time.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::captions::Caption;
+use crate::{ndi, settings, viewer};
+
+/// Longest delay accepted; stream delays past this are unusual.
+const MAX_DELAY_MS: u64 = 30_000;
+const IDLE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BroadcastDelaySettings {
+    /// 0 sends captions to broadcast outputs as soon as they arrive.
+    pub delay_ms: u64,
+}
+
+#[derive(Default)]
+pub struct BroadcastDelayState(Mutex<Option<Sender<(Instant, Caption)>>>);
+
+fn forward(app: &AppHandle, caption: &Caption) {
+    ndi::send_caption(app, caption);
+    viewer::send_caption(app, caption);
+}
+
+/// Sends a caption to the broadcast outputs once the configured delay has passed.
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    let delay = Duration::from_millis(settings::get(app).broadcast_delay.delay_ms);
+    let state = app.state::<BroadcastDelayState>();
+    let queue = state.0.lock().unwrap();
+
+    // Queued even without a delay, so captions stay in order when it is turned off.
+    let sent = queue.as_ref().is_some_and(|queue| {
+        queue
+            .send((Instant::now() + delay, caption.clone()))
+            .is_ok()
+    });
+    if !sent {
+        forward(app, caption);
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    let (tx, rx) = mpsc::channel::<(Instant, Caption)>();
+    *app.state::<BroadcastDelayState>().0.lock().unwrap() = Some(tx);
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut pending = VecDeque::new();
+        loop {
+            let timeout = pending
+                .front()
+                .map(|(due, _): &(Instant, Caption)| due.saturating_duration_since(Instant::now()))
+                .unwrap_or(IDLE);
+            match rx.recv_timeout(timeout) {
+                Ok(item) => pending.push_back(item),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            let now = Instant::now();
+            while pending.front().is_some_and(|(due, _)| *due <= now) {
+                if let Some((_, caption)) = pending.pop_front() {
+                    forward(&app, &caption);
+                }
+            }
+        }
+    });
+}
+
+/// Sets how long captions are held back from broadcast outputs; 0 turns the delay off.
+#[tauri::command]
+pub fn set_broadcast_delay(
+    app: AppHandle,
+    delay_ms: u64,
+) -> Result<BroadcastDelaySettings, String> {
+    if delay_ms > MAX_DELAY_MS {
+        return Err(format!(
+            "Delay must be at most {} seconds",
+            MAX_DELAY_MS / 1000
+        ));
+    }
+
+    let settings = settings::update(&app, |s| s.broadcast_delay.delay_ms = delay_ms)?;
+    println!(
+        "[BroadcastDelay] Broadcast outputs delayed by {} ms",
+        delay_ms
+    );
+    let _ = app.emit("broadcast-delay:changed", &settings.broadcast_delay);
+    Ok(settings.broadcast_delay)
+}
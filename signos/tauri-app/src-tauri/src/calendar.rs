@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::util::now_ms;
+use crate::{capture, panel, participants, settings};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Most polls skipped after repeated failures, about half an hour.
+const MAX_BACKOFF: u32 = 60;
+/// How far ahead the watcher looks for meetings.
+const LOOKAHEAD: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CalendarSettings {
+    /// Opt-in: the OS permission prompt is only shown once this is enabled.
+    pub enabled: bool,
+    /// Case-insensitive keywords matched against the event title. Empty matches every event.
+    pub keywords: Vec<String>,
+    /// How long before the meeting starts the panel is shown.
+    pub lead_seconds: u64,
+    pub auto_start_capture: bool,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keywords: vec!["meeting".into(), "call".into(), "standup".into()],
+            lead_seconds: 60,
+            auto_start_capture: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub id: String,
+    pub title: String,
+    /// Unix timestamp (ms).
+    pub starts_at: u64,
+    /// Display names of the organizer and invitees, where the calendar has them.
+    pub attendees: Vec<String>,
+}
+
+impl CalendarSettings {
+    fn matches(&self, event: &CalendarEvent) -> bool {
+        let title = event.title.to_lowercase();
+        self.keywords.is_empty()
+            || self
+                .keywords
+                .iter()
+                .any(|keyword| title.contains(&keyword.to_lowercase()))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use block2::RcBlock;
+    use objc2::runtime::{Bool, NSObjectProtocol};
+    use objc2::sel;
+    use objc2_event_kit::{EKAuthorizationStatus, EKEntityType, EKEventStore};
+    use objc2_foundation::{NSDate, NSError};
+
+    use super::CalendarEvent;
+
+    // `FullAccess` (macOS 14+) has the value of the older `Authorized`, so the
+    // status checks work on every version.
+    pub fn request_access() -> Result<bool, String> {
+        let status = unsafe { EKEventStore::authorizationStatusForEntityType(EKEntityType::Event) };
+        if status == EKAuthorizationStatus::FullAccess {
+            return Ok(true);
+        }
+
+        let store = unsafe { EKEventStore::new() };
+        let (tx, rx) = mpsc::channel();
+        let completion = RcBlock::new(move |granted: Bool, _error: *mut NSError| {
+            let _ = tx.send(granted.as_bool());
+        });
+
+        // Sending the macOS 14 selector to an older EventKit aborts the process.
+        if store.respondsToSelector(sel!(requestFullAccessToEventsWithCompletion:)) {
+            unsafe { store.requestFullAccessToEventsWithCompletion(RcBlock::as_ptr(&completion)) };
+        } else {
+            #[allow(deprecated)]
+            unsafe {
+                store.requestAccessToEntityType_completion(
+                    EKEntityType::Event,
+                    RcBlock::as_ptr(&completion),
+                )
+            };
+        }
+
+        // The user may leave the prompt open for a while.
+        rx.recv_timeout(Duration::from_secs(300))
+            .map_err(|_| "Timed out waiting for calendar permission".to_string())
+    }
+
+    pub fn upcoming_events(within: Duration) -> Result<Vec<CalendarEvent>, String> {
+        let status = unsafe { EKEventStore::authorizationStatusForEntityType(EKEntityType::Event) };
+        if status != EKAuthorizationStatus::FullAccess {
+            return Err("Calendar access has not been granted".into());
+        }
+
+        let store = unsafe { EKEventStore::new() };
+        let start = NSDate::now();
+        let end = NSDate::dateWithTimeIntervalSinceNow(within.as_secs_f64());
+
+        let events = unsafe {
+            let predicate =
+                store.predicateForEventsWithStartDate_endDate_calendars(&start, &end, None);
+            store.eventsMatchingPredicate(&predicate)
+        };
+
+        Ok(events
+            .iter()
+            .map(|event| unsafe {
+                let starts_at = event.startDate().timeIntervalSince1970() * 1000.0;
+                let mut attendees: Vec<String> = event
+                    .organizer()
+                    .and_then(|organizer| organizer.name())
+                    .map(|name| name.to_string())
+                    .into_iter()
+                    .collect();
+                for participant in event.attendees().iter().flat_map(|list| list.iter()) {
+                    if let Some(name) = participant.name().map(|name| name.to_string()) {
+                        if !attendees.contains(&name) {
+                            attendees.push(name);
+                        }
+                    }
+                }
+                CalendarEvent {
+                    id: event
+                        .eventIdentifier()
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    title: event.title().to_string(),
+                    starts_at: starts_at.max(0.0) as u64,
+                    attendees,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::time::Duration;
+
+    use windows::ApplicationModel::Appointments::{AppointmentManager, AppointmentStoreAccessType};
+    use windows::Foundation::{DateTime, TimeSpan};
+
+    use super::{now_ms, CalendarEvent};
+
+    /// 100ns ticks between 1601-01-01 (WinRT epoch) and 1970-01-01.
+    const UNIX_EPOCH_TICKS: i64 = 116_444_736_000_000_000;
+
+    fn store() -> Result<windows::ApplicationModel::Appointments::AppointmentStore, String> {
+        AppointmentManager::RequestStoreAsync(AppointmentStoreAccessType::AllCalendarsReadOnly)
+            .and_then(|operation| operation.get())
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn request_access() -> Result<bool, String> {
+        store().map(|_| true)
+    }
+
+    pub fn upcoming_events(within: Duration) -> Result<Vec<CalendarEvent>, String> {
+        let start = DateTime {
+            UniversalTime: now_ms() as i64 * 10_000 + UNIX_EPOCH_TICKS,
+        };
+        let length = TimeSpan {
+            Duration: within.as_millis() as i64 * 10_000,
+        };
+
+        let appointments = store()?
+            .FindAppointmentsAsync(start, length)
+            .and_then(|operation| operation.get())
+            .map_err(|e| e.to_string())?;
+
+        let mut events = Vec::new();
+        for appointment in appointments {
+            let Ok(start) = appointment.StartTime() else {
+                continue;
+            };
+            let mut attendees: Vec<String> = appointment
+                .Organizer()
+                .and_then(|organizer| organizer.DisplayName())
+                .map(|name| name.to_string())
+                .into_iter()
+                .filter(|name| !name.is_empty())
+                .collect();
+            for invitee in appointment.Invitees().into_iter().flatten() {
+                let Ok(name) = invitee.DisplayName().map(|name| name.to_string()) else {
+                    continue;
+                };
+                if !name.is_empty() && !attendees.contains(&name) {
+                    attendees.push(name);
+                }
+            }
+            events.push(CalendarEvent {
+                id: appointment
+                    .LocalId()
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                title: appointment
+                    .Subject()
+                    .map(|s| s.to_string())
+                    .unwrap_or_default(),
+                starts_at: ((start.UniversalTime - UNIX_EPOCH_TICKS) / 10_000).max(0) as u64,
+                attendees,
+            });
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use std::time::Duration;
+
+    use super::CalendarEvent;
+
+    const UNSUPPORTED: &str = "Calendar integration is not supported on this platform";
+
+    pub fn request_access() -> Result<bool, String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn upcoming_events(_within: Duration) -> Result<Vec<CalendarEvent>, String> {
+        Err(UNSUPPORTED.into())
+    }
+}
+
+fn arm_for_meeting(app: &AppHandle, config: &CalendarSettings, event: &CalendarEvent) {
+    // Titles can be private; the id is enough to match up with the calendar.
+    println!("[Calendar] Meeting {} is about to start", event.id);
+
+    if let Err(e) = panel::show(app) {
+        eprintln!("[Calendar] Failed to show panel: {}", e);
+    }
+
+    participants::from_meeting(app, &event.attendees);
+
+    if config.auto_start_capture && !capture::status(app).active {
+        if let Err(e) = capture::request_start(app) {
+            eprintln!("[Calendar] Failed to start capture: {}", e);
+        }
+    }
+
+    let _ = app.emit("calendar:meeting", event);
+}
+
+/// Starts the watcher that arms capture shortly before matching meetings.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || {
+        // Events already handled, so a meeting only triggers once.
+        let mut armed: HashSet<String> = HashSet::new();
+        // After a failure (usually denied access) polls are skipped, doubling
+        // up to MAX_BACKOFF, and a repeated error is not logged again.
+        let (mut skip, mut backoff, mut last_error) = (0u32, 0u32, None::<String>);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let config = settings::get(&app).calendar;
+            if !config.enabled {
+                continue;
+            }
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+
+            let events = match platform::upcoming_events(LOOKAHEAD) {
+                Ok(events) => {
+                    (backoff, last_error) = (0, None);
+                    events
+                }
+                Err(e) => {
+                    if last_error.as_ref() != Some(&e) {
+                        eprintln!("[Calendar] Failed to read events: {}", e);
+                    }
+                    backoff = (backoff * 2).clamp(1, MAX_BACKOFF);
+                    (skip, last_error) = (backoff, Some(e));
+                    continue;
+                }
+            };
+
+            let now = now_ms();
+            let lead_ms = config.lead_seconds * 1000;
+
+            for event in events.iter().filter(|event| config.matches(event)) {
+                let due = event.starts_at <= now + lead_ms && event.starts_at + lead_ms >= now;
+                if due && armed.insert(event.id.clone()) {
+                    arm_for_meeting(&app, &config, event);
+                }
+            }
+
+            // Forget events that are no longer upcoming.
+            armed.retain(|id| events.iter().any(|event| &event.id == id));
+        }
+    });
+}
+
+/// Shows the OS permission prompt. Called by the settings UI when the user opts in.
+#[tauri::command]
+pub async fn request_calendar_access() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(platform::request_access)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_upcoming_events(app: AppHandle) -> Result<Vec<CalendarEvent>, String> {
+    let config = settings::get(&app).calendar;
+
+    let events = tauri::async_runtime::spawn_blocking(|| platform::upcoming_events(LOOKAHEAD))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    Ok(events
+        .into_iter()
+        .filter(|event| config.matches(event))
+        .collect())
+}
@@ -0,0 +1,102 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::ipc::{InvokeBody, Request};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::landmarks::{self, VideoFrame};
+use crate::util::now_ms;
+use crate::{power, settings};
+
+/// Camera input for sign recognition.
+///
+/// Like audio, the camera is opened by the webview: the backend asks it to
+/// start or stop (`camera:start` / `camera:stop`) and receives downscaled
+/// RGBA frames through `push_video_frame`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CameraSettings {
+    /// Video input id from the webview. `None` uses the default camera.
+    pub device_id: Option<String>,
+    /// Frames per second sent to the backend.
+    pub fps: u32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            device_id: None,
+            fps: 15,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CameraState(Mutex<bool>);
+
+fn header(request: &Request<'_>, name: &str) -> Option<u32> {
+    request.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+pub fn is_active(app: &AppHandle) -> bool {
+    *app.state::<CameraState>().0.lock().unwrap()
+}
+
+pub fn request_start(app: &AppHandle) -> tauri::Result<()> {
+    app.emit(
+        "camera:start",
+        power::camera(app, settings::get(app).camera),
+    )
+}
+
+pub fn request_stop(app: &AppHandle) -> tauri::Result<()> {
+    app.emit("camera:stop", ())
+}
+
+#[tauri::command]
+pub fn start_camera(app: AppHandle) -> Result<(), String> {
+    request_start(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn stop_camera(app: AppHandle) -> Result<(), String> {
+    request_stop(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_camera_state(app: AppHandle) -> bool {
+    is_active(&app)
+}
+
+/// Called by the frontend once the camera actually opened or closed.
+#[tauri::command]
+pub fn report_camera_state(app: AppHandle, active: bool) {
+    *app.state::<CameraState>().0.lock().unwrap() = active;
+    let _ = app.emit("camera:state", active);
+}
+
+/// Receives an RGBA frame, sized by the `width` and `height` headers.
+#[tauri::command]
+pub fn push_video_frame(app: AppHandle, request: Request<'_>) -> Result<(), String> {
+    let InvokeBody::Raw(bytes) = request.body() else {
+        return Err("Expected raw RGBA data".into());
+    };
+    let (Some(width), Some(height)) = (header(&request, "width"), header(&request, "height"))
+    else {
+        return Err("Missing frame size".into());
+    };
+    if bytes.len() != (width * height * 4) as usize {
+        return Err("Frame size does not match its data".into());
+    }
+
+    landmarks::submit(
+        &app,
+        VideoFrame {
+            width,
+            height,
+            rgba: bytes.clone(),
+            timestamp: now_ms(),
+        },
+    );
+    Ok(())
+}
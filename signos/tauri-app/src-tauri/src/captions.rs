@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{
+    actions, avatar, broadcast_delay, file_sink, glossary, keywords, metrics, osc, pip, plugins,
+    punctuation, recording, redaction, relay, replay, scripting, stabilizer, sync,
+};
+
+/// A transcript message from the worker, forwarded by the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Caption {
+    pub text: String,
+    pub is_final: bool,
+    /// Unix timestamp (ms) set by the worker.
+    pub timestamp: u64,
+    pub latency_ms: Option<u64>,
+    /// Detected language, when the worker reports one.
+    pub language: Option<String>,
+    /// Diarized speaker id, when the worker reports one.
+    pub speaker: Option<u32>,
+    /// Label of the extra capture source this came from; `None` for the main input.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Entry point for every caption; runs the plugins and fans the result out to
+/// the enabled outputs. Returns the caption as it should be displayed, which
+/// is only unredacted when the redaction settings keep originals in the live view.
+pub fn push(app: &AppHandle, mut caption: Caption) -> Caption {
+    stabilizer::apply(app, &mut caption);
+    punctuation::apply(app, &mut caption);
+    glossary::apply(app, &mut caption);
+    plugins::apply(app, &mut caption);
+    if caption.text.trim().is_empty() {
+        return caption;
+    }
+    let shown = redaction::apply(app, &mut caption);
+
+    metrics::record_caption(app, &caption);
+    keywords::check(app, &caption);
+    actions::check(app, &caption);
+    osc::send_caption(app, &caption);
+    pip::send_caption(app, &shown);
+    recording::on_caption(app, &caption);
+    file_sink::write_caption(app, &caption);
+    scripting::on_transcript(app, &caption);
+    sync::send_caption(app, &caption);
+    relay::send_caption(app, &caption);
+    broadcast_delay::send_caption(app, &caption);
+    avatar::on_caption(app, &caption);
+    replay::record(app, &shown);
+
+    shown
+}
+
+#[tauri::command(async)]
+pub fn push_caption(app: AppHandle, caption: Caption) -> Caption {
+    push(&app, caption)
+}
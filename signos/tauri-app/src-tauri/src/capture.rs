@@ -0,0 +1,345 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::util::now_ms;
+use crate::{bluetooth, consent};
+
+/// Backend view of the capture pipeline.
+///
+/// Audio capture itself runs in the webview, so the backend only asks the
+/// frontend to start or stop (`capture:start` / `capture:stop`) and tracks
+/// the state the frontend reports back. Pausing is owned by the backend: the
+/// frontend keeps the connection open but stops sending audio while paused.
+#[derive(Default)]
+pub struct CaptureState(Mutex<CaptureStatus>);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureStatus {
+    pub active: bool,
+    pub paused: bool,
+    /// Unix timestamp (ms) of when the current capture started.
+    pub started_at: Option<u64>,
+    /// Unix timestamp (ms) at which a `start_capture_for` capture stops itself.
+    #[serde(default)]
+    pub ends_at: Option<u64>,
+}
+
+/// How long before a time-boxed capture stops that `capture:ending` is emitted.
+const STOP_WARNING_MS: u64 = 60_000;
+/// Longest time box `start_capture_for` takes, matching "caption for" links.
+const MAX_DURATION_SECS: u64 = 12 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptureEnding {
+    ends_at: u64,
+    remaining_ms: u64,
+}
+
+/// Audio inputs enumerated by the webview, which owns capture.
+#[derive(Default)]
+pub struct DeviceState(Mutex<AudioDevices>);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevices {
+    pub devices: Vec<AudioDevice>,
+    /// Id of the input the frontend will capture from.
+    pub selected: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevice {
+    pub id: String,
+    pub label: String,
+}
+
+/// Most inputs captured at once, the selected device included.
+const MAX_SOURCES: usize = 4;
+
+/// Inputs captured alongside the selected device, e.g. system audio or a
+/// second mic on a panel. Each gets its own worker connection in the
+/// frontend; their captions carry the source label and share one timeline.
+#[derive(Default)]
+pub struct SourceState(Mutex<Vec<CaptureSource>>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureSource {
+    /// Shown next to its captions, e.g. "Moderator".
+    pub label: String,
+    pub device_id: String,
+}
+
+pub fn status(app: &AppHandle) -> CaptureStatus {
+    app.state::<CaptureState>().0.lock().unwrap().clone()
+}
+
+pub fn devices(app: &AppHandle) -> AudioDevices {
+    app.state::<DeviceState>().0.lock().unwrap().clone()
+}
+
+pub fn sources(app: &AppHandle) -> Vec<CaptureSource> {
+    app.state::<SourceState>().0.lock().unwrap().clone()
+}
+
+/// Asks the frontend to start capturing (`capture:start`). Without the consent
+/// the policy needs, it asks for consent instead and the mic stays closed.
+pub fn request_start(app: &AppHandle) -> tauri::Result<()> {
+    if !consent::allowed(app) {
+        println!("[Capture] Waiting for consent before starting");
+        return app.emit("consent:required", ());
+    }
+    app.emit("capture:start", ())
+}
+
+pub fn request_stop(app: &AppHandle) -> tauri::Result<()> {
+    app.emit("capture:stop", ())
+}
+
+/// Asks the frontend to switch to another input (`capture:device`). Used by the tray menu.
+pub fn request_device(app: &AppHandle, id: &str) -> tauri::Result<()> {
+    app.emit("capture:device", id)
+}
+
+pub fn set_paused(app: &AppHandle, paused: bool) -> tauri::Result<()> {
+    let status = {
+        let state = app.state::<CaptureState>();
+        let mut status = state.0.lock().unwrap();
+        status.paused = paused && status.active;
+        status.clone()
+    };
+
+    app.emit("capture:paused", status.paused)?;
+    app.emit("capture:state", &status)
+}
+
+pub fn toggle_paused(app: &AppHandle) -> tauri::Result<()> {
+    set_paused(app, !status(app).paused)
+}
+
+fn sleep_until(at: u64) {
+    let now = now_ms();
+    if at > now {
+        thread::sleep(Duration::from_millis(at - now));
+    }
+}
+
+/// Whether the capture timer ending at `ends_at` is still the current one.
+fn timer_armed(app: &AppHandle, ends_at: u64) -> bool {
+    status(app).ends_at == Some(ends_at)
+}
+
+/// Stops capture at `ends_at`, warning a minute before. Superseded or
+/// cancelled timers, and captures that already stopped, are left alone.
+fn arm_timer(app: &AppHandle, ends_at: u64) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let warn_at = ends_at.saturating_sub(STOP_WARNING_MS);
+        if warn_at > now_ms() {
+            sleep_until(warn_at);
+            if timer_armed(&app, ends_at) && status(&app).active {
+                let _ = app.emit(
+                    "capture:ending",
+                    CaptureEnding {
+                        ends_at,
+                        remaining_ms: ends_at.saturating_sub(now_ms()),
+                    },
+                );
+            }
+        }
+
+        sleep_until(ends_at);
+        let active = {
+            let state = app.state::<CaptureState>();
+            let mut status = state.0.lock().unwrap();
+            if status.ends_at != Some(ends_at) {
+                return;
+            }
+            status.ends_at = None;
+            status.active
+        };
+        if active {
+            println!("[Capture] Time limit reached, stopping capture");
+            if let Err(e) = request_stop(&app) {
+                eprintln!("[Capture] Failed to stop timed capture: {}", e);
+            }
+        }
+    });
+}
+
+/// Sets or clears the time at which capture stops itself.
+fn set_deadline(app: &AppHandle, ends_at: Option<u64>) -> tauri::Result<()> {
+    let status = {
+        let state = app.state::<CaptureState>();
+        let mut status = state.0.lock().unwrap();
+        status.ends_at = ends_at;
+        status.clone()
+    };
+    if let Some(ends_at) = ends_at {
+        arm_timer(app, ends_at);
+    }
+    app.emit("capture:state", &status)
+}
+
+#[tauri::command]
+pub fn start_capture(app: AppHandle) -> Result<(), String> {
+    request_start(&app).map_err(|e| e.to_string())
+}
+
+/// Starts capture (or keeps the running one) and stops it after `duration_secs`,
+/// which also finalizes the recording. Replaces any earlier limit.
+#[tauri::command]
+pub fn start_capture_for(app: AppHandle, duration_secs: u64) -> Result<CaptureStatus, String> {
+    if duration_secs == 0 {
+        return Err("Duration must be at least one second".into());
+    }
+    if duration_secs > MAX_DURATION_SECS {
+        return Err(format!(
+            "Duration can be at most {} hours",
+            MAX_DURATION_SECS / 3600
+        ));
+    }
+    let deadline = duration_secs
+        .checked_mul(1000)
+        .and_then(|ms| now_ms().checked_add(ms))
+        .ok_or("Duration is too long")?;
+    set_deadline(&app, Some(deadline)).map_err(|e| e.to_string())?;
+    if !status(&app).active {
+        request_start(&app).map_err(|e| e.to_string())?;
+    }
+    Ok(status(&app))
+}
+
+/// Lets a time-boxed capture keep running.
+#[tauri::command]
+pub fn cancel_capture_timer(app: AppHandle) -> Result<CaptureStatus, String> {
+    set_deadline(&app, None).map_err(|e| e.to_string())?;
+    Ok(status(&app))
+}
+
+#[tauri::command]
+pub fn stop_capture(app: AppHandle) -> Result<(), String> {
+    request_stop(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn pause_capture(app: AppHandle) -> Result<(), String> {
+    set_paused(&app, true).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn resume_capture(app: AppHandle) -> Result<(), String> {
+    set_paused(&app, false).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_capture_status(app: AppHandle) -> CaptureStatus {
+    status(&app)
+}
+
+/// Called by the frontend whenever recording actually starts or stops.
+#[tauri::command]
+pub fn report_capture_state(app: AppHandle, active: bool) -> CaptureStatus {
+    if active && !consent::allowed(&app) {
+        consent::deny(&app);
+        return status(&app);
+    }
+
+    let status = {
+        let state = app.state::<CaptureState>();
+        let mut status = state.0.lock().unwrap();
+        if status.active != active {
+            status.active = active;
+            status.started_at = active.then(now_ms);
+            status.paused = false;
+            if !active {
+                status.ends_at = None;
+            }
+        }
+        status.clone()
+    };
+
+    let _ = app.emit("capture:state", &status);
+    status
+}
+
+/// Called by the frontend after enumerating inputs or changing the selection.
+#[tauri::command]
+pub fn report_audio_devices(app: AppHandle, devices: AudioDevices) {
+    *app.state::<DeviceState>().0.lock().unwrap() = devices.clone();
+    let _ = app.emit("capture:devices", &devices);
+    bluetooth::check(&app, &devices);
+}
+
+/// Adds an input to capture concurrently; applied live if capture is running (`capture:sources`).
+#[tauri::command]
+pub fn add_source(
+    app: AppHandle,
+    label: String,
+    device_id: String,
+) -> Result<Vec<CaptureSource>, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("A source needs a label".into());
+    }
+
+    let devices = devices(&app);
+    if devices.selected.as_deref() == Some(device_id.as_str()) {
+        return Err("This input is already the main capture device".into());
+    }
+    if !devices.devices.is_empty() && !devices.devices.iter().any(|d| d.id == device_id) {
+        return Err(format!("Unknown input device '{}'", device_id));
+    }
+
+    let sources = {
+        let state = app.state::<SourceState>();
+        let mut sources = state.0.lock().unwrap();
+        if sources.iter().any(|source| source.label == label) {
+            return Err(format!("A source named '{}' already exists", label));
+        }
+        if sources.iter().any(|source| source.device_id == device_id) {
+            return Err("This input is already being captured".into());
+        }
+        if sources.len() + 1 >= MAX_SOURCES {
+            return Err(format!(
+                "At most {} inputs can be captured at once",
+                MAX_SOURCES
+            ));
+        }
+
+        sources.push(CaptureSource { label, device_id });
+        sources.clone()
+    };
+
+    let _ = app.emit("capture:sources", &sources);
+    Ok(sources)
+}
+
+#[tauri::command]
+pub fn remove_source(app: AppHandle, label: String) -> Result<Vec<CaptureSource>, String> {
+    let sources = {
+        let state = app.state::<SourceState>();
+        let mut sources = state.0.lock().unwrap();
+        let count = sources.len();
+        sources.retain(|source| source.label != label);
+        if sources.len() == count {
+            return Err(format!("No source named '{}'", label));
+        }
+        sources.clone()
+    };
+
+    let _ = app.emit("capture:sources", &sources);
+    Ok(sources)
+}
+
+#[tauri::command]
+pub fn list_sources(app: AppHandle) -> Vec<CaptureSource> {
+    sources(&app)
+}
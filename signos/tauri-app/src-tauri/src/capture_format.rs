@@ -0,0 +1,400 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::ipc::{InvokeBody, Request, Response};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::{security, settings, transcription};
+
+/// Rate the worker expects, matching the webview capture path.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+const MIN_SAMPLE_RATE: u32 = 8_000;
+const MAX_SAMPLE_RATE: u32 = 192_000;
+/// One bit per channel in `channel_mask`.
+const MAX_CHANNELS: u16 = 32;
+/// Low-pass taps on each side of the center, per input frame per output sample.
+const FILTER_TAPS_PER_STEP: f64 = 16.0;
+
+/// Input format requested from the webview. Anything other than 16 kHz mono is
+/// captured as raw float samples and converted by `convert_audio`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CaptureFormatSettings {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Channels mixed into the pipeline, bit 0 being the first channel.
+    pub channel_mask: u32,
+}
+
+impl Default for CaptureFormatSettings {
+    fn default() -> Self {
+        Self {
+            sample_rate: TARGET_SAMPLE_RATE,
+            channels: 1,
+            channel_mask: 1,
+        }
+    }
+}
+
+impl CaptureFormatSettings {
+    fn validate(&self) -> Result<(), String> {
+        if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&self.sample_rate) {
+            return Err(format!(
+                "Sample rate must be between {} and {} Hz",
+                MIN_SAMPLE_RATE, MAX_SAMPLE_RATE
+            ));
+        }
+        if !(1..=MAX_CHANNELS).contains(&self.channels) {
+            return Err(format!("Channels must be between 1 and {}", MAX_CHANNELS));
+        }
+        if self.channel_mask == 0 || u64::from(self.channel_mask) >> self.channels != 0 {
+            return Err(format!(
+                "Channel mask must select at least one of the {} channels",
+                self.channels
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Windowed-sinc (Blackman) low-pass applied before downsampling, so content
+/// above the new Nyquist frequency doesn't fold back into the speech band.
+struct LowPass {
+    taps: Vec<f32>,
+    /// The last `taps.len() - 1` input samples, to continue across buffers.
+    history: Vec<f32>,
+}
+
+impl LowPass {
+    /// For input at `step` times the output rate. The stopband starts at the
+    /// output Nyquist frequency.
+    fn new(step: f64) -> Self {
+        let half = (FILTER_TAPS_PER_STEP * step).ceil() as usize;
+        let len = 2 * half + 1;
+        // Blackman transition width is about 5.5 / len, in cycles per sample.
+        let cutoff = (0.5 / step - 2.75 / len as f64).max(0.25 / step);
+
+        let mut taps: Vec<f64> = (0..len)
+            .map(|n| {
+                let x = n as f64 - half as f64;
+                let sinc = match x == 0.0 {
+                    true => 2.0 * cutoff,
+                    false => {
+                        (2.0 * std::f64::consts::PI * cutoff * x).sin() / (std::f64::consts::PI * x)
+                    }
+                };
+                let phase = 2.0 * std::f64::consts::PI * n as f64 / (len - 1) as f64;
+                sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+            })
+            .collect();
+        let sum: f64 = taps.iter().sum();
+        taps.iter_mut().for_each(|tap| *tap /= sum);
+
+        Self {
+            taps: taps.into_iter().map(|tap| tap as f32).collect(),
+            history: vec![0.0; len - 1],
+        }
+    }
+
+    fn apply(&mut self, input: &[f32]) -> Vec<f32> {
+        let mut buffer = std::mem::take(&mut self.history);
+        buffer.extend_from_slice(input);
+
+        let output = buffer
+            .windows(self.taps.len())
+            .map(|window| window.iter().zip(&self.taps).map(|(x, tap)| x * tap).sum())
+            .collect();
+        self.history = buffer.split_off(buffer.len() - (self.taps.len() - 1));
+        output
+    }
+}
+
+/// Mixes the selected channels of interleaved float audio down to mono and
+/// resamples it to 16 kHz Int16 PCM chunks.
+pub struct Resampler {
+    /// Samples per output chunk (`InferenceParams::chunk_ms`).
+    chunk_samples: usize,
+    channels: usize,
+    mask: u32,
+    /// Input frames per output sample.
+    step: f64,
+    /// Read position into the current input buffer; -1 is the last frame of the previous one.
+    position: f64,
+    last: f32,
+    pending: Vec<i16>,
+    /// Set when downsampling.
+    filter: Option<LowPass>,
+}
+
+impl Resampler {
+    pub fn new(input_rate: f64, channels: u16, mask: u32, chunk_samples: usize) -> Self {
+        let step = input_rate / TARGET_SAMPLE_RATE as f64;
+        Self {
+            chunk_samples,
+            channels: channels.max(1) as usize,
+            mask,
+            step,
+            position: 0.0,
+            last: 0.0,
+            pending: Vec::with_capacity(chunk_samples),
+            filter: (step > 1.0).then(|| LowPass::new(step)),
+        }
+    }
+
+    fn downmix(&self, interleaved: &[f32]) -> Vec<f32> {
+        let selected = (0..self.channels)
+            .filter(|channel| self.mask & (1 << channel) != 0)
+            .count()
+            .max(1) as f32;
+
+        interleaved
+            .chunks_exact(self.channels)
+            .map(|frame| {
+                let sum: f32 = frame
+                    .iter()
+                    .enumerate()
+                    .filter(|(channel, _)| self.mask & (1 << channel) != 0)
+                    .map(|(_, sample)| sample)
+                    .sum();
+                sum / selected
+            })
+            .collect()
+    }
+
+    /// Feeds one buffer and returns every chunk that filled up.
+    pub fn push(&mut self, interleaved: &[f32]) -> Vec<Vec<u8>> {
+        if self.channels == 1 {
+            return self.push_mono(interleaved);
+        }
+        let mono = self.downmix(interleaved);
+        self.push_mono(&mono)
+    }
+
+    fn push_mono(&mut self, input: &[f32]) -> Vec<Vec<u8>> {
+        match self.filter.as_mut() {
+            Some(filter) => {
+                let filtered = filter.apply(input);
+                self.interpolate(&filtered)
+            }
+            None => self.interpolate(input),
+        }
+    }
+
+    fn interpolate(&mut self, input: &[f32]) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        let Some(&tail) = input.last() else {
+            return chunks;
+        };
+
+        let len = input.len() as f64;
+        while self.position < len - 1.0 {
+            let index = self.position.floor();
+            let fraction = (self.position - index) as f32;
+            let index = index as isize;
+
+            let a = if index < 0 {
+                self.last
+            } else {
+                input[index as usize]
+            };
+            let b = input[(index + 1) as usize];
+            let sample = (a + (b - a) * fraction).clamp(-1.0, 1.0);
+            self.pending.push(match sample < 0.0 {
+                true => (sample * 32768.0) as i16,
+                false => (sample * 32767.0) as i16,
+            });
+
+            if self.pending.len() == self.chunk_samples {
+                chunks.push(self.pending.drain(..).flat_map(i16::to_le_bytes).collect());
+            }
+            self.position += self.step;
+        }
+
+        self.position -= len;
+        self.last = tail;
+        chunks
+    }
+}
+
+/// Converter for the webview capture path, rebuilt whenever the incoming format changes.
+#[derive(Default)]
+pub struct ConverterState(Mutex<Option<(CaptureFormatSettings, Resampler)>>);
+
+pub fn get(app: &AppHandle) -> CaptureFormatSettings {
+    settings::get(app).capture_format
+}
+
+fn header<T: std::str::FromStr>(request: &Request<'_>, name: &str) -> Option<T> {
+    request.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+#[tauri::command]
+pub fn get_capture_format(app: AppHandle) -> CaptureFormatSettings {
+    get(&app)
+}
+
+/// Selects the input format; the frontend restarts capture on `capture:format`.
+#[tauri::command]
+pub fn set_capture_format(
+    app: AppHandle,
+    webview: Webview,
+    sample_rate: u32,
+    channels: u16,
+    channel_mask: u32,
+) -> Result<CaptureFormatSettings, String> {
+    security::guard(&webview, "set_capture_format", None)?;
+    let format = CaptureFormatSettings {
+        sample_rate,
+        channels,
+        channel_mask,
+    };
+    format.validate()?;
+
+    settings::update(&app, |s| s.capture_format = format)?;
+    app.state::<ConverterState>().0.lock().unwrap().take();
+
+    println!(
+        "[Capture] Format set to {} Hz, {} channel(s), mask {:#b}",
+        sample_rate, channels, channel_mask
+    );
+    let _ = app.emit("capture:format", format);
+    Ok(format)
+}
+
+/// Converts interleaved little-endian f32 audio to 16 kHz Int16 PCM for the worker.
+///
+/// The webview may not honour the requested rate or channel count, so the
+/// actual values come in the `sample-rate` and `channels` headers. Returns
+/// whole chunks only, which may be none for a short input.
+#[tauri::command]
+pub fn convert_audio(app: AppHandle, request: Request<'_>) -> Result<Response, String> {
+    let InvokeBody::Raw(bytes) = request.body() else {
+        return Err("Expected raw float samples".into());
+    };
+
+    let configured = get(&app);
+    let format = CaptureFormatSettings {
+        sample_rate: header(&request, "sample-rate").unwrap_or(configured.sample_rate),
+        channels: header(&request, "channels").unwrap_or(configured.channels),
+        channel_mask: configured.channel_mask,
+    };
+    format.validate()?;
+
+    let samples: Vec<f32> = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    let chunk_samples = transcription::inference(&app).chunk_samples();
+
+    let state = app.state::<ConverterState>();
+    let mut converter = state.0.lock().unwrap();
+    let current = converter
+        .as_ref()
+        .map(|(current, resampler)| (*current, resampler.chunk_samples));
+    if current != Some((format, chunk_samples)) {
+        let resampler = Resampler::new(
+            format.sample_rate as f64,
+            format.channels,
+            format.channel_mask,
+            chunk_samples,
+        );
+        *converter = Some((format, resampler));
+    }
+
+    let (_, resampler) = converter.as_mut().unwrap();
+    Ok(Response::new(resampler.push(&samples).concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(sample_rate: u32, channels: u16, channel_mask: u32) -> CaptureFormatSettings {
+        CaptureFormatSettings {
+            sample_rate,
+            channels,
+            channel_mask,
+        }
+    }
+
+    fn sine(rate: f64, frequency: f64, seconds: f64) -> Vec<f32> {
+        (0..(rate * seconds) as usize)
+            .map(|n| {
+                (0.5 * (2.0 * std::f64::consts::PI * frequency * n as f64 / rate).sin()) as f32
+            })
+            .collect()
+    }
+
+    /// RMS of the output after the filter settled, on a 0..1 scale.
+    fn rms(chunks: &[Vec<u8>]) -> f64 {
+        let samples: Vec<f64> = chunks
+            .concat()
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f64 / 32768.0)
+            .skip(TARGET_SAMPLE_RATE as usize / 10)
+            .collect();
+        (samples.iter().map(|x| x * x).sum::<f64>() / samples.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn validates_the_channel_mask() {
+        assert!(format(48_000, 2, 0b11).validate().is_ok());
+        assert!(format(48_000, 2, 0b10).validate().is_ok());
+        assert!(format(48_000, 2, 0).validate().is_err());
+        assert!(format(48_000, 2, 0b100).validate().is_err());
+        assert!(format(48_000, 32, u32::MAX).validate().is_ok());
+        assert!(format(48_000, 0, 1).validate().is_err());
+        assert!(format(48_000, 33, 1).validate().is_err());
+        assert!(format(4_000, 1, 1).validate().is_err());
+    }
+
+    #[test]
+    fn keeps_16_khz_mono_and_fills_whole_chunks() {
+        let mut resampler = Resampler::new(16_000.0, 1, 1, 160);
+        let chunks = resampler.push(&vec![0.25; 1_600]);
+        assert!(chunks.len() >= 9);
+        assert!(chunks.iter().all(|chunk| chunk.len() == 320));
+        assert_eq!(i16::from_le_bytes([chunks[0][0], chunks[0][1]]), 8191);
+    }
+
+    #[test]
+    fn mixes_only_the_selected_channels() {
+        let stereo: Vec<f32> = (0..3_200)
+            .map(|n| if n % 2 == 0 { 0.0 } else { 0.5 })
+            .collect();
+        let mut resampler = Resampler::new(16_000.0, 2, 0b10, 160);
+        let chunks = resampler.push(&stereo);
+        assert_eq!(i16::from_le_bytes([chunks[0][0], chunks[0][1]]), 16383);
+    }
+
+    #[test]
+    fn downsampling_keeps_speech_and_filters_out_what_would_alias() {
+        let mut resampler = Resampler::new(48_000.0, 1, 1, 160);
+        let speech = rms(&resampler.push(&sine(48_000.0, 1_000.0, 1.0)));
+        assert!(
+            (speech - 0.5 / 2f64.sqrt()).abs() < 0.02,
+            "speech rms {}",
+            speech
+        );
+
+        // 12 kHz would fold back to 4 kHz without the low-pass.
+        let mut resampler = Resampler::new(48_000.0, 1, 1, 160);
+        let aliased = rms(&resampler.push(&sine(48_000.0, 12_000.0, 1.0)));
+        assert!(aliased < 0.01, "aliased rms {}", aliased);
+    }
+
+    #[test]
+    fn filters_across_buffer_boundaries() {
+        let input = sine(44_100.0, 1_000.0, 0.5);
+        let mut whole = Resampler::new(44_100.0, 1, 1, 160);
+        let mut split = Resampler::new(44_100.0, 1, 1, 160);
+        let expected = whole.push(&input).concat();
+        let mut actual = Vec::new();
+        for buffer in input.chunks(1_000) {
+            actual.extend(split.push(buffer).concat());
+        }
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+#[cfg(desktop)]
+use crate::panel;
+use crate::{capture, profiles};
+
+/// Startup flags, e.g. `signos --hidden --profile meetings --start-capture --monitor 2`.
+#[derive(Debug, Clone, Default)]
+pub struct CliArgs {
+    pub hidden: bool,
+    pub profile: Option<String>,
+    pub start_capture: bool,
+    /// 1-based index into the available monitors.
+    pub monitor: Option<usize>,
+}
+
+impl CliArgs {
+    /// Unknown arguments are ignored, since the OS and `signos://` links add their own.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().peekable();
+
+        while let Some(arg) = args.next() {
+            // Accept both `--flag value` and `--flag=value`. A missing value
+            // leaves the next flag alone.
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next_if(|next| !next.starts_with("--")))
+            };
+
+            match flag.as_str() {
+                "--hidden" => parsed.hidden = true,
+                "--start-capture" => parsed.start_capture = true,
+                "--profile" => parsed.profile = value().filter(|name| !name.is_empty()),
+                "--monitor" => match value().and_then(|n| n.parse().ok()).filter(|&n| n > 0) {
+                    Some(monitor) => parsed.monitor = Some(monitor),
+                    None => eprintln!("[CLI] --monitor expects a number starting at 1"),
+                },
+                _ => {}
+            }
+        }
+
+        parsed
+    }
+}
+
+/// Flags that need the frontend, applied once it reports it is ready.
+pub struct CliState(Mutex<Option<CliArgs>>);
+
+impl CliState {
+    pub fn new(args: CliArgs) -> Self {
+        Self(Mutex::new(Some(args)))
+    }
+}
+
+fn apply_actions(app: &AppHandle, args: &CliArgs) {
+    if let Some(profile) = &args.profile {
+        if let Err(e) = profiles::switch(app, profile) {
+            eprintln!("[CLI] {}", e);
+        }
+    }
+
+    if args.start_capture && !capture::status(app).active {
+        if let Err(e) = capture::request_start(app) {
+            eprintln!("[CLI] Failed to start capture: {}", e);
+        }
+    }
+}
+
+/// Applies the arguments of a second launch to the running instance.
+#[cfg(desktop)]
+pub fn forward(app: &AppHandle, argv: Vec<String>) {
+    // Links are delivered by the deep-link plugin (see `intents`).
+    let is_link = argv.iter().skip(1).any(|arg| arg.starts_with("signos:"));
+    let args = CliArgs::parse(argv.into_iter().skip(1));
+    println!("[CLI] Forwarded from a second instance: {:?}", args);
+
+    let window = app.get_webview_window(panel::MAIN_WINDOW);
+    if let (Some(window), Some(monitor)) = (window, args.monitor) {
+        if let Err(e) = panel::place(&window, Some(monitor)) {
+            eprintln!("[CLI] Failed to move panel: {}", e);
+        }
+    }
+
+    let result = match (args.hidden, is_link) {
+        (true, _) => panel::hide(app),
+        // Launching the app again brings the panel back.
+        (false, false) => panel::show(app),
+        (false, true) => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("[CLI] Failed to update panel: {}", e);
+    }
+
+    apply_actions(app, &args);
+}
+
+/// Applies the startup flags that were waiting for the frontend (see `startup::frontend_ready`).
+pub fn run_pending(app: &AppHandle) {
+    let pending = app.state::<CliState>().0.lock().unwrap().take();
+    if let Some(args) = pending {
+        apply_actions(app, &args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> CliArgs {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_flags_and_values() {
+        let args = parse(&[
+            "--hidden",
+            "--profile",
+            "meetings",
+            "--start-capture",
+            "--monitor",
+            "2",
+        ]);
+        assert!(args.hidden);
+        assert!(args.start_capture);
+        assert_eq!(args.profile.as_deref(), Some("meetings"));
+        assert_eq!(args.monitor, Some(2));
+    }
+
+    #[test]
+    fn accepts_inline_values() {
+        let args = parse(&["--profile=lectures", "--monitor=1"]);
+        assert_eq!(args.profile.as_deref(), Some("lectures"));
+        assert_eq!(args.monitor, Some(1));
+    }
+
+    #[test]
+    fn ignores_unknown_and_invalid_arguments() {
+        let args = parse(&[
+            "-psn_0_12345",
+            "signos://toggle",
+            "--profile=",
+            "--monitor",
+            "0",
+        ]);
+        assert!(!args.hidden);
+        assert_eq!(args.profile, None);
+        assert_eq!(args.monitor, None);
+
+        assert_eq!(parse(&["--monitor", "second"]).monitor, None);
+        assert_eq!(parse(&["--profile"]).profile, None);
+    }
+
+    #[test]
+    fn does_not_take_the_next_flag_as_a_value() {
+        let args = parse(&["--profile", "--hidden", "--monitor", "--start-capture"]);
+        assert_eq!(args.profile, None);
+        assert!(args.hidden);
+        assert_eq!(args.monitor, None);
+        assert!(args.start_capture);
+    }
+}
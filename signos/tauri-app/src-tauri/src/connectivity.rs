@@ -0,0 +1,200 @@
+//! Watches whether the worker is reachable while capturing and fails
+//! transcription over to the webview's own speech recognizer while it is not.
+//!
+//! That recognizer is the platform's speech service, which is itself
+//! cloud-backed in WebView2 and Chromium, so it covers an unreachable worker
+//! rather than a machine that is offline altogether.
+//!
+//! The webview owns both recognizers: it follows `transcription:provider`,
+//! and reports a dropped worker connection with `report_stt_dropped` so the
+//! switch does not wait for the next probe. Probes only run while capturing,
+//! and not on a metered connection.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+use crate::capture::{self, CaptureStatus};
+use crate::retry::{self, Failure};
+use crate::usage::{self, Integration};
+use crate::util::now_ms;
+use crate::{profiles, proxy, recording, settings};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConnectivitySettings {
+    pub probe_interval_seconds: u64,
+    /// Switch to the webview's recognizer when the worker is unreachable mid-session.
+    #[serde(alias = "localFallback")]
+    pub browser_fallback: bool,
+}
+
+impl Default for ConnectivitySettings {
+    fn default() -> Self {
+        Self {
+            probe_interval_seconds: 10,
+            browser_fallback: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SttProvider {
+    /// Streaming models behind the worker.
+    Cloud,
+    /// The webview's speech recognizer (Web Speech API).
+    #[serde(alias = "local")]
+    Browser,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityStatus {
+    pub online: bool,
+    pub provider: SttProvider,
+    /// Unix timestamp (ms) of the last probe.
+    pub checked_at: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+struct ProviderChange<'a> {
+    provider: SttProvider,
+    reason: &'a str,
+}
+
+pub struct ConnectivityState(Mutex<ConnectivityStatus>);
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self(Mutex::new(ConnectivityStatus {
+            online: true,
+            provider: SttProvider::Cloud,
+            checked_at: None,
+        }))
+    }
+}
+
+fn status(app: &AppHandle) -> ConnectivityStatus {
+    app.state::<ConnectivityState>().0.lock().unwrap().clone()
+}
+
+fn probe(app: &AppHandle) -> bool {
+    let agent: ureq::Agent = proxy::agent_config(app)
+        .timeout_global(Some(PROBE_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let url = profiles::worker_endpoint(app, "health");
+    // Any answer means the worker is reachable. Repeated failures trip the
+    // streaming breaker, so the frontend hears `integrations:degraded`.
+    retry::call(app, Integration::CloudStt, 1, || {
+        agent.get(&url).call().map(|_| ()).map_err(Failure::from)
+    })
+    .is_ok()
+}
+
+fn switch_provider(app: &AppHandle, provider: SttProvider, reason: &str) {
+    {
+        let state = app.state::<ConnectivityState>();
+        let mut status = state.0.lock().unwrap();
+        if status.provider == provider {
+            return;
+        }
+        status.provider = provider;
+    }
+
+    println!(
+        "[Connectivity] Transcribing with {:?} ({})",
+        provider, reason
+    );
+    let _ = app.emit(
+        "transcription:provider",
+        ProviderChange { provider, reason },
+    );
+
+    if let Err(e) = recording::annotate_provider(app, provider, reason) {
+        eprintln!("[Connectivity] Failed to annotate session: {}", e);
+    }
+}
+
+fn set_online(app: &AppHandle, online: bool) {
+    let changed = {
+        let state = app.state::<ConnectivityState>();
+        let mut status = state.0.lock().unwrap();
+        status.checked_at = Some(now_ms());
+        std::mem::replace(&mut status.online, online) != online
+    };
+    if changed {
+        println!(
+            "[Connectivity] {}",
+            if online { "Online" } else { "Offline" }
+        );
+        let _ = app.emit(
+            if online {
+                "network:online"
+            } else {
+                "network:offline"
+            },
+            (),
+        );
+    }
+
+    let capturing = capture::status(app).active;
+    let config = settings::get(app).connectivity;
+    match status(app).provider {
+        SttProvider::Cloud if !online && capturing && config.browser_fallback => {
+            switch_provider(app, SttProvider::Browser, "worker unreachable")
+        }
+        SttProvider::Browser if online => {
+            switch_provider(app, SttProvider::Cloud, "worker reachable")
+        }
+        _ => {}
+    }
+}
+
+/// Starts the probe loop.
+pub fn init(app: &AppHandle) {
+    app.manage(ConnectivityState::default());
+
+    // A new capture always starts on the worker.
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let active = serde_json::from_str::<CaptureStatus>(event.payload())
+            .map(|status| status.active)
+            .unwrap_or(true);
+        if !active {
+            let state = handle.state::<ConnectivityState>();
+            state.0.lock().unwrap().provider = SttProvider::Cloud;
+        }
+    });
+
+    let app = app.clone();
+    thread::spawn(move || loop {
+        let interval = settings::get(&app).connectivity.probe_interval_seconds;
+        thread::sleep(Duration::from_secs(interval.max(1)));
+        if !capture::status(&app).active || usage::is_metered(&app) {
+            continue;
+        }
+        set_online(&app, probe(&app));
+    });
+}
+
+#[tauri::command]
+pub fn get_connectivity(app: AppHandle) -> ConnectivityStatus {
+    status(&app)
+}
+
+/// Called by the webview when the worker connection closes mid-session. The
+/// next probe that reaches the worker switches back.
+#[tauri::command]
+pub fn report_stt_dropped(app: AppHandle) {
+    if settings::get(&app).connectivity.browser_fallback && capture::status(&app).active {
+        switch_provider(&app, SttProvider::Browser, "connection lost");
+    }
+}
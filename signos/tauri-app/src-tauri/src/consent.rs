@@ -0,0 +1,185 @@
+//! Consent policy and the on-screen capture indicator.
+//!
+//! With `consent.required` on, capture only runs after the user confirms,
+//! through `confirm_consent`, that everyone present agreed to being captured.
+//! Consent covers one capture session; it lapses once capture has been stopped
+//! for longer than a pipeline restart takes. Starts requested by the backend
+//! and native capture wait for consent, and capture that still comes up
+//! without it is stopped again.
+//!
+//! The tray icon shows a capture dot on its own (see `badge`); the optional
+//! indicator window keeps one visible over everything else as well.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Listener, Manager, Webview};
+#[cfg(desktop)]
+use tauri::{WebviewUrl, WebviewWindowBuilder};
+
+use crate::capture::{self, CaptureStatus};
+use crate::util::now_ms;
+use crate::{security, settings};
+
+/// A stop shorter than this (a watchdog restart, a webview reload) keeps consent.
+const RESTART_GRACE_MS: u64 = 10_000;
+#[cfg(desktop)]
+const INDICATOR_WINDOW: &str = "capture-indicator";
+#[cfg(desktop)]
+const INDICATOR_SIZE: f64 = 14.0;
+#[cfg(desktop)]
+const INDICATOR_MARGIN: f64 = 6.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConsentSettings {
+    /// Capture needs `confirm_consent` first.
+    pub required: bool,
+    /// Always-on-top dot in the top-right screen corner while capturing.
+    pub indicator_window: bool,
+}
+
+#[derive(Default)]
+struct Consent {
+    granted_at: Option<u64>,
+    /// When capture last stopped, if it is not running.
+    stopped_at: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct ConsentState(Mutex<Consent>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsentStatus {
+    pub required: bool,
+    pub granted: bool,
+}
+
+fn granted(app: &AppHandle) -> bool {
+    let consent = app.state::<ConsentState>();
+    let consent = consent.0.lock().unwrap();
+    consent.granted_at.is_some()
+        && consent
+            .stopped_at
+            .is_none_or(|stopped_at| now_ms().saturating_sub(stopped_at) < RESTART_GRACE_MS)
+}
+
+pub fn status(app: &AppHandle) -> ConsentStatus {
+    ConsentStatus {
+        required: settings::get(app).consent.required,
+        granted: granted(app),
+    }
+}
+
+/// Whether capture may run under the current policy.
+pub fn allowed(app: &AppHandle) -> bool {
+    !settings::get(app).consent.required || granted(app)
+}
+
+/// Called when capture came up without consent; stops it and asks for consent.
+pub fn deny(app: &AppHandle) {
+    eprintln!("[Consent] Capture started without consent, stopping it");
+    let _ = capture::request_stop(app);
+    let _ = app.emit("consent:required", ());
+}
+
+#[cfg(desktop)]
+fn show_indicator(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(INDICATOR_WINDOW) {
+        return window.show();
+    }
+
+    let window = WebviewWindowBuilder::new(
+        app,
+        INDICATOR_WINDOW,
+        WebviewUrl::App("indicator.html".into()),
+    )
+    .title("Signos is capturing")
+    .inner_size(INDICATOR_SIZE, INDICATOR_SIZE)
+    .resizable(false)
+    .decorations(false)
+    .transparent(true)
+    .shadow(false)
+    .always_on_top(true)
+    .visible_on_all_workspaces(true)
+    .skip_taskbar(true)
+    .focused(false)
+    .visible(false)
+    .build()?;
+    window.set_ignore_cursor_events(true)?;
+
+    if let Some(monitor) = window.primary_monitor()? {
+        let scale = monitor.scale_factor();
+        let origin = monitor.position().to_logical::<f64>(scale);
+        let size = monitor.size().to_logical::<f64>(scale);
+        window.set_position(tauri::LogicalPosition::new(
+            origin.x + size.width - INDICATOR_SIZE - INDICATOR_MARGIN,
+            origin.y + INDICATOR_MARGIN,
+        ))?;
+    }
+    window.show()
+}
+
+#[cfg(desktop)]
+fn update_indicator(app: &AppHandle, capturing: bool) {
+    let enabled = settings::get(app).consent.indicator_window;
+    let result = match (
+        capturing && enabled,
+        app.get_webview_window(INDICATOR_WINDOW),
+    ) {
+        (true, _) => show_indicator(app),
+        (false, Some(window)) => window.hide(),
+        (false, None) => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("[Consent] Failed to update capture indicator: {}", e);
+    }
+}
+
+#[cfg(mobile)]
+fn update_indicator(_app: &AppHandle, _capturing: bool) {}
+
+pub fn init(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let Ok(status) = serde_json::from_str::<CaptureStatus>(event.payload()) else {
+            return;
+        };
+        {
+            let consent = handle.state::<ConsentState>();
+            let mut consent = consent.0.lock().unwrap();
+            match (status.active, consent.stopped_at) {
+                (true, _) => consent.stopped_at = None,
+                (false, None) => consent.stopped_at = Some(now_ms()),
+                (false, Some(_)) => {}
+            }
+        }
+        update_indicator(&handle, status.active);
+    });
+
+    let handle = app.clone();
+    app.listen_any("settings:changed", move |_| {
+        update_indicator(&handle, capture::status(&handle).active);
+    });
+}
+
+#[tauri::command]
+pub fn get_consent_status(app: AppHandle) -> ConsentStatus {
+    status(&app)
+}
+
+/// Records that everyone present agreed to capture, for the next capture session.
+#[tauri::command]
+pub fn confirm_consent(app: AppHandle, webview: Webview) -> Result<ConsentStatus, String> {
+    security::guard(&webview, "confirm_consent", None)?;
+
+    {
+        let consent = app.state::<ConsentState>();
+        let mut consent = consent.0.lock().unwrap();
+        consent.granted_at = Some(now_ms());
+        consent.stopped_at = None;
+    }
+    println!("[Consent] Consent confirmed");
+    Ok(status(&app))
+}
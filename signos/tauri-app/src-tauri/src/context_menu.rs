@@ -0,0 +1,115 @@
+//! Native right-click menu on the panel.
+//!
+//! Built by the backend so the basic controls stay reachable when the webview
+//! only shows captions. The frontend forwards `contextmenu` events to
+//! `show_context_menu`; menu clicks are handled here.
+
+#[cfg(desktop)]
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Window};
+#[cfg(desktop)]
+use tauri::{LogicalPosition, Wry};
+
+#[cfg(desktop)]
+use crate::{capture, panel, profiles, settings};
+
+// Prefixed so the tray's menu handler, which sees every menu event, ignores them.
+#[cfg(desktop)]
+const TOGGLE_PANEL: &str = "panel-menu:toggle-panel";
+#[cfg(desktop)]
+const TOGGLE_PAUSE: &str = "panel-menu:toggle-pause";
+#[cfg(desktop)]
+const QUIT: &str = "panel-menu:quit";
+#[cfg(desktop)]
+const PROFILE_PREFIX: &str = "panel-menu:profile:";
+
+#[cfg(desktop)]
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let status = capture::status(app);
+    let profiles = settings::get(app).profiles;
+
+    let pause_label = match status.paused {
+        true => "Resume Captions",
+        false => "Pause Captions",
+    };
+
+    let profile_menu = Submenu::new(app, format!("Profile: {}", profiles.active), true)?;
+    for profile in &profiles.profiles {
+        profile_menu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", PROFILE_PREFIX, profile.name),
+            &profile.name,
+            true,
+            profile.name == profiles.active,
+            None::<&str>,
+        )?)?;
+    }
+
+    Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, TOGGLE_PANEL, "Hide Panel", true, None::<&str>)?,
+            &MenuItem::with_id(app, TOGGLE_PAUSE, pause_label, status.active, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &profile_menu,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, QUIT, "Quit Signos", true, None::<&str>)?,
+        ],
+    )
+}
+
+#[cfg(desktop)]
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    let result = match id {
+        TOGGLE_PANEL => panel::toggle(app).map_err(|e| e.to_string()),
+        TOGGLE_PAUSE => {
+            let paused = capture::status(app).paused;
+            capture::set_paused(app, !paused).map_err(|e| e.to_string())
+        }
+        QUIT => {
+            app.exit(0);
+            Ok(())
+        }
+        _ => match id.strip_prefix(PROFILE_PREFIX) {
+            Some(profile) => profiles::switch(app, profile).map(|_| ()),
+            None => return,
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("[ContextMenu] '{}' failed: {}", id, e);
+    }
+}
+
+#[cfg(desktop)]
+pub fn init(app: &AppHandle) {
+    app.on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+}
+
+#[cfg(mobile)]
+pub fn init(_app: &AppHandle) {}
+
+/// Pops up the menu at `x`, `y` (logical, relative to the window), or at the cursor.
+#[tauri::command]
+pub fn show_context_menu(
+    app: AppHandle,
+    window: Window,
+    x: Option<f64>,
+    y: Option<f64>,
+) -> Result<(), String> {
+    #[cfg(desktop)]
+    {
+        let menu = build_menu(&app).map_err(|e| e.to_string())?;
+        match (x, y) {
+            (Some(x), Some(y)) => window.popup_menu_at(&menu, LogicalPosition::new(x, y)),
+            _ => window.popup_menu(&menu),
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    #[cfg(mobile)]
+    {
+        let _ = (app, window, x, y);
+        Err("Context menus are not supported on this platform".into())
+    }
+}
@@ -0,0 +1,385 @@
+//! Two-way conversation mode between a hearing speaker and a deaf user.
+//!
+//! Turns alternate. While the hearing speaker has the floor their speech is
+//! captioned as usual. The deaf user's reply is composed by typing or
+//! fingerspelling, then voiced with the webview's speech synthesis or typed
+//! into the focused app. Capture is paused while a reply is spoken so it does
+//! not end up in the captions. Every turn change is emitted as
+//! `conversation:state` so the panel can follow along.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{capture, settings};
+
+/// Speaking pace assumed when the webview never reports the end of a reply.
+const MS_PER_WORD: u64 = 450;
+const SPEAKING_MARGIN: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplyOutput {
+    /// Read out loud with the webview's speech synthesis.
+    #[default]
+    Speech,
+    /// Typed into the focused app, e.g. a meeting chat.
+    Type,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConversationSettings {
+    pub output: ReplyOutput,
+    /// Speech synthesis voice name. `None` uses the system default.
+    pub voice: Option<String>,
+    /// Speech rate, 1.0 being normal.
+    pub rate: f32,
+    /// Pause capture while a reply is spoken.
+    pub pause_capture: bool,
+}
+
+impl Default for ConversationSettings {
+    fn default() -> Self {
+        Self {
+            output: ReplyOutput::default(),
+            voice: None,
+            rate: 1.0,
+            pause_capture: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Turn {
+    /// Conversation mode is off.
+    #[default]
+    Off,
+    /// The hearing speaker has the floor and is being captioned.
+    Listening,
+    /// The deaf user is writing or spelling a reply.
+    Composing,
+    /// The reply is being voiced.
+    Speaking,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationStatus {
+    pub turn: Turn,
+    /// Reply being composed.
+    pub draft: String,
+    /// Replies sent since the mode was started.
+    pub replies: u32,
+    pub last_reply: Option<String>,
+}
+
+#[derive(Default)]
+struct Conversation {
+    status: ConversationStatus,
+    /// Capture was paused by us for the reply being spoken.
+    paused_capture: bool,
+}
+
+#[derive(Default)]
+pub struct ConversationState(Mutex<Conversation>);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpeakRequest<'a> {
+    /// Matches `ConversationStatus::replies` at the time of the request.
+    reply: u32,
+    text: &'a str,
+    voice: Option<&'a str>,
+    rate: f32,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+
+    /// `kCGHIDEventTap`
+    const HID_EVENT_TAP: u32 = 0;
+    /// UTF-16 units a single keyboard event carries.
+    const MAX_UNITS: usize = 20;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+        fn CGEventCreateKeyboardEvent(source: *const c_void, key: u16, down: bool) -> *mut c_void;
+        fn CGEventKeyboardSetUnicodeString(event: *mut c_void, length: usize, text: *const u16);
+        fn CGEventPost(tap: u32, event: *mut c_void);
+        fn CFRelease(cf: *const c_void);
+    }
+
+    pub fn type_text(text: &str) -> Result<(), String> {
+        if unsafe { AXIsProcessTrusted() } == 0 {
+            return Err("Typing replies needs Accessibility access".into());
+        }
+        let units: Vec<u16> = text.encode_utf16().collect();
+        for chunk in units.chunks(MAX_UNITS) {
+            for down in [true, false] {
+                unsafe {
+                    let event = CGEventCreateKeyboardEvent(std::ptr::null(), 0, down);
+                    if event.is_null() {
+                        return Err("Failed to create a keyboard event".into());
+                    }
+                    CGEventKeyboardSetUnicodeString(event, chunk.len(), chunk.as_ptr());
+                    CGEventPost(HID_EVENT_TAP, event);
+                    CFRelease(event);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+        VIRTUAL_KEY,
+    };
+
+    fn key(unit: u16, up: bool) -> INPUT {
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: VIRTUAL_KEY(0),
+                    wScan: unit,
+                    dwFlags: match up {
+                        true => KEYEVENTF_UNICODE | KEYEVENTF_KEYUP,
+                        false => KEYEVENTF_UNICODE,
+                    },
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+
+    pub fn type_text(text: &str) -> Result<(), String> {
+        let inputs: Vec<INPUT> = text
+            .encode_utf16()
+            .flat_map(|unit| [key(unit, false), key(unit, true)])
+            .collect();
+        let sent = unsafe { SendInput(&inputs, size_of::<INPUT>() as i32) };
+        match sent as usize == inputs.len() {
+            true => Ok(()),
+            false => Err("Input was blocked by another application".into()),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn type_text(_text: &str) -> Result<(), String> {
+        Err("Typing replies is not supported on this platform".into())
+    }
+}
+
+pub fn status(app: &AppHandle) -> ConversationStatus {
+    app.state::<ConversationState>()
+        .0
+        .lock()
+        .unwrap()
+        .status
+        .clone()
+}
+
+/// Applies `f` to the conversation and emits `conversation:state` if the mode is on.
+fn update(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Conversation) -> Result<(), String>,
+) -> Result<ConversationStatus, String> {
+    let status = {
+        let state = app.state::<ConversationState>();
+        let mut conversation = state.0.lock().unwrap();
+        if conversation.status.turn == Turn::Off {
+            return Err("Conversation mode is off".into());
+        }
+        f(&mut conversation)?;
+        conversation.status.clone()
+    };
+    let _ = app.emit("conversation:state", &status);
+    Ok(status)
+}
+
+/// Hands the floor back to the hearing speaker once reply `reply` was voiced.
+fn finish_reply(app: &AppHandle, reply: u32) -> Result<ConversationStatus, String> {
+    let mut resume = false;
+    let status = update(app, |conversation| {
+        if conversation.status.turn == Turn::Speaking && conversation.status.replies == reply {
+            conversation.status.turn = Turn::Listening;
+            resume = std::mem::take(&mut conversation.paused_capture);
+        }
+        Ok(())
+    })?;
+
+    // Left alone if the user resumed or stopped in the meantime.
+    if resume && capture::status(app).paused {
+        if let Err(e) = capture::set_paused(app, false) {
+            eprintln!("[Conversation] Failed to resume capture: {}", e);
+        }
+    }
+    Ok(status)
+}
+
+fn speak(app: &AppHandle, reply: u32, text: &str) -> Result<(), String> {
+    let config = settings::get(app).conversation;
+    let capture = capture::status(app);
+    if config.pause_capture && capture.active && !capture.paused {
+        app.state::<ConversationState>()
+            .0
+            .lock()
+            .unwrap()
+            .paused_capture = true;
+        capture::set_paused(app, true).map_err(|e| e.to_string())?;
+    }
+
+    app.emit(
+        "conversation:speak",
+        SpeakRequest {
+            reply,
+            text,
+            voice: config.voice.as_deref(),
+            rate: config.rate,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    // In case the webview never reports back, e.g. after a reload.
+    let words = text.split_whitespace().count() as u64;
+    let timeout = Duration::from_millis(words * MS_PER_WORD) + SPEAKING_MARGIN;
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(timeout.div_f32(config.rate.max(0.1)));
+        let _ = finish_reply(&app, reply);
+    });
+    Ok(())
+}
+
+/// Appends a fingerspelled word to the draft while conversation mode is on.
+pub fn on_spelled_word(app: &AppHandle, word: &str) {
+    if status(app).turn == Turn::Off {
+        return;
+    }
+    let _ = update(app, |conversation| {
+        let status = &mut conversation.status;
+        if status.turn == Turn::Speaking {
+            return Ok(());
+        }
+        if !status.draft.is_empty() {
+            status.draft.push(' ');
+        }
+        status.draft.push_str(&word.to_lowercase());
+        status.turn = Turn::Composing;
+        Ok(())
+    });
+}
+
+/// Turns conversation mode on, starting with the hearing speaker's turn.
+/// Starts capture if it isn't running.
+#[tauri::command]
+pub fn start_conversation(app: AppHandle) -> Result<ConversationStatus, String> {
+    let status = {
+        let state = app.state::<ConversationState>();
+        let mut conversation = state.0.lock().unwrap();
+        *conversation = Conversation::default();
+        conversation.status.turn = Turn::Listening;
+        conversation.status.clone()
+    };
+    let _ = app.emit("conversation:state", &status);
+
+    if !capture::status(&app).active {
+        capture::request_start(&app).map_err(|e| e.to_string())?;
+    }
+    println!("[Conversation] Started");
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn stop_conversation(app: AppHandle) -> ConversationStatus {
+    let resume = {
+        let state = app.state::<ConversationState>();
+        let mut conversation = state.0.lock().unwrap();
+        std::mem::take(&mut *conversation).paused_capture
+    };
+    if resume && capture::status(&app).paused {
+        let _ = capture::set_paused(&app, false);
+    }
+
+    let status = ConversationStatus::default();
+    let _ = app.emit("conversation:state", &status);
+    println!("[Conversation] Stopped");
+    status
+}
+
+#[tauri::command]
+pub fn get_conversation_status(app: AppHandle) -> ConversationStatus {
+    status(&app)
+}
+
+/// Replaces the reply being composed. An empty draft gives the floor back to
+/// the hearing speaker.
+#[tauri::command]
+pub fn set_reply_draft(app: AppHandle, text: String) -> Result<ConversationStatus, String> {
+    update(&app, |conversation| {
+        let status = &mut conversation.status;
+        if status.turn == Turn::Speaking {
+            return Err("A reply is still being spoken".into());
+        }
+        status.turn = match text.trim().is_empty() {
+            true => Turn::Listening,
+            false => Turn::Composing,
+        };
+        status.draft = text;
+        Ok(())
+    })
+}
+
+/// Voices `text`, or the current draft, with the configured output.
+#[tauri::command]
+pub fn send_reply(app: AppHandle, text: Option<String>) -> Result<ConversationStatus, String> {
+    let mut reply = (0, String::new());
+    let status = update(&app, |conversation| {
+        let status = &mut conversation.status;
+        if status.turn == Turn::Speaking {
+            return Err("A reply is still being spoken".into());
+        }
+        let text = text.unwrap_or_else(|| status.draft.clone());
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Nothing to reply".into());
+        }
+
+        status.replies += 1;
+        status.draft.clear();
+        status.last_reply = Some(text.to_string());
+        status.turn = Turn::Speaking;
+        reply = (status.replies, text.to_string());
+        Ok(())
+    })?;
+    let (id, text) = reply;
+
+    let result = match settings::get(&app).conversation.output {
+        ReplyOutput::Speech => speak(&app, id, &text).map(|_| status),
+        ReplyOutput::Type => platform::type_text(&text).and_then(|_| finish_reply(&app, id)),
+    };
+    if let Err(e) = &result {
+        eprintln!("[Conversation] Failed to send reply: {}", e);
+        let _ = finish_reply(&app, id);
+    }
+    result
+}
+
+/// Reported by the webview when speech synthesis finished reply `reply`.
+#[tauri::command]
+pub fn finish_speaking(app: AppHandle, reply: u32) -> Result<ConversationStatus, String> {
+    finish_reply(&app, reply)
+}
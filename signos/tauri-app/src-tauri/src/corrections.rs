@@ -0,0 +1,163 @@
+//! Fixing recognition errors in stored transcripts.
+//!
+//! A correction replaces a segment's text in `session.json`, keeping the first
+//! recognized text next to it. Exports render from the stored session, so they
+//! pick corrections up on their own. When asked to, a short corrected run of
+//! words is also added to the glossary of the session's profile, so the same
+//! mistake is fixed in future captions.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::glossary::{self, GlossaryEntry};
+use crate::recording::{self, Segment};
+use crate::util::now_ms;
+
+/// Longest run of words learned from a single correction.
+const MAX_LEARNED_WORDS: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectedSegment {
+    pub session_id: String,
+    pub segment_id: usize,
+    pub segment: Segment,
+    /// Glossary entry added from the correction, if any.
+    pub learned: Option<GlossaryEntry>,
+}
+
+/// The words `corrected` changed in `original`, when they form one short run:
+/// `("signo s", "Signos")` for "thanks signo s team" -> "thanks Signos team".
+fn replaced_run(original: &str, corrected: &str) -> Option<(String, String)> {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = corrected.split_whitespace().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_run = &old[prefix..old.len() - suffix];
+    let new_run = &new[prefix..new.len() - suffix];
+    if old_run.is_empty()
+        || new_run.is_empty()
+        || old_run.len() > MAX_LEARNED_WORDS
+        || new_run.len() > MAX_LEARNED_WORDS
+    {
+        return None;
+    }
+
+    let trim = |run: &[&str]| {
+        run.join(" ")
+            .trim_matches(|c: char| c.is_ascii_punctuation())
+            .to_string()
+    };
+    let (pattern, replacement) = (trim(old_run), trim(new_run));
+    (!pattern.is_empty() && !replacement.is_empty() && pattern != replacement)
+        .then_some((pattern, replacement))
+}
+
+/// Replaces the text of the segment at `index` in session `id` (the active or
+/// latest one if `None`).
+fn correct(
+    app: &AppHandle,
+    id: Option<&str>,
+    index: usize,
+    text: &str,
+    learn: bool,
+) -> Result<CorrectedSegment, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Corrected text cannot be empty".into());
+    }
+
+    let mut previous = None;
+    let session = recording::update_session(app, id, |session| {
+        if let Some(segment) = session.segments.get_mut(index) {
+            previous = Some(segment.text.clone());
+            if segment.original_text.is_none() {
+                segment.original_text = Some(segment.text.clone());
+            }
+            segment.text = text.to_string();
+            segment.corrected_at = Some(now_ms());
+        }
+    })?;
+    let previous = previous.ok_or("Segment does not exist")?;
+    let segment = session.segments[index].clone();
+
+    let learned = match learn.then(|| replaced_run(&previous, text)).flatten() {
+        Some((pattern, replacement)) => {
+            let entry = GlossaryEntry {
+                pattern,
+                replacement,
+                regex: false,
+            };
+            match glossary::learn(app, &session.profile, entry.clone()) {
+                Ok(true) => Some(entry),
+                Ok(false) => None,
+                Err(e) => {
+                    eprintln!("[Corrections] Failed to add to the glossary: {}", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let corrected = CorrectedSegment {
+        session_id: session.id,
+        segment_id: index,
+        segment,
+        learned,
+    };
+    println!(
+        "[Corrections] Corrected segment {} of session {}",
+        index, corrected.session_id
+    );
+    let _ = app.emit("recording:segment-corrected", &corrected);
+    Ok(corrected)
+}
+
+/// Applies a correction to the segment of the active recording whose caption
+/// had `timestamp`, as sent back by a relay interpreter.
+pub fn correct_caption(app: &AppHandle, timestamp: u64, text: &str) {
+    let Ok(session) = recording::current_session(app) else {
+        return;
+    };
+    if !recording::is_recording(app) {
+        return;
+    }
+    let Some(index) = session
+        .segments
+        .iter()
+        .position(|segment| segment.timestamp == timestamp)
+    else {
+        return;
+    };
+
+    if let Err(e) = correct(app, Some(&session.id), index, text, false) {
+        eprintln!("[Corrections] Failed to apply correction: {}", e);
+    }
+}
+
+/// Replaces the text of a stored segment, `segment_id` being its index in the
+/// session. With `learn`, a short corrected run of words is added to the
+/// glossary of the session's profile.
+#[tauri::command]
+pub fn correct_segment(
+    app: AppHandle,
+    session_id: String,
+    segment_id: usize,
+    new_text: String,
+    learn: Option<bool>,
+) -> Result<CorrectedSegment, String> {
+    correct(
+        &app,
+        Some(&session_id),
+        segment_id,
+        &new_text,
+        learn.unwrap_or(false),
+    )
+}
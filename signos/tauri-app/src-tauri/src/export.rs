@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::connectivity::SttProvider;
+use crate::recording::{self, Bookmark, Session};
+use crate::redaction;
+
+/// How long an SRT cue stays up when the next segment does not cut it short.
+const MAX_CUE_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// `[mm:ss] text` lines, with bookmarks marked inline.
+    Text,
+    Srt,
+}
+
+/// `12:34`, or `1:02:34` past the hour.
+fn format_offset(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs / 3600 {
+        0 => format!("{:02}:{:02}", secs / 60, secs % 60),
+        hours => format!("{}:{:02}:{:02}", hours, secs / 60 % 60, secs % 60),
+    }
+}
+
+/// `01:02:03,456`
+fn format_srt_time(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+fn bookmark_text(bookmark: &Bookmark) -> String {
+    match &bookmark.label {
+        Some(label) => format!("Bookmark: {}", label),
+        None => "Bookmark".into(),
+    }
+}
+
+/// Segments (prefixed with the speaker's name when one is assigned), bookmarks
+/// and provider switches as (offset, text) in spoken order.
+fn entries(session: &Session) -> Vec<(u64, String)> {
+    let mut entries: Vec<(u64, String)> = session
+        .segments
+        .iter()
+        .map(|segment| {
+            let text = segment.text.trim();
+            let text = match segment
+                .speaker
+                .and_then(|id| session.speaker_names.get(&id))
+            {
+                Some(name) => format!("{}: {}", name, text),
+                None => text.to_string(),
+            };
+            (segment.offset_ms, text)
+        })
+        .chain(
+            session
+                .bookmarks
+                .iter()
+                .map(|bookmark| (bookmark.offset_ms, format!("★ {}", bookmark_text(bookmark)))),
+        )
+        .chain(session.provider_switches.iter().map(|switch| {
+            let provider = match switch.provider {
+                SttProvider::Cloud => "cloud",
+                SttProvider::Browser => "browser",
+            };
+            (
+                switch.offset_ms,
+                format!(
+                    "⇄ Switched to {} transcription ({})",
+                    provider, switch.reason
+                ),
+            )
+        }))
+        .collect();
+    entries.sort_by_key(|(offset, _)| *offset);
+    entries
+}
+
+fn to_text(session: &Session) -> String {
+    entries(session)
+        .into_iter()
+        .map(|(offset, text)| format!("[{}] {}\n", format_offset(offset), text))
+        .collect()
+}
+
+fn to_srt(session: &Session) -> String {
+    let entries = entries(session);
+    let mut srt = String::new();
+
+    for (index, (start, text)) in entries.iter().enumerate() {
+        let end = entries
+            .get(index + 1)
+            .map(|(next, _)| (*next).max(start + 1))
+            .unwrap_or(u64::MAX)
+            .min(start + MAX_CUE_MS);
+        srt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            format_srt_time(*start),
+            format_srt_time(end),
+            text
+        ));
+    }
+    srt
+}
+
+/// Renders a stored session for saving or sharing. Redaction is applied again,
+/// so sessions recorded before it was turned on are masked too.
+#[tauri::command]
+pub fn export_session(app: AppHandle, id: String, format: ExportFormat) -> Result<String, String> {
+    let mut session = recording::load_session(&app, &id)?;
+    for segment in &mut session.segments {
+        if let Cow::Owned(text) = redaction::redact(&app, &segment.text) {
+            segment.text = text;
+        }
+    }
+    Ok(match format {
+        ExportFormat::Text => to_text(&session),
+        ExportFormat::Srt => to_srt(&session),
+    })
+}
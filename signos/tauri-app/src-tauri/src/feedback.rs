@@ -0,0 +1,262 @@
+//! Sound and haptic cues for events the user should notice without looking
+//! at the panel, e.g. capture starting while the overlay is hidden.
+//!
+//! Each kind has its own system sound (and haptic pattern where the hardware
+//! has one), so they can be told apart by ear or touch alone.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener};
+
+use crate::capture::CaptureStatus;
+use crate::settings;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FeedbackSettings {
+    pub enabled: bool,
+    pub sound: bool,
+    /// Trackpad haptics on macOS, the Taptic Engine on iOS.
+    pub haptics: bool,
+    /// Cue when capture starts and stops.
+    pub capture: bool,
+    pub keywords: bool,
+}
+
+impl Default for FeedbackSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sound: true,
+            haptics: true,
+            capture: true,
+            keywords: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeedbackKind {
+    CaptureStart,
+    CaptureStop,
+    Keyword,
+    Error,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::{
+        NSHapticFeedbackManager, NSHapticFeedbackPattern, NSHapticFeedbackPerformanceTime,
+        NSHapticFeedbackPerformer, NSSound,
+    };
+    use objc2_foundation::NSString;
+    use tauri::AppHandle;
+
+    use super::FeedbackKind;
+
+    pub fn sound(app: &AppHandle, kind: FeedbackKind) -> Result<(), String> {
+        let name = match kind {
+            FeedbackKind::CaptureStart => "Tink",
+            FeedbackKind::CaptureStop => "Pop",
+            FeedbackKind::Keyword => "Glass",
+            FeedbackKind::Error => "Basso",
+        };
+        app.run_on_main_thread(
+            move || match NSSound::soundNamed(&NSString::from_str(name)) {
+                Some(sound) => {
+                    sound.play();
+                }
+                None => eprintln!("[Feedback] System sound '{}' not found", name),
+            },
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    /// Only felt while a finger is resting on a Force Touch trackpad.
+    pub fn haptic(app: &AppHandle, kind: FeedbackKind) -> Result<(), String> {
+        let pattern = match kind {
+            FeedbackKind::CaptureStart | FeedbackKind::CaptureStop => {
+                NSHapticFeedbackPattern::LevelChange
+            }
+            FeedbackKind::Keyword => NSHapticFeedbackPattern::Alignment,
+            FeedbackKind::Error => NSHapticFeedbackPattern::Generic,
+        };
+        app.run_on_main_thread(move || {
+            NSHapticFeedbackManager::defaultPerformer().performFeedbackPattern_performanceTime(
+                pattern,
+                NSHapticFeedbackPerformanceTime::Now,
+            );
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use tauri::AppHandle;
+    use windows::core::HSTRING;
+    use windows::Win32::Media::Audio::{PlaySoundW, SND_ALIAS, SND_ASYNC, SND_NODEFAULT};
+
+    use super::FeedbackKind;
+
+    /// Sound scheme aliases, so the user's chosen Windows sounds are used.
+    pub fn sound(_app: &AppHandle, kind: FeedbackKind) -> Result<(), String> {
+        let alias = match kind {
+            FeedbackKind::CaptureStart => "DeviceConnect",
+            FeedbackKind::CaptureStop => "DeviceDisconnect",
+            FeedbackKind::Keyword => "SystemNotification",
+            FeedbackKind::Error => "SystemHand",
+        };
+        let played = unsafe {
+            PlaySoundW(
+                &HSTRING::from(alias),
+                None,
+                SND_ALIAS | SND_ASYNC | SND_NODEFAULT,
+            )
+        };
+        played
+            .ok()
+            .map_err(|e| format!("Failed to play '{}': {}", alias, e))
+    }
+
+    pub fn haptic(_app: &AppHandle, _kind: FeedbackKind) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+    use objc2::MainThreadMarker;
+    use objc2_ui_kit::{
+        UIImpactFeedbackGenerator, UINotificationFeedbackGenerator, UINotificationFeedbackType,
+    };
+    use tauri::AppHandle;
+
+    use super::FeedbackKind;
+
+    /// The hardware switch silences system sounds, so iOS relies on haptics.
+    pub fn sound(_app: &AppHandle, _kind: FeedbackKind) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn haptic(app: &AppHandle, kind: FeedbackKind) -> Result<(), String> {
+        app.run_on_main_thread(move || {
+            let Some(mtm) = MainThreadMarker::new() else {
+                return;
+            };
+            let impact = |intensity| {
+                UIImpactFeedbackGenerator::new(mtm).impactOccurredWithIntensity(intensity)
+            };
+            let notify =
+                |kind| UINotificationFeedbackGenerator::new(mtm).notificationOccurred(kind);
+
+            match kind {
+                FeedbackKind::CaptureStart => impact(1.0),
+                FeedbackKind::CaptureStop => impact(0.5),
+                FeedbackKind::Keyword => notify(UINotificationFeedbackType::Warning),
+                FeedbackKind::Error => notify(UINotificationFeedbackType::Error),
+            }
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::process::Command;
+
+    use tauri::AppHandle;
+
+    use super::FeedbackKind;
+
+    /// freedesktop sound theme names, played through libcanberra.
+    pub fn sound(_app: &AppHandle, kind: FeedbackKind) -> Result<(), String> {
+        let id = match kind {
+            FeedbackKind::CaptureStart => "device-added",
+            FeedbackKind::CaptureStop => "device-removed",
+            FeedbackKind::Keyword => "message-new-instant",
+            FeedbackKind::Error => "dialog-error",
+        };
+        Command::new("canberra-gtk-play")
+            .args(["-i", id])
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to run canberra-gtk-play: {}", e))
+    }
+
+    pub fn haptic(_app: &AppHandle, _kind: FeedbackKind) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+    use tauri::AppHandle;
+
+    use super::FeedbackKind;
+
+    pub fn sound(_app: &AppHandle, _kind: FeedbackKind) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn haptic(_app: &AppHandle, _kind: FeedbackKind) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Plays the cue for `kind` through whichever outputs are enabled.
+pub fn play(app: &AppHandle, kind: FeedbackKind) {
+    let feedback = settings::get(app).feedback;
+    if !feedback.enabled {
+        return;
+    }
+    if feedback.sound {
+        if let Err(e) = platform::sound(app, kind) {
+            eprintln!("[Feedback] Failed to play {:?} sound: {}", kind, e);
+        }
+    }
+    if feedback.haptics {
+        if let Err(e) = platform::haptic(app, kind) {
+            eprintln!("[Feedback] Failed to play {:?} haptic: {}", kind, e);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    // Same as announcements: only start/stop transitions, not pause/resume.
+    let was_active = Mutex::new(false);
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let Ok(status) = serde_json::from_str::<CaptureStatus>(event.payload()) else {
+            return;
+        };
+        let mut was_active = was_active.lock().unwrap();
+        if status.active == *was_active {
+            return;
+        }
+        *was_active = status.active;
+
+        if settings::get(&handle).feedback.capture {
+            let kind = match status.active {
+                true => FeedbackKind::CaptureStart,
+                false => FeedbackKind::CaptureStop,
+            };
+            play(&handle, kind);
+        }
+    });
+
+    let handle = app.clone();
+    app.listen_any("keyword:hit", move |_| {
+        if settings::get(&handle).feedback.keywords {
+            play(&handle, FeedbackKind::Keyword);
+        }
+    });
+}
+
+/// Lets the frontend cue its own events, e.g. a failed worker connection.
+#[tauri::command]
+pub fn play_feedback(app: AppHandle, kind: FeedbackKind) {
+    play(&app, kind);
+}
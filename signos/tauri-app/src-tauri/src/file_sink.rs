@@ -0,0 +1,134 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::{focus, fs_access, metrics, settings};
+
+/// Appends final captions to plain text files.
+///
+/// The path template supports `~` and the placeholders `{date}`, `{app}`
+/// (frontmost application), `{profile}` and `{language}`. The resolved file
+/// has to be in the data directory or a folder granted with `grant_folder_access`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FileSinkSettings {
+    pub enabled: bool,
+    pub path_template: String,
+    /// Prefix each line with the caption time.
+    pub timestamps: bool,
+}
+
+impl Default for FileSinkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path_template: "~/Captions/{date}/{app}.txt".into(),
+            timestamps: true,
+        }
+    }
+}
+
+/// File the last caption was appended to.
+#[derive(Default)]
+pub struct FileSinkState(Mutex<Option<PathBuf>>);
+
+/// Placeholder values become a single path component, never a traversal.
+fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    match cleaned.trim().trim_matches('.') {
+        "" => "unknown".into(),
+        name => name.to_string(),
+    }
+}
+
+fn resolve_path(app: &AppHandle, template: &str) -> Result<PathBuf, String> {
+    let settings = settings::get(app);
+
+    let template = match template.strip_prefix("~/") {
+        Some(rest) => {
+            let home = app.path().home_dir().map_err(|e| e.to_string())?;
+            format!("{}/{}", home.display(), rest)
+        }
+        None => template.to_string(),
+    };
+
+    let path = template
+        .replace("{date}", &Local::now().format("%Y-%m-%d").to_string())
+        .replace("{app}", &sanitize(&focus::current(app).unwrap_or_default()))
+        .replace("{profile}", &sanitize(&settings.profiles.active))
+        .replace("{language}", &sanitize(&settings.transcription.language));
+    let path = PathBuf::from(path);
+
+    if !path.is_absolute() {
+        return Err("The transcript path must be absolute or start with ~/".into());
+    }
+    fs_access::check(app, &path)?;
+    Ok(path)
+}
+
+fn append(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+pub fn write_caption(app: &AppHandle, caption: &Caption) {
+    let config = settings::get(app).file_sink;
+    if !config.enabled || !caption.is_final {
+        return;
+    }
+
+    let line = match Local
+        .timestamp_millis_opt(caption.timestamp as i64)
+        .single()
+    {
+        Some(time) if config.timestamps => {
+            format!("[{}] {}", time.format("%H:%M:%S"), caption.text)
+        }
+        _ => caption.text.clone(),
+    };
+
+    let result = resolve_path(app, &config.path_template).and_then(|path| {
+        append(&path, &line).map_err(|e| e.to_string())?;
+        Ok(path)
+    });
+
+    match result {
+        Ok(path) => *app.state::<FileSinkState>().0.lock().unwrap() = Some(path),
+        Err(e) => {
+            eprintln!("[FileSink] Failed to write caption: {}", e);
+            metrics::record_error(app, "fileSink");
+        }
+    }
+}
+
+/// The transcript written to most recently in this run, if any.
+pub fn last_written(app: &AppHandle) -> Option<PathBuf> {
+    app.state::<FileSinkState>().0.lock().unwrap().clone()
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(FileSinkState::default());
+}
+
+/// Shows where the next caption would be written, for the settings UI.
+#[tauri::command]
+pub fn preview_transcript_path(app: AppHandle, template: String) -> Result<String, String> {
+    resolve_path(&app, &template).map(|path| path.display().to_string())
+}
@@ -0,0 +1,202 @@
+//! Fingerspelling recognition from hand landmarks.
+//!
+//! A constrained recognizer: only static handshapes that can be told apart
+//! from which fingers are extended, spread or touching the thumb. Letters
+//! that need motion (J, Z) or a side view are out of scope.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::landmarks::{Hand, LandmarkFrame};
+use crate::{camera, conversation};
+
+/// Frames a handshape must be held before its letter is emitted.
+const HOLD_FRAMES: u32 = 5;
+const MIN_CONFIDENCE: f32 = 0.8;
+/// Frames without a hand that end the current word.
+const WORD_GAP_FRAMES: u32 = 15;
+
+/// What a handshape looks like: thumb and four fingers extended or not, plus
+/// optional index/middle spread and thumb/index contact.
+struct Template {
+    letter: char,
+    extended: [bool; 5],
+    spread: Option<bool>,
+    touch: Option<bool>,
+}
+
+const TEMPLATES: [Template; 12] = [
+    template('A', [true, false, false, false, false], None, Some(false)),
+    template('B', [false, true, true, true, true], Some(false), None),
+    template('D', [false, true, false, false, false], None, None),
+    template('F', [true, false, true, true, true], None, Some(true)),
+    template('I', [false, false, false, false, true], None, None),
+    template('L', [true, true, false, false, false], None, None),
+    template('O', [false, false, false, false, false], None, Some(true)),
+    template('S', [false, false, false, false, false], None, Some(false)),
+    template('U', [false, true, true, false, false], Some(false), None),
+    template('V', [false, true, true, false, false], Some(true), None),
+    template('W', [false, true, true, true, false], None, None),
+    template('Y', [true, false, false, false, true], None, None),
+];
+
+const fn template(
+    letter: char,
+    extended: [bool; 5],
+    spread: Option<bool>,
+    touch: Option<bool>,
+) -> Template {
+    Template {
+        letter,
+        extended,
+        spread,
+        touch,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpelledLetter {
+    pub letter: char,
+    pub confidence: f32,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpelledWord {
+    pub word: String,
+    pub timestamp: u64,
+}
+
+#[derive(Default)]
+struct Speller {
+    candidate: Option<char>,
+    held: u32,
+    /// Last emitted letter; repeating it needs the hand to change shape first.
+    emitted: Option<char>,
+    word: String,
+    frames_without_hand: u32,
+}
+
+/// `None` while fingerspelling is off.
+#[derive(Default)]
+pub struct FingerspellingState(Mutex<Option<Speller>>);
+
+/// Best matching letter and how many of its features matched.
+fn classify(hand: &Hand) -> Option<(char, f32)> {
+    let shape = hand.shape()?;
+
+    TEMPLATES
+        .iter()
+        .map(|template| {
+            let mut total = 5;
+            let mut matched = template
+                .extended
+                .iter()
+                .zip(shape.extended)
+                .filter(|(want, have)| **want == *have)
+                .count();
+            for (want, have) in [
+                (template.spread, shape.spread),
+                (template.touch, shape.touch),
+            ] {
+                if let Some(want) = want {
+                    total += 1;
+                    matched += (want == have) as usize;
+                }
+            }
+            (template.letter, matched as f32 / total as f32)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(letter, score)| (letter, score * hand.score))
+}
+
+impl Speller {
+    fn push(&mut self, app: &AppHandle, frame: &LandmarkFrame) {
+        let Some(hand) = frame.hands.first() else {
+            self.frames_without_hand += 1;
+            self.candidate = None;
+            self.emitted = None;
+            if self.frames_without_hand == WORD_GAP_FRAMES && !self.word.is_empty() {
+                let word = std::mem::take(&mut self.word);
+                conversation::on_spelled_word(app, &word);
+                let _ = app.emit(
+                    "fingerspelling:word",
+                    SpelledWord {
+                        word,
+                        timestamp: frame.timestamp,
+                    },
+                );
+            }
+            return;
+        };
+        self.frames_without_hand = 0;
+
+        let Some((letter, confidence)) =
+            classify(hand).filter(|(_, confidence)| *confidence >= MIN_CONFIDENCE)
+        else {
+            self.candidate = None;
+            self.emitted = None;
+            return;
+        };
+
+        if self.candidate == Some(letter) {
+            self.held += 1;
+        } else {
+            self.candidate = Some(letter);
+            self.held = 1;
+        }
+
+        if self.held == HOLD_FRAMES && self.emitted != Some(letter) {
+            self.emitted = Some(letter);
+            self.word.push(letter);
+            let _ = app.emit(
+                "fingerspelling:letter",
+                SpelledLetter {
+                    letter,
+                    confidence,
+                    timestamp: frame.timestamp,
+                },
+            );
+        }
+    }
+}
+
+/// Feeds a landmark frame to the recognizer while it is running.
+pub fn on_frame(app: &AppHandle, frame: &LandmarkFrame) {
+    let state = app.state::<FingerspellingState>();
+    let mut speller = state.0.lock().unwrap();
+    if let Some(speller) = speller.as_mut() {
+        speller.push(app, frame);
+    }
+}
+
+pub fn is_active(app: &AppHandle) -> bool {
+    app.state::<FingerspellingState>()
+        .0
+        .lock()
+        .unwrap()
+        .is_some()
+}
+
+/// Starts emitting `fingerspelling:letter` and, after a pause, `fingerspelling:word`.
+/// Opens the camera if it is not running yet.
+#[tauri::command]
+pub fn start_fingerspelling(app: AppHandle) -> Result<(), String> {
+    *app.state::<FingerspellingState>().0.lock().unwrap() = Some(Speller::default());
+    if !camera::is_active(&app) {
+        camera::request_start(&app).map_err(|e| e.to_string())?;
+    }
+    println!("[Fingerspelling] Started");
+    Ok(())
+}
+
+/// Stops recognizing. The camera keeps running for the other stages.
+#[tauri::command]
+pub fn stop_fingerspelling(app: AppHandle) {
+    app.state::<FingerspellingState>().0.lock().unwrap().take();
+    println!("[Fingerspelling] Stopped");
+}
@@ -0,0 +1,108 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusChanged {
+    /// Display name of the frontmost application.
+    pub app: String,
+}
+
+/// Last frontmost app seen by the watcher.
+#[derive(Default)]
+pub struct FocusState(Mutex<Option<String>>);
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::NSWorkspace;
+
+    pub fn frontmost_app() -> Option<String> {
+        let app = NSWorkspace::sharedWorkspace().frontmostApplication()?;
+        app.localizedName().map(|name| name.to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::path::Path;
+
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    /// The executable name (without extension) of the foreground window's process.
+    pub fn frontmost_app() -> Option<String> {
+        unsafe {
+            let window = GetForegroundWindow();
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(window, Some(&mut pid));
+            if pid == 0 {
+                return None;
+            }
+
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buffer = [0u16; 260];
+            let mut len = buffer.len() as u32;
+            let result = QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(buffer.as_mut_ptr()),
+                &mut len,
+            );
+            let _ = CloseHandle(process);
+            result.ok()?;
+
+            let path = String::from_utf16_lossy(&buffer[..len as usize]);
+            Path::new(&path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn frontmost_app() -> Option<String> {
+        None
+    }
+}
+
+/// The frontmost app, if the platform reports one.
+pub fn current(app: &AppHandle) -> Option<String> {
+    app.state::<FocusState>().0.lock().unwrap().clone()
+}
+
+/// Starts the watcher that emits `focus:changed` when the frontmost app changes.
+pub fn init(app: &AppHandle) {
+    app.manage(FocusState::default());
+    let app = app.clone();
+
+    thread::spawn(move || {
+        let mut current: Option<String> = None;
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let frontmost = platform::frontmost_app();
+            if frontmost.is_none() || frontmost == current {
+                continue;
+            }
+
+            current = frontmost;
+            *app.state::<FocusState>().0.lock().unwrap() = current.clone();
+            if let Some(name) = &current {
+                let _ = app.emit("focus:changed", FocusChanged { app: name.clone() });
+            }
+        }
+    });
+}
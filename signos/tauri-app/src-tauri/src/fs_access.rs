@@ -0,0 +1,248 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Webview};
+#[cfg(desktop)]
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_dialog::FilePath;
+use tauri_plugin_fs::FsExt;
+
+use crate::{security, settings, storage};
+
+/// Folders the user granted the frontend access to, re-applied at startup.
+///
+/// The static capability only covers the frontend's `config.json`; anything
+/// else has to go through the native folder picker first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FsAccessSettings {
+    pub granted_folders: Vec<String>,
+}
+
+/// Folders revoked in this run. The Tauri scope cannot drop a pattern it was
+/// given, and forbidding one would block granting it again, so the webview
+/// keeps access to them until restart; backend commands refuse them now.
+#[derive(Default)]
+pub struct FsAccessState(Mutex<Vec<PathBuf>>);
+
+fn allow(app: &AppHandle, path: &Path) -> Result<(), String> {
+    app.fs_scope()
+        .allow_directory(path, true)
+        .map_err(|e| e.to_string())
+}
+
+/// Re-grants the persisted folders that still exist.
+#[cfg(desktop)]
+fn pick_folder(app: &AppHandle) -> Result<Option<FilePath>, String> {
+    Ok(app
+        .dialog()
+        .file()
+        .set_title("Grant Signos access to a folder")
+        .blocking_pick_folder())
+}
+
+/// Mobile pickers only hand out access to individual documents.
+#[cfg(mobile)]
+fn pick_folder(_app: &AppHandle) -> Result<Option<FilePath>, String> {
+    Err("Folder access grants are not supported on this platform".into())
+}
+
+/// `path` with symlinks resolved, also for files that do not exist yet: the
+/// nearest existing ancestor is canonicalized and the rest appended.
+fn resolve(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    resolved.extend(rest.iter().rev());
+    resolved
+}
+
+/// Only absolute paths without `..` are checked against the allowed folders.
+fn well_formed(path: &Path) -> Result<(), String> {
+    match path.is_absolute() && !path.components().any(|c| matches!(c, Component::ParentDir)) {
+        true => Ok(()),
+        false => Err(format!(
+            "'{}' must be an absolute path without '..'",
+            path.display()
+        )),
+    }
+}
+
+/// Whether `resolved` lies in `folder` once symlinks in both are followed.
+fn inside(resolved: &Path, folder: &Path) -> bool {
+    resolved.starts_with(resolve(folder))
+}
+
+/// Checks a path a backend command is about to read or write on the
+/// frontend's behalf. Allowed are the data directory, granted folders and
+/// files the user picked in a dialog, minus folders revoked in this run.
+pub fn check(app: &AppHandle, path: &Path) -> Result<(), String> {
+    well_formed(path)?;
+
+    let resolved = resolve(path);
+    let inside = |folder: &Path| inside(&resolved, folder);
+    let revoked = app
+        .try_state::<FsAccessState>()
+        .is_some_and(|state| state.0.lock().unwrap().iter().any(|folder| inside(folder)));
+
+    let allowed = !revoked
+        && (storage::data_dir(app).is_ok_and(|dir| inside(&dir))
+            || settings::get(app)
+                .fs_access
+                .granted_folders
+                .iter()
+                .any(|folder| inside(Path::new(folder)))
+            || app.fs_scope().is_allowed(&resolved));
+    match allowed {
+        true => Ok(()),
+        false => Err(format!(
+            "Signos has no access to '{}'; grant access to its folder first",
+            path.display()
+        )),
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(FsAccessState::default());
+    for folder in settings::get(app).fs_access.granted_folders {
+        let path = PathBuf::from(&folder);
+        if !path.is_dir() {
+            eprintln!("[FsAccess] Skipping missing folder {}", folder);
+            continue;
+        }
+        if let Err(e) = allow(app, &path) {
+            eprintln!("[FsAccess] Failed to grant {}: {}", folder, e);
+        }
+    }
+}
+
+/// Lets the user pick a folder and extends the fs scope to it. Returns `None` if cancelled.
+#[tauri::command]
+pub async fn grant_folder_access(
+    app: AppHandle,
+    webview: Webview,
+) -> Result<Option<String>, String> {
+    security::guard(&webview, "grant_folder_access", None)?;
+    let dialog = app.clone();
+    let picked = tauri::async_runtime::spawn_blocking(move || pick_folder(&dialog))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let Some(picked) = picked else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| e.to_string())?;
+    allow(&app, &path)?;
+    app.state::<FsAccessState>()
+        .0
+        .lock()
+        .unwrap()
+        .retain(|folder| resolve(folder) != resolve(&path));
+
+    let folder = path.display().to_string();
+    settings::update(&app, |s| {
+        if !s.fs_access.granted_folders.contains(&folder) {
+            s.fs_access.granted_folders.push(folder.clone());
+        }
+    })?;
+
+    println!("[FsAccess] Granted {}", folder);
+    Ok(Some(folder))
+}
+
+#[tauri::command]
+pub fn list_folder_access(app: AppHandle) -> Vec<String> {
+    settings::get(&app).fs_access.granted_folders
+}
+
+/// Stops granting the folder at startup and refuses it to backend commands
+/// right away. The webview keeps access until restart; see [`FsAccessState`].
+#[tauri::command]
+pub fn revoke_folder_access(
+    app: AppHandle,
+    webview: Webview,
+    folder: String,
+) -> Result<Vec<String>, String> {
+    security::guard(&webview, "revoke_folder_access", Some(&folder))?;
+    app.state::<FsAccessState>()
+        .0
+        .lock()
+        .unwrap()
+        .push(PathBuf::from(&folder));
+
+    let settings = settings::update(&app, |s| {
+        s.fs_access.granted_folders.retain(|f| *f != folder)
+    })?;
+    Ok(settings.fs_access.granted_folders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("signos-fs-access-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn rejects_relative_paths_and_parent_components() {
+        assert!(well_formed(Path::new("notes/export.txt")).is_err());
+        let dir = scratch("shape");
+        assert!(well_formed(&dir.join("..").join("export.txt")).is_err());
+        assert!(well_formed(&dir.join("export.txt")).is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resolves_files_that_do_not_exist_yet() {
+        let dir = scratch("missing");
+        let path = dir.join("exports").join("today.srt");
+        assert_eq!(resolve(&path), path);
+        assert!(inside(&resolve(&path), &dir));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sibling_folders_with_a_common_prefix_are_outside() {
+        let dir = scratch("prefix");
+        let (granted, sibling) = (dir.join("notes"), dir.join("notes-private"));
+        fs::create_dir_all(&granted).unwrap();
+        fs::create_dir_all(&sibling).unwrap();
+        assert!(!inside(&resolve(&sibling.join("a.txt")), &granted));
+        assert!(inside(&resolve(&granted.join("a.txt")), &granted));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_out_of_a_granted_folder() {
+        let dir = scratch("symlink");
+        let (granted, outside) = (dir.join("granted"), dir.join("outside"));
+        fs::create_dir_all(&granted).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, granted.join("escape")).unwrap();
+
+        let path = granted.join("escape").join("secret.txt");
+        assert_eq!(resolve(&path), outside.join("secret.txt"));
+        assert!(!inside(&resolve(&path), &granted));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,264 @@
+//! Keeps the panel usable over fullscreen games on Windows.
+//!
+//! An always-on-top window still draws over borderless fullscreen games, but
+//! the game can push itself above it when it takes focus, so the panel is
+//! raised again. Exclusive fullscreen owns the display and nothing else is
+//! composited on it; the panel moves to another monitor if there is one, and
+//! otherwise the UI is told it cannot be shown. `display:occluded` reports each
+//! change.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+#[cfg(target_os = "windows")]
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FullscreenSettings {
+    pub enabled: bool,
+    /// Move the panel off a monitor taken by an exclusive fullscreen game.
+    pub move_to_free_monitor: bool,
+}
+
+impl Default for FullscreenSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            move_to_free_monitor: true,
+        }
+    }
+}
+
+// Only Windows detects fullscreen apps.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FullscreenMode {
+    /// Owns the display; overlays are not composited over it.
+    Exclusive,
+    /// A window covering the monitor; overlays still work.
+    Borderless,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OcclusionAction {
+    /// Brought back above the game.
+    Raised,
+    /// Moved to another monitor.
+    Moved,
+    /// Cannot be shown while the game runs.
+    Hidden,
+}
+
+/// Sent with `display:occluded`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Occlusion {
+    /// A fullscreen app covers the panel's monitor.
+    pub occluded: bool,
+    pub mode: Option<FullscreenMode>,
+    pub app: Option<String>,
+    /// 1-based monitor the fullscreen app is on.
+    pub monitor: Option<usize>,
+    pub action: Option<OcclusionAction>,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+#[derive(Default)]
+struct Tracker {
+    status: Occlusion,
+    /// 1-based monitor the panel was moved away from.
+    moved_from: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct FullscreenState(Mutex<Tracker>);
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONULL,
+    };
+    use windows::Win32::UI::Shell::{SHQueryUserNotificationState, QUNS_RUNNING_D3D_FULL_SCREEN};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetClassNameW, GetForegroundWindow, GetShellWindow, GetWindowRect, GetWindowThreadProcessId,
+    };
+
+    use super::FullscreenMode;
+
+    /// The mode of a foreground window covering its whole monitor, with that
+    /// monitor's origin in physical pixels.
+    pub fn foreground_fullscreen() -> Option<(FullscreenMode, (i32, i32))> {
+        unsafe {
+            let window = GetForegroundWindow();
+            if window.is_invalid() || window == GetShellWindow() {
+                return None;
+            }
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(window, Some(&mut pid));
+            if pid == std::process::id() {
+                return None;
+            }
+
+            // The desktop covers every monitor too.
+            let mut class = [0u16; 64];
+            let len = GetClassNameW(window, &mut class).max(0) as usize;
+            if matches!(
+                String::from_utf16_lossy(&class[..len]).as_str(),
+                "Progman" | "WorkerW"
+            ) {
+                return None;
+            }
+
+            let monitor = MonitorFromWindow(window, MONITOR_DEFAULTTONULL);
+            if monitor.is_invalid() {
+                return None;
+            }
+            let mut info = MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                ..Default::default()
+            };
+            if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+                return None;
+            }
+            let mut rect = RECT::default();
+            GetWindowRect(window, &mut rect).ok()?;
+
+            let screen = info.rcMonitor;
+            let covers = rect.left <= screen.left
+                && rect.top <= screen.top
+                && rect.right >= screen.right
+                && rect.bottom >= screen.bottom;
+            if !covers {
+                return None;
+            }
+
+            let exclusive = SHQueryUserNotificationState()
+                .is_ok_and(|state| state == QUNS_RUNNING_D3D_FULL_SCREEN);
+            let mode = match exclusive {
+                true => FullscreenMode::Exclusive,
+                false => FullscreenMode::Borderless,
+            };
+            Some((mode, (screen.left, screen.top)))
+        }
+    }
+}
+
+/// Whether the foreground app covers its whole monitor. Only detected on Windows.
+pub fn is_fullscreen() -> bool {
+    #[cfg(target_os = "windows")]
+    return platform::foreground_fullscreen().is_some();
+
+    #[cfg(not(target_os = "windows"))]
+    false
+}
+
+#[cfg(target_os = "windows")]
+fn update(app: &AppHandle) -> Result<(), String> {
+    use tauri::Emitter;
+
+    use crate::{focus, panel, settings};
+
+    let Some(window) = app.get_webview_window(panel::MAIN_WINDOW) else {
+        return Ok(());
+    };
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let panel_monitor = window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .and_then(|current| {
+            monitors
+                .iter()
+                .position(|monitor| monitor.position() == current.position())
+        });
+
+    let state = app.state::<FullscreenState>();
+    let mut tracker = state.0.lock().unwrap();
+
+    let mut status = Occlusion::default();
+    if let Some((mode, (x, y))) = platform::foreground_fullscreen() {
+        let game_monitor = monitors
+            .iter()
+            .position(|monitor| monitor.position().x == x && monitor.position().y == y);
+        status.mode = Some(mode);
+        status.app = focus::current(app);
+        status.monitor = game_monitor.map(|index| index + 1);
+        status.occluded = game_monitor.is_some() && game_monitor == panel_monitor;
+    }
+
+    if status.occluded {
+        let raised =
+            tracker.status.occluded && tracker.status.action == Some(OcclusionAction::Raised);
+        status.action = Some(match status.mode {
+            Some(FullscreenMode::Borderless) if raised => OcclusionAction::Raised,
+            Some(FullscreenMode::Borderless) => {
+                // Re-entering the topmost band puts the panel above the game again.
+                window
+                    .set_always_on_top(false)
+                    .and_then(|_| window.set_always_on_top(true))
+                    .map_err(|e| e.to_string())?;
+                OcclusionAction::Raised
+            }
+            _ => {
+                let free = (0..monitors.len()).find(|index| Some(*index) != panel_monitor);
+                match free {
+                    Some(index) if settings::get(app).fullscreen.move_to_free_monitor => {
+                        panel::place(&window, Some(index + 1)).map_err(|e| e.to_string())?;
+                        tracker.moved_from = status.monitor;
+                        OcclusionAction::Moved
+                    }
+                    _ => OcclusionAction::Hidden,
+                }
+            }
+        });
+    } else if status.mode.is_none() {
+        if let Some(monitor) = tracker.moved_from.take() {
+            panel::place(&window, Some(monitor)).map_err(|e| e.to_string())?;
+        }
+    } else if tracker.status.action == Some(OcclusionAction::Moved) {
+        // Still in the game, with the panel on the other monitor.
+        status.action = Some(OcclusionAction::Moved);
+    }
+
+    if status != tracker.status {
+        println!("[Fullscreen] {:?}", status);
+        let _ = app.emit("display:occluded", &status);
+        tracker.status = status;
+    }
+    Ok(())
+}
+
+pub fn init(app: &AppHandle) {
+    #[cfg(target_os = "windows")]
+    {
+        let app = app.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(POLL_INTERVAL);
+            if !crate::settings::get(&app).fullscreen.enabled {
+                continue;
+            }
+            if let Err(e) = update(&app) {
+                eprintln!("[Fullscreen] Failed to adjust the panel: {}", e);
+            }
+        });
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = app;
+}
+
+#[tauri::command]
+pub fn get_occlusion(app: AppHandle) -> Occlusion {
+    app.state::<FullscreenState>()
+        .0
+        .lock()
+        .unwrap()
+        .status
+        .clone()
+}
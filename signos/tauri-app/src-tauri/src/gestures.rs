@@ -0,0 +1,147 @@
+//! Gesture shortcuts detected from hand landmarks, so the app can be
+//! controlled from the camera without touching the keyboard.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::landmarks::{LandmarkFrame, INDEX_MCP, THUMB_TIP};
+use crate::{capture, fingerspelling, panel, recording, settings};
+
+/// Frames a gesture must be held before it fires (~0.7s at 15 fps).
+const HOLD_FRAMES: u32 = 10;
+const MIN_CONFIDENCE: f32 = 0.8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GestureSettings {
+    pub enabled: bool,
+    pub bindings: Vec<GestureBinding>,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bindings: vec![
+                GestureBinding {
+                    gesture: Gesture::OpenPalm,
+                    action: GestureAction::TogglePause,
+                },
+                GestureBinding {
+                    gesture: Gesture::ThumbsUp,
+                    action: GestureAction::MarkMoment,
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GestureBinding {
+    pub gesture: Gesture,
+    pub action: GestureAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Gesture {
+    /// All five fingers extended and spread.
+    OpenPalm,
+    /// Fist with the thumb pointing up.
+    ThumbsUp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GestureAction {
+    TogglePanel,
+    ToggleCapture,
+    TogglePause,
+    MarkMoment,
+}
+
+#[derive(Clone, Serialize)]
+struct GestureFired {
+    gesture: Gesture,
+    action: GestureAction,
+}
+
+#[derive(Default)]
+struct Tracker {
+    candidate: Option<Gesture>,
+    held: u32,
+}
+
+#[derive(Default)]
+pub struct GestureState(Mutex<Tracker>);
+
+fn detect(frame: &LandmarkFrame) -> Option<Gesture> {
+    let hand = frame
+        .hands
+        .first()
+        .filter(|hand| hand.score >= MIN_CONFIDENCE)?;
+    let shape = hand.shape()?;
+
+    match shape.extended {
+        [true, true, true, true, true] if shape.spread => Some(Gesture::OpenPalm),
+        [true, false, false, false, false] => {
+            // Image y grows downwards: the thumb tip must sit well above the knuckles.
+            let rise = hand.points[INDEX_MCP][1] - hand.points[THUMB_TIP][1];
+            (rise > hand.palm_size() * 0.5).then_some(Gesture::ThumbsUp)
+        }
+        _ => None,
+    }
+}
+
+fn run_action(app: &AppHandle, gesture: Gesture, action: GestureAction) {
+    println!("[Gestures] {:?} -> {:?}", gesture, action);
+
+    let result = match action {
+        GestureAction::TogglePanel => panel::toggle(app).map_err(|e| e.to_string()),
+        GestureAction::ToggleCapture if capture::status(app).active => {
+            capture::request_stop(app).map_err(|e| e.to_string())
+        }
+        GestureAction::ToggleCapture => capture::request_start(app).map_err(|e| e.to_string()),
+        GestureAction::TogglePause => capture::toggle_paused(app).map_err(|e| e.to_string()),
+        GestureAction::MarkMoment => recording::mark(app, None).map(|_| ()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Gestures] {:?} failed: {}", action, e);
+    }
+
+    let _ = app.emit("gestures:triggered", GestureFired { gesture, action });
+}
+
+/// Fires a bound gesture once it has been held, then waits for the hand to
+/// change before firing again.
+pub fn on_frame(app: &AppHandle, frame: &LandmarkFrame) {
+    let config = settings::get(app).gestures;
+    // Fingerspelled letters use the same handshapes.
+    if !config.enabled || fingerspelling::is_active(app) {
+        return;
+    }
+
+    let gesture = detect(frame);
+    let fired = {
+        let state = app.state::<GestureState>();
+        let mut tracker = state.0.lock().unwrap();
+        if tracker.candidate == gesture {
+            tracker.held += 1;
+        } else {
+            tracker.candidate = gesture;
+            tracker.held = 1;
+        }
+        gesture.filter(|_| tracker.held == HOLD_FRAMES)
+    };
+
+    let Some(gesture) = fired else {
+        return;
+    };
+    for binding in config.bindings.iter().filter(|b| b.gesture == gesture) {
+        run_action(app, gesture, binding.action);
+    }
+}
@@ -0,0 +1,168 @@
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::settings;
+
+/// A replacement such as `{ "pattern": "signo s", "replacement": "Signos" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlossaryEntry {
+    /// Whole words matched case-insensitively, or a regex when `regex` is set.
+    pub pattern: String,
+    /// May refer to regex groups as `$1`.
+    pub replacement: String,
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl GlossaryEntry {
+    fn compile(&self) -> Result<Regex, String> {
+        let pattern = match self.regex {
+            true => self.pattern.clone(),
+            false => format!(r"\b{}\b", regex::escape(self.pattern.trim())),
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.regex)
+            .build()
+            .map_err(|e| e.to_string())
+    }
+}
+
+struct Compiled {
+    entries: Vec<GlossaryEntry>,
+    rules: Vec<(Regex, String)>,
+}
+
+/// Compiled entries of the active profile, rebuilt when they change.
+#[derive(Default)]
+pub struct GlossaryState(Mutex<Option<Compiled>>);
+
+fn profile_name(app: &AppHandle, profile: Option<String>) -> String {
+    profile.unwrap_or_else(|| settings::get(app).profiles.active)
+}
+
+fn entries(app: &AppHandle, profile: &str) -> Result<Vec<GlossaryEntry>, String> {
+    settings::get(app)
+        .profiles
+        .find(profile)
+        .map(|p| p.glossary.clone())
+        .ok_or_else(|| format!("Profile '{}' does not exist", profile))
+}
+
+fn update(
+    app: &AppHandle,
+    profile: &str,
+    f: impl FnOnce(&mut Vec<GlossaryEntry>),
+) -> Result<Vec<GlossaryEntry>, String> {
+    // Fails early for an unknown profile.
+    entries(app, profile)?;
+
+    settings::update(app, |s| {
+        if let Some(p) = s.profiles.profiles.iter_mut().find(|p| p.name == profile) {
+            f(&mut p.glossary);
+        }
+    })?;
+    entries(app, profile)
+}
+
+/// Applies the active profile's glossary to a caption.
+pub fn apply(app: &AppHandle, caption: &mut Caption) {
+    let settings = settings::get(app);
+    let Some(profile) = settings.profiles.find(&settings.profiles.active) else {
+        return;
+    };
+    if profile.glossary.is_empty() {
+        return;
+    }
+
+    let state = app.state::<GlossaryState>();
+    let mut compiled = state.0.lock().unwrap();
+    if compiled.as_ref().map(|c| &c.entries) != Some(&profile.glossary) {
+        let rules = profile
+            .glossary
+            .iter()
+            .filter_map(|entry| match entry.compile() {
+                Ok(regex) => Some((regex, entry.replacement.clone())),
+                Err(e) => {
+                    eprintln!("[Glossary] Skipping '{}': {}", entry.pattern, e);
+                    None
+                }
+            })
+            .collect();
+        *compiled = Some(Compiled {
+            entries: profile.glossary.clone(),
+            rules,
+        });
+    }
+
+    let Some(compiled) = compiled.as_ref() else {
+        return;
+    };
+    for (regex, replacement) in &compiled.rules {
+        if let Cow::Owned(text) = regex.replace_all(&caption.text, replacement) {
+            caption.text = text;
+        }
+    }
+}
+
+/// Adds an entry learned from a correction, unless the profile already has one
+/// for the pattern. Returns whether it was added.
+pub fn learn(app: &AppHandle, profile: &str, entry: GlossaryEntry) -> Result<bool, String> {
+    entry.compile()?;
+    let known = entries(app, profile)?
+        .iter()
+        .any(|e| e.pattern.eq_ignore_ascii_case(&entry.pattern));
+    if known {
+        return Ok(false);
+    }
+
+    update(app, profile, |glossary| glossary.push(entry))?;
+    Ok(true)
+}
+
+/// Lists a profile's glossary, the active profile's by default.
+#[tauri::command]
+pub fn list_glossary(
+    app: AppHandle,
+    profile: Option<String>,
+) -> Result<Vec<GlossaryEntry>, String> {
+    entries(&app, &profile_name(&app, profile))
+}
+
+/// Adds an entry, replacing any with the same pattern.
+#[tauri::command]
+pub fn set_glossary_entry(
+    app: AppHandle,
+    profile: Option<String>,
+    entry: GlossaryEntry,
+) -> Result<Vec<GlossaryEntry>, String> {
+    if entry.pattern.trim().is_empty() {
+        return Err("Pattern cannot be empty".into());
+    }
+    entry.compile()?;
+
+    let profile = profile_name(&app, profile);
+    update(&app, &profile, |glossary| {
+        match glossary.iter_mut().find(|e| e.pattern == entry.pattern) {
+            Some(existing) => *existing = entry,
+            None => glossary.push(entry),
+        }
+    })
+}
+
+#[tauri::command]
+pub fn remove_glossary_entry(
+    app: AppHandle,
+    profile: Option<String>,
+    pattern: String,
+) -> Result<Vec<GlossaryEntry>, String> {
+    let profile = profile_name(&app, profile);
+    update(&app, &profile, |glossary| {
+        glossary.retain(|e| e.pattern != pattern)
+    })
+}
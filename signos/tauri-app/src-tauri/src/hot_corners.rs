@@ -0,0 +1,187 @@
+//! Hot corners: pushing the cursor into a screen corner runs an action.
+//!
+//! The cursor is polled in the backend, so corners work whichever app is in
+//! front. A corner fires once the cursor has rested in it for the dwell time,
+//! and again only after it left. Corners of every monitor count.
+
+// The cursor can only be tracked on desktop.
+#![cfg_attr(mobile, allow(dead_code))]
+
+use std::collections::BTreeMap;
+#[cfg(desktop)]
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::hotkeys::{self, HotkeyAction};
+use crate::{fullscreen, settings};
+
+#[cfg(desktop)]
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Monitors are re-read this often, for displays plugged in or rearranged.
+#[cfg(desktop)]
+const MONITOR_REFRESH: Duration = Duration::from_secs(2);
+/// Size of the corner target in logical pixels.
+const CORNER_SIZE: f64 = 4.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HotCornerSettings {
+    pub enabled: bool,
+    pub corners: BTreeMap<Corner, HotkeyAction>,
+    /// How long the cursor has to rest in a corner, so passing through does nothing.
+    pub dwell_ms: u64,
+    /// Ignore corners while a fullscreen app (a game or a presentation) is in front.
+    pub disable_while_fullscreen: bool,
+}
+
+impl Default for HotCornerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            corners: BTreeMap::new(),
+            dwell_ms: 250,
+            disable_while_fullscreen: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A monitor as (x, y, width, height, scale factor), in physical pixels.
+type Screen = (f64, f64, f64, f64, f64);
+
+/// The corner of any screen the point is in.
+fn corner_at(screens: &[Screen], x: f64, y: f64) -> Option<Corner> {
+    screens
+        .iter()
+        .find_map(|&(left, top, width, height, scale)| {
+            let size = CORNER_SIZE * scale;
+            let (right, bottom) = (left + width, top + height);
+            if x < left || x >= right || y < top || y >= bottom {
+                return None;
+            }
+
+            let is_left = x < left + size;
+            let is_right = x >= right - size;
+            let is_top = y < top + size;
+            let is_bottom = y >= bottom - size;
+            match (is_left, is_right, is_top, is_bottom) {
+                (true, _, true, _) => Some(Corner::TopLeft),
+                (_, true, true, _) => Some(Corner::TopRight),
+                (true, _, _, true) => Some(Corner::BottomLeft),
+                (_, true, _, true) => Some(Corner::BottomRight),
+                _ => None,
+            }
+        })
+}
+
+fn trigger(app: &AppHandle, corner: Corner) {
+    let config = settings::get(app).hot_corners;
+    let Some(&action) = config.corners.get(&corner) else {
+        return;
+    };
+    if config.disable_while_fullscreen && fullscreen::is_fullscreen() {
+        return;
+    }
+
+    println!("[HotCorners] {:?} -> {:?}", corner, action);
+    hotkeys::perform(app, action);
+    let _ = app.emit(
+        "hot-corner:triggered",
+        serde_json::json!({ "corner": corner, "action": action }),
+    );
+}
+
+#[cfg(desktop)]
+fn screens(app: &AppHandle) -> Vec<Screen> {
+    app.available_monitors()
+        .map(|monitors| {
+            monitors
+                .iter()
+                .map(|monitor| {
+                    let (position, size) = (monitor.position(), monitor.size());
+                    (
+                        position.x as f64,
+                        position.y as f64,
+                        size.width as f64,
+                        size.height as f64,
+                        monitor.scale_factor(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn init(app: &AppHandle) {
+    #[cfg(desktop)]
+    {
+        let app = app.clone();
+        std::thread::spawn(move || {
+            let mut screens_at: Option<Instant> = None;
+            let mut screens_cache = Vec::new();
+            // Corner the cursor is in, since when, and whether it already fired.
+            let mut resting: Option<(Corner, Instant, bool)> = None;
+
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+                let config = settings::get(&app).hot_corners;
+                if !config.enabled || config.corners.is_empty() {
+                    resting = None;
+                    continue;
+                }
+
+                if screens_at.is_none_or(|at| at.elapsed() >= MONITOR_REFRESH) {
+                    screens_cache = screens(&app);
+                    screens_at = Some(Instant::now());
+                }
+                let corner = app
+                    .cursor_position()
+                    .ok()
+                    .and_then(|position| corner_at(&screens_cache, position.x, position.y));
+
+                resting = match (corner, resting) {
+                    (Some(corner), Some((current, since, fired))) if corner == current => {
+                        let due = since.elapsed() >= Duration::from_millis(config.dwell_ms);
+                        if due && !fired {
+                            trigger(&app, corner);
+                        }
+                        Some((corner, since, fired || due))
+                    }
+                    (Some(corner), _) => Some((corner, Instant::now(), false)),
+                    (None, _) => None,
+                };
+            }
+        });
+    }
+
+    #[cfg(mobile)]
+    let _ = app;
+}
+
+/// Binds `action` to `corner`, or clears the corner.
+#[tauri::command]
+pub fn set_hot_corner(
+    app: AppHandle,
+    corner: Corner,
+    action: Option<HotkeyAction>,
+) -> Result<HotCornerSettings, String> {
+    settings::update(&app, |s| match action {
+        Some(action) => {
+            s.hot_corners.corners.insert(corner, action);
+        }
+        None => {
+            s.hot_corners.corners.remove(&corner);
+        }
+    })
+    .map(|settings| settings.hot_corners)
+}
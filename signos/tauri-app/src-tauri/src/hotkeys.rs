@@ -0,0 +1,424 @@
+// Global shortcuts are desktop-only; on mobile the bindings are kept but never registered.
+#![cfg_attr(mobile, allow(dead_code))]
+
+use std::collections::{BTreeMap, HashMap};
+#[cfg(desktop)]
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+#[cfg(desktop)]
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+#[cfg(desktop)]
+use crate::layouts;
+use crate::onboarding::{self, OnboardingStep};
+use crate::{capture, panel, recording, settings, transcription};
+
+/// Action → accelerator, e.g. `{ "togglePanel": "CmdOrCtrl+Shift+Space" }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HotkeySettings {
+    pub bindings: BTreeMap<HotkeyAction, String>,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            bindings: BTreeMap::from([(HotkeyAction::TogglePanel, "CmdOrCtrl+Shift+Space".into())]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HotkeyAction {
+    TogglePanel,
+    ToggleCapture,
+    TogglePause,
+    CycleLanguage,
+    MarkMoment,
+}
+
+impl HotkeyAction {
+    const ALL: [Self; 5] = [
+        Self::TogglePanel,
+        Self::ToggleCapture,
+        Self::TogglePause,
+        Self::CycleLanguage,
+        Self::MarkMoment,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::TogglePanel => "Show/hide panel",
+            Self::ToggleCapture => "Start/stop capture",
+            Self::TogglePause => "Pause/resume capture",
+            Self::CycleLanguage => "Cycle language",
+            Self::MarkMoment => "Mark this moment",
+        }
+    }
+
+    /// Name passed to `on_hotkey` scripts and `hotkey:pressed` listeners.
+    fn name(self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInfo {
+    pub action: HotkeyAction,
+    pub label: &'static str,
+    pub accelerator: Option<String>,
+}
+
+/// What a registered shortcut triggers.
+#[derive(Debug, Clone)]
+enum Bound {
+    Action(HotkeyAction),
+    /// Recalls the layout preset with this name.
+    Layout(String),
+}
+
+/// Registered shortcut ids, so the plugin callback can find what is bound.
+#[derive(Default)]
+pub struct HotkeyState(Mutex<HashMap<u32, Bound>>);
+
+/// Shortcuts the OS (or the user's muscle memory) already owns.
+#[cfg(target_os = "macos")]
+const RESERVED: &[(&str, &str)] = &[
+    ("Super+Space", "Spotlight"),
+    ("Super+Alt+Space", "Finder search"),
+    ("Super+Tab", "App switcher"),
+    ("Super+Q", "Quit app"),
+    ("Super+W", "Close window"),
+    ("Super+H", "Hide app"),
+    ("Super+M", "Minimize window"),
+    ("Super+Shift+3", "Screenshot"),
+    ("Super+Shift+4", "Screenshot selection"),
+    ("Super+Shift+5", "Screenshot toolbar"),
+    ("Control+Space", "Input source switching"),
+    ("Control+Super+Q", "Lock screen"),
+    ("Control+Super+Space", "Character viewer"),
+];
+
+#[cfg(target_os = "windows")]
+const RESERVED: &[(&str, &str)] = &[
+    ("Alt+Tab", "App switcher"),
+    ("Alt+F4", "Close window"),
+    ("Alt+Space", "Window menu"),
+    ("Control+Shift+Escape", "Task Manager"),
+    ("Super+D", "Show desktop"),
+    ("Super+E", "File Explorer"),
+    ("Super+L", "Lock screen"),
+    ("Super+R", "Run"),
+    ("Super+Tab", "Task view"),
+    ("Super+Space", "Input language switching"),
+    ("Super+Shift+S", "Snipping Tool"),
+    ("Super+Period", "Emoji panel"),
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const RESERVED: &[(&str, &str)] = &[
+    ("Alt+Tab", "App switcher"),
+    ("Alt+F4", "Close window"),
+    ("Super+L", "Lock screen"),
+    ("Control+Alt+T", "Terminal"),
+    ("Control+Alt+Delete", "Log out"),
+    ("Super+Space", "Input source switching"),
+];
+
+#[cfg(desktop)]
+fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    Shortcut::from_str(accelerator)
+        .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))
+}
+
+/// Rejects accelerators that are invalid, reserved by the OS or bound to another action.
+#[cfg(desktop)]
+fn check_conflicts(
+    bindings: &BTreeMap<HotkeyAction, String>,
+    action: HotkeyAction,
+    accelerator: &str,
+) -> Result<Shortcut, String> {
+    let shortcut = parse(accelerator)?;
+
+    if let Some((_, owner)) = RESERVED
+        .iter()
+        .find(|(reserved, _)| parse(reserved).ok() == Some(shortcut))
+    {
+        return Err(format!(
+            "'{}' is used by the system ({})",
+            accelerator, owner
+        ));
+    }
+
+    if let Some((other, _)) = bindings
+        .iter()
+        .find(|(other, bound)| **other != action && parse(bound).ok() == Some(shortcut))
+    {
+        return Err(format!(
+            "'{}' is already bound to '{}'",
+            accelerator,
+            other.label()
+        ));
+    }
+
+    Ok(shortcut)
+}
+
+/// Rejects accelerators that already recall a layout preset other than `except`.
+#[cfg(desktop)]
+fn check_layouts(
+    app: &AppHandle,
+    shortcut: Shortcut,
+    accelerator: &str,
+    except: Option<&str>,
+) -> Result<(), String> {
+    let layouts = settings::get(app).layouts;
+    match layouts.presets.iter().find(|preset| {
+        Some(preset.name.as_str()) != except
+            && preset
+                .hotkey
+                .as_deref()
+                .is_some_and(|bound| parse(bound).ok() == Some(shortcut))
+    }) {
+        Some(preset) => Err(format!(
+            "'{}' already recalls the layout '{}'",
+            accelerator, preset.name
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Runs an action without announcing it; also used by hot corners.
+pub fn perform(app: &AppHandle, action: HotkeyAction) {
+    let result = match action {
+        HotkeyAction::TogglePanel => panel::toggle(app).map_err(|e| e.to_string()),
+        HotkeyAction::ToggleCapture if capture::status(app).active => {
+            capture::request_stop(app).map_err(|e| e.to_string())
+        }
+        HotkeyAction::ToggleCapture => capture::request_start(app).map_err(|e| e.to_string()),
+        HotkeyAction::TogglePause => capture::toggle_paused(app).map_err(|e| e.to_string()),
+        HotkeyAction::CycleLanguage => transcription::cycle_language(app).map(|_| ()),
+        HotkeyAction::MarkMoment => recording::mark(app, None).map(|_| ()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Hotkeys] {:?} failed: {}", action, e);
+    }
+}
+
+fn run_action(app: &AppHandle, action: HotkeyAction) {
+    perform(app, action);
+    let _ = app.emit("hotkey:pressed", action.name());
+}
+
+/// Callback for the global shortcut plugin.
+#[cfg(desktop)]
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+
+    let action = app
+        .state::<HotkeyState>()
+        .0
+        .lock()
+        .unwrap()
+        .get(&shortcut.id())
+        .cloned();
+
+    match action {
+        Some(Bound::Action(action)) => run_action(app, action),
+        Some(Bound::Layout(name)) => {
+            if let Err(e) = layouts::recall(app, &name) {
+                eprintln!("[Hotkeys] Failed to recall layout '{}': {}", name, e);
+            }
+        }
+        None => {}
+    }
+}
+
+/// (Re)registers every binding from settings, replacing the previous set.
+#[cfg(desktop)]
+pub fn register_all(app: &AppHandle) {
+    let shortcuts = app.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        eprintln!("[Hotkeys] Failed to unregister shortcuts: {}", e);
+    }
+
+    let state = app.state::<HotkeyState>();
+    let mut registered = state.0.lock().unwrap();
+    registered.clear();
+
+    let settings = settings::get(app);
+    let layouts = settings.layouts.presets.into_iter().filter_map(|preset| {
+        let accelerator = preset.hotkey?;
+        Some((Bound::Layout(preset.name), accelerator))
+    });
+    let bound = settings
+        .hotkeys
+        .bindings
+        .into_iter()
+        .map(|(action, accelerator)| (Bound::Action(action), accelerator))
+        .chain(layouts);
+
+    for (bound, accelerator) in bound {
+        let result = parse(&accelerator).and_then(|shortcut| {
+            shortcuts
+                .register(shortcut)
+                .map(|_| shortcut)
+                .map_err(|e| e.to_string())
+        });
+
+        match result {
+            Ok(shortcut) => {
+                registered.insert(shortcut.id(), bound);
+            }
+            Err(e) => eprintln!("[Hotkeys] Failed to register {:?}: {}", bound, e),
+        }
+    }
+}
+
+#[cfg(mobile)]
+pub fn register_all(_app: &AppHandle) {}
+
+/// Conflict check plus a trial registration, which fails if another app already holds the shortcut.
+#[cfg(desktop)]
+fn check_available(
+    app: &AppHandle,
+    bindings: &BTreeMap<HotkeyAction, String>,
+    action: HotkeyAction,
+    accelerator: &str,
+) -> Result<(), String> {
+    let shortcut = check_conflicts(bindings, action, accelerator)?;
+    check_layouts(app, shortcut, accelerator, None)?;
+
+    let shortcuts = app.global_shortcut();
+    if bindings.get(&action).and_then(|bound| parse(bound).ok()) != Some(shortcut) {
+        shortcuts
+            .register(shortcut)
+            .and_then(|_| shortcuts.unregister(shortcut))
+            .map_err(|e| format!("'{}' is unavailable: {}", accelerator, e))?;
+    }
+    Ok(())
+}
+
+/// Like `check_available`, for the hotkey recalling layout preset `name`.
+#[cfg(desktop)]
+pub fn check_layout_hotkey(app: &AppHandle, name: &str, accelerator: &str) -> Result<(), String> {
+    let settings = settings::get(app);
+    let bindings = &settings.hotkeys.bindings;
+    let shortcut = parse(accelerator)?;
+
+    if let Some((_, owner)) = RESERVED
+        .iter()
+        .find(|(reserved, _)| parse(reserved).ok() == Some(shortcut))
+    {
+        return Err(format!(
+            "'{}' is used by the system ({})",
+            accelerator, owner
+        ));
+    }
+    if let Some((action, _)) = bindings
+        .iter()
+        .find(|(_, bound)| parse(bound).ok() == Some(shortcut))
+    {
+        return Err(format!(
+            "'{}' is already bound to '{}'",
+            accelerator,
+            action.label()
+        ));
+    }
+    check_layouts(app, shortcut, accelerator, Some(name))?;
+
+    let current = settings
+        .layouts
+        .find(name)
+        .and_then(|preset| preset.hotkey.as_deref())
+        .and_then(|bound| parse(bound).ok());
+    if current != Some(shortcut) {
+        let shortcuts = app.global_shortcut();
+        shortcuts
+            .register(shortcut)
+            .and_then(|_| shortcuts.unregister(shortcut))
+            .map_err(|e| format!("'{}' is unavailable: {}", accelerator, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(mobile)]
+pub fn check_layout_hotkey(
+    _app: &AppHandle,
+    _name: &str,
+    _accelerator: &str,
+) -> Result<(), String> {
+    Err("Global shortcuts are not supported on this platform".into())
+}
+
+#[cfg(mobile)]
+fn check_available(
+    _app: &AppHandle,
+    _bindings: &BTreeMap<HotkeyAction, String>,
+    _action: HotkeyAction,
+    _accelerator: &str,
+) -> Result<(), String> {
+    Err("Global shortcuts are not supported on this platform".into())
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(HotkeyState::default());
+    register_all(app);
+}
+
+#[tauri::command]
+pub fn list_actions(app: AppHandle) -> Vec<ActionInfo> {
+    let bindings = settings::get(&app).hotkeys.bindings;
+
+    HotkeyAction::ALL
+        .into_iter()
+        .map(|action| ActionInfo {
+            action,
+            label: action.label(),
+            accelerator: bindings.get(&action).cloned(),
+        })
+        .collect()
+}
+
+/// Binds `action` to `accelerator`, or unbinds it when `None`, and re-registers all shortcuts.
+#[tauri::command]
+pub fn set_binding(
+    app: AppHandle,
+    action: HotkeyAction,
+    accelerator: Option<String>,
+) -> Result<Vec<ActionInfo>, String> {
+    let bindings = settings::get(&app).hotkeys.bindings;
+    let binding = accelerator.is_some();
+
+    if let Some(accelerator) = &accelerator {
+        check_available(&app, &bindings, action, accelerator)?;
+    }
+
+    settings::update(&app, |s| match accelerator {
+        Some(accelerator) => {
+            s.hotkeys.bindings.insert(action, accelerator);
+        }
+        None => {
+            s.hotkeys.bindings.remove(&action);
+        }
+    })?;
+
+    register_all(&app);
+    if binding {
+        if let Err(e) = onboarding::complete(&app, OnboardingStep::Hotkey) {
+            eprintln!("[Hotkeys] Failed to complete onboarding step: {}", e);
+        }
+    }
+    Ok(list_actions(app))
+}
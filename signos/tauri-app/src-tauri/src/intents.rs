@@ -0,0 +1,301 @@
+//! Actions exposed to OS automation, so Signos can take part in user workflows.
+//!
+//! - macOS Services ("Toggle Signos", "Caption for 30 Minutes", "Translate with
+//!   Signos"), declared under `NSServices` in `Info.plist`.
+//! - `signos://` links, which Shortcuts.app and Siri run through "Open URL":
+//!   `signos://toggle`, `signos://caption?minutes=30`, `signos://translate-clipboard`.
+//!   The Windows jump list launches the app with these links too.
+//!
+//! Native App Intents need Swift-generated metadata the Rust bundle cannot
+//! provide, so both entry points funnel into [`run`] instead.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::usage::{self, Integration};
+use crate::{capture, file_sink, panel, profiles, proxy, recording, secure_input};
+
+const SCHEME: &str = "signos";
+const DEFAULT_CAPTION_MINUTES: u64 = 30;
+/// Longer "caption for" requests are cut down to this.
+const MAX_CAPTION_MINUTES: u64 = 12 * 60;
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intent {
+    Toggle,
+    Show,
+    StartCapture,
+    /// Opens the latest file-sink transcript, else the latest recorded session.
+    OpenTranscript,
+    /// Shows the panel and captions until the time is up.
+    CaptionFor {
+        minutes: u64,
+    },
+    TranslateClipboard,
+    TranslateText(String),
+}
+
+impl Intent {
+    fn from_url(url: &Url) -> Option<Self> {
+        if url.scheme() != SCHEME {
+            return None;
+        }
+
+        // `signos://caption` puts the action in the host, `signos:caption` in the path.
+        let action = url
+            .host_str()
+            .unwrap_or_else(|| url.path())
+            .trim_matches('/');
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        match action {
+            "toggle" => Some(Self::Toggle),
+            "show" => Some(Self::Show),
+            "start-capture" => Some(Self::StartCapture),
+            "open-transcript" => Some(Self::OpenTranscript),
+            "caption" => Some(Self::CaptionFor {
+                minutes: param("minutes")
+                    .and_then(|minutes| minutes.parse().ok())
+                    .unwrap_or(DEFAULT_CAPTION_MINUTES),
+            }),
+            "translate-clipboard" => Some(Self::TranslateClipboard),
+            "translate" => param("text").map(Self::TranslateText),
+            _ => None,
+        }
+    }
+}
+
+/// Bumped by every "caption for" request, so only the latest one stops capture.
+#[derive(Default)]
+struct CaptionTimer(Mutex<u64>);
+
+fn caption_for(app: &AppHandle, minutes: u64) -> Result<(), String> {
+    if minutes == 0 {
+        return Err("Caption time must be at least a minute".into());
+    }
+    let minutes = minutes.min(MAX_CAPTION_MINUTES);
+    let duration = minutes
+        .checked_mul(60)
+        .map(Duration::from_secs)
+        .ok_or("Caption time is too long")?;
+
+    panel::show(app).map_err(|e| e.to_string())?;
+    if !capture::status(app).active {
+        capture::request_start(app).map_err(|e| e.to_string())?;
+    }
+
+    let generation = {
+        let timer = app.state::<CaptionTimer>();
+        let mut generation = timer.0.lock().unwrap();
+        *generation += 1;
+        *generation
+    };
+
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(duration);
+
+        let current = *app.state::<CaptionTimer>().0.lock().unwrap();
+        if current == generation && capture::status(&app).active {
+            println!("[Intents] Caption time is up after {} minutes", minutes);
+            if let Err(e) = capture::request_stop(&app) {
+                eprintln!("[Intents] Failed to stop capture: {}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Asks the worker for the signs of `text` and shows them on the panel (`signs:received`).
+fn translate(app: &AppHandle, text: String) -> Result<(), String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Nothing to translate".into());
+    }
+    if secure_input::is_active(app) {
+        return Err("Translation is paused while a password field is focused".into());
+    }
+    panel::show(app).map_err(|e| e.to_string())?;
+
+    let url = profiles::worker_endpoint(app, "signs");
+    let app = app.clone();
+    thread::spawn(move || {
+        let agent: ureq::Agent = proxy::agent_config(&app)
+            .timeout_global(Some(TRANSLATE_TIMEOUT))
+            .build()
+            .into();
+
+        let result = usage::post_json::<serde_json::Value>(
+            &app,
+            Integration::Signs,
+            &agent,
+            &url,
+            &serde_json::json!({ "text": text }),
+        );
+
+        match result {
+            Ok(signs) => {
+                let _ = app.emit("signs:received", signs);
+            }
+            Err(e) => eprintln!("[Intents] Translation failed: {}", e),
+        }
+    });
+
+    Ok(())
+}
+
+fn open_transcript(app: &AppHandle) -> Result<(), String> {
+    let path = file_sink::last_written(app)
+        .or_else(|| {
+            let id = recording::latest_session_id(app)?;
+            recording::session_path(app, &id).ok()
+        })
+        .ok_or("No transcript has been written yet")?;
+
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+pub fn run(app: &AppHandle, intent: Intent) {
+    let result = match intent {
+        Intent::Toggle => panel::toggle(app).map_err(|e| e.to_string()),
+        Intent::Show => panel::show(app).map_err(|e| e.to_string()),
+        Intent::StartCapture if capture::status(app).active => Ok(()),
+        Intent::StartCapture => capture::request_start(app).map_err(|e| e.to_string()),
+        Intent::OpenTranscript => open_transcript(app),
+        Intent::CaptionFor { minutes } => caption_for(app, minutes),
+        Intent::TranslateClipboard => app
+            .clipboard()
+            .read_text()
+            .map_err(|e| e.to_string())
+            .and_then(|text| translate(app, text)),
+        Intent::TranslateText(text) => translate(app, text),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Intents] Failed: {}", e);
+    }
+}
+
+fn open_urls(app: &AppHandle, urls: &[Url]) {
+    for url in urls {
+        match Intent::from_url(url) {
+            Some(intent) => run(app, intent),
+            None => eprintln!("[Intents] Unknown link '{}'", url),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::rc::Retained;
+    use objc2::runtime::NSObject;
+    use objc2::{define_class, msg_send, DefinedClass, MainThreadMarker, MainThreadOnly};
+    use objc2_app_kit::{NSApplication, NSPasteboard, NSPasteboardTypeString};
+    use objc2_foundation::NSString;
+    use tauri::AppHandle;
+
+    use super::{run, Intent, DEFAULT_CAPTION_MINUTES};
+
+    define_class!(
+        /// Receives the `NSServices` messages declared in `Info.plist`.
+        #[unsafe(super(NSObject))]
+        #[thread_kind = MainThreadOnly]
+        #[name = "SignosServiceProvider"]
+        #[ivars = AppHandle]
+        struct ServiceProvider;
+
+        impl ServiceProvider {
+            #[unsafe(method(toggleSignos:userData:error:))]
+            fn toggle_signos(
+                &self,
+                _pasteboard: &NSPasteboard,
+                _user_data: Option<&NSString>,
+                _error: *mut *mut NSString,
+            ) {
+                run(self.ivars(), Intent::Toggle);
+            }
+
+            #[unsafe(method(captionForThirtyMinutes:userData:error:))]
+            fn caption_for_thirty_minutes(
+                &self,
+                _pasteboard: &NSPasteboard,
+                _user_data: Option<&NSString>,
+                _error: *mut *mut NSString,
+            ) {
+                let minutes = DEFAULT_CAPTION_MINUTES;
+                run(self.ivars(), Intent::CaptionFor { minutes });
+            }
+
+            /// Translates the selected text the service was invoked with.
+            #[unsafe(method(translateText:userData:error:))]
+            fn translate_text(
+                &self,
+                pasteboard: &NSPasteboard,
+                _user_data: Option<&NSString>,
+                _error: *mut *mut NSString,
+            ) {
+                let text = unsafe { pasteboard.stringForType(NSPasteboardTypeString) };
+                match text {
+                    Some(text) => run(self.ivars(), Intent::TranslateText(text.to_string())),
+                    None => eprintln!("[Intents] Service was invoked without text"),
+                }
+            }
+        }
+    );
+
+    impl ServiceProvider {
+        fn new(mtm: MainThreadMarker, app: AppHandle) -> Retained<Self> {
+            let this = Self::alloc(mtm).set_ivars(app);
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    /// Must be called on the main thread, i.e. from `setup`.
+    pub fn register_services(app: &AppHandle) {
+        let Some(mtm) = MainThreadMarker::new() else {
+            eprintln!("[Intents] Services must be registered on the main thread");
+            return;
+        };
+
+        let provider = ServiceProvider::new(mtm, app.clone());
+        unsafe { NSApplication::sharedApplication(mtm).setServicesProvider(Some(&provider)) };
+
+        // NSApplication does not retain its services provider; it lives as long as the app.
+        std::mem::forget(provider);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn register_services(_app: &AppHandle) {}
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(CaptionTimer::default());
+    platform::register_services(app);
+
+    // A link that launched the app, then any opened while it is running.
+    let deep_link = app.deep_link();
+    if let Ok(Some(urls)) = deep_link.get_current() {
+        open_urls(app, &urls);
+    }
+
+    let handle = app.clone();
+    deep_link.on_open_url(move |event| open_urls(&handle, &event.urls()));
+}
@@ -0,0 +1,330 @@
+//! Journal of destructive operations, so the most recent ones can be undone
+//! within a short grace window.
+//!
+//! Deleted files are moved into `trash/` in the data directory and only
+//! removed once the window closes; overwritten settings keep their previous
+//! value in memory. Nothing survives a restart.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::settings::{self, Settings};
+use crate::util::now_ms;
+use crate::{hotkeys, storage};
+
+const TRASH_DIR: &str = "trash";
+const GRACE: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    DeleteSession,
+    PurgeHistory,
+    OverwriteSettings,
+}
+
+/// Sent with `journal:recorded`, `journal:undone` and `journal:expired`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    pub id: u64,
+    pub kind: OperationKind,
+    pub description: String,
+    /// Unix timestamp (ms) after which the operation can no longer be undone.
+    pub expires_at: u64,
+}
+
+enum Undo {
+    /// (original, trashed) paths to move back.
+    Restore(Vec<(PathBuf, PathBuf)>),
+    /// (previous, written) settings of an overwrite.
+    Settings(Box<(Settings, Settings)>),
+}
+
+struct Entry {
+    operation: Operation,
+    undo: Undo,
+}
+
+#[derive(Default)]
+struct Journal {
+    entries: Vec<Entry>,
+    next_id: u64,
+}
+
+#[derive(Default)]
+pub struct JournalState(Mutex<Journal>);
+
+fn trash_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(TRASH_DIR))
+        .map_err(|e| e.to_string())
+}
+
+fn next_id(app: &AppHandle) -> u64 {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    journal.next_id += 1;
+    journal.next_id
+}
+
+fn record(app: &AppHandle, id: u64, kind: OperationKind, description: String, undo: Undo) {
+    let operation = Operation {
+        id,
+        kind,
+        description,
+        expires_at: now_ms() + GRACE.as_millis() as u64,
+    };
+    let _ = app.emit("journal:recorded", &operation);
+
+    let state = app.state::<JournalState>();
+    state
+        .0
+        .lock()
+        .unwrap()
+        .entries
+        .push(Entry { operation, undo });
+
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(GRACE);
+        expire(&app, id);
+    });
+}
+
+/// Drops an entry once its grace window has passed, deleting its trashed files.
+fn expire(app: &AppHandle, id: u64) {
+    let entry = {
+        let state = app.state::<JournalState>();
+        let mut journal = state.0.lock().unwrap();
+        let Some(index) = journal.entries.iter().position(|e| e.operation.id == id) else {
+            return;
+        };
+        journal.entries.remove(index)
+    };
+
+    if let Undo::Restore(_) = entry.undo {
+        if let Ok(dir) = trash_dir(app) {
+            if let Err(e) = fs::remove_dir_all(dir.join(id.to_string())) {
+                eprintln!("[Journal] Failed to empty trash for #{}: {}", id, e);
+            }
+        }
+    }
+    let _ = app.emit("journal:expired", &entry.operation);
+}
+
+/// Deletes `paths` (files or directories) in a way `undo_last_operation` can revert.
+pub fn remove(
+    app: &AppHandle,
+    kind: OperationKind,
+    description: String,
+    paths: Vec<PathBuf>,
+) -> Result<(), String> {
+    let id = next_id(app);
+    let dir = trash_dir(app)?.join(id.to_string());
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let mut moved = Vec::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        let trashed = dir.join(index.to_string());
+        match fs::rename(&path, &trashed) {
+            Ok(()) => moved.push((path, trashed)),
+            Err(e) => eprintln!("[Journal] Failed to remove {}: {}", path.display(), e),
+        }
+    }
+    if moved.is_empty() {
+        let _ = fs::remove_dir_all(&dir);
+        return Err("Nothing was removed".into());
+    }
+
+    println!("[Journal] #{} {}", id, description);
+    record(app, id, kind, description, Undo::Restore(moved));
+    Ok(())
+}
+
+/// Remembers the settings an overwrite replaced, and what it wrote.
+pub fn record_settings(app: &AppHandle, previous: Settings, written: Settings) {
+    record(
+        app,
+        next_id(app),
+        OperationKind::OverwriteSettings,
+        "Overwrote settings".into(),
+        Undo::Settings(Box::new((previous, written))),
+    );
+}
+
+/// Moves trashed files back. On failure, returns how many were restored.
+fn restore_files(moved: &[(PathBuf, PathBuf)]) -> Result<(), (usize, String)> {
+    for (index, (original, trashed)) in moved.iter().enumerate() {
+        if original.exists() {
+            return Err((
+                index,
+                format!("{} exists again; move it away first", original.display()),
+            ));
+        }
+        if let Some(parent) = original.parent() {
+            fs::create_dir_all(parent).map_err(|e| (index, e.to_string()))?;
+        }
+        fs::rename(trashed, original).map_err(|e| (index, e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Three-way merge: values the overwrite changed and nothing touched since go
+/// back to `previous`; anything changed since (new pairings, the device id)
+/// is kept.
+fn revert(
+    current: &mut serde_json::Value,
+    previous: &serde_json::Value,
+    written: &serde_json::Value,
+) {
+    use serde_json::Value;
+
+    if current == written {
+        *current = previous.clone();
+        return;
+    }
+    if let (Value::Object(current), Value::Object(previous), Value::Object(written)) =
+        (current, previous, written)
+    {
+        for (key, value) in current.iter_mut() {
+            if let (Some(previous), Some(written)) = (previous.get(key), written.get(key)) {
+                revert(value, previous, written);
+            }
+        }
+    }
+}
+
+fn revert_settings(app: &AppHandle, previous: &Settings, written: &Settings) -> Result<(), String> {
+    let to_value = |settings: &Settings| serde_json::to_value(settings).map_err(|e| e.to_string());
+    let (previous, written) = (to_value(previous)?, to_value(written)?);
+    let mut merged = to_value(&settings::get(app))?;
+    revert(&mut merged, &previous, &written);
+    let merged: Settings = serde_json::from_value(merged).map_err(|e| e.to_string())?;
+
+    settings::update(app, |current| *current = merged)?;
+    hotkeys::register_all(app);
+    Ok(())
+}
+
+/// Anything left in the trash is from a previous run and can no longer be undone.
+pub fn init(app: &AppHandle) {
+    app.manage(JournalState::default());
+
+    if let Ok(dir) = trash_dir(app) {
+        if dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                eprintln!("[Journal] Failed to empty trash: {}", e);
+            }
+        }
+    }
+}
+
+/// Reverts the most recent operation still within its grace window.
+#[tauri::command]
+pub fn undo_last_operation(app: AppHandle) -> Result<Operation, String> {
+    let state = app.state::<JournalState>();
+    let mut entry = state
+        .0
+        .lock()
+        .unwrap()
+        .entries
+        .pop()
+        .ok_or("Nothing to undo")?;
+
+    let result = match &entry.undo {
+        Undo::Restore(moved) => restore_files(moved).map(|_| {
+            if let Ok(dir) = trash_dir(&app) {
+                let _ = fs::remove_dir_all(dir.join(entry.operation.id.to_string()));
+            }
+        }),
+        Undo::Settings(settings) => {
+            revert_settings(&app, &settings.0, &settings.1).map_err(|e| (0, e))
+        }
+    };
+
+    // Kept for another try, minus whatever was already restored.
+    if let Err((restored, e)) = result {
+        if let Undo::Restore(moved) = &mut entry.undo {
+            moved.drain(..restored);
+        }
+        state.0.lock().unwrap().entries.push(entry);
+        return Err(e);
+    }
+
+    println!("[Journal] Undid #{}", entry.operation.id);
+    let _ = app.emit("journal:undone", &entry.operation);
+    Ok(entry.operation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("signos-journal-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn reverts_an_untouched_overwrite() {
+        let previous = json!({ "theme": "dark", "viewer": { "port": 1 } });
+        let written = json!({ "theme": "light", "viewer": { "port": 2 } });
+        let mut current = written.clone();
+        revert(&mut current, &previous, &written);
+        assert_eq!(current, previous);
+    }
+
+    #[test]
+    fn keeps_values_changed_since_the_overwrite() {
+        let previous = json!({ "theme": "dark", "sync": { "paired": [], "name": "a" } });
+        let written = json!({ "theme": "light", "sync": { "paired": [], "name": "b" } });
+        let mut current =
+            json!({ "theme": "light", "sync": { "paired": ["tablet"], "name": "b" } });
+        revert(&mut current, &previous, &written);
+        assert_eq!(
+            current,
+            json!({ "theme": "dark", "sync": { "paired": ["tablet"], "name": "a" } })
+        );
+    }
+
+    #[test]
+    fn keeps_keys_the_overwrite_did_not_know() {
+        let previous = json!({ "theme": "dark" });
+        let written = json!({ "theme": "light" });
+        let mut current = json!({ "theme": "light", "added": true });
+        revert(&mut current, &previous, &written);
+        assert_eq!(current, json!({ "theme": "dark", "added": true }));
+    }
+
+    #[test]
+    fn restores_trashed_files_until_one_is_in_the_way() {
+        let dir = scratch("restore");
+        let (original, trashed) = (dir.join("a").join("session.json"), dir.join("trashed-a"));
+        let (blocked, trashed_blocked) = (dir.join("b.json"), dir.join("trashed-b"));
+        fs::write(&trashed, "a").unwrap();
+        fs::write(&trashed_blocked, "b").unwrap();
+        fs::write(&blocked, "new").unwrap();
+
+        let moved = [
+            (original.clone(), trashed.clone()),
+            (blocked, trashed_blocked.clone()),
+        ];
+        let (restored, _) = restore_files(&moved).unwrap_err();
+        assert_eq!(restored, 1);
+        assert_eq!(fs::read_to_string(&original).unwrap(), "a");
+        assert!(!trashed.exists());
+        assert!(trashed_blocked.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::captions::Caption;
+use crate::settings;
+
+/// Words the user wants to be alerted about (their name, "deadline", ...).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct KeywordSettings {
+    /// Matched case-insensitively against final captions.
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeywordHit {
+    pub keyword: String,
+    pub text: String,
+    pub timestamp: u64,
+}
+
+/// Emits `keyword:hit` for every configured keyword found in a final caption.
+pub fn check(app: &AppHandle, caption: &Caption) {
+    if !caption.is_final {
+        return;
+    }
+
+    let text = caption.text.to_lowercase();
+    let keywords = settings::get(app).keywords.keywords;
+
+    for keyword in keywords.iter().filter(|k| !k.trim().is_empty()) {
+        if text.contains(&keyword.to_lowercase()) {
+            let _ = app.emit(
+                "keyword:hit",
+                KeywordHit {
+                    keyword: keyword.clone(),
+                    text: caption.text.clone(),
+                    timestamp: caption.timestamp,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Hand landmark inference for sign input.
+//!
+//! Camera frames go through a MediaPipe-compatible hand landmark model
+//! (`hand_landmark.onnx`: a 224x224 RGB crop in, 21 landmarks, hand presence
+//! and handedness out) run with ONNX Runtime. Each result is emitted as
+//! `landmarks:frame` and handed to the recognition stages.
+//!
+//! Only active in builds with the `landmarks` feature; the ONNX Runtime
+//! library is loaded when the first frame arrives.
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{fingerspelling, gestures, power, settings, storage, thermal, watchdog};
+
+const MODEL_FILE: &str = "hand_landmark.onnx";
+/// Used in low-power mode when present.
+const LITE_MODEL_FILE: &str = "hand_landmark_lite.onnx";
+/// Side of the square model input.
+pub const INPUT_SIZE: u32 = 224;
+/// Wait before trying to load a missing or broken model again.
+const LOAD_RETRY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LandmarkSettings {
+    /// Model to load. `None` uses `models/hand_landmark.onnx` in the data directory.
+    pub model_path: Option<String>,
+    /// Smaller model for low-power mode. `None` uses `models/hand_landmark_lite.onnx`.
+    pub lite_model_path: Option<String>,
+    /// ONNX Runtime library. `None` searches the usual library paths.
+    pub runtime_path: Option<String>,
+    /// Hand presence below this is reported as no hand.
+    pub min_confidence: f32,
+}
+
+impl Default for LandmarkSettings {
+    fn default() -> Self {
+        Self {
+            model_path: None,
+            lite_model_path: None,
+            runtime_path: None,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+pub struct VideoFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+    /// Unix timestamp (ms) of capture.
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hand {
+    /// `left` or `right`, as seen by the camera.
+    pub handedness: String,
+    pub score: f32,
+    /// x and y normalized to the frame (0-1), z relative to the wrist.
+    pub points: Vec<[f32; 3]>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LandmarkFrame {
+    pub timestamp: u64,
+    /// Empty when no hand is in view.
+    pub hands: Vec<Hand>,
+}
+
+pub const WRIST: usize = 0;
+const THUMB_IP: usize = 3;
+pub const THUMB_TIP: usize = 4;
+pub const INDEX_MCP: usize = 5;
+const MIDDLE_MCP: usize = 9;
+/// (pip, tip) per finger, index to pinky.
+const FINGERS: [(usize, usize); 4] = [(6, 8), (10, 12), (14, 16), (18, 20)];
+
+/// Coarse handshape features shared by the recognition stages.
+#[derive(Debug, Clone, Copy)]
+pub struct Handshape {
+    /// Thumb, index, middle, ring, pinky.
+    pub extended: [bool; 5],
+    /// Index and middle fingers apart.
+    pub spread: bool,
+    /// Thumb and index tips touching.
+    pub touch: bool,
+}
+
+fn distance(points: &[[f32; 3]], a: usize, b: usize) -> f32 {
+    let (a, b) = (points[a], points[b]);
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+impl Hand {
+    /// Wrist to middle knuckle, the scale the shape thresholds are relative to.
+    pub fn palm_size(&self) -> f32 {
+        distance(&self.points, WRIST, MIDDLE_MCP).max(f32::EPSILON)
+    }
+
+    pub fn shape(&self) -> Option<Handshape> {
+        let points = &self.points;
+        if points.len() < 21 {
+            return None;
+        }
+
+        let mut extended = [false; 5];
+        extended[0] =
+            distance(points, THUMB_TIP, INDEX_MCP) > distance(points, THUMB_IP, INDEX_MCP) * 1.2;
+        for (finger, (pip, tip)) in FINGERS.iter().enumerate() {
+            extended[finger + 1] =
+                distance(points, WRIST, *tip) > distance(points, WRIST, *pip) * 1.1;
+        }
+
+        Some(Handshape {
+            extended,
+            spread: distance(points, FINGERS[0].1, FINGERS[1].1)
+                > distance(points, INDEX_MCP, MIDDLE_MCP) * 1.5,
+            touch: distance(points, THUMB_TIP, FINGERS[0].1) < self.palm_size() * 0.25,
+        })
+    }
+}
+
+/// Holds at most one pending frame; newer frames are dropped while inference is busy.
+pub struct LandmarkState(SyncSender<VideoFrame>);
+
+/// Nearest-neighbour resize to the square model input, as RGB floats in 0-1 (NHWC).
+fn to_input(frame: &VideoFrame) -> Vec<f32> {
+    let size = INPUT_SIZE as usize;
+    let mut input = Vec::with_capacity(size * size * 3);
+    for y in 0..size {
+        let source_y = y * frame.height as usize / size;
+        for x in 0..size {
+            let source_x = x * frame.width as usize / size;
+            let offset = (source_y * frame.width as usize + source_x) * 4;
+            input.extend(
+                frame.rgba[offset..offset + 3]
+                    .iter()
+                    .map(|&channel| channel as f32 / 255.0),
+            );
+        }
+    }
+    input
+}
+
+#[cfg(feature = "landmarks")]
+mod model {
+    use std::path::Path;
+
+    use ort::session::Session;
+    use ort::value::Tensor;
+
+    use super::{Hand, INPUT_SIZE};
+
+    const LANDMARK_COUNT: usize = 21;
+
+    pub struct HandModel(Session);
+
+    /// Some conversions output logits rather than probabilities.
+    fn probability(value: f32) -> f32 {
+        match (0.0..=1.0).contains(&value) {
+            true => value,
+            false => 1.0 / (1.0 + (-value).exp()),
+        }
+    }
+
+    impl HandModel {
+        pub fn load(model: &Path, runtime: Option<&str>) -> Result<Self, String> {
+            if let Some(runtime) = runtime {
+                ort::init_from(runtime)
+                    .commit()
+                    .map_err(|e| e.to_string())?;
+            }
+            Session::builder()
+                .and_then(|builder| builder.commit_from_file(model))
+                .map(Self)
+                .map_err(|e| e.to_string())
+        }
+
+        pub fn run(&mut self, input: Vec<f32>) -> Result<Option<Hand>, String> {
+            let size = INPUT_SIZE as usize;
+            let tensor =
+                Tensor::from_array(([1, size, size, 3], input)).map_err(|e| e.to_string())?;
+            let outputs = self
+                .0
+                .run(ort::inputs![tensor])
+                .map_err(|e| e.to_string())?;
+
+            let extract = |index: usize| -> Result<Vec<f32>, String> {
+                outputs[index]
+                    .try_extract_tensor::<f32>()
+                    .map(|(_, data)| data.to_vec())
+                    .map_err(|e| e.to_string())
+            };
+            let landmarks = extract(0)?;
+            let score = probability(extract(1)?.first().copied().unwrap_or(0.0));
+            let right = probability(extract(2)?.first().copied().unwrap_or(0.0)) >= 0.5;
+
+            if landmarks.len() < LANDMARK_COUNT * 3 {
+                return Err("Unexpected landmark output".into());
+            }
+            let scale = INPUT_SIZE as f32;
+            Ok(Some(Hand {
+                handedness: if right { "right" } else { "left" }.into(),
+                score,
+                points: landmarks
+                    .chunks_exact(3)
+                    .take(LANDMARK_COUNT)
+                    .map(|p| [p[0] / scale, p[1] / scale, p[2] / scale])
+                    .collect(),
+            }))
+        }
+    }
+}
+
+#[cfg(not(feature = "landmarks"))]
+mod model {
+    use std::path::Path;
+
+    use super::Hand;
+
+    pub struct HandModel;
+
+    impl HandModel {
+        pub fn load(_model: &Path, _runtime: Option<&str>) -> Result<Self, String> {
+            Err("This build does not include hand landmark support".into())
+        }
+
+        pub fn run(&mut self, _input: Vec<f32>) -> Result<Option<Hand>, String> {
+            Ok(None)
+        }
+    }
+}
+
+/// Paths of the full and lite models, configured or in the data directory.
+fn model_paths(app: &AppHandle, config: &LandmarkSettings) -> Result<(PathBuf, PathBuf), String> {
+    let models = storage::data_dir(app)
+        .map_err(|e| e.to_string())?
+        .join(storage::MODELS_DIR);
+    let full: PathBuf = match &config.model_path {
+        Some(path) => path.into(),
+        None => models.join(MODEL_FILE),
+    };
+    let lite: PathBuf = match &config.lite_model_path {
+        Some(path) => path.into(),
+        None => models.join(LITE_MODEL_FILE),
+    };
+    Ok((full, lite))
+}
+
+/// Whether the full model has been downloaded.
+pub fn model_installed(app: &AppHandle) -> bool {
+    model_paths(app, &settings::get(app).landmarks).is_ok_and(|(full, _)| full.is_file())
+}
+
+/// Loads the lite model when `lite` is set and it is installed, otherwise the full one.
+fn load_model(app: &AppHandle, lite: bool) -> Result<model::HandModel, String> {
+    let config = settings::get(app).landmarks;
+    let (full, lite_path) = model_paths(app, &config)?;
+    let path = match lite && lite_path.exists() {
+        true => lite_path,
+        false => full,
+    };
+    if !path.exists() {
+        return Err(format!(
+            "Hand landmark model not found at {}",
+            path.display()
+        ));
+    }
+    model::HandModel::load(&path, config.runtime_path.as_deref())
+}
+
+/// Emits a result to the frontend (if `emit`) and passes it on to the recognizers.
+fn dispatch(app: &AppHandle, frame: &LandmarkFrame, emit: bool) {
+    if emit {
+        let _ = app.emit("landmarks:frame", frame);
+    }
+    fingerspelling::on_frame(app, frame);
+    gestures::on_frame(app, frame);
+}
+
+/// Queues a camera frame for inference.
+pub fn submit(app: &AppHandle, frame: VideoFrame) {
+    let Some(state) = app.try_state::<LandmarkState>() else {
+        return;
+    };
+    if let Err(TrySendError::Disconnected(_)) = state.0.try_send(frame) {
+        eprintln!("[Landmarks] Inference thread has stopped");
+    }
+}
+
+/// Starts the inference thread. The model is loaded with the first frame, so
+/// nothing is paid until the camera is used.
+pub fn init(app: &AppHandle) {
+    let (tx, rx) = mpsc::sync_channel::<VideoFrame>(1);
+    app.manage(LandmarkState(tx));
+
+    watchdog::spawn_supervised(app, "landmarks", move |app| {
+        let mut model: Option<model::HandModel> = None;
+        let mut loaded_lite = false;
+        let mut retry_at: Option<Instant> = None;
+        let mut last_emit: Option<Instant> = None;
+
+        for frame in rx.iter() {
+            // Swap models when low-power mode or thermal pressure comes and goes.
+            let lite = power::low_power(app) || thermal::degraded(app);
+            if model.is_some() && lite != loaded_lite {
+                model = None;
+                retry_at = None;
+            }
+            if model.is_none() {
+                if retry_at.is_some_and(|at| Instant::now() < at) {
+                    continue;
+                }
+                match load_model(app, lite) {
+                    Ok(loaded) => {
+                        println!(
+                            "[Landmarks] Loaded hand landmark model{}",
+                            if lite { " (lite)" } else { "" }
+                        );
+                        model = Some(loaded);
+                        loaded_lite = lite;
+                    }
+                    Err(e) => {
+                        eprintln!("[Landmarks] {}", e);
+                        let _ = app.emit("landmarks:error", &e);
+                        retry_at = Some(Instant::now() + LOAD_RETRY);
+                        continue;
+                    }
+                }
+            }
+            let Some(hand_model) = model.as_mut() else {
+                continue;
+            };
+
+            let min_confidence = settings::get(app).landmarks.min_confidence;
+            let hands = match hand_model.run(to_input(&frame)) {
+                Ok(hand) => hand
+                    .filter(|hand| hand.score >= min_confidence)
+                    .into_iter()
+                    .collect(),
+                Err(e) => {
+                    eprintln!("[Landmarks] Inference failed: {}", e);
+                    continue;
+                }
+            };
+
+            let emit = power::should_emit(app, &mut last_emit);
+            dispatch(
+                app,
+                &LandmarkFrame {
+                    timestamp: frame.timestamp,
+                    hands,
+                },
+                emit,
+            );
+        }
+    });
+}
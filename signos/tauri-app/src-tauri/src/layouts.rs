@@ -0,0 +1,340 @@
+//! Named window layouts, for users who move between a desk and a projector.
+//!
+//! A preset stores, for each managed window, the monitor it is on, the corner
+//! or edge it is anchored to with the distance from it, its size and opacity.
+//! Anchoring instead of absolute positions keeps a preset usable when a
+//! monitor changes resolution. Presets are recalled by command or by hotkey.
+
+// Windows can only be moved on desktop.
+#![cfg_attr(mobile, allow(dead_code))]
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::panel::MAIN_WINDOW;
+use crate::{hotkeys, settings};
+
+/// Windows a layout positions. The capture indicator is left out on purpose:
+/// it must always stay visible where consent put it.
+const MANAGED_WINDOWS: &[&str] = &[MAIN_WINDOW];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LayoutSettings {
+    pub presets: Vec<LayoutPreset>,
+}
+
+impl LayoutSettings {
+    pub fn find(&self, name: &str) -> Option<&LayoutPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutPreset {
+    pub name: String,
+    /// Accelerator that recalls the preset, registered with the other hotkeys.
+    #[serde(default)]
+    pub hotkey: Option<String>,
+    /// By window label.
+    pub windows: BTreeMap<String, WindowLayout>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowLayout {
+    /// 1-based, in the order the system lists monitors.
+    pub monitor: usize,
+    pub anchor: Anchor,
+    /// Distance from the anchor toward the middle of the monitor, in logical pixels.
+    pub offset_x: f64,
+    pub offset_y: f64,
+    /// Logical pixels.
+    pub width: f64,
+    pub height: f64,
+    /// 0 (invisible) to 1 (opaque).
+    pub opacity: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Start,
+    Middle,
+    End,
+}
+
+impl Anchor {
+    /// (horizontal, vertical)
+    fn aligns(self) -> (Align, Align) {
+        match self {
+            Self::TopLeft => (Align::Start, Align::Start),
+            Self::Top => (Align::Middle, Align::Start),
+            Self::TopRight => (Align::End, Align::Start),
+            Self::Left => (Align::Start, Align::Middle),
+            Self::Center => (Align::Middle, Align::Middle),
+            Self::Right => (Align::End, Align::Middle),
+            Self::BottomLeft => (Align::Start, Align::End),
+            Self::Bottom => (Align::Middle, Align::End),
+            Self::BottomRight => (Align::End, Align::End),
+        }
+    }
+
+    fn from_aligns(aligns: (Align, Align)) -> Self {
+        match aligns {
+            (Align::Start, Align::Start) => Self::TopLeft,
+            (Align::Middle, Align::Start) => Self::Top,
+            (Align::End, Align::Start) => Self::TopRight,
+            (Align::Start, Align::Middle) => Self::Left,
+            (Align::Middle, Align::Middle) => Self::Center,
+            (Align::End, Align::Middle) => Self::Right,
+            (Align::Start, Align::End) => Self::BottomLeft,
+            (Align::Middle, Align::End) => Self::Bottom,
+            (Align::End, Align::End) => Self::BottomRight,
+        }
+    }
+}
+
+/// Opacity last applied to each window; the webview renders it, so it cannot be read back.
+#[derive(Default)]
+pub struct LayoutState(Mutex<HashMap<String, f64>>);
+
+/// Position along one axis of a monitor `span` long, for a window `length` long.
+fn place_axis(align: Align, span: f64, length: f64, offset: f64) -> f64 {
+    match align {
+        Align::Start => offset,
+        Align::Middle => (span - length) / 2.0 + offset,
+        Align::End => span - length - offset,
+    }
+}
+
+/// The anchor side a window at `position` is nearest to, with its offset from it.
+fn anchor_axis(span: f64, position: f64, length: f64) -> (Align, f64) {
+    let center = position + length / 2.0;
+    if center < span / 3.0 {
+        (Align::Start, position)
+    } else if center > span * 2.0 / 3.0 {
+        (Align::End, span - position - length)
+    } else {
+        (Align::Middle, position - (span - length) / 2.0)
+    }
+}
+
+fn set_opacity(app: &AppHandle, label: &str, opacity: f64) {
+    app.state::<LayoutState>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(label.to_string(), opacity);
+    let _ = app.emit_to(label, "layout:opacity", opacity);
+}
+
+#[cfg(desktop)]
+fn capture(app: &AppHandle, label: &str) -> Result<Option<WindowLayout>, String> {
+    let Some(window) = app.get_webview_window(label) else {
+        return Ok(None);
+    };
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let Some(current) = window.current_monitor().map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let index = monitors
+        .iter()
+        .position(|monitor| monitor.position() == current.position())
+        .unwrap_or(0);
+
+    let scale = current.scale_factor();
+    let origin = current.position().to_logical::<f64>(scale);
+    let screen = current.size().to_logical::<f64>(scale);
+    let position = window
+        .outer_position()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let size = window
+        .outer_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+
+    let (horizontal, offset_x) = anchor_axis(screen.width, position.x - origin.x, size.width);
+    let (vertical, offset_y) = anchor_axis(screen.height, position.y - origin.y, size.height);
+    let opacity = app
+        .state::<LayoutState>()
+        .0
+        .lock()
+        .unwrap()
+        .get(label)
+        .copied()
+        .unwrap_or(1.0);
+
+    Ok(Some(WindowLayout {
+        monitor: index + 1,
+        anchor: Anchor::from_aligns((horizontal, vertical)),
+        offset_x,
+        offset_y,
+        width: size.width,
+        height: size.height,
+        opacity,
+    }))
+}
+
+#[cfg(desktop)]
+fn apply(app: &AppHandle, label: &str, layout: &WindowLayout) -> Result<(), String> {
+    let Some(window) = app.get_webview_window(label) else {
+        return Ok(());
+    };
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    // Falls back to the primary monitor when the preset's one is unplugged.
+    let monitor = match monitors.into_iter().nth(layout.monitor.saturating_sub(1)) {
+        Some(monitor) => monitor,
+        None => match window.primary_monitor().map_err(|e| e.to_string())? {
+            Some(monitor) => monitor,
+            None => return Ok(()),
+        },
+    };
+
+    let scale = monitor.scale_factor();
+    let origin = monitor.position().to_logical::<f64>(scale);
+    let screen = monitor.size().to_logical::<f64>(scale);
+    let width = layout.width.min(screen.width);
+    let height = layout.height.min(screen.height);
+    let (horizontal, vertical) = layout.anchor.aligns();
+
+    window
+        .set_size(tauri::LogicalSize::new(width, height))
+        .map_err(|e| e.to_string())?;
+    window
+        .set_position(tauri::LogicalPosition::new(
+            origin.x + place_axis(horizontal, screen.width, width, layout.offset_x),
+            origin.y + place_axis(vertical, screen.height, height, layout.offset_y),
+        ))
+        .map_err(|e| e.to_string())?;
+    set_opacity(app, label, layout.opacity.clamp(0.0, 1.0));
+    Ok(())
+}
+
+#[cfg(mobile)]
+fn capture(_app: &AppHandle, _label: &str) -> Result<Option<WindowLayout>, String> {
+    Err("Window layouts are not supported on this platform".into())
+}
+
+#[cfg(mobile)]
+fn apply(_app: &AppHandle, _label: &str, _layout: &WindowLayout) -> Result<(), String> {
+    Err("Window layouts are not supported on this platform".into())
+}
+
+/// Moves every managed window to where preset `name` puts it.
+pub fn recall(app: &AppHandle, name: &str) -> Result<(), String> {
+    let preset = settings::get(app)
+        .layouts
+        .find(name)
+        .cloned()
+        .ok_or_else(|| format!("Layout '{}' does not exist", name))?;
+
+    for (label, layout) in &preset.windows {
+        apply(app, label, layout)?;
+    }
+    println!("[Layouts] Recalled '{}'", name);
+    let _ = app.emit("layout:recalled", name);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_layouts(app: AppHandle) -> Vec<LayoutPreset> {
+    settings::get(&app).layouts.presets
+}
+
+/// Saves the current arrangement of the managed windows as preset `name`,
+/// replacing a preset with the same name but keeping its hotkey.
+#[tauri::command]
+pub fn save_layout(app: AppHandle, name: String) -> Result<LayoutPreset, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Layout name cannot be empty".into());
+    }
+
+    let mut windows = BTreeMap::new();
+    for label in MANAGED_WINDOWS {
+        if let Some(layout) = capture(&app, label)? {
+            windows.insert(label.to_string(), layout);
+        }
+    }
+    let preset = LayoutPreset {
+        hotkey: settings::get(&app)
+            .layouts
+            .find(&name)
+            .and_then(|preset| preset.hotkey.clone()),
+        name,
+        windows,
+    };
+
+    let saved = preset.clone();
+    settings::update(&app, |s| {
+        match s.layouts.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => s.layouts.presets.push(preset),
+        }
+    })?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub fn recall_layout(app: AppHandle, name: String) -> Result<(), String> {
+    recall(&app, &name)
+}
+
+#[tauri::command]
+pub fn delete_layout(app: AppHandle, name: String) -> Result<(), String> {
+    let had_hotkey = settings::get(&app)
+        .layouts
+        .find(&name)
+        .is_some_and(|preset| preset.hotkey.is_some());
+    settings::update(&app, |s| s.layouts.presets.retain(|p| p.name != name))?;
+    if had_hotkey {
+        hotkeys::register_all(&app);
+    }
+    Ok(())
+}
+
+/// Binds `accelerator` to recall preset `name`, or unbinds it when `None`.
+#[tauri::command]
+pub fn set_layout_hotkey(
+    app: AppHandle,
+    name: String,
+    accelerator: Option<String>,
+) -> Result<LayoutPreset, String> {
+    if settings::get(&app).layouts.find(&name).is_none() {
+        return Err(format!("Layout '{}' does not exist", name));
+    }
+    if let Some(accelerator) = &accelerator {
+        hotkeys::check_layout_hotkey(&app, &name, accelerator)?;
+    }
+
+    let settings = settings::update(&app, |s| {
+        if let Some(preset) = s.layouts.presets.iter_mut().find(|p| p.name == name) {
+            preset.hotkey = accelerator;
+        }
+    })?;
+    hotkeys::register_all(&app);
+    settings
+        .layouts
+        .find(&name)
+        .cloned()
+        .ok_or_else(|| format!("Layout '{}' does not exist", name))
+}
@@ -1,5 +1,97 @@
+use std::sync::Mutex;
+
 use tauri::Manager;
 
+mod accessibility;
+mod actions;
+mod announce;
+mod audio_feed;
+mod avatar;
+mod backup;
+mod badge;
+mod benchmark;
+mod bluetooth;
+mod broadcast_delay;
+mod calendar;
+mod camera;
+mod captions;
+mod capture;
+mod capture_format;
+mod cli;
+mod connectivity;
+mod consent;
+mod context_menu;
+mod conversation;
+mod corrections;
+mod export;
+mod feedback;
+mod file_sink;
+mod fingerspelling;
+mod focus;
+mod fs_access;
+mod fullscreen;
+mod gestures;
+mod glossary;
+mod hot_corners;
+mod hotkeys;
+mod intents;
+mod journal;
+mod keywords;
+mod landmarks;
+mod layouts;
+mod lifecycle;
+mod llm;
+mod metrics;
+mod midi;
+mod monitor;
+mod mqtt;
+mod native_capture;
+mod ndi;
+mod onboarding;
+mod osc;
+mod overlay;
+mod panel;
+mod participants;
+mod pip;
+mod plugins;
+mod power;
+mod profiles;
+mod proxy;
+mod punctuation;
+mod recording;
+mod redaction;
+mod relay;
+mod replay;
+mod reset;
+mod retranscribe;
+mod retry;
+mod schedule;
+mod screen_watch;
+mod scripting;
+mod secure_input;
+mod security;
+mod selftest;
+mod settings;
+mod share;
+mod simplify;
+mod stabilizer;
+mod startup;
+mod stats;
+mod storage;
+mod streamdeck;
+mod summary;
+mod sync;
+mod system_captions;
+mod taskbar;
+mod thermal;
+mod transcription;
+#[cfg(desktop)]
+mod tray;
+mod usage;
+mod util;
+mod viewer;
+mod watchdog;
+
 #[cfg(target_os = "macos")]
 use tauri_nspanel::{tauri_panel, CollectionBehavior, PanelLevel, StyleMask, WebviewWindowExt};
 
@@ -15,10 +107,39 @@ tauri_panel! {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let mut builder = tauri::Builder::default()
+    let cli = cli::CliArgs::parse(std::env::args().skip(1));
+
+    // rumqttc, ureq and tungstenite each bring a rustls crypto provider; with
+    // more than one linked and no process default, the first handshake panics.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let mut builder = tauri::Builder::default();
+
+    #[cfg(desktop)]
+    {
+        // Must come first so a second launch exits before initializing anything.
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            cli::forward(app, argv)
+        }));
+    }
+
+    builder = builder
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_fs::init());
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_clipboard_manager::init());
+
+    #[cfg(desktop)]
+    {
+        builder = builder.plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(hotkeys::handle_shortcut)
+                .build(),
+        );
+    }
 
     // Add nspanel plugin only on macOS
     #[cfg(target_os = "macos")]
@@ -27,14 +148,254 @@ pub fn run() {
     }
 
     builder
-        .setup(|app| {
+        .manage(cli::CliState::new(cli.clone()))
+        .manage(capture::CaptureState::default())
+        .manage(capture::DeviceState::default())
+        .manage(capture::SourceState::default())
+        .manage(capture_format::ConverterState::default())
+        .manage(bluetooth::BluetoothState::default())
+        .manage(stabilizer::StabilizerState::default())
+        .manage(glossary::GlossaryState::default())
+        .manage(redaction::RedactionState::default())
+        .manage(camera::CameraState::default())
+        .manage(fingerspelling::FingerspellingState::default())
+        .manage(gestures::GestureState::default())
+        .manage(metrics::Metrics::default())
+        .manage(midi::MidiState::default())
+        .manage(retry::RetryState::default())
+        .manage(audio_feed::AudioFeedState::default())
+        .manage(screen_watch::ScreenWatchState::default())
+        .manage(lifecycle::LifecycleState::default())
+        .manage(watchdog::WatchdogState::default())
+        .manage(replay::ReplayState::default())
+        .manage(participants::ParticipantsState::default())
+        .manage(consent::ConsentState::default())
+        .manage(secure_input::SecureInputState::default())
+        .manage(power::PowerState::default())
+        .manage(thermal::ThermalState::default())
+        .manage(llm::LlmState::default())
+        .manage(simplify::SimplifyState::default())
+        .manage(conversation::ConversationState::default())
+        .manage(relay::RelayState::default())
+        .manage(broadcast_delay::BroadcastDelayState::default())
+        .manage(fullscreen::FullscreenState::default())
+        .manage(layouts::LayoutState::default())
+        .manage(reset::ResetState::default())
+        .on_page_load(replay::on_page_load)
+        .invoke_handler(tauri::generate_handler![
+            badge::set_badge_count,
+            calendar::request_calendar_access,
+            calendar::get_upcoming_events,
+            captions::push_caption,
+            capture::start_capture,
+            capture::start_capture_for,
+            capture::cancel_capture_timer,
+            capture::stop_capture,
+            capture::pause_capture,
+            capture::resume_capture,
+            capture::get_capture_status,
+            capture::report_capture_state,
+            consent::get_consent_status,
+            consent::confirm_consent,
+            capture::report_audio_devices,
+            capture::add_source,
+            capture::remove_source,
+            capture::list_sources,
+            capture_format::get_capture_format,
+            capture_format::set_capture_format,
+            capture_format::convert_audio,
+            bluetooth::switch_to_builtin_mic,
+            monitor::get_monitor,
+            monitor::set_monitor,
+            punctuation::set_punctuation_language,
+            file_sink::preview_transcript_path,
+            fs_access::grant_folder_access,
+            fs_access::list_folder_access,
+            fs_access::revoke_folder_access,
+            hotkeys::list_actions,
+            hotkeys::set_binding,
+            metrics::report_dropped_frames,
+            metrics::report_error,
+            midi::list_midi_ports,
+            midi::reconnect_midi,
+            midi::start_midi_learn,
+            midi::cancel_midi_learn,
+            native_capture::native_capture_available,
+            native_capture::start_native_capture,
+            native_capture::stop_native_capture,
+            onboarding::get_onboarding_state,
+            onboarding::complete_step,
+            overlay::get_overlay_status,
+            overlay::request_overlay_permission,
+            overlay::set_overlay_enabled,
+            panel::show_panel,
+            panel::hide_panel,
+            panel::toggle_panel,
+            context_menu::show_context_menu,
+            pip::start_caption_pip,
+            pip::stop_caption_pip,
+            plugins::install_plugin,
+            plugins::list_plugins,
+            plugins::remove_plugin,
+            profiles::list_profiles,
+            profiles::switch_profile,
+            profiles::save_profile,
+            profiles::delete_profile,
+            glossary::list_glossary,
+            glossary::set_glossary_entry,
+            glossary::remove_glossary_entry,
+            redaction::validate_redaction_pattern,
+            recording::start_recording,
+            recording::stop_recording,
+            recording::push_audio,
+            recording::get_session,
+            recording::mark_moment,
+            recording::resume_session,
+            recording::dismiss_orphaned_session,
+            participants::get_participants,
+            participants::set_participants,
+            participants::assign_speaker,
+            recording::delete_session,
+            recording::purge_history,
+            journal::undo_last_operation,
+            backup::create_backup,
+            backup::restore_backup,
+            connectivity::get_connectivity,
+            connectivity::report_stt_dropped,
+            proxy::get_proxy,
+            proxy::set_proxy,
+            usage::get_data_usage,
+            usage::reset_data_usage,
+            usage::set_metered,
+            usage::report_data_usage,
+            retry::get_degraded_integrations,
+            export::export_session,
+            retranscribe::retranscribe_session,
+            retranscribe::list_retranscribe_jobs,
+            selftest::run_self_test,
+            benchmark::run_benchmark,
+            audio_feed::feed_audio_file,
+            audio_feed::audio_feed_pending,
+            audio_feed::start_audio_feed,
+            audio_feed::stop_audio_feed,
+            screen_watch::start_screen_watch,
+            screen_watch::stop_screen_watch,
+            secure_input::get_secure_input_active,
+            screen_watch::get_screen_watch,
+            system_captions::get_system_captions,
+            system_captions::set_system_caption_mode,
+            announce::announce_message,
+            accessibility::get_accessibility_prefs,
+            feedback::play_feedback,
+            summary::summarize_session,
+            actions::get_action_items,
+            avatar::subscribe_avatar,
+            camera::start_camera,
+            camera::stop_camera,
+            camera::get_camera_state,
+            camera::report_camera_state,
+            camera::push_video_frame,
+            fingerspelling::start_fingerspelling,
+            fingerspelling::stop_fingerspelling,
+            schedule::validate_cron,
+            scripting::reload_scripts,
+            security::get_security_audit,
+            settings::get_settings,
+            settings::set_settings,
+            share::share_text,
+            share::share_file,
+            startup::frontend_ready,
+            startup::reset_settings,
+            lifecycle::quit_app,
+            lifecycle::restart_app,
+            watchdog::pipeline_heartbeat,
+            replay::get_replay_snapshot,
+            stats::get_session_stats,
+            storage::get_storage_info,
+            sync::list_sync_devices,
+            sync::pair_device,
+            sync::respond_to_pairing,
+            sync::unpair_device,
+            sync::start_mirroring,
+            sync::stop_mirroring,
+            sync::hand_off_session,
+            taskbar::set_show_in_taskbar,
+            transcription::get_language,
+            transcription::set_transcription_language,
+            transcription::get_model,
+            transcription::set_model,
+            transcription::get_inference_params,
+            transcription::set_inference_params,
+            power::get_power_status,
+            power::set_low_power_mode,
+            thermal::get_thermal_status,
+            llm::set_llm_provider,
+            simplify::simplify_text,
+            conversation::start_conversation,
+            conversation::stop_conversation,
+            conversation::get_conversation_status,
+            conversation::set_reply_draft,
+            conversation::send_reply,
+            conversation::finish_speaking,
+            relay::create_relay_invite,
+            relay::pair_interpreter,
+            relay::list_relay_peers,
+            relay::set_relay_permissions,
+            relay::start_relay,
+            relay::stop_relay,
+            relay::unpair_relay_peer,
+            relay::send_relay_correction,
+            relay::send_relay_translation,
+            relay::subscribe_relay_audio,
+            corrections::correct_segment,
+            broadcast_delay::set_broadcast_delay,
+            fullscreen::get_occlusion,
+            hot_corners::set_hot_corner,
+            layouts::list_layouts,
+            layouts::save_layout,
+            layouts::recall_layout,
+            layouts::delete_layout,
+            layouts::set_layout_hotkey,
+            reset::request_reset,
+            reset::reset_app,
+            viewer::get_viewer_qr,
+            viewer::get_viewer_url,
+        ])
+        .setup(move |app| {
+            storage::init(app.handle());
+            security::init(app.handle());
+            let safe_mode = startup::init(app.handle());
+            let settings = match safe_mode {
+                true => settings::Settings::default(),
+                false => settings::load(app.handle()),
+            };
+            app.manage(settings::SettingsState(Mutex::new(settings)));
+            fs_access::init(app.handle());
+            journal::init(app.handle());
+            proxy::init(app.handle());
+            usage::init(app.handle());
             #[cfg(target_os = "macos")]
             {
                 // Set activation policy to Accessory to prevent dock icon
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
 
-            let window = app.get_webview_window("main").unwrap();
+            // Created here rather than from the config so CLI flags apply before it appears.
+            let window_config = app
+                .config()
+                .app
+                .windows
+                .iter()
+                .find(|window| window.label == panel::MAIN_WINDOW)
+                .cloned()
+                .expect("main window missing from tauri.conf.json");
+            let mut window_builder =
+                tauri::WebviewWindowBuilder::from_config(app.handle(), &window_config)?
+                    .visible(!cli.hidden);
+            if let Some(url) = proxy::webview_proxy(app.handle()) {
+                window_builder = window_builder.proxy_url(url);
+            }
+            let window = window_builder.build()?;
 
             // Convert window to panel on macOS for fullscreen support
             #[cfg(target_os = "macos")]
@@ -58,29 +419,65 @@ pub fn run() {
                 );
             }
 
-            // Get the primary monitor
-            if let Some(monitor) = window.current_monitor()? {
-                let screen_size = monitor.size();
-
-                // Use configured window size (290x380) instead of outer_size
-                // to avoid issues with frameless windows on macOS
-                let window_width = 290;
-                let window_height = 380;
-
-                // Calculate bottom-right corner position
-                // Add padding from edges (20px from right and bottom)
-                let x = screen_size.width as i32 - window_width - 300;
-                let y = screen_size.height as i32 - window_height - 400;
-
-                // Position window in bottom-right corner
-                window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-                    x,
-                    y,
-                }))?;
+            if let Some(monitor) = cli.monitor {
+                println!("[CLI] Placing panel on monitor {}", monitor);
             }
+            panel::place(&window, cli.monitor)?;
+
+            #[cfg(desktop)]
+            tray::init(app.handle())?;
+            context_menu::init(app.handle());
+            badge::init(app.handle());
+            consent::init(app.handle());
+            // Plugins, scripts and integrations are the usual suspects after repeated crashes.
+            if !safe_mode {
+                plugins::init(app.handle());
+                schedule::init(app.handle());
+                calendar::init(app.handle());
+                streamdeck::init(app.handle());
+                midi::init(app.handle());
+                osc::init(app.handle());
+                ndi::init(app.handle());
+                mqtt::init(app.handle());
+                metrics::init(app.handle());
+                scripting::init(app.handle());
+                sync::init(app.handle());
+                relay::init(app.handle());
+                viewer::init(app.handle());
+            }
+            focus::init(app.handle());
+            fullscreen::init(app.handle());
+            secure_input::init(app.handle());
+            power::init(app.handle());
+            thermal::init(app.handle());
+            llm::init(app.handle());
+            broadcast_delay::init(app.handle());
+            system_captions::init(app.handle());
+            announce::init(app.handle());
+            accessibility::init(app.handle());
+            feedback::init(app.handle());
+            recording::init(app.handle());
+            watchdog::init(app.handle());
+            connectivity::init(app.handle());
+            retranscribe::init(app.handle());
+            hotkeys::init(app.handle());
+            hot_corners::init(app.handle());
+            intents::init(app.handle());
+            file_sink::init(app.handle());
+            taskbar::init(app.handle());
+            pip::init(app.handle());
+            overlay::init(app.handle());
+            avatar::init(app.handle());
+            landmarks::init(app.handle());
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Covers exits that skip quit_app, e.g. the tray or the OS closing the app.
+            if let tauri::RunEvent::Exit = event {
+                lifecycle::shutdown(app, true);
+            }
+        });
 }
@@ -0,0 +1,140 @@
+//! Orderly quit and restart.
+//!
+//! Subsystems register exit hooks with `on_exit` from their `init`; they run
+//! in reverse registration order once capture has stopped, whether the app
+//! exits through `quit_app`/`restart_app` or any other path (tray, close).
+//! Listeners stop accepting through `close_on_exit`, so a restarted app can
+//! bind their ports again.
+
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{audio_feed, camera, capture, native_capture, screen_watch};
+
+/// How long the frontend gets to close its devices before exiting anyway.
+const CAPTURE_STOP_TIMEOUT: Duration = Duration::from_secs(3);
+const CAPTURE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long `close_on_exit` tries to reach its listener.
+const WAKE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Called with whether the user asked to keep unsaved work.
+type ExitHook = Box<dyn Fn(&AppHandle, bool) + Send + Sync>;
+
+#[derive(Default)]
+pub struct LifecycleState {
+    hooks: Mutex<Vec<(&'static str, ExitHook)>>,
+    shut_down: AtomicBool,
+}
+
+/// Registers a hook to run once when the app quits or restarts.
+pub fn on_exit(
+    app: &AppHandle,
+    name: &'static str,
+    hook: impl Fn(&AppHandle, bool) + Send + Sync + 'static,
+) {
+    app.state::<LifecycleState>()
+        .hooks
+        .lock()
+        .unwrap()
+        .push((name, Box::new(hook)));
+}
+
+/// Stops the accept loop of `listener` at exit. The returned flag is set by
+/// an exit hook, which then connects once so a blocked `accept` returns; the
+/// loop checks the flag after each accept and drops the listener once it's set.
+pub fn close_on_exit(
+    app: &AppHandle,
+    name: &'static str,
+    listener: &TcpListener,
+) -> Arc<AtomicBool> {
+    let closed = Arc::new(AtomicBool::new(false));
+    let port = listener.local_addr().map(|address| address.port());
+    let flag = closed.clone();
+    on_exit(app, name, move |_, _| {
+        flag.store(true, Ordering::SeqCst);
+        if let Ok(port) = port {
+            let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+            let _ = TcpStream::connect_timeout(&address, WAKE_TIMEOUT);
+        }
+    });
+    closed
+}
+
+/// Asks the frontend to stop capture and waits until it reports the devices closed.
+/// Must not run on the main thread, which handles that report.
+fn stop_capture(app: &AppHandle) {
+    if !capture::status(app).active {
+        return;
+    }
+    if let Err(e) = capture::request_stop(app) {
+        eprintln!("[Lifecycle] Failed to request capture stop: {}", e);
+        return;
+    }
+
+    let deadline = Instant::now() + CAPTURE_STOP_TIMEOUT;
+    while capture::status(app).active {
+        if Instant::now() >= deadline {
+            eprintln!("[Lifecycle] Capture did not stop in time, exiting anyway");
+            return;
+        }
+        thread::sleep(CAPTURE_POLL_INTERVAL);
+    }
+}
+
+/// Releases backend-owned inputs and runs the exit hooks. Only the first call does anything.
+pub fn shutdown(app: &AppHandle, save: bool) {
+    let state = app.state::<LifecycleState>();
+    if state.shut_down.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    println!("[Lifecycle] Shutting down (save: {})", save);
+    let _ = app.emit("app:exiting", save);
+
+    audio_feed::stop_audio_feed(app.clone());
+    screen_watch::stop_screen_watch(app.clone());
+    let _ = native_capture::stop_native_capture(app.clone());
+    let _ = camera::stop_camera(app.clone());
+
+    let hooks = std::mem::take(&mut *state.hooks.lock().unwrap());
+    for (name, hook) in hooks.iter().rev() {
+        println!("[Lifecycle] Running {} exit hook", name);
+        hook(app, save);
+    }
+}
+
+/// Stops capture, runs the exit hooks and quits. With `save` off, the session
+/// being recorded is discarded instead of written.
+#[tauri::command]
+pub async fn quit_app(app: AppHandle, save: bool) -> Result<(), String> {
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        stop_capture(&handle);
+        shutdown(&handle, save);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    app.exit(0);
+    Ok(())
+}
+
+/// Same cleanup as `quit_app` (keeping unsaved work), then relaunches.
+#[tauri::command]
+pub async fn restart_app(app: AppHandle) -> Result<(), String> {
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        stop_capture(&handle);
+        shutdown(&handle, true);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    println!("[Lifecycle] Restarting");
+    app.request_restart();
+    Ok(())
+}
@@ -0,0 +1,547 @@
+//! Pluggable LLM providers for post-processing text.
+//!
+//! Summaries, plain-language rewrites and sign glossing call [`complete`],
+//! which runs the request on the [`LlmProvider`] selected in `llm.provider`:
+//! the worker's model, a remote OpenAI-compatible API, or a local GGUF model
+//! served by llama.cpp's `llama-server`. The answer is streamed to the
+//! frontend as `llm:token` events while it is generated and ends with
+//! `llm:done` or `llm:failed`. Each provider has its own request rate limit.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::retry::{self, Failure};
+use crate::usage::{self, Integration};
+use crate::{lifecycle, profiles, proxy, settings, storage};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+/// Attempts per request, including the first.
+const ATTEMPTS: u32 = 3;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const LOCAL_MODEL_FILE: &str = "llm.gguf";
+const LOCAL_SERVER: &str = "llama-server";
+/// Loading a large model can take a while on first use.
+const LOCAL_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+const LOCAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum LlmProviderKind {
+    /// The worker's `POST /complete` route.
+    #[default]
+    Worker,
+    /// Any OpenAI-compatible chat completions API.
+    Remote,
+    /// A GGUF model run on-device with llama.cpp.
+    Local,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LlmSettings {
+    pub provider: LlmProviderKind,
+    /// Base URL of the remote API, including the version, e.g. `https://api.openai.com/v1`.
+    pub api_url: String,
+    pub api_key: String,
+    pub api_model: String,
+    /// GGUF model for the local provider. `None` uses `models/llm.gguf` in the data directory.
+    pub model_path: Option<String>,
+    /// llama.cpp's `llama-server`. `None` looks it up on the `PATH`.
+    pub server_path: Option<String>,
+    /// Requests per minute, overriding a provider's default limit.
+    pub requests_per_minute: BTreeMap<LlmProviderKind, usize>,
+}
+
+impl Default for LlmSettings {
+    fn default() -> Self {
+        Self {
+            provider: LlmProviderKind::default(),
+            api_url: "https://api.openai.com/v1".into(),
+            api_key: String::new(),
+            api_model: "gpt-4o-mini".into(),
+            model_path: None,
+            server_path: None,
+            requests_per_minute: BTreeMap::new(),
+        }
+    }
+}
+
+/// One prompt for a provider. Also the body of the worker's `POST /complete`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Completion {
+    /// Instructions for the model.
+    pub system: String,
+    /// The text to work on.
+    pub prompt: String,
+    pub max_tokens: u32,
+    pub temperature: f32,
+}
+
+/// Runs completions on one kind of backend.
+pub trait LlmProvider {
+    fn kind(&self) -> LlmProviderKind;
+    /// Requests per minute unless `llm.requestsPerMinute` says otherwise.
+    fn default_rate_limit(&self) -> usize;
+    /// Runs `request`, passing each piece of the answer to `on_token` as it arrives.
+    fn complete(
+        &self,
+        app: &AppHandle,
+        integration: Integration,
+        request: &Completion,
+        on_token: &mut dyn FnMut(&str),
+    ) -> Result<(), String>;
+}
+
+struct WorkerProvider;
+
+struct RemoteProvider;
+
+struct LocalProvider;
+
+struct LocalServer {
+    child: Child,
+    model: PathBuf,
+    port: u16,
+}
+
+#[derive(Default)]
+pub struct LlmState {
+    /// Recent request times per provider.
+    calls: Mutex<HashMap<LlmProviderKind, VecDeque<Instant>>>,
+    server: Mutex<Option<LocalServer>>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenEvent<'a> {
+    request_id: &'a str,
+    token: &'a str,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DoneEvent<'a> {
+    request_id: &'a str,
+    provider: LlmProviderKind,
+    text: &'a str,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FailedEvent<'a> {
+    request_id: &'a str,
+    error: &'a str,
+}
+
+/// An OpenAI-style chat completions request.
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 2],
+    max_tokens: u32,
+    temperature: f32,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Where a provider sends its requests.
+struct Endpoint<'a> {
+    agent: ureq::Agent,
+    url: String,
+    api_key: Option<&'a str>,
+    /// Leaves the device, so it counts towards data usage.
+    metered: bool,
+}
+
+fn agent(app: &AppHandle) -> ureq::Agent {
+    proxy::agent_config(app)
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into()
+}
+
+/// POSTs `body` and feeds each `data:` payload of the event stream it answers
+/// with to `on_data`, until `[DONE]` or the end of the stream.
+fn post_stream(
+    app: &AppHandle,
+    integration: Integration,
+    endpoint: &Endpoint,
+    body: &impl Serialize,
+    on_data: &mut dyn FnMut(&str),
+) -> Result<(), String> {
+    let json = serde_json::to_string(body).map_err(|e| e.to_string())?;
+    let response = retry::call(app, integration, ATTEMPTS, || {
+        let request = endpoint
+            .agent
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "text/event-stream");
+        let request = match endpoint.api_key {
+            Some(key) => request.header("Authorization", format!("Bearer {}", key)),
+            None => request,
+        };
+        request.send(&json).map_err(Failure::from)
+    })?;
+
+    let mut received = 0;
+    for line in BufReader::new(response.into_body().into_reader()).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        received += line.len() as u64 + 1;
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            continue;
+        };
+        if data == "[DONE]" {
+            break;
+        }
+        on_data(data);
+    }
+
+    if endpoint.metered {
+        usage::record(app, integration, json.len() as u64, received);
+    }
+    Ok(())
+}
+
+/// Streams a chat completion from an OpenAI-compatible API; `endpoint.url` is its base URL.
+fn chat(
+    app: &AppHandle,
+    integration: Integration,
+    endpoint: Endpoint,
+    model: &str,
+    request: &Completion,
+    on_token: &mut dyn FnMut(&str),
+) -> Result<(), String> {
+    let body = ChatRequest {
+        model,
+        messages: [
+            ChatMessage {
+                role: "system",
+                content: &request.system,
+            },
+            ChatMessage {
+                role: "user",
+                content: &request.prompt,
+            },
+        ],
+        max_tokens: request.max_tokens,
+        temperature: request.temperature,
+        stream: true,
+    };
+    let endpoint = Endpoint {
+        url: format!("{}/chat/completions", endpoint.url.trim_end_matches('/')),
+        ..endpoint
+    };
+
+    post_stream(app, integration, &endpoint, &body, &mut |data| {
+        let Ok(chunk) = serde_json::from_str::<serde_json::Value>(data) else {
+            return;
+        };
+        if let Some(token) = chunk["choices"][0]["delta"]["content"].as_str() {
+            on_token(token);
+        }
+    })
+}
+
+impl LlmProvider for WorkerProvider {
+    fn kind(&self) -> LlmProviderKind {
+        LlmProviderKind::Worker
+    }
+
+    fn default_rate_limit(&self) -> usize {
+        20
+    }
+
+    fn complete(
+        &self,
+        app: &AppHandle,
+        integration: Integration,
+        request: &Completion,
+        on_token: &mut dyn FnMut(&str),
+    ) -> Result<(), String> {
+        let endpoint = Endpoint {
+            agent: agent(app),
+            url: profiles::worker_endpoint(app, "complete"),
+            api_key: None,
+            metered: true,
+        };
+        post_stream(
+            app,
+            integration,
+            &endpoint,
+            request,
+            // Workers AI streams `{"response": "<token>"}` events.
+            &mut |data| {
+                let Ok(chunk) = serde_json::from_str::<serde_json::Value>(data) else {
+                    return;
+                };
+                if let Some(token) = chunk["response"].as_str() {
+                    on_token(token);
+                }
+            },
+        )
+    }
+}
+
+impl LlmProvider for RemoteProvider {
+    fn kind(&self) -> LlmProviderKind {
+        LlmProviderKind::Remote
+    }
+
+    fn default_rate_limit(&self) -> usize {
+        30
+    }
+
+    fn complete(
+        &self,
+        app: &AppHandle,
+        integration: Integration,
+        request: &Completion,
+        on_token: &mut dyn FnMut(&str),
+    ) -> Result<(), String> {
+        let config = settings::get(app).llm;
+        if config.api_url.trim().is_empty() {
+            return Err("No LLM API URL is configured".into());
+        }
+        let endpoint = Endpoint {
+            agent: agent(app),
+            url: config.api_url.clone(),
+            api_key: Some(config.api_key.as_str()).filter(|key| !key.is_empty()),
+            metered: true,
+        };
+        chat(
+            app,
+            integration,
+            endpoint,
+            &config.api_model,
+            request,
+            on_token,
+        )
+    }
+}
+
+impl LocalProvider {
+    fn model_path(app: &AppHandle) -> Result<PathBuf, String> {
+        match settings::get(app).llm.model_path {
+            Some(path) => Ok(path.into()),
+            None => storage::data_dir(app)
+                .map(|dir| dir.join(storage::MODELS_DIR).join(LOCAL_MODEL_FILE))
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Port of a running `llama-server` for the configured model, starting one if needed.
+    fn server_port(app: &AppHandle) -> Result<u16, String> {
+        let model = Self::model_path(app)?;
+        let state = app.state::<LlmState>();
+        let mut server = state.server.lock().unwrap();
+
+        if let Some(running) = server.as_mut() {
+            if running.model == model && matches!(running.child.try_wait(), Ok(None)) {
+                return Ok(running.port);
+            }
+            let _ = running.child.kill();
+            let _ = running.child.wait();
+            *server = None;
+        }
+        if !model.exists() {
+            return Err(format!("LLM model not found at {}", model.display()));
+        }
+
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .map(|addr| addr.port())
+            .map_err(|e| e.to_string())?;
+        let program = settings::get(app)
+            .llm
+            .server_path
+            .unwrap_or_else(|| LOCAL_SERVER.into());
+        let mut child = Command::new(&program)
+            .arg("--model")
+            .arg(&model)
+            .args(["--host", "127.0.0.1", "--port", &port.to_string()])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+        println!(
+            "[LLM] Started llama.cpp with {} on port {}",
+            model.display(),
+            port
+        );
+
+        let health = format!("http://127.0.0.1:{}/health", port);
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(LOCAL_POLL_INTERVAL))
+            .build()
+            .into();
+        let deadline = Instant::now() + LOCAL_STARTUP_TIMEOUT;
+        // Answers 503 while the model is loading.
+        while agent.get(&health).call().is_err() {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(format!("llama.cpp exited while starting ({})", status));
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                return Err("llama.cpp did not load the model in time".into());
+            }
+            thread::sleep(LOCAL_POLL_INTERVAL);
+        }
+
+        *server = Some(LocalServer { child, model, port });
+        Ok(port)
+    }
+}
+
+impl LlmProvider for LocalProvider {
+    fn kind(&self) -> LlmProviderKind {
+        LlmProviderKind::Local
+    }
+
+    fn default_rate_limit(&self) -> usize {
+        60
+    }
+
+    fn complete(
+        &self,
+        app: &AppHandle,
+        integration: Integration,
+        request: &Completion,
+        on_token: &mut dyn FnMut(&str),
+    ) -> Result<(), String> {
+        let port = Self::server_port(app)?;
+        let endpoint = Endpoint {
+            // Never through the proxy.
+            agent: ureq::Agent::config_builder()
+                .timeout_global(Some(REQUEST_TIMEOUT))
+                .build()
+                .into(),
+            url: format!("http://127.0.0.1:{}/v1", port),
+            api_key: None,
+            metered: false,
+        };
+        // llama-server answers with whatever model it loaded.
+        chat(
+            app,
+            integration,
+            endpoint,
+            LOCAL_MODEL_FILE,
+            request,
+            on_token,
+        )
+    }
+}
+
+fn provider(kind: LlmProviderKind) -> Box<dyn LlmProvider> {
+    match kind {
+        LlmProviderKind::Worker => Box::new(WorkerProvider),
+        LlmProviderKind::Remote => Box::new(RemoteProvider),
+        LlmProviderKind::Local => Box::new(LocalProvider),
+    }
+}
+
+fn check_rate_limit(app: &AppHandle, provider: &dyn LlmProvider) -> Result<(), String> {
+    let kind = provider.kind();
+    let limit = settings::get(app)
+        .llm
+        .requests_per_minute
+        .get(&kind)
+        .copied()
+        .unwrap_or_else(|| provider.default_rate_limit());
+
+    let state = app.state::<LlmState>();
+    let mut calls = state.calls.lock().unwrap();
+    let recent = calls.entry(kind).or_default();
+    while recent.front().is_some_and(|t| t.elapsed() >= RATE_WINDOW) {
+        recent.pop_front();
+    }
+    if recent.len() >= limit {
+        return Err(format!(
+            "The {:?} LLM provider is limited to {} requests per minute",
+            kind, limit
+        ));
+    }
+    recent.push_back(Instant::now());
+    Ok(())
+}
+
+/// Runs `request` on the selected provider and returns the whole answer.
+/// Streams it as `llm:token` events tagged with `request_id`, then ends with
+/// `llm:done` or `llm:failed`.
+pub fn complete(
+    app: &AppHandle,
+    request_id: &str,
+    integration: Integration,
+    request: &Completion,
+) -> Result<String, String> {
+    let provider = provider(settings::get(app).llm.provider);
+    let mut text = String::new();
+    let result = check_rate_limit(app, provider.as_ref()).and_then(|_| {
+        provider.complete(app, integration, request, &mut |token| {
+            text.push_str(token);
+            let _ = app.emit("llm:token", TokenEvent { request_id, token });
+        })
+    });
+
+    match result {
+        Ok(()) => {
+            let text = text.trim().to_string();
+            let _ = app.emit(
+                "llm:done",
+                DoneEvent {
+                    request_id,
+                    provider: provider.kind(),
+                    text: &text,
+                },
+            );
+            Ok(text)
+        }
+        Err(e) => {
+            eprintln!("[LLM] {:?} request failed: {}", provider.kind(), e);
+            let _ = app.emit(
+                "llm:failed",
+                FailedEvent {
+                    request_id,
+                    error: &e,
+                },
+            );
+            Err(e)
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    lifecycle::on_exit(app, "llm", |app, _| {
+        if let Some(mut server) = app.state::<LlmState>().server.lock().unwrap().take() {
+            let _ = server.child.kill();
+            let _ = server.child.wait();
+        }
+    });
+}
+
+/// Selects the provider used for summaries, rewrites and LLM glossing.
+#[tauri::command]
+pub fn set_llm_provider(
+    app: AppHandle,
+    provider: LlmProviderKind,
+) -> Result<LlmProviderKind, String> {
+    settings::update(&app, |s| s.llm.provider = provider)?;
+    let _ = app.emit("llm:provider", provider);
+    Ok(provider)
+}
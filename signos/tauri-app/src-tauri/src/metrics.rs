@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::{settings, sync};
+
+/// Sources the frontend may report errors for; the backend records its own.
+const FRONTEND_SOURCES: &[&str] = &["websocket", "worker"];
+
+/// Serves Prometheus text-format metrics on `http://127.0.0.1:<port>/metrics`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9464,
+        }
+    }
+}
+
+/// Process-wide counters, managed from startup so uptime covers the whole run.
+pub struct Metrics {
+    started: Instant,
+    captions_partial: AtomicU64,
+    captions_final: AtomicU64,
+    latency_sum_ms: AtomicU64,
+    latency_count: AtomicU64,
+    dropped_frames: AtomicU64,
+    /// Error count per source (`websocket`, `worker`, `osc`, ...).
+    errors: Mutex<BTreeMap<String, u64>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            captions_partial: AtomicU64::new(0),
+            captions_final: AtomicU64::new(0),
+            latency_sum_ms: AtomicU64::new(0),
+            latency_count: AtomicU64::new(0),
+            dropped_frames: AtomicU64::new(0),
+            errors: Mutex::default(),
+        }
+    }
+}
+
+impl Metrics {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}\n{value}");
+        };
+
+        metric(
+            "signos_uptime_seconds",
+            "gauge",
+            "Seconds since the app started.",
+            format!("signos_uptime_seconds {}", self.started.elapsed().as_secs()),
+        );
+        metric(
+            "signos_captions_total",
+            "counter",
+            "Captions received from the worker.",
+            format!(
+                "signos_captions_total{{kind=\"partial\"}} {}\nsignos_captions_total{{kind=\"final\"}} {}",
+                self.captions_partial.load(Ordering::Relaxed),
+                self.captions_final.load(Ordering::Relaxed),
+            ),
+        );
+        metric(
+            "signos_pipeline_latency_ms",
+            "summary",
+            "Audio to caption latency reported by the worker.",
+            format!(
+                "signos_pipeline_latency_ms_sum {}\nsignos_pipeline_latency_ms_count {}",
+                self.latency_sum_ms.load(Ordering::Relaxed),
+                self.latency_count.load(Ordering::Relaxed),
+            ),
+        );
+        metric(
+            "signos_dropped_frames_total",
+            "counter",
+            "Audio frames captured while the worker connection was down.",
+            format!(
+                "signos_dropped_frames_total {}",
+                self.dropped_frames.load(Ordering::Relaxed)
+            ),
+        );
+
+        let errors = self.errors.lock().unwrap();
+        let lines: Vec<String> = errors
+            .iter()
+            .map(|(source, count)| {
+                format!(
+                    "signos_errors_total{{source=\"{}\"}} {}",
+                    escape_label(source),
+                    count
+                )
+            })
+            .collect();
+        metric(
+            "signos_errors_total",
+            "counter",
+            "Errors by source.",
+            lines.join("\n"),
+        );
+
+        out
+    }
+}
+
+/// Escapes a label value for the Prometheus text format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub fn record_caption(app: &AppHandle, caption: &Caption) {
+    let metrics = app.state::<Metrics>();
+
+    let captions = if caption.is_final {
+        &metrics.captions_final
+    } else {
+        &metrics.captions_partial
+    };
+    captions.fetch_add(1, Ordering::Relaxed);
+
+    if let Some(latency) = caption.latency_ms {
+        metrics.latency_sum_ms.fetch_add(latency, Ordering::Relaxed);
+        metrics.latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn record_error(app: &AppHandle, source: &str) {
+    let metrics = app.state::<Metrics>();
+    *metrics
+        .errors
+        .lock()
+        .unwrap()
+        .entry(source.to_string())
+        .or_default() += 1;
+}
+
+fn handle_client(app: &AppHandle, mut stream: TcpStream) -> Result<(), String> {
+    let mut request_line = String::new();
+    BufReader::new(&stream)
+        .take(8 * 1024)
+        .read_line(&mut request_line)
+        .map_err(|e| e.to_string())?;
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let response = if path == "/metrics" {
+        let body = app.state::<Metrics>().render();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|e| e.to_string())
+}
+
+/// Starts the metrics endpoint if it is enabled in settings.
+pub fn init(app: &AppHandle) {
+    let config = settings::get(app).metrics;
+    if !config.enabled {
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[Metrics] Failed to bind port {}: {}", config.port, e);
+            return;
+        }
+    };
+    println!("[Metrics] Serving http://127.0.0.1:{}/metrics", config.port);
+
+    sync::serve(app, listener, "Metrics", handle_client);
+}
+
+/// Audio frames the frontend had to discard, reported in batches.
+#[tauri::command]
+pub fn report_dropped_frames(app: AppHandle, count: u64) {
+    app.state::<Metrics>()
+        .dropped_frames
+        .fetch_add(count, Ordering::Relaxed);
+}
+
+#[tauri::command]
+pub fn report_error(app: AppHandle, source: String) -> Result<(), String> {
+    if !FRONTEND_SOURCES.contains(&source.as_str()) {
+        return Err(format!("Unknown error source '{}'", source));
+    }
+    record_error(&app, &source);
+    Ok(())
+}
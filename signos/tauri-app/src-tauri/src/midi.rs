@@ -0,0 +1,228 @@
+// midir has no Android backend; bindings are kept but no port is ever opened there.
+#![cfg_attr(target_os = "android", allow(dead_code))]
+
+use std::sync::Mutex;
+
+#[cfg(not(target_os = "android"))]
+use midir::{MidiInput, MidiInputConnection};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{capture, panel, settings, transcription};
+
+const CLIENT_NAME: &str = "Signos";
+#[cfg(target_os = "android")]
+const UNSUPPORTED: &str = "MIDI is not supported on this platform";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MidiSettings {
+    pub enabled: bool,
+    /// Input port to listen on. `None` picks the first available port.
+    pub port: Option<String>,
+    pub bindings: Vec<MidiBinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiBinding {
+    pub trigger: MidiTrigger,
+    pub action: MidiAction,
+}
+
+/// A note-on or control-change message on a given channel (0-15).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MidiTrigger {
+    Note { channel: u8, note: u8 },
+    Cc { channel: u8, controller: u8 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MidiAction {
+    TogglePanel,
+    ToggleCapture,
+    TogglePause,
+    CycleLanguage,
+}
+
+#[derive(Default)]
+pub struct MidiState {
+    #[cfg(not(target_os = "android"))]
+    connection: Mutex<Option<MidiInputConnection<()>>>,
+    /// Action waiting to be bound to the next incoming message.
+    learning: Mutex<Option<MidiAction>>,
+}
+
+impl MidiTrigger {
+    /// Parses a raw message, ignoring note-offs and released (value < 64) controls.
+    fn from_message(message: &[u8]) -> Option<Self> {
+        let [status, data1, data2, ..] = *message else {
+            return None;
+        };
+        let channel = status & 0x0F;
+
+        match status & 0xF0 {
+            0x90 if data2 > 0 => Some(Self::Note {
+                channel,
+                note: data1,
+            }),
+            0xB0 if data2 >= 64 => Some(Self::Cc {
+                channel,
+                controller: data1,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn run_action(app: &AppHandle, action: MidiAction) {
+    let result = match action {
+        MidiAction::TogglePanel => panel::toggle(app).map_err(|e| e.to_string()),
+        MidiAction::ToggleCapture if capture::status(app).active => {
+            capture::request_stop(app).map_err(|e| e.to_string())
+        }
+        MidiAction::ToggleCapture => capture::request_start(app).map_err(|e| e.to_string()),
+        MidiAction::TogglePause => capture::toggle_paused(app).map_err(|e| e.to_string()),
+        MidiAction::CycleLanguage => transcription::cycle_language(app).map(|_| ()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[MIDI] {:?} failed: {}", action, e);
+    }
+}
+
+fn learn(app: &AppHandle, trigger: MidiTrigger, action: MidiAction) {
+    let binding = MidiBinding { trigger, action };
+
+    let result = settings::update(app, |s| {
+        // A trigger maps to a single action, and each action to a single trigger.
+        s.midi
+            .bindings
+            .retain(|b| b.trigger != trigger && b.action != action);
+        s.midi.bindings.push(binding.clone());
+    });
+
+    match result {
+        Ok(_) => {
+            let _ = app.emit("midi:learned", &binding);
+        }
+        Err(e) => eprintln!("[MIDI] Failed to save binding: {}", e),
+    }
+}
+
+fn handle_message(app: &AppHandle, message: &[u8]) {
+    let Some(trigger) = MidiTrigger::from_message(message) else {
+        return;
+    };
+
+    let learning = app.state::<MidiState>().learning.lock().unwrap().take();
+    if let Some(action) = learning {
+        learn(app, trigger, action);
+        return;
+    }
+
+    let bindings = settings::get(app).midi.bindings;
+    for binding in bindings.iter().filter(|b| b.trigger == trigger) {
+        run_action(app, binding.action);
+    }
+}
+
+/// (Re)connects to the configured input port, or disconnects if MIDI is disabled.
+#[cfg(not(target_os = "android"))]
+pub fn connect(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<MidiState>();
+    let mut connection = state.connection.lock().unwrap();
+
+    // Dropping the previous connection closes it.
+    *connection = None;
+
+    let config = settings::get(app).midi;
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let input = MidiInput::new(CLIENT_NAME).map_err(|e| e.to_string())?;
+    let ports = input.ports();
+    let port = match &config.port {
+        Some(name) => ports
+            .iter()
+            .find(|port| input.port_name(port).as_deref() == Ok(name.as_str())),
+        None => ports.first(),
+    }
+    .ok_or("No MIDI input port available")?;
+
+    let port_name = input.port_name(port).unwrap_or_default();
+    let handle = app.clone();
+
+    *connection = Some(
+        input
+            .connect(
+                port,
+                "signos-input",
+                move |_, message, _| handle_message(&handle, message),
+                (),
+            )
+            .map_err(|e| e.to_string())?,
+    );
+
+    println!("[MIDI] Listening on '{}'", port_name);
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+pub fn connect(app: &AppHandle) -> Result<(), String> {
+    match settings::get(app).midi.enabled {
+        true => Err(UNSUPPORTED.into()),
+        false => Ok(()),
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    if let Err(e) = connect(app) {
+        eprintln!("[MIDI] Failed to connect: {}", e);
+    }
+
+    #[cfg(not(target_os = "android"))]
+    crate::lifecycle::on_exit(app, "midi", |app, _| {
+        let connection = app.state::<MidiState>().connection.lock().unwrap().take();
+        if let Some(connection) = connection {
+            connection.close();
+        }
+    });
+}
+
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub fn list_midi_ports() -> Result<Vec<String>, String> {
+    let input = MidiInput::new(CLIENT_NAME).map_err(|e| e.to_string())?;
+    Ok(input
+        .ports()
+        .iter()
+        .filter_map(|port| input.port_name(port).ok())
+        .collect())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub fn list_midi_ports() -> Result<Vec<String>, String> {
+    Err(UNSUPPORTED.into())
+}
+
+/// Applies the current MIDI settings, e.g. after the user picked another port.
+#[tauri::command]
+pub fn reconnect_midi(app: AppHandle) -> Result<(), String> {
+    connect(&app)
+}
+
+/// Binds `action` to the next note or control the user presses (`midi:learned`).
+#[tauri::command]
+pub fn start_midi_learn(app: AppHandle, action: MidiAction) {
+    *app.state::<MidiState>().learning.lock().unwrap() = Some(action);
+}
+
+#[tauri::command]
+pub fn cancel_midi_learn(app: AppHandle) {
+    app.state::<MidiState>().learning.lock().unwrap().take();
+}
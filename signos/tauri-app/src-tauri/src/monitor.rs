@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::settings;
+
+const MAX_GAIN: f32 = 4.0;
+
+/// Plays the captured audio back so users can hear what the pipeline hears.
+///
+/// The webview owns capture, so it taps the processed stream and renders it
+/// to `output_device`; the backend only stores the choice and announces
+/// changes (`capture:monitor`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MonitorSettings {
+    pub enabled: bool,
+    /// Output device id from the webview. `None` plays on the default output.
+    pub output_device: Option<String>,
+    /// Linear gain, 1.0 being unchanged.
+    pub gain: f32,
+}
+
+impl Default for MonitorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_device: None,
+            gain: 1.0,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_monitor(app: AppHandle) -> MonitorSettings {
+    settings::get(&app).monitor
+}
+
+/// Applied live if capture is running.
+#[tauri::command]
+pub fn set_monitor(
+    app: AppHandle,
+    enabled: bool,
+    output_device: Option<String>,
+    gain: f32,
+) -> Result<MonitorSettings, String> {
+    if !(0.0..=MAX_GAIN).contains(&gain) {
+        return Err(format!("Gain must be between 0 and {}", MAX_GAIN));
+    }
+
+    let monitor = MonitorSettings {
+        enabled,
+        output_device: output_device.filter(|id| !id.is_empty()),
+        gain,
+    };
+    settings::update(&app, |s| s.monitor = monitor.clone())?;
+
+    let _ = app.emit("capture:monitor", &monitor);
+    Ok(monitor)
+}
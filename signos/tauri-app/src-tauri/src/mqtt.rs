@@ -0,0 +1,138 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use rumqttc::{Client, MqttOptions, QoS, Transport};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener};
+
+use crate::capture::CaptureStatus;
+use crate::{lifecycle, settings};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MqttSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Uses TLS with the system root certificates (usually port 8883).
+    pub tls: bool,
+    pub topics: MqttTopics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MqttTopics {
+    /// Retained `{"visible": bool}`.
+    pub panel: String,
+    /// `{"event": "started" | "stopped", "startedAt": ms}`.
+    pub session: String,
+    /// The `keyword:hit` payload.
+    pub keyword: String,
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".into(),
+            port: 1883,
+            client_id: "signos".into(),
+            username: None,
+            password: None,
+            tls: false,
+            topics: MqttTopics::default(),
+        }
+    }
+}
+
+impl Default for MqttTopics {
+    fn default() -> Self {
+        Self {
+            panel: "signos/panel".into(),
+            session: "signos/session".into(),
+            keyword: "signos/keyword".into(),
+        }
+    }
+}
+
+fn publish(client: &Client, topic: &str, retain: bool, payload: String) {
+    if let Err(e) = client.try_publish(topic, QoS::AtLeastOnce, retain, payload) {
+        eprintln!("[MQTT] Failed to publish to {}: {}", topic, e);
+    }
+}
+
+/// Connects to the broker and publishes backend events, if enabled in settings.
+pub fn init(app: &AppHandle) {
+    let config = settings::get(app).mqtt;
+    if !config.enabled {
+        return;
+    }
+
+    let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let Some(username) = &config.username {
+        options.set_credentials(username, config.password.as_deref().unwrap_or_default());
+    }
+    if config.tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+
+    let (client, mut connection) = Client::new(options, 32);
+
+    // Drive the connection; rumqttc reconnects on the next poll after an error.
+    thread::spawn(move || {
+        for event in connection.iter() {
+            if let Err(e) = event {
+                eprintln!("[MQTT] Connection error: {}", e);
+                thread::sleep(RECONNECT_DELAY);
+            }
+        }
+    });
+
+    let topics = config.topics;
+
+    let (c, topic) = (client.clone(), topics.panel.clone());
+    app.listen_any("panel:visibility", move |event| {
+        let visible = event.payload() == "true";
+        publish(&c, &topic, true, format!("{{\"visible\":{}}}", visible));
+    });
+
+    // Only publish transitions, not every pause/resume.
+    let was_active = Mutex::new(false);
+    let (c, topic) = (client.clone(), topics.session.clone());
+    app.listen_any("capture:state", move |event| {
+        let Ok(status) = serde_json::from_str::<CaptureStatus>(event.payload()) else {
+            return;
+        };
+
+        let mut was_active = was_active.lock().unwrap();
+        if status.active == *was_active {
+            return;
+        }
+        *was_active = status.active;
+
+        let payload = serde_json::json!({
+            "event": if status.active { "started" } else { "stopped" },
+            "startedAt": status.started_at,
+        });
+        publish(&c, &topic, false, payload.to_string());
+    });
+
+    let (c, topic) = (client.clone(), topics.keyword);
+    app.listen_any("keyword:hit", move |event| {
+        publish(&c, &topic, false, event.payload().to_string());
+    });
+
+    // Queued messages would otherwise be lost with the process.
+    lifecycle::on_exit(app, "mqtt", move |_, _| {
+        if let Err(e) = client.disconnect() {
+            eprintln!("[MQTT] Failed to disconnect: {}", e);
+        }
+    });
+}
@@ -0,0 +1,166 @@
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::AppHandle;
+
+use crate::{consent, transcription};
+
+/// Microphone capture through AVAudioEngine, which keeps running while the app
+/// is in the background (unlike getUserMedia in WKWebView). Needs
+/// NSMicrophoneUsageDescription and the `audio` background mode in Info.plist.
+#[cfg(target_os = "ios")]
+mod platform {
+    use std::cell::RefCell;
+    use std::ptr::NonNull;
+    use std::sync::Mutex;
+
+    use block2::RcBlock;
+    use objc2::rc::Retained;
+    use objc2_avf_audio::{
+        AVAudioEngine, AVAudioPCMBuffer, AVAudioSession, AVAudioSessionCategoryOptions,
+        AVAudioSessionCategoryPlayAndRecord, AVAudioSessionModeDefault, AVAudioTime,
+    };
+    use tauri::ipc::{Channel, InvokeResponseBody};
+
+    use crate::capture_format::Resampler;
+
+    thread_local! {
+        // Only touched from the main thread.
+        static ENGINE: RefCell<Option<Retained<AVAudioEngine>>> = const { RefCell::new(None) };
+    }
+
+    pub const AVAILABLE: bool = true;
+
+    fn configure_session() -> Result<(), String> {
+        let (Some(category), Some(mode)) =
+            (unsafe { AVAudioSessionCategoryPlayAndRecord }, unsafe {
+                AVAudioSessionModeDefault
+            })
+        else {
+            return Err("Audio session constants are unavailable".into());
+        };
+
+        let session = unsafe { AVAudioSession::sharedInstance() };
+        unsafe {
+            session.setCategory_mode_options_error(
+                category,
+                mode,
+                AVAudioSessionCategoryOptions::MixWithOthers
+                    | AVAudioSessionCategoryOptions::AllowBluetoothHFP,
+            )
+        }
+        .map_err(|e| e.localizedDescription().to_string())?;
+
+        unsafe { session.setActive_error(true) }.map_err(|e| e.localizedDescription().to_string())
+    }
+
+    /// Starts the engine and streams PCM chunks to `channel`. Runs on the main thread.
+    pub fn start(channel: Channel<InvokeResponseBody>, chunk_samples: usize) -> Result<(), String> {
+        stop();
+        configure_session()?;
+
+        let engine = unsafe { AVAudioEngine::new() };
+        let input = unsafe { engine.inputNode() };
+        let format = unsafe { input.outputFormatForBus(0) };
+        let resampler = Mutex::new(Resampler::new(
+            unsafe { format.sampleRate() },
+            1,
+            1,
+            chunk_samples,
+        ));
+
+        let tap = RcBlock::new(
+            move |buffer: NonNull<AVAudioPCMBuffer>, _when: NonNull<AVAudioTime>| {
+                let buffer = unsafe { buffer.as_ref() };
+                let channels = unsafe { buffer.floatChannelData() };
+                if channels.is_null() {
+                    return;
+                }
+
+                // Only the first channel is used; the worker expects mono.
+                let samples = unsafe {
+                    std::slice::from_raw_parts((*channels).as_ptr(), buffer.frameLength() as usize)
+                };
+
+                for chunk in resampler.lock().unwrap().push(samples) {
+                    let _ = channel.send(InvokeResponseBody::Raw(chunk));
+                }
+            },
+        );
+
+        unsafe {
+            input.installTapOnBus_bufferSize_format_block(
+                0,
+                4096,
+                Some(&format),
+                RcBlock::as_ptr(&tap),
+            );
+            engine.prepare();
+            engine.startAndReturnError()
+        }
+        .map_err(|e| e.localizedDescription().to_string())?;
+
+        ENGINE.with(|slot| *slot.borrow_mut() = Some(engine));
+        println!("[NativeCapture] Started AVAudioEngine");
+        Ok(())
+    }
+
+    pub fn stop() {
+        let Some(engine) = ENGINE.with(|slot| slot.borrow_mut().take()) else {
+            return;
+        };
+
+        unsafe {
+            engine.inputNode().removeTapOnBus(0);
+            engine.stop();
+            let _ = AVAudioSession::sharedInstance().setActive_error(false);
+        }
+        println!("[NativeCapture] Stopped AVAudioEngine");
+    }
+}
+
+#[cfg(not(target_os = "ios"))]
+mod platform {
+    use tauri::ipc::{Channel, InvokeResponseBody};
+
+    pub const AVAILABLE: bool = false;
+
+    pub fn start(
+        _channel: Channel<InvokeResponseBody>,
+        _chunk_samples: usize,
+    ) -> Result<(), String> {
+        Err("Native capture is only supported on iOS".into())
+    }
+
+    pub fn stop() {}
+}
+
+/// Whether the frontend should capture through `start_native_capture` instead of getUserMedia.
+#[tauri::command]
+pub fn native_capture_available() -> bool {
+    platform::AVAILABLE
+}
+
+/// Streams 16 kHz Int16 PCM chunks to `on_audio`, the same format the webview pipeline produces.
+#[tauri::command]
+pub async fn start_native_capture(
+    app: AppHandle,
+    on_audio: Channel<InvokeResponseBody>,
+) -> Result<(), String> {
+    if !consent::allowed(&app) {
+        return Err("Everyone present has to consent before capturing".into());
+    }
+    let chunk_samples = transcription::inference(&app).chunk_samples();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(platform::start(on_audio, chunk_samples));
+    })
+    .map_err(|e| e.to_string())?;
+
+    rx.recv().map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn stop_native_capture(app: AppHandle) -> Result<(), String> {
+    app.run_on_main_thread(platform::stop)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,440 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::captions::Caption;
+
+/// Publishes captions as an NDI source so vision mixers can key them over
+/// the programme without screen-capturing the overlay.
+///
+/// Only active in builds with the `ndi` feature; the NDI runtime itself is
+/// loaded when the output starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NdiSettings {
+    pub enabled: bool,
+    pub source_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub font_size: f32,
+    /// TTF/OTF used to render the keyed frame. `None` tries common system fonts.
+    pub font_path: Option<String>,
+    /// Clears the frame after this long without a new caption.
+    pub clear_after_ms: u64,
+}
+
+impl Default for NdiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source_name: "Signos Captions".into(),
+            width: 1920,
+            height: 1080,
+            font_size: 56.0,
+            font_path: None,
+            clear_after_ms: 5000,
+        }
+    }
+}
+
+#[cfg(feature = "ndi")]
+mod output {
+    use std::ffi::{c_char, c_void, CString};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::sync::Mutex;
+    use std::thread::{self, JoinHandle};
+    use std::time::{Duration, Instant};
+
+    use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+    use libloading::Library;
+
+    use super::NdiSettings;
+
+    const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+    const MARGIN: f32 = 48.0;
+
+    #[cfg(target_os = "windows")]
+    const LIBRARY_NAMES: &[&str] = &["Processing.NDI.Lib.x64.dll"];
+    #[cfg(target_os = "macos")]
+    const LIBRARY_NAMES: &[&str] = &["libndi.dylib", "/usr/local/lib/libndi.dylib"];
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    const LIBRARY_NAMES: &[&str] = &["libndi.so.6", "libndi.so.5", "libndi.so"];
+
+    #[cfg(target_os = "windows")]
+    const SYSTEM_FONTS: &[&str] = &[
+        "C:\\Windows\\Fonts\\segoeui.ttf",
+        "C:\\Windows\\Fonts\\arial.ttf",
+    ];
+    #[cfg(target_os = "macos")]
+    const SYSTEM_FONTS: &[&str] = &[
+        "/System/Library/Fonts/Supplemental/Arial.ttf",
+        "/Library/Fonts/Arial.ttf",
+    ];
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    const SYSTEM_FONTS: &[&str] = &[
+        "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+        "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    ];
+
+    // Mirrors of the NDI SDK structs we use (Processing.NDI.Send.h / Processing.NDI.structs.h).
+    #[repr(C)]
+    struct SendCreate {
+        ndi_name: *const c_char,
+        groups: *const c_char,
+        clock_video: bool,
+        clock_audio: bool,
+    }
+
+    #[repr(C)]
+    struct VideoFrame {
+        xres: i32,
+        yres: i32,
+        four_cc: u32,
+        frame_rate_n: i32,
+        frame_rate_d: i32,
+        picture_aspect_ratio: f32,
+        frame_format_type: i32,
+        timecode: i64,
+        data: *mut u8,
+        line_stride_in_bytes: i32,
+        metadata: *const c_char,
+        timestamp: i64,
+    }
+
+    #[repr(C)]
+    struct MetadataFrame {
+        length: i32,
+        timecode: i64,
+        data: *mut c_char,
+    }
+
+    const FOURCC_BGRA: u32 = u32::from_le_bytes(*b"BGRA");
+    const FRAME_FORMAT_PROGRESSIVE: i32 = 1;
+    const TIMECODE_SYNTHESIZE: i64 = i64::MAX;
+
+    type Initialize = unsafe extern "C" fn() -> bool;
+    type SendCreateFn = unsafe extern "C" fn(*const SendCreate) -> *mut c_void;
+    type SendDestroy = unsafe extern "C" fn(*mut c_void);
+    type SendVideo = unsafe extern "C" fn(*mut c_void, *const VideoFrame);
+    type SendMetadata = unsafe extern "C" fn(*mut c_void, *const MetadataFrame);
+
+    struct NdiSender {
+        // Kept alive for as long as the function pointers are used.
+        _library: Library,
+        instance: *mut c_void,
+        destroy: SendDestroy,
+        send_video: SendVideo,
+        send_metadata: SendMetadata,
+    }
+
+    // The NDI sender instance may be used from any single thread at a time.
+    unsafe impl Send for NdiSender {}
+
+    impl Drop for NdiSender {
+        fn drop(&mut self) {
+            unsafe { (self.destroy)(self.instance) };
+        }
+    }
+
+    impl NdiSender {
+        fn create(name: &str) -> Result<Self, String> {
+            let library = LIBRARY_NAMES
+                .iter()
+                .find_map(|name| unsafe { Library::new(*name).ok() })
+                .ok_or("NDI runtime not found; install it from ndi.video")?;
+
+            unsafe {
+                let initialize = *library
+                    .get::<Initialize>(b"NDIlib_initialize\0")
+                    .map_err(|e| e.to_string())?;
+                let create = *library
+                    .get::<SendCreateFn>(b"NDIlib_send_create\0")
+                    .map_err(|e| e.to_string())?;
+                let destroy = *library
+                    .get::<SendDestroy>(b"NDIlib_send_destroy\0")
+                    .map_err(|e| e.to_string())?;
+                let send_video = *library
+                    .get::<SendVideo>(b"NDIlib_send_send_video_v2\0")
+                    .map_err(|e| e.to_string())?;
+                let send_metadata = *library
+                    .get::<SendMetadata>(b"NDIlib_send_send_metadata\0")
+                    .map_err(|e| e.to_string())?;
+
+                if !initialize() {
+                    return Err("This CPU is not supported by NDI".into());
+                }
+
+                let name = CString::new(name).map_err(|e| e.to_string())?;
+                let settings = SendCreate {
+                    ndi_name: name.as_ptr(),
+                    groups: std::ptr::null(),
+                    clock_video: false,
+                    clock_audio: false,
+                };
+                let instance = create(&settings);
+                if instance.is_null() {
+                    return Err("Failed to create NDI sender".into());
+                }
+
+                Ok(Self {
+                    _library: library,
+                    instance,
+                    destroy,
+                    send_video,
+                    send_metadata,
+                })
+            }
+        }
+
+        fn send_frame(&self, frame: &mut [u8], width: u32, height: u32) {
+            let video = VideoFrame {
+                xres: width as i32,
+                yres: height as i32,
+                four_cc: FOURCC_BGRA,
+                frame_rate_n: 10,
+                frame_rate_d: 1,
+                picture_aspect_ratio: width as f32 / height as f32,
+                frame_format_type: FRAME_FORMAT_PROGRESSIVE,
+                timecode: TIMECODE_SYNTHESIZE,
+                data: frame.as_mut_ptr(),
+                line_stride_in_bytes: (width * 4) as i32,
+                metadata: std::ptr::null(),
+                timestamp: 0,
+            };
+            unsafe { (self.send_video)(self.instance, &video) };
+        }
+
+        /// Also sends the raw text so receivers that read metadata don't need OCR.
+        fn send_text(&self, text: &str, is_final: bool) {
+            let xml = format!(
+                "<signos_caption final=\"{}\">{}</signos_caption>",
+                is_final,
+                text.replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+            );
+            let Ok(xml) = CString::new(xml) else {
+                return;
+            };
+
+            let metadata = MetadataFrame {
+                length: xml.as_bytes_with_nul().len() as i32,
+                timecode: TIMECODE_SYNTHESIZE,
+                data: xml.as_ptr() as *mut c_char,
+            };
+            unsafe { (self.send_metadata)(self.instance, &metadata) };
+        }
+    }
+
+    fn load_font(config: &NdiSettings) -> Result<FontVec, String> {
+        let candidates: Vec<&str> = match &config.font_path {
+            Some(path) => vec![path.as_str()],
+            None => SYSTEM_FONTS.to_vec(),
+        };
+
+        candidates
+            .iter()
+            .find_map(|path| std::fs::read(path).ok())
+            .ok_or_else(|| "No usable font found; set ndi.fontPath".to_string())
+            .and_then(|bytes| FontVec::try_from_vec(bytes).map_err(|e| e.to_string()))
+    }
+
+    /// Greedy word wrap, measuring with the font's advances.
+    fn wrap_lines(font: &FontVec, scale: PxScale, text: &str, max_width: f32) -> Vec<String> {
+        let scaled = font.as_scaled(scale);
+        let measure = |s: &str| -> f32 {
+            s.chars()
+                .map(|c| scaled.h_advance(scaled.glyph_id(c)))
+                .sum()
+        };
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut current = String::new();
+
+        for word in text.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+
+            if measure(&candidate) > max_width && !current.is_empty() {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+
+        lines
+    }
+
+    /// Renders the caption as a lower third: white text on a translucent box,
+    /// transparent everywhere else so the mixer can key it.
+    fn render(font: &FontVec, config: &NdiSettings, text: &str) -> Vec<u8> {
+        let (width, height) = (config.width as usize, config.height as usize);
+        let mut frame = vec![0u8; width * height * 4];
+        if text.is_empty() {
+            return frame;
+        }
+
+        let scale = PxScale::from(config.font_size);
+        let scaled = font.as_scaled(scale);
+        let line_height = scaled.height() + scaled.line_gap();
+
+        // Keep the last two lines, like broadcast roll-up captions.
+        let lines = wrap_lines(font, scale, text, width as f32 - MARGIN * 4.0);
+        let lines = &lines[lines.len().saturating_sub(2)..];
+
+        let box_height = line_height * lines.len() as f32 + MARGIN;
+        let box_top = (height as f32 - MARGIN - box_height).max(0.0) as usize;
+        for y in box_top..(box_top + box_height as usize).min(height) {
+            for x in MARGIN as usize..width.saturating_sub(MARGIN as usize) {
+                let i = (y * width + x) * 4;
+                frame[i + 3] = 160;
+            }
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            let baseline =
+                box_top as f32 + MARGIN / 2.0 + scaled.ascent() + line_height * row as f32;
+            let mut caret = MARGIN * 2.0;
+
+            for c in line.chars() {
+                let glyph_id = scaled.glyph_id(c);
+                let glyph = glyph_id.with_scale_and_position(scale, point(caret, baseline));
+                caret += scaled.h_advance(glyph_id);
+
+                let Some(outlined) = font.outline_glyph(glyph) else {
+                    continue;
+                };
+                let bounds = outlined.px_bounds();
+
+                outlined.draw(|gx, gy, coverage| {
+                    let x = bounds.min.x as i32 + gx as i32;
+                    let y = bounds.min.y as i32 + gy as i32;
+                    if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+                        return;
+                    }
+
+                    let i = (y as usize * width + x as usize) * 4;
+                    let value = (coverage * 255.0) as u8;
+                    frame[i] = frame[i].max(value);
+                    frame[i + 1] = frame[i + 1].max(value);
+                    frame[i + 2] = frame[i + 2].max(value);
+                    frame[i + 3] = frame[i + 3].max(value);
+                });
+            }
+        }
+
+        frame
+    }
+
+    struct Update {
+        text: String,
+        is_final: bool,
+    }
+
+    pub struct NdiOutput {
+        updates: Mutex<Option<Sender<Update>>>,
+        thread: Mutex<Option<JoinHandle<()>>>,
+    }
+
+    impl NdiOutput {
+        pub fn start(config: NdiSettings) -> Result<Self, String> {
+            let sender = NdiSender::create(&config.source_name)?;
+            let font = load_font(&config)?;
+            let (tx, rx) = mpsc::channel();
+
+            let thread = thread::spawn(move || run(sender, font, config, rx));
+
+            Ok(Self {
+                updates: Mutex::new(Some(tx)),
+                thread: Mutex::new(Some(thread)),
+            })
+        }
+
+        pub fn send(&self, text: &str, is_final: bool) {
+            if let Some(updates) = self.updates.lock().unwrap().as_ref() {
+                let _ = updates.send(Update {
+                    text: text.to_string(),
+                    is_final,
+                });
+            }
+        }
+
+        /// Ends the frame loop and waits for it to destroy the NDI sender, so
+        /// receivers see the source go away.
+        pub fn stop(&self) {
+            self.updates.lock().unwrap().take();
+            if let Some(thread) = self.thread.lock().unwrap().take() {
+                let _ = thread.join();
+            }
+        }
+    }
+
+    /// Re-sends the current frame at a steady rate so receivers keep the source live.
+    fn run(sender: NdiSender, font: FontVec, config: NdiSettings, updates: Receiver<Update>) {
+        let mut frame = render(&font, &config, "");
+        let mut last_update = Instant::now();
+        let mut showing = false;
+
+        loop {
+            match updates.recv_timeout(FRAME_INTERVAL) {
+                Ok(update) => {
+                    frame = render(&font, &config, &update.text);
+                    sender.send_text(&update.text, update.is_final);
+                    last_update = Instant::now();
+                    showing = true;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            if showing && last_update.elapsed() > Duration::from_millis(config.clear_after_ms) {
+                frame = render(&font, &config, "");
+                showing = false;
+            }
+
+            sender.send_frame(&mut frame, config.width, config.height);
+        }
+    }
+}
+
+#[cfg(feature = "ndi")]
+pub fn init(app: &AppHandle) {
+    use tauri::Manager;
+
+    let config = crate::settings::get(app).ndi;
+    if !config.enabled {
+        return;
+    }
+
+    match output::NdiOutput::start(config) {
+        Ok(output) => {
+            app.manage(output);
+            crate::lifecycle::on_exit(app, "ndi", |app, _| {
+                app.state::<output::NdiOutput>().stop();
+            });
+        }
+        Err(e) => eprintln!("[NDI] Failed to start output: {}", e),
+    }
+}
+
+#[cfg(not(feature = "ndi"))]
+pub fn init(_app: &AppHandle) {}
+
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    #[cfg(feature = "ndi")]
+    {
+        use tauri::Manager;
+
+        if let Some(output) = app.try_state::<output::NdiOutput>() {
+            output.send(&caption.text, caption.is_final);
+        }
+    }
+
+    #[cfg(not(feature = "ndi"))]
+    let _ = (app, caption);
+}
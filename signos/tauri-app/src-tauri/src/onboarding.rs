@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::util::now_ms;
+use crate::{capture, landmarks, settings};
+
+/// First-run wizard progress, persisted so it resumes after a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OnboardingSettings {
+    pub completed: Vec<OnboardingStep>,
+    /// Unix timestamp (ms) of when the last step was completed.
+    pub finished_at: Option<u64>,
+}
+
+/// Steps in the order the wizard walks through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnboardingStep {
+    /// Microphone access, and on macOS Accessibility access. Completing the
+    /// step shows the system prompts that haven't been answered yet.
+    Permissions,
+    Device,
+    /// The hand landmark model has been downloaded.
+    Model,
+    Hotkey,
+}
+
+impl OnboardingStep {
+    const ALL: [Self; 4] = [Self::Permissions, Self::Device, Self::Model, Self::Hotkey];
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingState {
+    pub steps: Vec<StepState>,
+    /// First incomplete step, i.e. what the wizard should show.
+    pub current: Option<OnboardingStep>,
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepState {
+    pub step: OnboardingStep,
+    pub completed: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use block2::RcBlock;
+    use objc2::runtime::Bool;
+    use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio};
+    use objc2_foundation::{NSDictionary, NSNumber, NSString};
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        static kAXTrustedCheckOptionPrompt: &'static NSString;
+        fn AXIsProcessTrustedWithOptions(options: &NSDictionary<NSString, NSNumber>) -> u8;
+    }
+
+    fn request_microphone() -> Result<bool, String> {
+        let media_type = unsafe { AVMediaTypeAudio }.ok_or("AVFoundation is unavailable")?;
+        let status = unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) };
+        if status != AVAuthorizationStatus::NotDetermined {
+            return Ok(status == AVAuthorizationStatus::Authorized);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let handler = RcBlock::new(move |granted: Bool| {
+            let _ = tx.send(granted.as_bool());
+        });
+        unsafe {
+            AVCaptureDevice::requestAccessForMediaType_completionHandler(media_type, &handler)
+        };
+
+        // The user may leave the prompt open for a while.
+        rx.recv_timeout(Duration::from_secs(300))
+            .map_err(|_| "Timed out waiting for microphone permission".to_string())
+    }
+
+    /// Shows the system prompt pointing to System Settings when not trusted yet.
+    fn request_accessibility() -> bool {
+        let prompt = NSNumber::new_bool(true);
+        let options =
+            NSDictionary::from_slices(&[unsafe { kAXTrustedCheckOptionPrompt }], &[&*prompt]);
+        unsafe { AXIsProcessTrustedWithOptions(&options) != 0 }
+    }
+
+    pub fn request_permissions() -> Result<(), String> {
+        if !request_microphone()? {
+            return Err("Microphone access was denied; allow it in System Settings".into());
+        }
+        if !request_accessibility() {
+            return Err("Allow Accessibility access in System Settings, then try again".into());
+        }
+        Ok(())
+    }
+}
+
+/// The webview prompts for the microphone when it enumerates inputs, which the
+/// device step depends on.
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    pub fn request_permissions() -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn state(config: &OnboardingSettings) -> OnboardingState {
+    let steps: Vec<StepState> = OnboardingStep::ALL
+        .into_iter()
+        .map(|step| StepState {
+            step,
+            completed: config.completed.contains(&step),
+        })
+        .collect();
+    let current = steps.iter().find(|s| !s.completed).map(|s| s.step);
+
+    OnboardingState {
+        steps,
+        current,
+        finished: current.is_none(),
+    }
+}
+
+/// Checks what the backend can verify itself before a step counts as done.
+fn verify(app: &AppHandle, step: OnboardingStep) -> Result<(), String> {
+    match step {
+        OnboardingStep::Permissions => platform::request_permissions(),
+        OnboardingStep::Model if !landmarks::model_installed(app) => {
+            Err("The hand landmark model has not been downloaded".into())
+        }
+        OnboardingStep::Device if capture::devices(app).selected.is_none() => {
+            Err("No audio input has been selected".into())
+        }
+        OnboardingStep::Hotkey if settings::get(app).hotkeys.bindings.is_empty() => {
+            Err("No shortcut has been bound".into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Marks `step` as done and notifies the wizard (`onboarding:changed`).
+pub fn complete(app: &AppHandle, step: OnboardingStep) -> Result<OnboardingState, String> {
+    let current = settings::get(app).onboarding;
+    if current.completed.contains(&step) {
+        return Ok(state(&current));
+    }
+
+    verify(app, step)?;
+
+    let settings = settings::update(app, |s| {
+        // Another call may have completed it while this one was verifying.
+        if s.onboarding.completed.contains(&step) {
+            return;
+        }
+        s.onboarding.completed.push(step);
+        if state(&s.onboarding).finished {
+            s.onboarding.finished_at = Some(now_ms());
+        }
+    })?;
+
+    let state = state(&settings.onboarding);
+    let _ = app.emit("onboarding:changed", &state);
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn get_onboarding_state(app: AppHandle) -> OnboardingState {
+    state(&settings::get(&app).onboarding)
+}
+
+/// Async since the permission step waits for the user to answer the prompts.
+#[tauri::command(async)]
+pub fn complete_step(app: AppHandle, step: OnboardingStep) -> Result<OnboardingState, String> {
+    complete(&app, step)
+}
@@ -0,0 +1,127 @@
+use std::net::UdpSocket;
+
+use rosc::{OscMessage, OscPacket, OscType};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::captions::Caption;
+use crate::{capture, lifecycle, metrics, panel, settings};
+
+/// Backend events after which the full state is re-sent.
+const STATE_EVENTS: [&str; 4] = [
+    "panel:visibility",
+    "capture:state",
+    "profile:changed",
+    "language:changed",
+];
+
+/// Sends captions and state to show-control tools (QLab, Resolume, lighting desks).
+///
+/// Addresses, relative to `address_prefix`:
+/// - `/caption/partial` (string), `/caption/final` (string)
+/// - `/panel/visible`, `/capture/active`, `/capture/paused` (int 0/1)
+/// - `/profile`, `/language` (string)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OscSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub address_prefix: String,
+}
+
+impl Default for OscSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".into(),
+            port: 9000,
+            address_prefix: "/signos".into(),
+        }
+    }
+}
+
+pub struct OscState {
+    socket: Option<UdpSocket>,
+}
+
+fn send(app: &AppHandle, messages: Vec<(&str, OscType)>) {
+    let config = settings::get(app).osc;
+    if !config.enabled {
+        return;
+    }
+
+    // Not managed when integrations are skipped in safe mode.
+    let Some(state) = app.try_state::<OscState>() else {
+        return;
+    };
+    let Some(socket) = &state.socket else {
+        return;
+    };
+
+    for (address, arg) in messages {
+        let packet = OscPacket::Message(OscMessage {
+            addr: format!("{}{}", config.address_prefix.trim_end_matches('/'), address),
+            args: vec![arg],
+        });
+
+        let result = rosc::encoder::encode(&packet)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                socket
+                    .send_to(&bytes, (config.host.as_str(), config.port))
+                    .map_err(|e| e.to_string())
+            });
+
+        if let Err(e) = result {
+            eprintln!("[OSC] Failed to send {}: {}", address, e);
+            metrics::record_error(app, "osc");
+        }
+    }
+}
+
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    let address = if caption.is_final {
+        "/caption/final"
+    } else {
+        "/caption/partial"
+    };
+    send(app, vec![(address, OscType::String(caption.text.clone()))]);
+}
+
+fn send_state(app: &AppHandle) {
+    let capture = capture::status(app);
+    let settings = settings::get(app);
+
+    send(
+        app,
+        vec![
+            (
+                "/panel/visible",
+                OscType::Int(panel::is_visible(app) as i32),
+            ),
+            ("/capture/active", OscType::Int(capture.active as i32)),
+            ("/capture/paused", OscType::Int(capture.paused as i32)),
+            ("/profile", OscType::String(settings.profiles.active)),
+            (
+                "/language",
+                OscType::String(settings.transcription.language),
+            ),
+        ],
+    );
+}
+
+pub fn init(app: &AppHandle) {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .inspect_err(|e| eprintln!("[OSC] Failed to bind socket: {}", e))
+        .ok();
+    app.manage(OscState { socket });
+
+    for event in STATE_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |_| send_state(&handle));
+    }
+
+    // Hooks run once capture has stopped, so show control sees it end.
+    lifecycle::on_exit(app, "osc", |app, _| send_state(app));
+}
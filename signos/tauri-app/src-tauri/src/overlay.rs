@@ -0,0 +1,344 @@
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::panel::MAIN_WINDOW;
+use crate::settings;
+
+/// Android only: hosts the panel in a system overlay so captions float above
+/// other apps. `build.rs` adds `SYSTEM_ALERT_WINDOW` to the Android manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OverlaySettings {
+    pub enabled: bool,
+    /// Overlay height in dp; it always spans the screen width.
+    pub height: u32,
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            height: 160,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayStatus {
+    pub supported: bool,
+    /// Whether the user allowed "Display over other apps".
+    pub permitted: bool,
+    pub active: bool,
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+    use std::sync::{mpsc, Mutex};
+
+    use jni::objects::{GlobalRef, JObject, JString, JValue};
+    use jni::JNIEnv;
+    use tauri::WebviewWindow;
+
+    const TYPE_APPLICATION_OVERLAY: i32 = 2038;
+    const FLAG_NOT_FOCUSABLE: i32 = 0x8;
+    const PIXEL_FORMAT_TRANSLUCENT: i32 = -3;
+    const MATCH_PARENT: i32 = -1;
+    const GRAVITY_BOTTOM: i32 = 0x50;
+
+    /// Where the webview lived before it was moved into the overlay.
+    struct Host {
+        parent: GlobalRef,
+        index: i32,
+    }
+
+    static HOST: Mutex<Option<Host>> = Mutex::new(None);
+
+    pub const SUPPORTED: bool = true;
+
+    /// Runs `f` on the webview's UI thread and waits for the result.
+    fn exec<T: Send + 'static>(
+        window: &WebviewWindow,
+        f: impl FnOnce(&mut JNIEnv, &JObject, &JObject) -> jni::errors::Result<T> + Send + 'static,
+    ) -> Result<T, String> {
+        let (tx, rx) = mpsc::channel();
+        window
+            .with_webview(move |webview| {
+                webview.jni_handle().exec(move |env, activity, webview| {
+                    let result = f(env, activity, webview);
+                    // A pending Java exception would poison every later JNI call.
+                    if env.exception_check().unwrap_or(false) {
+                        let _ = env.exception_describe();
+                        let _ = env.exception_clear();
+                    }
+                    let _ = tx.send(result);
+                });
+            })
+            .map_err(|e| e.to_string())?;
+
+        rx.recv()
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())
+    }
+
+    fn can_draw_overlays(env: &mut JNIEnv, activity: &JObject) -> jni::errors::Result<bool> {
+        env.call_static_method(
+            "android/provider/Settings",
+            "canDrawOverlays",
+            "(Landroid/content/Context;)Z",
+            &[JValue::Object(activity)],
+        )?
+        .z()
+    }
+
+    fn window_manager<'a>(
+        env: &mut JNIEnv<'a>,
+        activity: &JObject,
+    ) -> jni::errors::Result<JObject<'a>> {
+        let service = env.new_string("window")?;
+        env.call_method(
+            activity,
+            "getSystemService",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[JValue::Object(&service)],
+        )?
+        .l()
+    }
+
+    pub fn is_permitted(window: &WebviewWindow) -> Result<bool, String> {
+        exec(window, |env, activity, _| can_draw_overlays(env, activity))
+    }
+
+    pub fn is_active() -> bool {
+        HOST.lock().unwrap().is_some()
+    }
+
+    /// Opens the system "Display over other apps" screen for this app.
+    pub fn request_permission(window: &WebviewWindow) -> Result<(), String> {
+        exec(window, |env, activity, _| {
+            let package = env
+                .call_method(activity, "getPackageName", "()Ljava/lang/String;", &[])?
+                .l()?;
+            let package: String = env.get_string(&JString::from(package))?.into();
+
+            let uri = env.new_string(format!("package:{}", package))?;
+            let uri = env
+                .call_static_method(
+                    "android/net/Uri",
+                    "parse",
+                    "(Ljava/lang/String;)Landroid/net/Uri;",
+                    &[JValue::Object(&uri)],
+                )?
+                .l()?;
+
+            let action = env.new_string("android.settings.action.MANAGE_OVERLAY_PERMISSION")?;
+            let intent = env.new_object(
+                "android/content/Intent",
+                "(Ljava/lang/String;Landroid/net/Uri;)V",
+                &[JValue::Object(&action), JValue::Object(&uri)],
+            )?;
+
+            env.call_method(
+                activity,
+                "startActivity",
+                "(Landroid/content/Intent;)V",
+                &[JValue::Object(&intent)],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Moves the webview out of the activity into an overlay anchored to the bottom of the screen.
+    pub fn show(window: &WebviewWindow, height: u32) -> Result<(), String> {
+        if is_active() {
+            return Ok(());
+        }
+
+        let host = exec(window, move |env, activity, webview| {
+            if !can_draw_overlays(env, activity)? {
+                return Ok(None);
+            }
+
+            let resources = env
+                .call_method(
+                    activity,
+                    "getResources",
+                    "()Landroid/content/res/Resources;",
+                    &[],
+                )?
+                .l()?;
+            let metrics = env
+                .call_method(
+                    &resources,
+                    "getDisplayMetrics",
+                    "()Landroid/util/DisplayMetrics;",
+                    &[],
+                )?
+                .l()?;
+            let density = env.get_field(&metrics, "density", "F")?.f()?;
+
+            let parent = env
+                .call_method(webview, "getParent", "()Landroid/view/ViewParent;", &[])?
+                .l()?;
+            if parent.is_null() {
+                return Ok(None);
+            }
+            let index = env
+                .call_method(
+                    &parent,
+                    "indexOfChild",
+                    "(Landroid/view/View;)I",
+                    &[JValue::Object(webview)],
+                )?
+                .i()?;
+            env.call_method(
+                &parent,
+                "removeView",
+                "(Landroid/view/View;)V",
+                &[JValue::Object(webview)],
+            )?;
+
+            let params = env.new_object(
+                "android/view/WindowManager$LayoutParams",
+                "(IIIII)V",
+                &[
+                    JValue::Int(MATCH_PARENT),
+                    JValue::Int((height as f32 * density) as i32),
+                    JValue::Int(TYPE_APPLICATION_OVERLAY),
+                    JValue::Int(FLAG_NOT_FOCUSABLE),
+                    JValue::Int(PIXEL_FORMAT_TRANSLUCENT),
+                ],
+            )?;
+            env.set_field(&params, "gravity", "I", JValue::Int(GRAVITY_BOTTOM))?;
+
+            let manager = window_manager(env, activity)?;
+            env.call_method(
+                &manager,
+                "addView",
+                "(Landroid/view/View;Landroid/view/ViewGroup$LayoutParams;)V",
+                &[JValue::Object(webview), JValue::Object(&params)],
+            )?;
+
+            Ok(Some(Host {
+                parent: env.new_global_ref(parent)?,
+                index,
+            }))
+        })?
+        .ok_or("Overlay permission has not been granted")?;
+
+        *HOST.lock().unwrap() = Some(host);
+        println!("[Overlay] Panel moved into overlay");
+        Ok(())
+    }
+
+    /// Puts the webview back where it was in the activity.
+    pub fn hide(window: &WebviewWindow) -> Result<(), String> {
+        let Some(host) = HOST.lock().unwrap().take() else {
+            return Ok(());
+        };
+
+        exec(window, move |env, activity, webview| {
+            let manager = window_manager(env, activity)?;
+            env.call_method(
+                &manager,
+                "removeView",
+                "(Landroid/view/View;)V",
+                &[JValue::Object(webview)],
+            )?;
+            env.call_method(
+                host.parent.as_obj(),
+                "addView",
+                "(Landroid/view/View;I)V",
+                &[JValue::Object(webview), JValue::Int(host.index)],
+            )?;
+            Ok(())
+        })?;
+
+        println!("[Overlay] Panel returned to the app");
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+mod platform {
+    use tauri::WebviewWindow;
+
+    const UNSUPPORTED: &str = "Overlay windows are only supported on Android";
+
+    pub const SUPPORTED: bool = false;
+
+    pub fn is_permitted(_window: &WebviewWindow) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    pub fn is_active() -> bool {
+        false
+    }
+
+    pub fn request_permission(_window: &WebviewWindow) -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn show(_window: &WebviewWindow, _height: u32) -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn hide(_window: &WebviewWindow) -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+}
+
+fn main_window(app: &AppHandle) -> Result<tauri::WebviewWindow, String> {
+    app.get_webview_window(MAIN_WINDOW)
+        .ok_or_else(|| "Main window not found".to_string())
+}
+
+fn status(app: &AppHandle) -> Result<OverlayStatus, String> {
+    let window = main_window(app)?;
+    Ok(OverlayStatus {
+        supported: platform::SUPPORTED,
+        permitted: platform::is_permitted(&window)?,
+        active: platform::is_active(),
+    })
+}
+
+pub fn init(app: &AppHandle) {
+    let config = settings::get(app).overlay;
+    if !platform::SUPPORTED || !config.enabled {
+        return;
+    }
+
+    // The JNI calls are answered on the UI thread, which is busy running setup.
+    let app = app.clone();
+    thread::spawn(move || {
+        let result = main_window(&app).and_then(|window| platform::show(&window, config.height));
+        if let Err(e) = result {
+            eprintln!("[Overlay] Failed to show overlay: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_overlay_status(app: AppHandle) -> Result<OverlayStatus, String> {
+    status(&app)
+}
+
+/// Sends the user to the system settings screen; re-check `get_overlay_status` when the app resumes.
+#[tauri::command]
+pub async fn request_overlay_permission(app: AppHandle) -> Result<(), String> {
+    platform::request_permission(&main_window(&app)?)
+}
+
+#[tauri::command]
+pub async fn set_overlay_enabled(app: AppHandle, enabled: bool) -> Result<OverlayStatus, String> {
+    let window = main_window(&app)?;
+    match enabled {
+        true => platform::show(&window, settings::get(&app).overlay.height)?,
+        false => platform::hide(&window)?,
+    }
+
+    settings::update(&app, |s| s.overlay.enabled = enabled)?;
+    status(&app)
+}
@@ -0,0 +1,75 @@
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
+
+pub const MAIN_WINDOW: &str = "main";
+
+/// Moves the panel to the bottom-right area of `monitor` (1-based), or of its current monitor.
+pub fn place(window: &WebviewWindow, monitor: Option<usize>) -> tauri::Result<()> {
+    let target = match monitor {
+        Some(index) => window.available_monitors()?.into_iter().nth(index - 1),
+        None => None,
+    };
+    let Some(monitor) = target.or(window.current_monitor()?) else {
+        return Ok(());
+    };
+
+    let screen_size = monitor.size();
+    let origin = monitor.position();
+
+    // Use configured window size (290x380) instead of outer_size
+    // to avoid issues with frameless windows on macOS
+    let window_width = 290;
+    let window_height = 380;
+
+    // Calculate bottom-right corner position
+    // Add padding from edges (20px from right and bottom)
+    let x = origin.x + screen_size.width as i32 - window_width - 300;
+    let y = origin.y + screen_size.height as i32 - window_height - 400;
+
+    // Position window in bottom-right corner
+    window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+}
+
+pub fn is_visible(app: &AppHandle) -> bool {
+    app.get_webview_window(MAIN_WINDOW)
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
+
+pub fn show(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        window.show()?;
+        app.emit("panel:visibility", true)?;
+    }
+    Ok(())
+}
+
+pub fn hide(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        window.hide()?;
+        app.emit("panel:visibility", false)?;
+    }
+    Ok(())
+}
+
+pub fn toggle(app: &AppHandle) -> tauri::Result<()> {
+    if is_visible(app) {
+        hide(app)
+    } else {
+        show(app)
+    }
+}
+
+#[tauri::command]
+pub fn show_panel(app: AppHandle) -> Result<(), String> {
+    show(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn hide_panel(app: AppHandle) -> Result<(), String> {
+    hide(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn toggle_panel(app: AppHandle) -> Result<(), String> {
+    toggle(&app).map_err(|e| e.to_string())
+}
@@ -0,0 +1,145 @@
+//! Names for the people in a recorded meeting.
+//!
+//! Participants come from the attendees of the calendar event that armed
+//! capture, or are entered by hand. They are stored on the session, and each
+//! diarized speaker id can be mapped to one of them so exports show who said
+//! what instead of a bare speaker number.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::recording::{self, Session};
+use crate::util::now_ms;
+
+/// Meeting attendees are only used by a recording that starts within this long.
+const PENDING_TTL_MS: u64 = 30 * 60 * 1000;
+
+/// Attendees of the last armed meeting, waiting for its recording to start.
+#[derive(Default)]
+pub struct ParticipantsState(Mutex<Option<(u64, Vec<String>)>>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participants {
+    pub session_id: String,
+    pub participants: Vec<String>,
+    pub speaker_names: BTreeMap<u32, String>,
+}
+
+impl From<&Session> for Participants {
+    fn from(session: &Session) -> Self {
+        Self {
+            session_id: session.id.clone(),
+            participants: session.participants.clone(),
+            speaker_names: session.speaker_names.clone(),
+        }
+    }
+}
+
+/// Trimmed, non-empty and without duplicates, in the given order.
+fn clean(names: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for name in names.iter().map(|name| name.trim()) {
+        if !name.is_empty() && !cleaned.iter().any(|existing| existing == name) {
+            cleaned.push(name.to_string());
+        }
+    }
+    cleaned
+}
+
+fn changed(app: &AppHandle, session: &Session) -> Participants {
+    let participants = Participants::from(session);
+    let _ = app.emit("session:participants", &participants);
+    participants
+}
+
+/// Called when a calendar meeting arms capture. Attendees go on the session
+/// already being recorded, or on the next one to start.
+pub fn from_meeting(app: &AppHandle, attendees: &[String]) {
+    let names = clean(attendees);
+    if names.is_empty() {
+        return;
+    }
+
+    if recording::is_recording(app) {
+        let merged = recording::update_session(app, None, |session| {
+            let mut all = session.participants.clone();
+            all.extend(names);
+            session.participants = clean(&all);
+        });
+        match merged {
+            Ok(session) => {
+                changed(app, &session);
+            }
+            Err(e) => eprintln!("[Participants] Failed to add meeting attendees: {}", e),
+        }
+        return;
+    }
+
+    println!(
+        "[Participants] {} attendees for the next session",
+        names.len()
+    );
+    *app.state::<ParticipantsState>().0.lock().unwrap() = Some((now_ms(), names));
+}
+
+/// Attendees for a session that is starting now, if a meeting armed it recently.
+pub fn take_pending(app: &AppHandle) -> Vec<String> {
+    match app.state::<ParticipantsState>().0.lock().unwrap().take() {
+        Some((armed_at, names)) if now_ms().saturating_sub(armed_at) < PENDING_TTL_MS => names,
+        _ => Vec::new(),
+    }
+}
+
+/// Participants of session `id`, or of the current session.
+#[tauri::command]
+pub fn get_participants(app: AppHandle, id: Option<String>) -> Result<Participants, String> {
+    let session = match id {
+        Some(id) => recording::load_session(&app, &id)?,
+        None => recording::current_session(&app)?,
+    };
+    Ok(Participants::from(&session))
+}
+
+/// Replaces the participant list. Speaker names stay as assigned.
+#[tauri::command]
+pub fn set_participants(
+    app: AppHandle,
+    id: Option<String>,
+    names: Vec<String>,
+) -> Result<Participants, String> {
+    let names = clean(&names);
+    let session = recording::update_session(&app, id.as_deref(), |session| {
+        session.participants = names;
+    })?;
+    Ok(changed(&app, &session))
+}
+
+/// Maps diarized `speaker` to `name`, or clears the mapping. New names are added
+/// to the participant list.
+#[tauri::command]
+pub fn assign_speaker(
+    app: AppHandle,
+    id: Option<String>,
+    speaker: u32,
+    name: Option<String>,
+) -> Result<Participants, String> {
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    let session = recording::update_session(&app, id.as_deref(), |session| match name {
+        Some(name) => {
+            if !session.participants.contains(&name) {
+                session.participants.push(name.clone());
+            }
+            session.speaker_names.insert(speaker, name);
+        }
+        None => {
+            session.speaker_names.remove(&speaker);
+        }
+    })?;
+    Ok(changed(&app, &session))
+}
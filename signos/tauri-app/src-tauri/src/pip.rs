@@ -0,0 +1,216 @@
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::captions::Caption;
+use crate::panel::MAIN_WINDOW;
+
+/// Floating caption view on iOS, hosted in Picture-in-Picture so captions stay
+/// visible over other apps. `Info.ios.plist` declares the `audio` background
+/// mode; the capture session keeps the app alive while in the background.
+#[cfg(target_os = "ios")]
+mod platform {
+    use std::cell::RefCell;
+
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyClass;
+    use objc2::{msg_send, AllocAnyThread, MainThreadMarker};
+    use objc2_av_kit::{AVPictureInPictureController, AVPictureInPictureControllerContentSource};
+    use objc2_core_foundation::{CGPoint, CGRect, CGSize};
+    use objc2_foundation::NSString;
+    use objc2_ui_kit::{
+        NSTextAlignment, UIColor, UIFont, UILabel, UIView, UIViewAutoresizing, UIViewController,
+    };
+    use tauri::WebviewWindow;
+
+    const VIEW_SIZE: CGSize = CGSize {
+        width: 640.0,
+        height: 180.0,
+    };
+
+    struct CaptionView {
+        controller: Retained<AVPictureInPictureController>,
+        label: Retained<UILabel>,
+    }
+
+    thread_local! {
+        // UIKit objects, only touched from the main thread.
+        static VIEW: RefCell<Option<CaptionView>> = const { RefCell::new(None) };
+    }
+
+    pub const SUPPORTED: bool = true;
+
+    pub fn attach(window: &WebviewWindow) -> Result<(), String> {
+        window
+            .with_webview(|webview| {
+                if let Err(e) = build(webview.inner()) {
+                    eprintln!("[PiP] Failed to set up caption view: {}", e);
+                }
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Builds the PiP controller around the webview. Runs on the main thread.
+    fn build(webview: *mut std::ffi::c_void) -> Result<(), String> {
+        let mtm = MainThreadMarker::new().ok_or("PiP must be set up on the main thread")?;
+
+        if !unsafe { AVPictureInPictureController::isPictureInPictureSupported() } {
+            return Err("Picture-in-Picture is not supported on this device".into());
+        }
+
+        // The video call controller is the only PiP content that can host an arbitrary view.
+        let class = AnyClass::get(c"AVPictureInPictureVideoCallViewController")
+            .ok_or("Picture-in-Picture needs iOS 15 or later")?;
+        let content: Retained<UIViewController> = unsafe { msg_send![class, new] };
+        content.setPreferredContentSize(VIEW_SIZE);
+
+        let frame = CGRect::new(CGPoint::new(0.0, 0.0), VIEW_SIZE);
+        let label = UILabel::initWithFrame(mtm.alloc(), frame);
+        label.setNumberOfLines(0);
+        label.setTextAlignment(NSTextAlignment::Center);
+        label.setAdjustsFontSizeToFitWidth(true);
+        label.setAutoresizingMask(
+            UIViewAutoresizing::FlexibleWidth | UIViewAutoresizing::FlexibleHeight,
+        );
+        unsafe {
+            label.setFont(Some(&UIFont::boldSystemFontOfSize(28.0)));
+            label.setTextColor(Some(&UIColor::whiteColor()));
+        }
+        label.setBackgroundColor(Some(&UIColor::blackColor()));
+
+        let view = content.view().ok_or("PiP content has no view")?;
+        view.setBackgroundColor(Some(&UIColor::blackColor()));
+        view.addSubview(&label);
+
+        let source_view =
+            unsafe { (webview as *const UIView).as_ref() }.ok_or("Webview has no view")?;
+        let source: Retained<AVPictureInPictureControllerContentSource> = unsafe {
+            msg_send![
+                AVPictureInPictureControllerContentSource::alloc(),
+                initWithActiveVideoCallSourceView: source_view,
+                contentViewController: &*content
+            ]
+        };
+
+        let controller = unsafe {
+            AVPictureInPictureController::initWithContentSource(
+                AVPictureInPictureController::alloc(),
+                &source,
+            )
+        };
+        // Captions follow the user out of the app without an extra tap.
+        unsafe { controller.setCanStartPictureInPictureAutomaticallyFromInline(true) };
+
+        VIEW.with(|view| *view.borrow_mut() = Some(CaptionView { controller, label }));
+        Ok(())
+    }
+
+    pub fn set_text(text: &str) {
+        VIEW.with(|view| {
+            if let Some(view) = view.borrow().as_ref() {
+                view.label.setText(Some(&NSString::from_str(text)));
+            }
+        });
+    }
+
+    pub fn start() -> Result<(), String> {
+        VIEW.with(|view| {
+            let view = view.borrow();
+            let view = view.as_ref().ok_or("Picture-in-Picture is not available")?;
+            unsafe {
+                if !view.controller.isPictureInPicturePossible() {
+                    return Err("Picture-in-Picture is not possible right now".into());
+                }
+                view.controller.startPictureInPicture();
+            }
+            Ok(())
+        })
+    }
+
+    pub fn stop() -> Result<(), String> {
+        VIEW.with(|view| {
+            if let Some(view) = view.borrow().as_ref() {
+                unsafe { view.controller.stopPictureInPicture() };
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "ios"))]
+mod platform {
+    use tauri::WebviewWindow;
+
+    const UNSUPPORTED: &str = "Picture-in-Picture captions are only supported on iOS";
+
+    pub const SUPPORTED: bool = false;
+
+    pub fn attach(_window: &WebviewWindow) -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn set_text(_text: &str) {}
+
+    pub fn start() -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn stop() -> Result<(), String> {
+        Err(UNSUPPORTED.into())
+    }
+}
+
+/// Mirrors the latest caption into the PiP view.
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    if !platform::SUPPORTED {
+        return;
+    }
+
+    let text = caption.text.clone();
+    let _ = app.run_on_main_thread(move || platform::set_text(&text));
+}
+
+pub fn init(app: &AppHandle) {
+    if !platform::SUPPORTED {
+        return;
+    }
+
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+
+    if let Err(e) = platform::attach(&window) {
+        eprintln!("[PiP] Failed to access webview: {}", e);
+    }
+
+    // Leaving PiP up after capture stops would show a stale caption.
+    let handle = app.clone();
+    app.listen_any("capture:stop", move |_| {
+        let _ = handle.run_on_main_thread(|| {
+            platform::set_text("");
+            let _ = platform::stop();
+        });
+    });
+}
+
+fn on_main_thread(
+    app: &AppHandle,
+    f: impl FnOnce() -> Result<(), String> + Send + 'static,
+) -> Result<(), String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(f());
+    })
+    .map_err(|e| e.to_string())?;
+
+    rx.recv().map_err(|e| e.to_string())?
+}
+
+/// Pops the caption view out of the app, e.g. from a "float captions" button.
+#[tauri::command]
+pub async fn start_caption_pip(app: AppHandle) -> Result<(), String> {
+    on_main_thread(&app, platform::start)
+}
+
+#[tauri::command]
+pub async fn stop_caption_pip(app: AppHandle) -> Result<(), String> {
+    on_main_thread(&app, platform::stop)
+}
@@ -0,0 +1,271 @@
+//! Sandboxed WASM plugins that rewrite caption text before it is displayed.
+//!
+//! A plugin is a core WASM module with no imports that exports:
+//! - `memory`
+//! - `alloc(len: i32) -> i32`, returning a buffer the host writes the UTF-8 input to
+//! - `transform(ptr: i32, len: i32) -> i64`, returning the output as `(ptr << 32) | len`
+//!
+//! Plugins run in file-name order, each with a fuel, time and memory budget. A
+//! plugin that fails or runs out of budget is skipped and the text passes
+//! through unchanged.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State, Webview};
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::captions::Caption;
+use crate::{fs_access, metrics, security, startup, storage};
+
+const PLUGINS_DIR: &str = "plugins";
+/// Roughly a few million instructions per caption.
+const FUEL_PER_CALL: u64 = 10_000_000;
+/// The engine's epoch advances this often...
+const EPOCH_TICK: Duration = Duration::from_millis(50);
+/// ...and a call is interrupted after this many ticks, however cheap its
+/// instructions are by fuel.
+const DEADLINE_TICKS: u64 = 4;
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+struct Plugin {
+    name: String,
+    module: Module,
+}
+
+pub struct PluginHost {
+    engine: Engine,
+    plugins: Mutex<Vec<Plugin>>,
+}
+
+pub fn plugins_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(PLUGINS_DIR))
+        .map_err(|e| e.to_string())
+}
+
+/// Compiles a module and checks it exports the plugin ABI.
+fn compile(engine: &Engine, bytes: &[u8]) -> Result<Module, String> {
+    let module = Module::new(engine, bytes).map_err(|e| e.to_string())?;
+
+    if module.imports().len() > 0 {
+        return Err("Plugins may not import host functions".into());
+    }
+    for export in ["memory", "alloc", "transform"] {
+        if module.get_export(export).is_none() {
+            return Err(format!("Plugin does not export '{}'", export));
+        }
+    }
+
+    Ok(module)
+}
+
+fn load_all(engine: &Engine, dir: &Path) -> Vec<Plugin> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let bytes = fs::read(&path).ok()?;
+            match compile(engine, &bytes) {
+                Ok(module) => Some(Plugin { name, module }),
+                Err(e) => {
+                    eprintln!("[Plugins] Skipping '{}': {}", name, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn run(engine: &Engine, module: &Module, input: &str) -> Result<String, String> {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY_BYTES)
+        .build();
+    let mut store: Store<StoreLimits> = Store::new(engine, limits);
+    store.limiter(|limits| limits);
+    store.set_fuel(FUEL_PER_CALL).map_err(|e| e.to_string())?;
+    store.set_epoch_deadline(DEADLINE_TICKS);
+
+    let instance = Instance::new(&mut store, module, &[]).map_err(|e| e.to_string())?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or("Missing memory export")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, "alloc")
+        .map_err(|e| e.to_string())?;
+    let transform = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, "transform")
+        .map_err(|e| e.to_string())?;
+
+    let len = i32::try_from(input.len()).map_err(|e| e.to_string())?;
+    let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+    memory
+        .write(&mut store, ptr as usize, input.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let packed = transform
+        .call(&mut store, (ptr, len))
+        .map_err(|e| e.to_string())? as u64;
+    let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize);
+
+    let output = memory
+        .data(&store)
+        .get(out_ptr..out_ptr + out_len)
+        .ok_or("Output is out of bounds")?;
+    String::from_utf8(output.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Runs the caption text through every installed plugin.
+pub fn apply(app: &AppHandle, caption: &mut Caption) {
+    let Some(host) = app.try_state::<PluginHost>() else {
+        return;
+    };
+    let plugins = host.plugins.lock().unwrap();
+
+    for plugin in plugins.iter() {
+        match run(&host.engine, &plugin.module, &caption.text) {
+            Ok(text) => caption.text = text,
+            Err(e) => {
+                eprintln!("[Plugins] '{}' failed: {}", plugin.name, e);
+                metrics::record_error(app, "plugin");
+            }
+        }
+    }
+}
+
+/// Not managed in safe mode, or when the engine failed to start.
+fn host(app: &AppHandle) -> Result<State<'_, PluginHost>, String> {
+    app.try_state::<PluginHost>()
+        .ok_or_else(|| match startup::is_safe_mode(app) {
+            true => "Plugins are disabled in safe mode".into(),
+            false => "The plugin runtime failed to start".into(),
+        })
+}
+
+fn reload(app: &AppHandle) -> Result<(), String> {
+    let host = host(app)?;
+    let plugins = load_all(&host.engine, &plugins_dir(app)?);
+    *host.plugins.lock().unwrap() = plugins;
+    Ok(())
+}
+
+/// Creates the plugin host and loads the installed plugins.
+pub fn init(app: &AppHandle) {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    config.epoch_interruption(true);
+    // iOS doesn't allow JIT-compiled code, so plugins run on the Pulley
+    // interpreter there.
+    #[cfg(target_os = "ios")]
+    if let Err(e) = config.target("pulley64") {
+        eprintln!("[Plugins] Failed to select the interpreter: {}", e);
+        return;
+    }
+
+    let engine = match Engine::new(&config) {
+        Ok(engine) => engine,
+        Err(e) => {
+            eprintln!("[Plugins] Failed to create engine: {}", e);
+            return;
+        }
+    };
+
+    let ticker = engine.clone();
+    thread::spawn(move || loop {
+        thread::sleep(EPOCH_TICK);
+        ticker.increment_epoch();
+    });
+
+    app.manage(PluginHost {
+        engine,
+        plugins: Mutex::default(),
+    });
+
+    if let Err(e) = reload(app) {
+        eprintln!("[Plugins] Failed to load plugins: {}", e);
+    }
+}
+
+/// Copies a `.wasm` file into the plugins directory after validating it.
+#[tauri::command]
+pub fn install_plugin(
+    app: AppHandle,
+    webview: Webview,
+    path: String,
+) -> Result<PluginInfo, String> {
+    security::guard(&webview, "install_plugin", Some(&path))?;
+    let source = PathBuf::from(path);
+    fs_access::check(&app, &source)?;
+    let name = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .ok_or("Invalid plugin path")?;
+
+    let bytes = fs::read(&source).map_err(|e| e.to_string())?;
+    compile(&host(&app)?.engine, &bytes)?;
+
+    let dir = plugins_dir(&app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.wasm", name)), &bytes).map_err(|e| e.to_string())?;
+
+    reload(&app)?;
+    println!("[Plugins] Installed '{}'", name);
+
+    Ok(PluginInfo {
+        name,
+        size_bytes: bytes.len() as u64,
+    })
+}
+
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    let dir = plugins_dir(&app)?;
+    let host = host(&app)?;
+    let plugins = host.plugins.lock().unwrap();
+
+    Ok(plugins
+        .iter()
+        .map(|plugin| PluginInfo {
+            name: plugin.name.clone(),
+            size_bytes: fs::metadata(dir.join(format!("{}.wasm", plugin.name)))
+                .map(|m| m.len())
+                .unwrap_or(0),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn remove_plugin(app: AppHandle, webview: Webview, name: String) -> Result<(), String> {
+    security::guard(&webview, "remove_plugin", Some(&name))?;
+    // Plugin names are file stems, never paths.
+    if name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err("Invalid plugin name".into());
+    }
+    host(&app)?;
+
+    fs::remove_file(plugins_dir(&app)?.join(format!("{}.wasm", name)))
+        .map_err(|e| e.to_string())?;
+    reload(&app)
+}
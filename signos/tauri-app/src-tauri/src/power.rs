@@ -0,0 +1,189 @@
+//! Low-power mode: one switch that trades accuracy and smoothness for battery.
+//!
+//! While it is on, hand landmarks use the lite model, the camera sends fewer
+//! frames, audio goes to the worker in larger chunks and high-rate UI events
+//! are throttled. It can be turned on by hand or follow the system's own
+//! Low Power Mode (Battery Saver on Windows).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::camera::{self, CameraSettings};
+use crate::settings;
+use crate::transcription::{self, InferenceParams};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PowerSettings {
+    /// Set with `set_low_power_mode`.
+    pub low_power: bool,
+    /// Also use low-power mode while the system's is on.
+    pub follow_system: bool,
+    /// Camera frame rate cap.
+    pub camera_fps: u32,
+    /// Smallest audio chunk sent to the worker.
+    pub chunk_ms: u32,
+    /// Shortest gap between high-rate UI events such as `landmarks:frame`.
+    pub event_interval_ms: u64,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            low_power: false,
+            follow_system: true,
+            camera_fps: 5,
+            chunk_ms: 1000,
+            event_interval_ms: 250,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PowerState {
+    /// The system's low-power mode, as of the last poll.
+    system: AtomicBool,
+    /// Effective mode last announced with `power:changed`.
+    active: AtomicBool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub low_power: bool,
+    pub manual: bool,
+    pub system: bool,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+    use objc2_foundation::NSProcessInfo;
+
+    pub fn system_low_power() -> bool {
+        NSProcessInfo::processInfo().isLowPowerModeEnabled()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    /// Battery Saver sets `SystemStatusFlag`.
+    pub fn system_low_power() -> bool {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        unsafe { GetSystemPowerStatus(&mut status) }.is_ok() && status.SystemStatusFlag == 1
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "ios", target_os = "windows")))]
+mod platform {
+    pub fn system_low_power() -> bool {
+        false
+    }
+}
+
+pub fn status(app: &AppHandle) -> PowerStatus {
+    let config = settings::get(app).power;
+    let system = config.follow_system && app.state::<PowerState>().system.load(Ordering::SeqCst);
+    PowerStatus {
+        low_power: config.low_power || system,
+        manual: config.low_power,
+        system,
+    }
+}
+
+pub fn low_power(app: &AppHandle) -> bool {
+    status(app).low_power
+}
+
+/// Inference parameters with the low-power chunk size applied.
+pub fn inference(app: &AppHandle, params: InferenceParams) -> InferenceParams {
+    match low_power(app) {
+        true => InferenceParams {
+            chunk_ms: params.chunk_ms.max(settings::get(app).power.chunk_ms),
+            ..params
+        },
+        false => params,
+    }
+}
+
+/// Camera settings with the low-power frame rate applied.
+pub fn camera(app: &AppHandle, camera: CameraSettings) -> CameraSettings {
+    match low_power(app) {
+        true => CameraSettings {
+            fps: camera.fps.min(settings::get(app).power.camera_fps.max(1)),
+            ..camera
+        },
+        false => camera,
+    }
+}
+
+/// Whether a high-rate event last sent at `last` may be sent again; updates `last` if so.
+pub fn should_emit(app: &AppHandle, last: &mut Option<Instant>) -> bool {
+    if low_power(app) {
+        let interval = Duration::from_millis(settings::get(app).power.event_interval_ms);
+        if last.is_some_and(|last| last.elapsed() < interval) {
+            return false;
+        }
+    }
+    *last = Some(Instant::now());
+    true
+}
+
+/// Re-applies everything that depends on the mode, if it changed.
+fn refresh(app: &AppHandle) {
+    let status = status(app);
+    if app
+        .state::<PowerState>()
+        .active
+        .swap(status.low_power, Ordering::SeqCst)
+        == status.low_power
+    {
+        return;
+    }
+
+    println!(
+        "[Power] Low-power mode {}",
+        if status.low_power { "on" } else { "off" }
+    );
+    let _ = app.emit("power:changed", &status);
+    let _ = app.emit("inference:changed", transcription::inference(app));
+    // Reopened with the new frame rate.
+    if camera::is_active(app) {
+        if let Err(e) = camera::request_start(app) {
+            eprintln!("[Power] Failed to restart camera: {}", e);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || loop {
+        app.state::<PowerState>()
+            .system
+            .store(platform::system_low_power(), Ordering::SeqCst);
+        refresh(&app);
+        thread::sleep(POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_power_status(app: AppHandle) -> PowerStatus {
+    status(&app)
+}
+
+/// Turns manual low-power mode on or off. It stays on while the system's is,
+/// when `follow_system` is set.
+#[tauri::command]
+pub fn set_low_power_mode(app: AppHandle, enabled: bool) -> Result<PowerStatus, String> {
+    settings::update(&app, |s| s.power.low_power = enabled)?;
+    refresh(&app);
+    Ok(status(&app))
+}
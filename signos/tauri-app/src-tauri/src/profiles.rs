@@ -0,0 +1,132 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::glossary::GlossaryEntry;
+use crate::{settings, storage};
+
+const DEFAULT_PROFILE: &str = "default";
+const DEFAULT_WORKER_URL: &str = "ws://localhost:8787";
+
+/// A named set of overrides the user can switch between (e.g. "meetings", "lectures").
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+    /// Overrides the worker URL from the frontend config when set.
+    pub worker_url: Option<String>,
+    /// Overrides the selected audio input when set.
+    pub audio_device_id: Option<String>,
+    /// Replacements applied to every caption while this profile is active.
+    pub glossary: Vec<GlossaryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProfileSettings {
+    pub active: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.into(),
+            profiles: vec![Profile {
+                name: DEFAULT_PROFILE.into(),
+                ..Default::default()
+            }],
+        }
+    }
+}
+
+impl ProfileSettings {
+    pub fn find(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+}
+
+/// The worker URL currently in effect: the active profile's override, else the frontend config.
+pub fn worker_url(app: &AppHandle) -> String {
+    let settings = settings::get(app);
+    let profile_url = settings
+        .profiles
+        .find(&settings.profiles.active)
+        .and_then(|profile| profile.worker_url.clone())
+        .filter(|url| !url.is_empty());
+
+    profile_url
+        .or_else(|| {
+            // Written by the frontend; only `workerUrl` is read here.
+            let path = storage::data_dir(app)
+                .ok()?
+                .join(storage::FRONTEND_CONFIG_FILE);
+            let config: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+            config["workerUrl"].as_str().map(str::to_string)
+        })
+        .unwrap_or_else(|| DEFAULT_WORKER_URL.into())
+}
+
+/// HTTP endpoint on the current worker, e.g. `ws://host/path` becomes `http://host/path/transcribe`.
+pub fn worker_endpoint(app: &AppHandle, endpoint: &str) -> String {
+    let url = worker_url(app)
+        .replacen("wss://", "https://", 1)
+        .replacen("ws://", "http://", 1);
+    format!("{}/{}", url.trim_end_matches('/'), endpoint)
+}
+
+/// Makes `name` the active profile and tells the frontend to apply its overrides.
+pub fn switch(app: &AppHandle, name: &str) -> Result<Profile, String> {
+    let profile = settings::get(app)
+        .profiles
+        .find(name)
+        .cloned()
+        .ok_or_else(|| format!("Profile '{}' does not exist", name))?;
+
+    settings::update(app, |s| s.profiles.active = profile.name.clone())?;
+    let _ = app.emit("profile:changed", &profile);
+
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> ProfileSettings {
+    settings::get(&app).profiles
+}
+
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, name: String) -> Result<Profile, String> {
+    switch(&app, &name)
+}
+
+/// Creates or replaces a profile by name.
+#[tauri::command]
+pub fn save_profile(app: AppHandle, profile: Profile) -> Result<ProfileSettings, String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name cannot be empty".into());
+    }
+
+    settings::update(&app, |s| {
+        match s
+            .profiles
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == profile.name)
+        {
+            Some(existing) => *existing = profile,
+            None => s.profiles.profiles.push(profile),
+        }
+    })
+    .map(|s| s.profiles)
+}
+
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, name: String) -> Result<ProfileSettings, String> {
+    if settings::get(&app).profiles.active == name {
+        return Err("Cannot delete the active profile".into());
+    }
+
+    settings::update(&app, |s| s.profiles.profiles.retain(|p| p.name != name)).map(|s| s.profiles)
+}
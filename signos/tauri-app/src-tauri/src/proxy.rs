@@ -0,0 +1,358 @@
+//! Proxy configuration shared by every outbound HTTP client.
+//!
+//! Clients get their agent from [`agent_config`], which applies the resolved
+//! proxy. The resolution (system settings or a PAC file) is cached and
+//! refreshed when the settings change. The webview follows the system proxy
+//! on its own; a manual proxy is applied to it when the window is created.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Url};
+use ureq::config::ConfigBuilder;
+use ureq::typestate::AgentScope;
+use ureq::Proxy;
+
+use crate::settings;
+
+const PAC_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyMode {
+    /// Never use a proxy.
+    Direct,
+    /// `HTTPS_PROXY`/`ALL_PROXY`, then the OS proxy settings.
+    #[default]
+    System,
+    /// `url` below.
+    Manual,
+    /// The PAC file at `pac_url`.
+    Pac,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    /// `http://`, `https://`, `socks4://` or `socks5://`, optionally with `user:password@`.
+    pub url: Option<String>,
+    pub pac_url: Option<String>,
+    /// Hosts that are always reached directly, e.g. a local worker.
+    pub bypass: Vec<String>,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            mode: ProxyMode::System,
+            url: None,
+            pac_url: None,
+            bypass: vec!["localhost".into(), "127.0.0.1".into()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyStatus {
+    pub settings: ProxySettings,
+    /// Proxy currently in use; `None` connects directly.
+    pub resolved: Option<String>,
+}
+
+/// Resolved proxy URL. The outer `None` means not resolved yet.
+#[derive(Default)]
+pub struct ProxyState(Mutex<Option<Option<String>>>);
+
+// Only read from the OS on macOS and Windows.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+enum SystemProxy {
+    Url(String),
+    Pac(String),
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::collections::HashMap;
+    use std::process::Command;
+
+    use super::SystemProxy;
+
+    /// Reads the network settings through `scutil --proxy`.
+    pub fn system_proxy() -> Option<SystemProxy> {
+        let output = Command::new("scutil").arg("--proxy").output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let values: HashMap<&str, &str> = text
+            .lines()
+            .filter_map(|line| line.trim().split_once(" : "))
+            .collect();
+        let enabled = |key: &str| values.get(key) == Some(&"1");
+
+        if enabled("ProxyAutoConfigEnable") {
+            if let Some(url) = values.get("ProxyAutoConfigURLString") {
+                return Some(SystemProxy::Pac(url.to_string()));
+            }
+        }
+        for (prefix, scheme) in [("HTTPS", "http"), ("HTTP", "http"), ("SOCKS", "socks5")] {
+            if enabled(&format!("{}Enable", prefix)) {
+                let host = values.get(format!("{}Proxy", prefix).as_str())?;
+                let port = values.get(format!("{}Port", prefix).as_str())?;
+                return Some(SystemProxy::Url(format!("{}://{}:{}", scheme, host, port)));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ffi::c_void;
+
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::System::Registry::{
+        RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RRF_RT_REG_SZ,
+    };
+
+    use super::SystemProxy;
+
+    const INTERNET_SETTINGS: PCWSTR =
+        w!(r"Software\Microsoft\Windows\CurrentVersion\Internet Settings");
+
+    fn read_dword(name: PCWSTR) -> Option<u32> {
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                INTERNET_SETTINGS,
+                name,
+                RRF_RT_REG_DWORD,
+                None,
+                Some(&mut value as *mut u32 as *mut c_void),
+                Some(&mut size),
+            )
+        }
+        .ok()
+        .ok()?;
+        Some(value)
+    }
+
+    fn read_string(name: PCWSTR) -> Option<String> {
+        let mut buffer = [0u16; 2048];
+        let mut size = std::mem::size_of_val(&buffer) as u32;
+        unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                INTERNET_SETTINGS,
+                name,
+                RRF_RT_REG_SZ,
+                None,
+                Some(buffer.as_mut_ptr() as *mut c_void),
+                Some(&mut size),
+            )
+        }
+        .ok()
+        .ok()?;
+        let len = (size as usize / 2).saturating_sub(1);
+        Some(String::from_utf16_lossy(&buffer[..len])).filter(|value| !value.is_empty())
+    }
+
+    /// Reads the WinINet settings shown under Settings > Network > Proxy.
+    pub fn system_proxy() -> Option<SystemProxy> {
+        if let Some(url) = read_string(w!("AutoConfigURL")) {
+            return Some(SystemProxy::Pac(url));
+        }
+        if read_dword(w!("ProxyEnable")) != Some(1) {
+            return None;
+        }
+
+        // Either `host:port` or per protocol, e.g. `http=host:port;https=host:port`.
+        let server = read_string(w!("ProxyServer"))?;
+        if !server.contains('=') {
+            return Some(SystemProxy::Url(format!("http://{}", server)));
+        }
+        let entries: Vec<(&str, &str)> = server
+            .split(';')
+            .filter_map(|entry| entry.split_once('='))
+            .collect();
+        [("https", "http"), ("http", "http"), ("socks", "socks4")]
+            .into_iter()
+            .find_map(|(protocol, scheme)| {
+                entries
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(protocol))
+                    .map(|(_, address)| SystemProxy::Url(format!("{}://{}", scheme, address)))
+            })
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::SystemProxy;
+
+    /// Desktop-specific settings are not read here; the environment covers Linux.
+    pub fn system_proxy() -> Option<SystemProxy> {
+        None
+    }
+}
+
+fn env_proxy() -> Option<String> {
+    [
+        "ALL_PROXY",
+        "all_proxy",
+        "HTTPS_PROXY",
+        "https_proxy",
+        "HTTP_PROXY",
+        "http_proxy",
+    ]
+    .into_iter()
+    .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+}
+
+/// Picks the first proxy a PAC file returns. The script is not evaluated, so
+/// host-dependent rules all resolve to that proxy.
+fn from_pac(url: &str) -> Result<Option<String>, String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(PAC_TIMEOUT))
+        .proxy(None)
+        .build()
+        .into();
+    let script = agent
+        .get(url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| e.to_string())?;
+
+    let directive = Regex::new(r"\b(PROXY|HTTPS|SOCKS5?)\s+([A-Za-z0-9.\-\[\]:]+:\d+)")
+        .map_err(|e| e.to_string())?;
+    Ok(directive.captures(&script).map(|captures| {
+        let scheme = match &captures[1] {
+            "HTTPS" => "https",
+            "PROXY" => "http",
+            _ => "socks5",
+        };
+        format!("{}://{}", scheme, &captures[2])
+    }))
+}
+
+fn resolve(config: &ProxySettings) -> Result<Option<String>, String> {
+    match config.mode {
+        ProxyMode::Direct => Ok(None),
+        ProxyMode::Manual => Ok(config.url.clone()),
+        ProxyMode::Pac => from_pac(config.pac_url.as_deref().ok_or("No PAC URL configured")?),
+        ProxyMode::System => match env_proxy() {
+            Some(url) => Ok(Some(url)),
+            None => match platform::system_proxy() {
+                Some(SystemProxy::Url(url)) => Ok(Some(url)),
+                Some(SystemProxy::Pac(url)) => from_pac(&url),
+                None => Ok(None),
+            },
+        },
+    }
+}
+
+/// Builds the ureq proxy for `url`, with the bypass list applied.
+fn build_proxy(url: &str, bypass: &[String]) -> Result<Proxy, String> {
+    let parsed = Proxy::new(url).map_err(|e| format!("Invalid proxy '{}': {}", url, e))?;
+    let mut builder = Proxy::builder(parsed.protocol())
+        .host(parsed.host())
+        .port(parsed.port());
+    if let Some(username) = parsed.username() {
+        builder = builder.username(username);
+    }
+    if let Some(password) = parsed.password() {
+        builder = builder.password(password);
+    }
+    for host in bypass {
+        builder = builder.no_proxy(host);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn refresh(app: &AppHandle) {
+    let config = settings::get(app).proxy;
+    let resolved = resolve(&config).unwrap_or_else(|e| {
+        eprintln!("[Proxy] Failed to resolve proxy: {}", e);
+        None
+    });
+
+    match &resolved {
+        Some(url) => println!("[Proxy] Using {}", url),
+        None => println!("[Proxy] Connecting directly"),
+    }
+    *app.state::<ProxyState>().0.lock().unwrap() = Some(resolved);
+}
+
+fn resolved(app: &AppHandle) -> Option<String> {
+    app.state::<ProxyState>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(env_proxy)
+}
+
+/// Agent configuration with the current proxy applied; every outbound client starts here.
+pub fn agent_config(app: &AppHandle) -> ConfigBuilder<AgentScope> {
+    let config = settings::get(app).proxy;
+    let proxy = resolved(app).and_then(|url| match build_proxy(&url, &config.bypass) {
+        Ok(proxy) => Some(proxy),
+        Err(e) => {
+            eprintln!("[Proxy] {}", e);
+            None
+        }
+    });
+    ureq::Agent::config_builder().proxy(proxy)
+}
+
+/// Manual proxy for the webview, which only takes `http://` and `socks5://`.
+/// Changes apply the next time the window is created.
+pub fn webview_proxy(app: &AppHandle) -> Option<Url> {
+    let config = settings::get(app).proxy;
+    if config.mode != ProxyMode::Manual {
+        return None;
+    }
+    Url::parse(config.url.as_deref()?)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "socks5"))
+}
+
+/// Resolves the proxy in the background; a PAC file may take a while to fetch.
+pub fn init(app: &AppHandle) {
+    app.manage(ProxyState::default());
+
+    let app = app.clone();
+    thread::spawn(move || refresh(&app));
+}
+
+#[tauri::command]
+pub fn get_proxy(app: AppHandle) -> ProxyStatus {
+    ProxyStatus {
+        settings: settings::get(&app).proxy,
+        resolved: resolved(&app),
+    }
+}
+
+#[tauri::command]
+pub async fn set_proxy(app: AppHandle, proxy: ProxySettings) -> Result<ProxyStatus, String> {
+    if proxy.mode == ProxyMode::Manual {
+        build_proxy(proxy.url.as_deref().ok_or("No proxy URL configured")?, &[])?;
+    }
+    if proxy.mode == ProxyMode::Pac && proxy.pac_url.is_none() {
+        return Err("No PAC URL configured".into());
+    }
+
+    settings::update(&app, |s| s.proxy = proxy)?;
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || refresh(&handle))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = get_proxy(app.clone());
+    let _ = app.emit("proxy:changed", &status);
+    Ok(status)
+}
@@ -0,0 +1,179 @@
+//! Punctuation and casing for engines that emit lowercase, unpunctuated text.
+//!
+//! This is a handful of rules rather than a model: the first word and `I` are
+//! capitalized, and a final segment ends in `.` or, when it opens like a
+//! question, `?` (`¿…?` in Spanish). It is off until enabled for a language.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::captions::Caption;
+use crate::settings;
+
+/// Text that already has punctuation or capitals is left alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PunctuationSettings {
+    /// Languages (as reported by the worker, e.g. `es`) the stage runs for.
+    pub languages: Vec<String>,
+}
+
+/// Spanish question openers, used to restore `¿…?`.
+const SPANISH_QUESTION_WORDS: [&str; 10] = [
+    "qué", "quién", "quiénes", "cómo", "cuándo", "dónde", "cuál", "cuáles", "cuánto", "por qué",
+];
+const ENGLISH_QUESTION_WORDS: [&str; 8] = [
+    "what", "who", "whom", "how", "when", "where", "which", "why",
+];
+/// Open a question only when a subject follows: "is it" but not "is what".
+const ENGLISH_AUXILIARIES: [&str; 4] = ["is", "are", "do", "does"];
+const ENGLISH_SUBJECTS: [&str; 10] = [
+    "i", "you", "he", "she", "it", "we", "they", "this", "that", "there",
+];
+
+fn needs_restoring(text: &str) -> bool {
+    !text
+        .chars()
+        .any(|c| c.is_uppercase() || matches!(c, '.' | ',' | '?' | '!' | '¿' | '¡'))
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Base language of a tag such as `en-US`.
+fn base_language(language: &str) -> &str {
+    language.split(['-', '_']).next().unwrap_or(language)
+}
+
+fn restore(text: &str, language: &str, is_final: bool) -> String {
+    let language = base_language(language);
+    let trimmed = text.trim();
+
+    let mut restored = trimmed
+        .split_whitespace()
+        .map(|word| match (language, word) {
+            ("en", "i") => "I".to_string(),
+            ("en", word) if word.starts_with("i'") => capitalize_first(word),
+            _ => word.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    restored = capitalize_first(&restored);
+
+    // Only a finished segment is known to end here.
+    if is_final && !restored.is_empty() {
+        let lower = trimmed.to_lowercase();
+        let question = match language {
+            "es" => SPANISH_QUESTION_WORDS
+                .iter()
+                .any(|word| lower.starts_with(&format!("{} ", word))),
+            "en" => {
+                let mut words = lower.split_whitespace();
+                match (words.next(), words.next()) {
+                    (Some(first), Some(second)) if ENGLISH_AUXILIARIES.contains(&first) => {
+                        ENGLISH_SUBJECTS.contains(&second)
+                    }
+                    (Some(first), Some(_)) => ENGLISH_QUESTION_WORDS.contains(&first),
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+
+        restored = match (question, language) {
+            (true, "es") => format!("¿{}?", restored),
+            (true, _) => format!("{}?", restored),
+            (false, _) => format!("{}.", restored),
+        };
+    }
+
+    restored
+}
+
+/// Restores punctuation and casing when the caption's language is enabled.
+pub fn apply(app: &AppHandle, caption: &mut Caption) {
+    if !needs_restoring(&caption.text) {
+        return;
+    }
+
+    let settings = settings::get(app);
+    let language = caption
+        .language
+        .clone()
+        .unwrap_or(settings.transcription.language);
+    let enabled = settings
+        .punctuation
+        .languages
+        .iter()
+        .any(|enabled| enabled.eq_ignore_ascii_case(base_language(&language)));
+
+    if enabled {
+        caption.text = restore(&caption.text, &language, caption.is_final);
+    }
+}
+
+/// Turns the stage on or off for one language.
+#[tauri::command]
+pub fn set_punctuation_language(
+    app: AppHandle,
+    language: String,
+    enabled: bool,
+) -> Result<Vec<String>, String> {
+    let language = base_language(language.trim()).to_lowercase();
+    if language.is_empty() {
+        return Err("Language must not be empty".into());
+    }
+
+    let settings = settings::update(&app, |s| {
+        let languages = &mut s.punctuation.languages;
+        languages.retain(|l| !l.eq_ignore_ascii_case(&language));
+        if enabled {
+            languages.push(language.clone());
+        }
+    })?;
+    Ok(settings.punctuation.languages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capitalizes_and_ends_final_segments() {
+        assert_eq!(restore("hello there", "en", true), "Hello there.");
+        assert_eq!(restore("  hello there ", "en-US", true), "Hello there.");
+        assert_eq!(restore("hello there", "en", false), "Hello there");
+        assert_eq!(restore("", "en", true), "");
+    }
+
+    #[test]
+    fn capitalizes_english_i() {
+        assert_eq!(restore("i think i'm late", "en", true), "I think I'm late.");
+        assert_eq!(restore("sí i", "es", false), "Sí i");
+    }
+
+    #[test]
+    fn recognizes_questions() {
+        assert_eq!(restore("what time is it", "en", true), "What time is it?");
+        assert_eq!(restore("are you coming", "en", true), "Are you coming?");
+        assert_eq!(restore("is what he said", "en", true), "Is what he said.");
+        assert_eq!(restore("do the dishes", "en", true), "Do the dishes.");
+        assert_eq!(restore("what", "en", true), "What.");
+        assert_eq!(restore("qué hora es", "es", true), "¿Qué hora es?");
+        assert_eq!(restore("por qué no", "es", true), "¿Por qué no?");
+        assert_eq!(restore("what time is it", "fr", true), "What time is it.");
+    }
+
+    #[test]
+    fn leaves_punctuated_text_alone() {
+        assert!(needs_restoring("hello there"));
+        assert!(!needs_restoring("Hello there"));
+        assert!(!needs_restoring("hello, there"));
+        assert!(!needs_restoring("¿qué"));
+    }
+}
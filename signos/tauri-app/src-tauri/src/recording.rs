@@ -0,0 +1,941 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use chrono::Local;
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::{InvokeBody, Request};
+use tauri::{AppHandle, Emitter, Listener, Manager, Webview};
+
+use crate::captions::Caption;
+use crate::capture::{self, CaptureStatus};
+use crate::connectivity::SttProvider;
+use crate::journal::{self, OperationKind};
+use crate::summary::Summary;
+use crate::util::now_ms;
+use crate::{lifecycle, participants, relay, security, settings, storage};
+
+const RECORDINGS_DIR: &str = "recordings";
+const AUDIO_FILE: &str = "audio.wav";
+const SESSION_FILE: &str = "session.json";
+/// Written first and renamed over `session.json`, so a kill mid-write leaves
+/// the previous version in place rather than torn JSON.
+const SESSION_TEMP_FILE: &str = "session.json.tmp";
+/// Segments appended one JSON line at a time before `session.json` is rewritten,
+/// so a kill mid-write can't lose the transcript. Deleted when the session stops.
+const TRANSCRIPT_WAL: &str = "transcript.wal";
+/// Matches the PCM produced by `audio-processor.js`.
+const SAMPLE_RATE: u32 = 16_000;
+const WAV_SPEC: WavSpec = WavSpec {
+    channels: 1,
+    sample_rate: SAMPLE_RATE,
+    bits_per_sample: 16,
+    sample_format: SampleFormat::Int,
+};
+/// New captions are only in the log until `session.json` is next rewritten,
+/// at most this often while recording.
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RecordingSettings {
+    /// Recordings older than this are deleted. 0 keeps them forever.
+    pub retention_days: u64,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self { retention_days: 30 }
+    }
+}
+
+/// `session.json`, stored next to `audio.wav` in `recordings/<id>/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    /// Unix timestamps (ms).
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    pub language: String,
+    pub profile: String,
+    pub segments: Vec<Segment>,
+    /// Set once the segments were replaced by an offline re-transcription.
+    #[serde(default)]
+    pub retranscribed_at: Option<u64>,
+    /// Latest `summarize_session` result.
+    #[serde(default)]
+    pub summary: Option<Summary>,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// Where transcription failed over between the worker and the device.
+    #[serde(default)]
+    pub provider_switches: Vec<ProviderSwitch>,
+    /// Set when the app was killed mid-session and the session was finalized on the next launch.
+    #[serde(default)]
+    pub recovered_at: Option<u64>,
+    /// Names of the people in the meeting, from calendar attendees or `set_participants`.
+    #[serde(default)]
+    pub participants: Vec<String>,
+    /// Diarized speaker ids mapped to participant names with `assign_speaker`.
+    #[serde(default)]
+    pub speaker_names: BTreeMap<u32, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    pub text: String,
+    /// Position in `audio.wav` the segment was spoken at.
+    pub offset_ms: u64,
+    /// Worker timestamp of the caption (ms).
+    pub timestamp: u64,
+    #[serde(default)]
+    pub speaker: Option<u32>,
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Capture source label, for sessions with several inputs.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Text as first recognized, once the segment was corrected.
+    #[serde(default)]
+    pub original_text: Option<String>,
+    /// Unix timestamp (ms) of the latest correction.
+    #[serde(default)]
+    pub corrected_at: Option<u64>,
+}
+
+/// A moment flagged with `mark_moment`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    pub label: Option<String>,
+    /// Position in `audio.wav`.
+    pub offset_ms: u64,
+    /// Unix timestamp (ms).
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSwitch {
+    pub provider: SttProvider,
+    pub reason: String,
+    /// Position in `audio.wav`.
+    pub offset_ms: u64,
+    /// Unix timestamp (ms).
+    pub created_at: u64,
+}
+
+struct ActiveRecording {
+    dir: PathBuf,
+    writer: WavWriter<BufWriter<File>>,
+    samples: u64,
+    session: Session,
+    saved_at: Instant,
+}
+
+#[derive(Default)]
+pub struct RecorderState(Mutex<Option<ActiveRecording>>);
+
+/// Session left open by a killed run, offered to the frontend for resuming.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedSession {
+    pub id: String,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    pub segments: usize,
+    pub duration_ms: u64,
+}
+
+#[derive(Default)]
+pub struct OrphanState(Mutex<Option<OrphanedSession>>);
+
+pub fn recordings_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(RECORDINGS_DIR))
+        .map_err(|e| e.to_string())
+}
+
+fn write_session(dir: &Path, session: &Session) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    let temp = dir.join(SESSION_TEMP_FILE);
+    fs::write(&temp, json)
+        .and_then(|_| fs::rename(&temp, dir.join(SESSION_FILE)))
+        .map_err(|e| e.to_string())
+}
+
+/// Directory of a stored session. Ids are generated here, never paths.
+pub fn session_dir(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\', '.']) {
+        return Err(format!("Invalid session id '{}'", id));
+    }
+    Ok(recordings_dir(app)?.join(id))
+}
+
+pub fn audio_path(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    Ok(session_dir(app, id)?.join(AUDIO_FILE))
+}
+
+pub fn session_path(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    Ok(session_dir(app, id)?.join(SESSION_FILE))
+}
+
+pub fn load_session(app: &AppHandle, id: &str) -> Result<Session, String> {
+    read_session(&session_dir(app, id)?).map_err(|e| format!("Session '{}' not found: {}", id, e))
+}
+
+pub fn read_session(dir: &Path) -> Result<Session, String> {
+    let json = fs::read_to_string(dir.join(SESSION_FILE)).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+pub fn save_session(app: &AppHandle, session: &Session) -> Result<(), String> {
+    write_session(&session_dir(app, &session.id)?, session)
+}
+
+/// Writes a session received from another device, whose directory doesn't
+/// exist here yet.
+pub fn store_session(dir: &Path, session: &Session) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    write_session(dir, session)
+}
+
+/// Deletes recordings older than the retention period.
+fn apply_retention(app: &AppHandle) -> Result<(), String> {
+    let days = settings::get(app).recording.retention_days;
+    if days == 0 {
+        return Ok(());
+    }
+
+    let max_age = Duration::from_secs(days * 24 * 60 * 60);
+    let Ok(entries) = fs::read_dir(recordings_dir(app)?) else {
+        return Ok(());
+    };
+
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > max_age);
+
+        if expired && entry.path().is_dir() {
+            println!(
+                "[Recording] Deleting expired recording {:?}",
+                entry.file_name()
+            );
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                eprintln!(
+                    "[Recording] Failed to delete {:?}: {}",
+                    entry.file_name(),
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the directory of a new session. Two sessions started within the
+/// same second get a counter, rather than sharing one directory.
+fn create_session_dir(app: &AppHandle) -> Result<(String, PathBuf), String> {
+    let root = recordings_dir(app)?;
+    fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    for n in 1..100 {
+        let id = match n {
+            1 => stamp.clone(),
+            n => format!("{}-{}", stamp, n),
+        };
+        let dir = root.join(&id);
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok((id, dir)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Err("Too many sessions started at once".into())
+}
+
+/// Session ids sort chronologically, so the newest is the largest.
+pub fn latest_session_id(app: &AppHandle) -> Option<String> {
+    fs::read_dir(recordings_dir(app).ok()?)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().join(SESSION_FILE).is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .max()
+}
+
+/// The session being recorded, or else the most recent one on disk.
+pub fn current_session(app: &AppHandle) -> Result<Session, String> {
+    if let Some(recording) = app.state::<RecorderState>().0.lock().unwrap().as_ref() {
+        return Ok(recording.session.clone());
+    }
+
+    let id = latest_session_id(app).ok_or("No recorded sessions")?;
+    load_session(app, &id)
+}
+
+pub fn is_recording(app: &AppHandle) -> bool {
+    app.state::<RecorderState>().0.lock().unwrap().is_some()
+}
+
+pub fn start(app: &AppHandle) -> Result<String, String> {
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    if let Some(recording) = active.as_ref() {
+        return Ok(recording.session.id.clone());
+    }
+
+    let settings = settings::get(app);
+    let (id, dir) = create_session_dir(app)?;
+
+    let writer = WavWriter::create(dir.join(AUDIO_FILE), WAV_SPEC).map_err(|e| e.to_string())?;
+
+    let session = Session {
+        id: id.clone(),
+        started_at: now_ms(),
+        ended_at: None,
+        language: settings.transcription.language,
+        profile: settings.profiles.active,
+        segments: Vec::new(),
+        retranscribed_at: None,
+        summary: None,
+        bookmarks: Vec::new(),
+        provider_switches: Vec::new(),
+        recovered_at: None,
+        participants: participants::take_pending(app),
+        speaker_names: BTreeMap::new(),
+    };
+    write_session(&dir, &session)?;
+
+    *active = Some(ActiveRecording {
+        dir,
+        writer,
+        samples: 0,
+        session,
+        saved_at: Instant::now(),
+    });
+    drop(active);
+
+    println!("[Recording] Started session {}", id);
+    let _ = app.emit("recording:state", true);
+    Ok(id)
+}
+
+pub fn stop(app: &AppHandle) -> Result<Option<Session>, String> {
+    let Some(mut recording) = app.state::<RecorderState>().0.lock().unwrap().take() else {
+        return Ok(None);
+    };
+
+    recording.session.ended_at = Some(now_ms());
+    recording.writer.finalize().map_err(|e| e.to_string())?;
+    write_session(&recording.dir, &recording.session)?;
+    let _ = fs::remove_file(recording.dir.join(TRANSCRIPT_WAL));
+
+    println!("[Recording] Stopped session {}", recording.session.id);
+    let _ = app.emit("recording:state", false);
+
+    if let Err(e) = apply_retention(app) {
+        eprintln!("[Recording] Failed to apply retention: {}", e);
+    }
+    Ok(Some(recording.session))
+}
+
+/// Drops the active recording without keeping its files.
+pub fn discard(app: &AppHandle) -> Result<(), String> {
+    let Some(recording) = app.state::<RecorderState>().0.lock().unwrap().take() else {
+        return Ok(());
+    };
+    drop(recording.writer);
+    fs::remove_dir_all(&recording.dir).map_err(|e| e.to_string())?;
+
+    println!("[Recording] Discarded session {}", recording.session.id);
+    let _ = app.emit("recording:state", false);
+    Ok(())
+}
+
+fn append_wal(dir: &Path, segment: &Segment) -> Result<(), String> {
+    let line = serde_json::to_string(segment).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(TRANSCRIPT_WAL))
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+}
+
+/// Segments in the write-ahead log. A torn last line is skipped.
+fn read_wal(dir: &Path) -> Vec<Segment> {
+    fs::read_to_string(dir.join(TRANSCRIPT_WAL))
+        .map(|log| {
+            log.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Rewrites the RIFF and data chunk sizes of a WAV whose writer never
+/// finalized it, so the audio written before the kill is readable.
+fn repair_wav(path: &Path) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+
+    // Walk the chunks after the 12-byte RIFF header to find `data`.
+    let mut offset = 12u64;
+    let data_offset = loop {
+        let mut header = [0u8; 8];
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut header))
+            .map_err(|_| "No data chunk found".to_string())?;
+        if &header[..4] == b"data" {
+            break offset;
+        }
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+        offset += 8 + size + size % 2;
+    };
+
+    // Whole 16-bit samples only.
+    let data_len = len.saturating_sub(data_offset + 8) & !1;
+    file.set_len(data_offset + 8 + data_len)
+        .map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(4))
+        .and_then(|_| file.write_all(&((data_offset + data_len) as u32).to_le_bytes()))
+        .and_then(|_| file.seek(SeekFrom::Start(data_offset + 4)))
+        .and_then(|_| file.write_all(&(data_len as u32).to_le_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as u64)
+}
+
+/// Stands in for a `session.json` that doesn't parse, written by a version
+/// that didn't replace it atomically. The id gives the start time; the
+/// segments come from the write-ahead log in `recover`.
+fn rebuild_session(app: &AppHandle, id: &str) -> Session {
+    let settings = settings::get(app);
+    let started_at = id
+        .get(..15)
+        .and_then(|stamp| chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S").ok())
+        .and_then(|time| time.and_local_timezone(Local).single())
+        .map(|time| time.timestamp_millis().max(0) as u64)
+        .unwrap_or_else(now_ms);
+
+    Session {
+        id: id.to_string(),
+        started_at,
+        ended_at: None,
+        language: settings.transcription.language,
+        profile: settings.profiles.active,
+        segments: Vec::new(),
+        retranscribed_at: None,
+        summary: None,
+        bookmarks: Vec::new(),
+        provider_switches: Vec::new(),
+        recovered_at: None,
+        participants: Vec::new(),
+        speaker_names: BTreeMap::new(),
+    }
+}
+
+/// Finalizes a session the previous run never stopped: repairs the audio,
+/// restores the transcript from the write-ahead log and closes it.
+fn recover(app: &AppHandle, id: &str) -> Result<OrphanedSession, String> {
+    let dir = session_dir(app, id)?;
+    let audio = dir.join(AUDIO_FILE);
+    let mut session = load_session(app, id).unwrap_or_else(|e| {
+        eprintln!("[Recording] Rebuilding session {} from its log: {}", id, e);
+        rebuild_session(app, id)
+    });
+
+    if audio.is_file() {
+        if let Err(e) = repair_wav(&audio) {
+            eprintln!("[Recording] Failed to repair audio of {}: {}", id, e);
+        }
+    }
+    // The log only holds segments since the last (re)start, and can be ahead of `session.json`.
+    let last = session.segments.last().map(|segment| segment.timestamp);
+    session.segments.extend(
+        read_wal(&dir)
+            .into_iter()
+            .filter(|segment| last.is_none_or(|last| segment.timestamp > last)),
+    );
+    session.ended_at = modified_ms(&audio).or(Some(now_ms()));
+    session.recovered_at = Some(now_ms());
+    write_session(&dir, &session)?;
+    let _ = fs::remove_file(dir.join(TRANSCRIPT_WAL));
+
+    let duration_ms = WavReader::open(&audio)
+        .map(|reader| reader.duration() as u64 * 1000 / SAMPLE_RATE as u64)
+        .unwrap_or(0);
+    println!(
+        "[Recording] Recovered session {} ({} segments)",
+        id,
+        session.segments.len()
+    );
+    Ok(OrphanedSession {
+        id: session.id,
+        started_at: session.started_at,
+        ended_at: session.ended_at,
+        segments: session.segments.len(),
+        duration_ms,
+    })
+}
+
+/// Recovers every session left open by a killed run. The newest one is kept
+/// to offer for resuming once the frontend is up.
+fn recover_orphans(app: &AppHandle) -> Result<Option<OrphanedSession>, String> {
+    let Ok(entries) = fs::read_dir(recordings_dir(app)?) else {
+        return Ok(None);
+    };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join(SESSION_FILE).is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|id| match load_session(app, id) {
+            Ok(session) => session.ended_at.is_none(),
+            // Torn by a kill mid-write; the log still has the transcript.
+            Err(_) => session_dir(app, id).is_ok_and(|dir| dir.join(TRANSCRIPT_WAL).is_file()),
+        })
+        .collect();
+    ids.sort();
+
+    let mut latest = None;
+    for id in ids {
+        match recover(app, &id) {
+            Ok(orphan) => latest = Some(orphan),
+            Err(e) => eprintln!("[Recording] Failed to recover session {}: {}", id, e),
+        }
+    }
+    Ok(latest)
+}
+
+/// Emits `recording:orphaned` for a recovered session waiting to be resumed or dismissed.
+pub fn offer_resume(app: &AppHandle) {
+    let orphan = app.state::<OrphanState>().0.lock().unwrap().clone();
+    if let Some(orphan) = orphan {
+        let _ = app.emit("recording:orphaned", &orphan);
+    }
+}
+
+/// Reopens a recovered or handed-off session and records into it again. A
+/// handed-off session has no audio here, so `audio.wav` starts at the handoff.
+fn resume(app: &AppHandle, id: &str) -> Result<Session, String> {
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    if active.is_some() {
+        return Err("Stop the current recording before resuming another".into());
+    }
+
+    let dir = session_dir(app, id)?;
+    let mut session = load_session(app, id)?;
+    let audio = dir.join(AUDIO_FILE);
+    let writer = match audio.is_file() {
+        true => WavWriter::append(&audio),
+        false => WavWriter::create(&audio, WAV_SPEC),
+    }
+    .map_err(|e| e.to_string())?;
+    let samples = writer.len() as u64;
+
+    session.ended_at = None;
+    write_session(&dir, &session)?;
+    *active = Some(ActiveRecording {
+        dir,
+        writer,
+        samples,
+        session: session.clone(),
+        saved_at: Instant::now(),
+    });
+    drop(active);
+
+    println!("[Recording] Resumed session {}", id);
+    let _ = app.emit("recording:state", true);
+    Ok(session)
+}
+
+/// Stores a session handed off by another device and continues it: new
+/// captions land in the same session.
+pub fn continue_handoff(app: &AppHandle, session: &Session) -> Result<Session, String> {
+    store_session(&session_dir(app, &session.id)?, session)?;
+    resume(app, &session.id)
+}
+
+/// Adds a final caption to the active recording, aligned to the audio written so far.
+pub fn on_caption(app: &AppHandle, caption: &Caption) {
+    if !caption.is_final {
+        return;
+    }
+
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    let Some(recording) = active.as_mut() else {
+        return;
+    };
+
+    // The caption arrives `latency_ms` after the words were spoken.
+    let audio_ms = recording.samples * 1000 / SAMPLE_RATE as u64;
+    let segment = Segment {
+        text: caption.text.clone(),
+        offset_ms: audio_ms.saturating_sub(caption.latency_ms.unwrap_or(0)),
+        timestamp: caption.timestamp,
+        speaker: caption.speaker,
+        language: caption.language.clone(),
+        source: caption.source.clone(),
+        original_text: None,
+        corrected_at: None,
+    };
+    if let Err(e) = append_wal(&recording.dir, &segment) {
+        eprintln!("[Recording] Failed to append to transcript log: {}", e);
+    }
+    recording.session.segments.push(segment);
+
+    // The log already holds the segment; `stop` and `recover` catch up.
+    if recording.saved_at.elapsed() < SAVE_INTERVAL {
+        return;
+    }
+    match write_session(&recording.dir, &recording.session) {
+        Ok(()) => recording.saved_at = Instant::now(),
+        Err(e) => eprintln!("[Recording] Failed to save transcript: {}", e),
+    }
+}
+
+/// Applies `edit` to session `id` (the active or latest one if `None`) and
+/// saves it, whether it is being recorded or already on disk.
+pub fn update_session(
+    app: &AppHandle,
+    id: Option<&str>,
+    edit: impl FnOnce(&mut Session),
+) -> Result<Session, String> {
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    if let Some(recording) = active
+        .as_mut()
+        .filter(|recording| id.is_none_or(|id| id == recording.session.id))
+    {
+        edit(&mut recording.session);
+        write_session(&recording.dir, &recording.session)?;
+        return Ok(recording.session.clone());
+    }
+    drop(active);
+
+    let id = match id {
+        Some(id) => id.to_string(),
+        None => latest_session_id(app).ok_or("No recorded sessions")?,
+    };
+    let mut session = load_session(app, &id)?;
+    edit(&mut session);
+    save_session(app, &session)?;
+    Ok(session)
+}
+
+/// Bookmarks the current position of the active recording.
+pub fn mark(app: &AppHandle, label: Option<String>) -> Result<Bookmark, String> {
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    let recording = active.as_mut().ok_or("No active recording")?;
+
+    let bookmark = Bookmark {
+        label: label.filter(|label| !label.trim().is_empty()),
+        offset_ms: recording.samples * 1000 / SAMPLE_RATE as u64,
+        created_at: now_ms(),
+    };
+    recording.session.bookmarks.push(bookmark.clone());
+    write_session(&recording.dir, &recording.session)?;
+    drop(active);
+
+    println!("[Recording] Bookmark at {} ms", bookmark.offset_ms);
+    let _ = app.emit("recording:bookmark", &bookmark);
+    Ok(bookmark)
+}
+
+/// Notes a transcription provider switch in the active recording, if any.
+pub fn annotate_provider(
+    app: &AppHandle,
+    provider: SttProvider,
+    reason: &str,
+) -> Result<(), String> {
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    let Some(recording) = active.as_mut() else {
+        return Ok(());
+    };
+
+    recording.session.provider_switches.push(ProviderSwitch {
+        provider,
+        reason: reason.into(),
+        offset_ms: recording.samples * 1000 / SAMPLE_RATE as u64,
+        created_at: now_ms(),
+    });
+    write_session(&recording.dir, &recording.session)
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(RecorderState::default());
+    let orphan = recover_orphans(app).unwrap_or_else(|e| {
+        eprintln!("[Recording] Failed to look for interrupted sessions: {}", e);
+        None
+    });
+    app.manage(OrphanState(Mutex::new(orphan)));
+
+    // Capture stopping ends the recording, so files are always finalized.
+    let handle = app.clone();
+    app.listen_any("capture:state", move |event| {
+        let active = serde_json::from_str::<CaptureStatus>(event.payload())
+            .map(|status| status.active)
+            .unwrap_or(true);
+        if !active && is_recording(&handle) {
+            if let Err(e) = stop(&handle) {
+                eprintln!("[Recording] Failed to stop: {}", e);
+            }
+        }
+    });
+
+    lifecycle::on_exit(app, "recording", |app, save| {
+        let result = match save {
+            true => stop(app).map(|_| ()),
+            false => discard(app),
+        };
+        if let Err(e) = result {
+            eprintln!("[Recording] Failed to close session on exit: {}", e);
+        }
+    });
+
+    if let Err(e) = apply_retention(app) {
+        eprintln!("[Recording] Failed to apply retention: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn start_recording(app: AppHandle, webview: Webview) -> Result<String, String> {
+    security::guard(&webview, "start_recording", None)?;
+    start(&app)
+}
+
+#[tauri::command]
+pub fn stop_recording(app: AppHandle) -> Result<Option<Session>, String> {
+    stop(&app)
+}
+
+#[tauri::command]
+pub fn get_session(app: AppHandle, id: String) -> Result<Session, String> {
+    load_session(&app, &id)
+}
+
+/// Deletes a stored session; `undo_last_operation` brings it back for a while.
+#[tauri::command]
+pub fn delete_session(app: AppHandle, webview: Webview, id: String) -> Result<(), String> {
+    security::guard(&webview, "delete_session", None)?;
+    let recording = app.state::<RecorderState>();
+    if recording.0.lock().unwrap().as_ref().map(|r| &r.session.id) == Some(&id) {
+        return Err("Stop the recording before deleting it".into());
+    }
+
+    let dir = session_dir(&app, &id)?;
+    if !dir.is_dir() {
+        return Err(format!("Session '{}' not found", id));
+    }
+    journal::remove(
+        &app,
+        OperationKind::DeleteSession,
+        format!("Deleted session {}", id),
+        vec![dir],
+    )
+}
+
+/// Deletes every stored session except the one being recorded.
+#[tauri::command]
+pub fn purge_history(app: AppHandle, webview: Webview) -> Result<usize, String> {
+    security::guard(&webview, "purge_history", None)?;
+    let active = app
+        .state::<RecorderState>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|r| r.dir.clone());
+
+    let dirs: Vec<PathBuf> = fs::read_dir(recordings_dir(&app)?)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(SESSION_FILE).is_file() && Some(path) != active.as_ref())
+        .collect();
+    if dirs.is_empty() {
+        return Err("No recorded sessions".into());
+    }
+
+    let count = dirs.len();
+    journal::remove(
+        &app,
+        OperationKind::PurgeHistory,
+        format!("Deleted {} sessions", count),
+        dirs,
+    )?;
+    Ok(count)
+}
+
+/// Continues the session the previous run was killed in, as offered by `recording:orphaned`.
+#[tauri::command]
+pub fn resume_session(app: AppHandle, webview: Webview, id: String) -> Result<Session, String> {
+    security::guard(&webview, "resume_session", Some(&id))?;
+    let state = app.state::<OrphanState>();
+    if state.0.lock().unwrap().as_ref().map(|orphan| &orphan.id) != Some(&id) {
+        return Err(format!("Session '{}' is not waiting to be resumed", id));
+    }
+
+    let session = resume(&app, &id)?;
+    state.0.lock().unwrap().take();
+    capture::request_start(&app).map_err(|e| e.to_string())?;
+    Ok(session)
+}
+
+/// Keeps the recovered session as it is and stops offering it.
+#[tauri::command]
+pub fn dismiss_orphaned_session(app: AppHandle) {
+    app.state::<OrphanState>().0.lock().unwrap().take();
+}
+
+#[tauri::command]
+pub fn mark_moment(app: AppHandle, label: Option<String>) -> Result<Bookmark, String> {
+    mark(&app, label)
+}
+
+/// Receives raw 16-bit PCM chunks from the frontend while a recording is active.
+#[tauri::command(async)]
+pub fn push_audio(app: AppHandle, request: Request<'_>) -> Result<(), String> {
+    let InvokeBody::Raw(bytes) = request.body() else {
+        return Err("Expected raw PCM data".into());
+    };
+    relay::send_audio(&app, bytes);
+
+    let state = app.state::<RecorderState>();
+    let mut active = state.0.lock().unwrap();
+    let Some(recording) = active.as_mut() else {
+        return Ok(());
+    };
+
+    for sample in bytes.chunks_exact(2) {
+        recording
+            .writer
+            .write_sample(i16::from_le_bytes([sample[0], sample[1]]))
+            .map_err(|e| e.to_string())?;
+    }
+    recording.samples += bytes.len() as u64 / 2;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("signos-recording-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn segment(text: &str, offset_ms: u64) -> Segment {
+        Segment {
+            text: text.into(),
+            offset_ms,
+            timestamp: offset_ms,
+            speaker: None,
+            language: None,
+            source: None,
+            original_text: None,
+            corrected_at: None,
+        }
+    }
+
+    /// A WAV as a killed writer leaves it: samples on disk, sizes still zero.
+    fn unfinalized_wav(path: &Path, samples: &[i16]) {
+        let mut writer = WavWriter::create(path, WAV_SPEC).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut file = OpenOptions::new().write(true).open(path).unwrap();
+        file.seek(SeekFrom::Start(4)).unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+        file.seek(SeekFrom::End(-(samples.len() as i64 * 2) - 4))
+            .unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+    }
+
+    #[test]
+    fn reads_the_wal_back_and_skips_a_torn_line() {
+        let dir = scratch("wal");
+        append_wal(&dir, &segment("hello", 0)).unwrap();
+        append_wal(&dir, &segment("world", 1200)).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join(TRANSCRIPT_WAL))
+            .unwrap();
+        file.write_all(br#"{"text":"cut of"#).unwrap();
+
+        let segments = read_wal(&dir);
+        let texts: Vec<_> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["hello", "world"]);
+        assert_eq!(segments[1].offset_ms, 1200);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reads_no_segments_without_a_wal() {
+        let dir = scratch("no-wal");
+        assert!(read_wal(&dir).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn repairs_the_sizes_of_an_unfinalized_wav() {
+        let dir = scratch("repair");
+        let path = dir.join(AUDIO_FILE);
+        unfinalized_wav(&path, &[1, -2, 3, -4]);
+        // Half a sample written as the app was killed.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[7])
+            .unwrap();
+
+        repair_wav(&path).unwrap();
+        let mut reader = WavReader::open(&path).unwrap();
+        let samples: Vec<i16> = reader.samples().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, [1, -2, 3, -4]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_to_repair_a_file_without_a_data_chunk() {
+        let dir = scratch("no-data");
+        let path = dir.join(AUDIO_FILE);
+        fs::write(&path, b"RIFF\0\0\0\0WAVE").unwrap();
+        assert!(repair_wav(&path).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
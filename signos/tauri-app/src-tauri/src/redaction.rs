@@ -0,0 +1,222 @@
+//! Masks sensitive content before it is stored or leaves the device.
+//!
+//! Runs on every caption after the text stages, so recordings, transcript
+//! files and network outputs only ever see the masked text. The panel can
+//! optionally keep showing the original, which is never written anywhere.
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::settings;
+
+/// 13–19 digits, optionally grouped with spaces or dashes as they are read out.
+const CARD_PATTERN: &str = r"\b\d(?:[ -]?\d){12,18}\b";
+const EMAIL_PATTERN: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    /// Digit runs that pass the Luhn check.
+    pub credit_cards: bool,
+    pub emails: bool,
+    /// Extra regexes; every match is masked.
+    pub patterns: Vec<String>,
+    /// Replaces each match.
+    pub mask: String,
+    /// Show the unmasked captions in the panel and Picture-in-Picture view.
+    pub keep_in_live_view: bool,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            credit_cards: true,
+            emails: true,
+            patterns: Vec::new(),
+            mask: "[redacted]".into(),
+            keep_in_live_view: false,
+        }
+    }
+}
+
+struct Compiled {
+    settings: RedactionSettings,
+    card: Regex,
+    rules: Vec<Regex>,
+}
+
+/// Rules compiled from the current settings, rebuilt when they change.
+#[derive(Default)]
+pub struct RedactionState(Mutex<Option<Compiled>>);
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match i % 2 {
+            0 => d,
+            _ if d * 2 > 9 => d * 2 - 9,
+            _ => d * 2,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn compile(settings: &RedactionSettings) -> Compiled {
+    let mut rules = Vec::new();
+    if settings.emails {
+        rules.push(Regex::new(EMAIL_PATTERN).expect("valid email pattern"));
+    }
+    for pattern in settings.patterns.iter().filter(|p| !p.trim().is_empty()) {
+        match Regex::new(pattern) {
+            Ok(regex) => rules.push(regex),
+            Err(e) => eprintln!("[Redaction] Skipping '{}': {}", pattern, e),
+        }
+    }
+    Compiled {
+        settings: settings.clone(),
+        card: Regex::new(CARD_PATTERN).expect("valid card pattern"),
+        rules,
+    }
+}
+
+impl Compiled {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mask = self.settings.mask.as_str();
+        let mut text = Cow::Borrowed(text);
+
+        if self.settings.credit_cards {
+            let replaced = self.card.replace_all(&text, |caps: &Captures| {
+                let digits: Vec<u32> = caps[0].chars().filter_map(|c| c.to_digit(10)).collect();
+                match luhn_valid(&digits) {
+                    true => mask.to_string(),
+                    false => caps[0].to_string(),
+                }
+            });
+            if let Cow::Owned(replaced) = replaced {
+                text = Cow::Owned(replaced);
+            }
+        }
+        for rule in &self.rules {
+            if let Cow::Owned(replaced) = rule.replace_all(&text, regex::NoExpand(mask)) {
+                text = Cow::Owned(replaced);
+            }
+        }
+        text
+    }
+}
+
+/// `text` with every enabled rule applied; unchanged when redaction is off.
+pub fn redact<'a>(app: &AppHandle, text: &'a str) -> Cow<'a, str> {
+    let config = settings::get(app).redaction;
+    if !config.enabled {
+        return Cow::Borrowed(text);
+    }
+
+    let state = app.state::<RedactionState>();
+    let mut compiled = state.0.lock().unwrap();
+    if compiled.as_ref().map(|c| &c.settings) != Some(&config) {
+        *compiled = Some(compile(&config));
+    }
+    match compiled.as_ref() {
+        Some(compiled) => compiled.redact(text),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Masks `caption` in place and returns the version the live view should show.
+pub fn apply(app: &AppHandle, caption: &mut Caption) -> Caption {
+    let original = caption.clone();
+    if let Cow::Owned(text) = redact(app, &caption.text) {
+        caption.text = text;
+    }
+
+    match settings::get(app).redaction.keep_in_live_view {
+        true => original,
+        false => caption.clone(),
+    }
+}
+
+/// Checks a custom pattern before the settings UI saves it.
+#[tauri::command]
+pub fn validate_redaction_pattern(pattern: String) -> Result<(), String> {
+    Regex::new(&pattern).map(|_| ()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(number: &str) -> Vec<u32> {
+        number.chars().filter_map(|c| c.to_digit(10)).collect()
+    }
+
+    fn compiled(edit: impl FnOnce(&mut RedactionSettings)) -> Compiled {
+        let mut settings = RedactionSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        edit(&mut settings);
+        compile(&settings)
+    }
+
+    #[test]
+    fn checks_luhn() {
+        assert!(luhn_valid(&digits("4111111111111111")));
+        assert!(luhn_valid(&digits("79927398713")));
+        assert!(!luhn_valid(&digits("4111111111111112")));
+    }
+
+    #[test]
+    fn masks_card_numbers_that_pass_luhn() {
+        let rules = compiled(|_| {});
+        assert_eq!(
+            rules.redact("it is 4111 1111 1111 1111 thanks"),
+            "it is [redacted] thanks"
+        );
+        assert_eq!(rules.redact("4111-1111-1111-1111"), "[redacted]");
+        assert_eq!(
+            rules.redact("order 4111 1111 1111 1112"),
+            "order 4111 1111 1111 1112"
+        );
+        // Too short to be a card.
+        assert_eq!(rules.redact("call 555 1234"), "call 555 1234");
+    }
+
+    #[test]
+    fn masks_emails_and_custom_patterns() {
+        let rules = compiled(|s| s.patterns = vec![r"\bproject \w+".into()]);
+        assert_eq!(
+            rules.redact("mail Ana.Diaz@example.com about project falcon"),
+            "mail [redacted] about [redacted]"
+        );
+    }
+
+    #[test]
+    fn respects_disabled_rules() {
+        let rules = compiled(|s| {
+            s.credit_cards = false;
+            s.emails = false;
+        });
+        let text = "4111 1111 1111 1111 ana@example.com";
+        assert!(matches!(rules.redact(text), Cow::Borrowed(t) if t == text));
+    }
+
+    #[test]
+    fn skips_invalid_patterns_and_keeps_the_mask_literal() {
+        let rules = compiled(|s| {
+            s.patterns = vec!["(".into(), "secret".into()];
+            s.mask = "$0".into();
+        });
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.redact("the secret word"), "the $0 word");
+    }
+}
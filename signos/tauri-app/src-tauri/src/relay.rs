@@ -0,0 +1,848 @@
+//! Interpreter relay: streams a session to a remote human interpreter.
+//!
+//! The interpreter's instance listens and hands out one-time invites. Pairing
+//! with an invite runs X25519 with the invite secret mixed into the key, so only
+//! whoever was given the invite can pair. While relaying, the user's captions,
+//! and audio if they allow it, are sent over ChaCha20-Poly1305 frames; the
+//! interpreter answers with corrections and translations, which are only
+//! accepted when the user granted them.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::captions::{self, Caption};
+use crate::sync::{
+    self, channel_keys, from_hex, random, read_plain, send_plain, to_hex, PairedDevice, Sealer,
+    IDLE_TIMEOUT, KEEPALIVE,
+};
+use crate::util::now_ms;
+use crate::{corrections, lifecycle, security, settings, viewer};
+
+const DEFAULT_PORT: u16 = 28193;
+const INVITE_SCHEME: &str = "signos-relay://";
+const INVITE_TTL: Duration = Duration::from_secs(10 * 60);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Caption source for what the interpreter sends back; never relayed again.
+const INTERPRETER_SOURCE: &str = "Interpreter";
+
+/// First byte of every sealed relay frame.
+const FRAME_MESSAGE: u8 = 0;
+const FRAME_AUDIO: u8 = 1;
+/// Sent when there was nothing else to send for a while, so the peer knows the
+/// connection is alive.
+const FRAME_PING: u8 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RelaySettings {
+    /// Accept relays from paired users, as an interpreter.
+    pub listen: bool,
+    pub port: u16,
+    /// Host put in invites for interpreters reached over the internet;
+    /// the LAN address is used when unset.
+    pub public_host: Option<String>,
+    /// Interpreters this device relays to.
+    pub interpreters: Vec<Interpreter>,
+    /// Users this device interprets for.
+    pub clients: Vec<PairedDevice>,
+}
+
+impl Default for RelaySettings {
+    fn default() -> Self {
+        Self {
+            listen: false,
+            port: DEFAULT_PORT,
+            public_host: None,
+            interpreters: Vec::new(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interpreter {
+    pub id: String,
+    pub name: String,
+    /// `host:port` from the invite.
+    pub address: String,
+    /// Hex-encoded key derived during pairing.
+    pub key: String,
+    #[serde(default)]
+    pub permissions: RelayPermissions,
+}
+
+/// What an interpreter may receive and send back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RelayPermissions {
+    pub audio: bool,
+    pub captions: bool,
+    pub corrections: bool,
+    pub translations: bool,
+}
+
+impl Default for RelayPermissions {
+    fn default() -> Self {
+        Self {
+            audio: false,
+            captions: true,
+            corrections: true,
+            translations: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelayRole {
+    Interpreter,
+    Client,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayPeer {
+    pub id: String,
+    pub name: String,
+    pub role: RelayRole,
+    pub connected: bool,
+    /// Only set for interpreters.
+    pub permissions: Option<RelayPermissions>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayStatus {
+    /// Interpreters currently relayed to.
+    pub active: Vec<String>,
+    /// At least one of them receives audio, so the frontend should forward it.
+    pub audio: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Message {
+    /// Opens a pairing connection with an invite. Sent in the clear.
+    Hello {
+        id: String,
+        name: String,
+        public_key: String,
+        invite: String,
+    },
+    Welcome {
+        id: String,
+        name: String,
+        public_key: String,
+    },
+    /// Confirms both sides derived the same key.
+    Paired,
+    /// Opens a relay between paired devices. Sent in the clear, with a fresh
+    /// nonce for the connection's keys.
+    Auth {
+        id: String,
+        nonce: String,
+    },
+    /// Answers `Auth` with the other nonce. Sent in the clear; everything after is encrypted.
+    Challenge {
+        nonce: String,
+    },
+    Permissions {
+        permissions: RelayPermissions,
+    },
+    Caption {
+        caption: Caption,
+    },
+    /// Replaces the text of the caption with this timestamp.
+    Correction {
+        timestamp: u64,
+        text: String,
+    },
+    Translation {
+        text: String,
+        language: Option<String>,
+    },
+}
+
+struct Invite {
+    secret: [u8; 16],
+    created: Instant,
+}
+
+struct Relay {
+    name: String,
+    permissions: RelayPermissions,
+    frames: Sender<Vec<u8>>,
+}
+
+#[derive(Default)]
+pub struct RelayState {
+    /// Invites handed out by this interpreter, by invite id.
+    invites: Mutex<HashMap<String, Invite>>,
+    /// Relays to interpreters, by interpreter id.
+    relays: Mutex<HashMap<String, Relay>>,
+    /// Connected users, by client id, for sending corrections back.
+    sessions: Mutex<HashMap<String, Sender<Vec<u8>>>>,
+    /// Webviews playing relayed audio.
+    listeners: Mutex<Vec<Channel<InvokeResponseBody>>>,
+}
+
+fn derive(secret: &StaticSecret, peer_key: &str, invite_secret: &[u8]) -> Result<[u8; 32], String> {
+    let peer: [u8; 32] = from_hex(peer_key)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Invalid public key")?;
+    let shared = secret.diffie_hellman(&PublicKey::from(peer));
+
+    Ok(Sha256::new()
+        .chain_update(b"signos-relay-key")
+        .chain_update(invite_secret)
+        .chain_update(shared.as_bytes())
+        .finalize()
+        .into())
+}
+
+fn message_frame(message: &Message) -> Vec<u8> {
+    let mut frame = vec![FRAME_MESSAGE];
+    frame.extend(serde_json::to_vec(message).unwrap_or_default());
+    frame
+}
+
+/// Splits `signos-relay://host:port/<invite id>.<secret>`.
+fn parse_invite(invite: &str) -> Result<(String, String, [u8; 16]), String> {
+    let invalid = "Invalid relay invite";
+    let rest = invite.trim().strip_prefix(INVITE_SCHEME).ok_or(invalid)?;
+    let (address, token) = rest.split_once('/').ok_or(invalid)?;
+    let (id, secret) = token.split_once('.').ok_or(invalid)?;
+    let secret = from_hex(secret)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(invalid)?;
+    Ok((address.to_string(), id.to_string(), secret))
+}
+
+fn connect(address: &str) -> Result<TcpStream, String> {
+    let address = address
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or("Interpreter address did not resolve")?;
+    let stream =
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    let _ = stream.set_nodelay(true);
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(sync::IO_TIMEOUT));
+    Ok(stream)
+}
+
+fn client_key(app: &AppHandle, id: &str) -> Option<[u8; 32]> {
+    settings::get(app)
+        .relay
+        .clients
+        .iter()
+        .find(|client| client.id == id)
+        .and_then(|client| from_hex(&client.key))
+        .and_then(|key| key.try_into().ok())
+}
+
+fn emit_status(app: &AppHandle) {
+    let state = app.state::<RelayState>();
+    let status = {
+        let relays = state.relays.lock().unwrap();
+        RelayStatus {
+            active: relays.keys().cloned().collect(),
+            audio: relays.values().any(|relay| relay.permissions.audio),
+        }
+    };
+    let _ = app.emit("relay:state", &status);
+}
+
+/// Writes queued frames until the queue is dropped or the connection fails,
+/// pinging while there is nothing to send.
+fn spawn_writer(mut stream: TcpStream, mut sealer: Sealer, label: String) -> Sender<Vec<u8>> {
+    let (tx, rx) = mpsc::channel::<Vec<u8>>();
+    thread::spawn(move || {
+        loop {
+            let frame = match rx.recv_timeout(KEEPALIVE) {
+                Ok(frame) => frame,
+                Err(RecvTimeoutError::Timeout) => vec![FRAME_PING],
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if let Err(e) = sealer.write(&mut stream, &frame) {
+                eprintln!("[Relay] Connection to {} closed: {}", label, e);
+                break;
+            }
+        }
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+    tx
+}
+
+/// Applies what an interpreter sent back, within the user's permissions.
+fn on_reply(app: &AppHandle, id: &str, message: Message) {
+    let Some((name, permissions)) = app
+        .state::<RelayState>()
+        .relays
+        .lock()
+        .unwrap()
+        .get(id)
+        .map(|relay| (relay.name.clone(), relay.permissions))
+    else {
+        return;
+    };
+
+    match message {
+        Message::Correction { timestamp, text } if permissions.corrections => {
+            corrections::correct_caption(app, timestamp, &text);
+            let _ = app.emit(
+                "relay:correction",
+                serde_json::json!({ "interpreterId": id, "interpreter": name, "timestamp": timestamp, "text": text }),
+            );
+        }
+        Message::Translation { text, language } if permissions.translations => {
+            captions::push(
+                app,
+                Caption {
+                    text,
+                    is_final: true,
+                    timestamp: now_ms(),
+                    latency_ms: None,
+                    language,
+                    speaker: None,
+                    source: Some(INTERPRETER_SOURCE.into()),
+                },
+            );
+        }
+        Message::Correction { .. } | Message::Translation { .. } => {
+            println!("[Relay] Dropped a reply {} is not permitted to send", name);
+        }
+        _ => {}
+    }
+}
+
+fn handle_pairing(
+    app: &AppHandle,
+    mut stream: TcpStream,
+    id: String,
+    name: String,
+    public_key: String,
+    invite: String,
+) -> Result<(), String> {
+    let invite = app
+        .state::<RelayState>()
+        .invites
+        .lock()
+        .unwrap()
+        .remove(&invite)
+        .filter(|invite| invite.created.elapsed() < INVITE_TTL)
+        .ok_or("Unknown or expired invite")?;
+
+    let secret = StaticSecret::from(random::<32>()?);
+    let own_key = to_hex(PublicKey::from(&secret).as_bytes());
+    let own = settings::get(app).sync;
+    send_plain(
+        &mut stream,
+        &Message::Welcome {
+            id: own.device_id,
+            name: own.device_name,
+            public_key: own_key.clone(),
+        },
+    )?;
+
+    let key = derive(&secret, &public_key, &invite.secret)?;
+    let (mut sealer, mut opener) =
+        channel_keys(&key, public_key.as_bytes(), own_key.as_bytes(), false);
+    let Message::Paired = opener.receive(&mut stream)? else {
+        return Err("Unexpected pairing confirmation".into());
+    };
+
+    let client = PairedDevice {
+        id: id.clone(),
+        name: name.clone(),
+        key: to_hex(&key),
+    };
+    settings::update(app, |s| {
+        s.relay.clients.retain(|paired| paired.id != client.id);
+        s.relay.clients.push(client);
+    })?;
+    sealer.send(&mut stream, &Message::Paired)?;
+
+    println!("[Relay] Paired with {}", name);
+    let _ = app.emit(
+        "relay:paired",
+        serde_json::json!({ "clientId": id, "name": name }),
+    );
+    Ok(())
+}
+
+/// Handles a frame a connected user relayed, as the interpreter.
+fn on_client_frame(app: &AppHandle, id: &str, frame: &[u8]) {
+    match frame.split_first() {
+        Some((&FRAME_AUDIO, pcm)) => {
+            app.state::<RelayState>()
+                .listeners
+                .lock()
+                .unwrap()
+                .retain(|channel| channel.send(InvokeResponseBody::Raw(pcm.to_vec())).is_ok());
+        }
+        Some((&FRAME_MESSAGE, json)) => match serde_json::from_slice(json) {
+            Ok(Message::Caption { caption }) => {
+                let _ = app.emit(
+                    "relay:caption",
+                    serde_json::json!({ "clientId": id, "caption": caption }),
+                );
+            }
+            Ok(Message::Permissions { permissions }) => {
+                let _ = app.emit(
+                    "relay:permissions",
+                    serde_json::json!({ "clientId": id, "permissions": permissions }),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[Relay] Invalid message from {}: {}", id, e),
+        },
+        _ => {}
+    }
+}
+
+/// Receives a user's relay, as the interpreter.
+fn handle_session(
+    app: &AppHandle,
+    mut stream: TcpStream,
+    id: String,
+    nonce: String,
+) -> Result<(), String> {
+    let key = client_key(app, &id).ok_or("Relay from an unpaired user")?;
+    let theirs = from_hex(&nonce).ok_or("Invalid nonce")?;
+    let nonce = random::<32>()?;
+    send_plain(
+        &mut stream,
+        &Message::Challenge {
+            nonce: to_hex(&nonce),
+        },
+    )?;
+    let (sealer, mut opener) = channel_keys(&key, &theirs, &nonce, false);
+
+    // Only a peer holding the key gets past the first frame, so a replayed
+    // `Auth` cannot take the session; a second live one for the user is refused.
+    let first = opener.read(&mut stream)?;
+    let state = app.state::<RelayState>();
+    {
+        let mut sessions = state.sessions.lock().unwrap();
+        if sessions.contains_key(&id) {
+            return Err(format!("{} is already connected", id));
+        }
+        let writer = stream.try_clone().map_err(|e| e.to_string())?;
+        sessions.insert(id.clone(), spawn_writer(writer, sealer, id.clone()));
+    }
+    let _ = stream.set_read_timeout(Some(IDLE_TIMEOUT));
+    let _ = app.emit(
+        "relay:session",
+        serde_json::json!({ "clientId": id, "connected": true }),
+    );
+
+    on_client_frame(app, &id, &first);
+    let result = loop {
+        match opener.read(&mut stream) {
+            Ok(frame) => on_client_frame(app, &id, &frame),
+            Err(e) => break Err(e),
+        }
+    };
+
+    state.sessions.lock().unwrap().remove(&id);
+    let _ = app.emit(
+        "relay:session",
+        serde_json::json!({ "clientId": id, "connected": false }),
+    );
+    result
+}
+
+fn handle_connection(app: &AppHandle, mut stream: TcpStream) -> Result<(), String> {
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    match read_plain(&mut stream)? {
+        Message::Hello {
+            id,
+            name,
+            public_key,
+            invite,
+        } => handle_pairing(app, stream, id, name, public_key, invite),
+        Message::Auth { id, nonce } => handle_session(app, stream, id, nonce),
+        _ => Err("Unexpected opening message".into()),
+    }
+}
+
+/// Relays a caption to the interpreters allowed to see captions.
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    let Some(state) = app.try_state::<RelayState>() else {
+        return;
+    };
+    if caption.source.as_deref() == Some(INTERPRETER_SOURCE) {
+        return;
+    }
+
+    let mut relays = state.relays.lock().unwrap();
+    if relays.is_empty() {
+        return;
+    }
+    let frame = message_frame(&Message::Caption {
+        caption: caption.clone(),
+    });
+    relays
+        .retain(|_, relay| !relay.permissions.captions || relay.frames.send(frame.clone()).is_ok());
+}
+
+/// Relays a chunk of 16 kHz Int16 PCM to the interpreters allowed to hear audio.
+pub fn send_audio(app: &AppHandle, pcm: &[u8]) {
+    let Some(state) = app.try_state::<RelayState>() else {
+        return;
+    };
+
+    let mut relays = state.relays.lock().unwrap();
+    if !relays.values().any(|relay| relay.permissions.audio) {
+        return;
+    }
+    let mut frame = Vec::with_capacity(pcm.len() + 1);
+    frame.push(FRAME_AUDIO);
+    frame.extend_from_slice(pcm);
+    relays.retain(|_, relay| !relay.permissions.audio || relay.frames.send(frame.clone()).is_ok());
+}
+
+fn send_to_client(app: &AppHandle, id: &str, message: &Message) -> Result<(), String> {
+    app.state::<RelayState>()
+        .sessions
+        .lock()
+        .unwrap()
+        .get(id)
+        .ok_or("User is not connected")?
+        .send(message_frame(message))
+        .map_err(|_| "Connection to the user closed".into())
+}
+
+pub fn init(app: &AppHandle) {
+    // Dropping the senders ends the writer threads, which shut their connections.
+    lifecycle::on_exit(app, "relay", |app, _| {
+        let state = app.state::<RelayState>();
+        state.relays.lock().unwrap().clear();
+        state.sessions.lock().unwrap().clear();
+        state.listeners.lock().unwrap().clear();
+    });
+
+    let config = settings::get(app).relay;
+    if !config.listen {
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[Relay] Failed to bind port {}: {}", config.port, e);
+            return;
+        }
+    };
+    println!("[Relay] Listening for relays on port {}", config.port);
+
+    sync::serve(app, listener, "Relay", handle_connection);
+}
+
+/// Creates a one-time invite for a user to pair with this interpreter. It
+/// expires after ten minutes.
+#[tauri::command]
+pub fn create_relay_invite(app: AppHandle) -> Result<String, String> {
+    let config = settings::get(&app).relay;
+    if !config.listen {
+        return Err("Relay listening is disabled".into());
+    }
+
+    let host = match config.public_host.filter(|host| !host.trim().is_empty()) {
+        Some(host) => host.trim().to_string(),
+        None => viewer::lan_ip().ok_or("No network connection")?.to_string(),
+    };
+    let id = to_hex(&random::<8>()?);
+    let secret = random::<16>()?;
+
+    let state = app.state::<RelayState>();
+    let mut invites = state.invites.lock().unwrap();
+    invites.retain(|_, invite| invite.created.elapsed() < INVITE_TTL);
+    invites.insert(
+        id.clone(),
+        Invite {
+            secret,
+            created: Instant::now(),
+        },
+    );
+    Ok(format!(
+        "{}{}:{}/{}.{}",
+        INVITE_SCHEME,
+        host,
+        config.port,
+        id,
+        to_hex(&secret)
+    ))
+}
+
+/// Pairs with the interpreter that created `invite`. Nothing is relayed until
+/// `start_relay`; audio stays off until granted with `set_relay_permissions`.
+#[tauri::command]
+pub async fn pair_interpreter(
+    webview: Webview,
+    app: AppHandle,
+    invite: String,
+) -> Result<RelayPeer, String> {
+    security::guard(&webview, "pair_interpreter", None)?;
+    let (address, invite_id, invite_secret) = parse_invite(&invite)?;
+
+    let mut stream = connect(&address)?;
+    let secret = StaticSecret::from(random::<32>()?);
+    let own_key = to_hex(PublicKey::from(&secret).as_bytes());
+    let own = settings::get(&app).sync;
+    send_plain(
+        &mut stream,
+        &Message::Hello {
+            id: own.device_id,
+            name: own.device_name,
+            public_key: own_key.clone(),
+            invite: invite_id,
+        },
+    )?;
+
+    let Message::Welcome {
+        id,
+        name,
+        public_key,
+    } = read_plain(&mut stream)?
+    else {
+        return Err("The interpreter rejected the invite".into());
+    };
+    let key = derive(&secret, &public_key, &invite_secret)?;
+    let (mut sealer, mut opener) =
+        channel_keys(&key, own_key.as_bytes(), public_key.as_bytes(), true);
+    sealer.send(&mut stream, &Message::Paired)?;
+    let Message::Paired = opener.receive(&mut stream)? else {
+        return Err("Unexpected pairing confirmation".into());
+    };
+
+    let interpreter = Interpreter {
+        id: id.clone(),
+        name: name.clone(),
+        address,
+        key: to_hex(&key),
+        permissions: RelayPermissions::default(),
+    };
+    let permissions = interpreter.permissions;
+    settings::update(&app, |s| {
+        s.relay
+            .interpreters
+            .retain(|paired| paired.id != interpreter.id);
+        s.relay.interpreters.push(interpreter);
+    })?;
+
+    println!("[Relay] Paired with interpreter {}", name);
+    Ok(RelayPeer {
+        id,
+        name,
+        role: RelayRole::Interpreter,
+        connected: false,
+        permissions: Some(permissions),
+    })
+}
+
+#[tauri::command]
+pub fn list_relay_peers(app: AppHandle) -> Vec<RelayPeer> {
+    let config = settings::get(&app).relay;
+    let state = app.state::<RelayState>();
+    let relays = state.relays.lock().unwrap();
+    let sessions = state.sessions.lock().unwrap();
+
+    let interpreters = config
+        .interpreters
+        .into_iter()
+        .map(|interpreter| RelayPeer {
+            connected: relays.contains_key(&interpreter.id),
+            id: interpreter.id,
+            name: interpreter.name,
+            role: RelayRole::Interpreter,
+            permissions: Some(interpreter.permissions),
+        });
+    let clients = config.clients.into_iter().map(|client| RelayPeer {
+        connected: sessions.contains_key(&client.id),
+        id: client.id,
+        name: client.name,
+        role: RelayRole::Client,
+        permissions: None,
+    });
+    interpreters.chain(clients).collect()
+}
+
+/// Changes what an interpreter may receive and send back; applies to a live relay immediately.
+#[tauri::command]
+pub fn set_relay_permissions(
+    app: AppHandle,
+    webview: Webview,
+    id: String,
+    permissions: RelayPermissions,
+) -> Result<(), String> {
+    security::guard(&webview, "set_relay_permissions", Some(&id))?;
+    let mut found = false;
+    settings::update(&app, |s| {
+        if let Some(interpreter) = s
+            .relay
+            .interpreters
+            .iter_mut()
+            .find(|paired| paired.id == id)
+        {
+            interpreter.permissions = permissions;
+            found = true;
+        }
+    })?;
+    if !found {
+        return Err("Interpreter is not paired".into());
+    }
+
+    if let Some(relay) = app
+        .state::<RelayState>()
+        .relays
+        .lock()
+        .unwrap()
+        .get_mut(&id)
+    {
+        relay.permissions = permissions;
+        let _ = relay
+            .frames
+            .send(message_frame(&Message::Permissions { permissions }));
+    }
+    emit_status(&app);
+    Ok(())
+}
+
+/// Starts relaying to a paired interpreter until `stop_relay` is called.
+#[tauri::command]
+pub async fn start_relay(webview: Webview, app: AppHandle, id: String) -> Result<(), String> {
+    security::guard(&webview, "start_relay", Some(&id))?;
+    let interpreter = settings::get(&app)
+        .relay
+        .interpreters
+        .into_iter()
+        .find(|paired| paired.id == id)
+        .ok_or("Interpreter is not paired")?;
+    let key: [u8; 32] = from_hex(&interpreter.key)
+        .and_then(|key| key.try_into().ok())
+        .ok_or("Invalid pairing key")?;
+
+    let mut stream = connect(&interpreter.address)?;
+    let nonce = random::<32>()?;
+    let own_id = settings::get(&app).sync.device_id;
+    send_plain(
+        &mut stream,
+        &Message::Auth {
+            id: own_id,
+            nonce: to_hex(&nonce),
+        },
+    )?;
+    let Message::Challenge { nonce: theirs } = read_plain(&mut stream)? else {
+        return Err("The interpreter refused the relay".into());
+    };
+    let theirs = from_hex(&theirs).ok_or("Invalid nonce")?;
+    let (sealer, mut opener) = channel_keys(&key, &nonce, &theirs, true);
+    let _ = stream.set_read_timeout(Some(IDLE_TIMEOUT));
+
+    let mut reader = stream.try_clone().map_err(|e| e.to_string())?;
+    let frames = spawn_writer(stream, sealer, interpreter.name.clone());
+    let _ = frames.send(message_frame(&Message::Permissions {
+        permissions: interpreter.permissions,
+    }));
+
+    let relay = Relay {
+        name: interpreter.name.clone(),
+        permissions: interpreter.permissions,
+        frames,
+    };
+    app.state::<RelayState>()
+        .relays
+        .lock()
+        .unwrap()
+        .insert(id.clone(), relay);
+    println!("[Relay] Relaying to {}", interpreter.name);
+    emit_status(&app);
+
+    thread::spawn(move || {
+        loop {
+            let frame = match opener.read(&mut reader) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    eprintln!("[Relay] Relay to {} ended: {}", interpreter.name, e);
+                    break;
+                }
+            };
+            if let Some((&FRAME_MESSAGE, json)) = frame.split_first() {
+                match serde_json::from_slice(json) {
+                    Ok(message) => on_reply(&app, &id, message),
+                    Err(e) => eprintln!("[Relay] Invalid reply from {}: {}", interpreter.name, e),
+                }
+            }
+        }
+        app.state::<RelayState>().relays.lock().unwrap().remove(&id);
+        emit_status(&app);
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_relay(app: AppHandle, id: String) {
+    // Dropping the sender closes the connection, which ends the reader too.
+    app.state::<RelayState>().relays.lock().unwrap().remove(&id);
+    emit_status(&app);
+}
+
+#[tauri::command]
+pub fn unpair_relay_peer(app: AppHandle, id: String) -> Result<(), String> {
+    let state = app.state::<RelayState>();
+    state.relays.lock().unwrap().remove(&id);
+    state.sessions.lock().unwrap().remove(&id);
+    settings::update(&app, |s| {
+        s.relay.interpreters.retain(|paired| paired.id != id);
+        s.relay.clients.retain(|paired| paired.id != id);
+    })?;
+    emit_status(&app);
+    Ok(())
+}
+
+/// Sends a corrected caption back to a connected user, as the interpreter.
+#[tauri::command]
+pub fn send_relay_correction(
+    app: AppHandle,
+    client_id: String,
+    timestamp: u64,
+    text: String,
+) -> Result<(), String> {
+    send_to_client(&app, &client_id, &Message::Correction { timestamp, text })
+}
+
+/// Sends a translation back to a connected user, as the interpreter.
+#[tauri::command]
+pub fn send_relay_translation(
+    app: AppHandle,
+    client_id: String,
+    text: String,
+    language: Option<String>,
+) -> Result<(), String> {
+    send_to_client(&app, &client_id, &Message::Translation { text, language })
+}
+
+/// Streams audio relayed by connected users to `on_audio` as 16 kHz Int16 PCM.
+#[tauri::command]
+pub fn subscribe_relay_audio(app: AppHandle, on_audio: Channel<InvokeResponseBody>) {
+    app.state::<RelayState>()
+        .listeners
+        .lock()
+        .unwrap()
+        .push(on_audio);
+}
@@ -0,0 +1,76 @@
+//! Keeps a live session going across webview reloads and crashes.
+//!
+//! The backend holds on to capture state and the last few final captions. When
+//! the main webview loads again it asks for `get_replay_snapshot`, redraws the
+//! captions and restarts capture without ending the session, so the recording
+//! and outputs carry on as if nothing happened.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{AppHandle, Manager, Webview};
+
+use crate::captions::Caption;
+use crate::capture::{self, CaptureStatus};
+use crate::panel::MAIN_WINDOW;
+use crate::recording;
+
+/// Final captions kept for the next page load.
+const REPLAY_CAPTIONS: usize = 20;
+
+#[derive(Default)]
+pub struct ReplayState {
+    captions: Mutex<VecDeque<Caption>>,
+    /// Page loads of the main webview since launch.
+    loads: AtomicU32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySnapshot {
+    /// This page load replaced an earlier one (reload or crash).
+    pub reloaded: bool,
+    /// Oldest first.
+    pub captions: Vec<Caption>,
+    pub capture: CaptureStatus,
+    pub recording: bool,
+}
+
+pub fn record(app: &AppHandle, caption: &Caption) {
+    if !caption.is_final {
+        return;
+    }
+    let state = app.state::<ReplayState>();
+    let mut captions = state.captions.lock().unwrap();
+    if captions.len() == REPLAY_CAPTIONS {
+        captions.pop_front();
+    }
+    captions.push_back(caption.clone());
+}
+
+/// Page load hook for every webview; only the main one is tracked.
+pub fn on_page_load(webview: &Webview, payload: &PageLoadPayload<'_>) {
+    if webview.label() != MAIN_WINDOW || payload.event() != PageLoadEvent::Started {
+        return;
+    }
+    let state = webview.state::<ReplayState>();
+    if state.loads.fetch_add(1, Ordering::SeqCst) > 0 {
+        println!("[Replay] Main webview reloaded, keeping the session running");
+    }
+}
+
+/// State for the frontend to pick up where the previous page left off.
+#[tauri::command]
+pub fn get_replay_snapshot(app: AppHandle) -> ReplaySnapshot {
+    let state = app.state::<ReplayState>();
+    let captions = state.captions.lock().unwrap().iter().cloned().collect();
+    ReplaySnapshot {
+        reloaded: state.loads.load(Ordering::SeqCst) > 1,
+        captions,
+        capture: capture::status(&app),
+        recording: recording::is_recording(&app),
+    }
+}
@@ -0,0 +1,193 @@
+//! Wiping user data, to start over or before handing a machine to someone else.
+//!
+//! A reset takes two calls: `request_reset` describes what a scope would
+//! remove and hands out a short-lived token, and `reset_app` only runs with
+//! that token for the very same scope. Unlike `delete_session` and
+//! `purge_history`, nothing goes through the undo journal: the point is that
+//! the data is gone. The app restarts afterwards when settings or models were
+//! wiped, since both are held in memory.
+//!
+//! There is no OS keychain involved: API keys, passwords and pairing keys live
+//! in the settings file, so clearing credentials blanks those fields.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::util::now_ms;
+use crate::{plugins, recording, scripting, security, settings, startup, storage, sync, usage};
+
+const TRASH_DIR: &str = "trash";
+/// How long a confirmation token stays valid.
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+
+/// What a reset removes; at least one part has to be selected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResetScope {
+    /// Backend settings, the frontend's `config.json`, installed plugins, the
+    /// automation script, data usage totals and the startup record.
+    pub settings: bool,
+    /// Downloaded landmark and language models.
+    pub models: bool,
+    /// Recorded sessions, including ones waiting in the undo trash.
+    pub sessions: bool,
+    /// API keys, passwords and pairing keys, which are kept in the settings.
+    pub credentials: bool,
+}
+
+impl ResetScope {
+    fn is_empty(&self) -> bool {
+        !(self.settings || self.models || self.sessions || self.credentials)
+    }
+}
+
+/// Returned by `request_reset`; `token` has to be passed back to `reset_app`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetConfirmation {
+    pub token: String,
+    pub scope: ResetScope,
+    /// Unix timestamp (ms) after which the token is rejected.
+    pub expires_at: u64,
+    /// What will be removed, for the confirmation dialog.
+    pub summary: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ResetState(Mutex<Option<(String, ResetScope, u64)>>);
+
+fn data_path(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(name))
+        .map_err(|e| e.to_string())
+}
+
+fn remove(path: &Path) -> Result<(), String> {
+    let result = match path.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    };
+    match result {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("Failed to remove {}: {}", path.display(), e))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn count_entries(path: &Path) -> usize {
+    fs::read_dir(path).map(|dir| dir.count()).unwrap_or(0)
+}
+
+fn summarize(app: &AppHandle, scope: ResetScope) -> Result<Vec<String>, String> {
+    let mut summary = Vec::new();
+    if scope.settings {
+        summary.push("All settings, profiles, hotkeys, plugins and the automation script".into());
+    }
+    if scope.models {
+        let count = count_entries(&data_path(app, storage::MODELS_DIR)?);
+        summary.push(format!("{} downloaded model files", count));
+    }
+    if scope.sessions {
+        let count = count_entries(&recording::recordings_dir(app)?);
+        summary.push(format!("{} recorded sessions", count));
+    }
+    if scope.credentials && !scope.settings {
+        summary.push("API keys, passwords and paired devices".into());
+    }
+    Ok(summary)
+}
+
+/// Blanks every secret held in the settings and forgets paired devices.
+fn clear_credentials(app: &AppHandle) -> Result<(), String> {
+    settings::update(app, |s| {
+        s.llm.api_key.clear();
+        s.mqtt.password = None;
+        s.sync.paired.clear();
+        s.relay.interpreters.clear();
+        s.relay.clients.clear();
+    })
+    .map(|_| ())
+}
+
+/// Prepares a reset of `scope`, returning the token `reset_app` needs.
+#[tauri::command]
+pub fn request_reset(
+    app: AppHandle,
+    webview: Webview,
+    scope: ResetScope,
+) -> Result<ResetConfirmation, String> {
+    security::guard(&webview, "request_reset", None)?;
+    if scope.is_empty() {
+        return Err("Nothing selected to reset".into());
+    }
+    if scope.sessions && recording::is_recording(&app) {
+        return Err("Stop the recording before deleting sessions".into());
+    }
+
+    let token = sync::to_hex(&sync::random::<16>()?);
+    let expires_at = now_ms() + TOKEN_TTL.as_millis() as u64;
+    *app.state::<ResetState>().0.lock().unwrap() = Some((token.clone(), scope, expires_at));
+
+    Ok(ResetConfirmation {
+        token,
+        scope,
+        expires_at,
+        summary: summarize(&app, scope)?,
+    })
+}
+
+/// Wipes what `scope` selects. `token` comes from `request_reset` for the same
+/// scope and can be used once.
+#[tauri::command]
+pub fn reset_app(
+    app: AppHandle,
+    webview: Webview,
+    scope: ResetScope,
+    token: String,
+) -> Result<(), String> {
+    security::guard(&webview, "reset_app", None)?;
+    let pending = app.state::<ResetState>().0.lock().unwrap().take();
+    match pending {
+        Some((expected, confirmed, expires_at))
+            if expected == token && confirmed == scope && now_ms() <= expires_at => {}
+        Some((expected, _, _)) if expected == token => {
+            return Err("Confirmation expired or was for a different reset".into());
+        }
+        _ => return Err("Invalid confirmation token".into()),
+    }
+    if scope.sessions && recording::is_recording(&app) {
+        return Err("Stop the recording before deleting sessions".into());
+    }
+
+    if scope.sessions {
+        remove(&recording::recordings_dir(&app)?)?;
+        remove(&data_path(&app, TRASH_DIR)?)?;
+    }
+    if scope.models {
+        remove(&data_path(&app, storage::MODELS_DIR)?)?;
+    }
+    if scope.settings {
+        // Settings are rewritten from memory until the restart below.
+        remove(&data_path(&app, storage::FRONTEND_CONFIG_FILE)?)?;
+        remove(&plugins::plugins_dir(&app)?)?;
+        remove(&scripting::script_path(&app)?)?;
+        remove(&usage::usage_path(&app)?)?;
+        remove(&startup::record_path(&app).map_err(|e| e.to_string())?)?;
+        settings::delete_file(&app)?;
+    } else if scope.credentials {
+        clear_credentials(&app)?;
+    }
+
+    println!("[Reset] Reset {:?}", scope);
+    let _ = app.emit("app:reset", scope);
+    if scope.settings || scope.models {
+        app.restart();
+    }
+    Ok(())
+}
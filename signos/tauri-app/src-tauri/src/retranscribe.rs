@@ -0,0 +1,274 @@
+use std::io::Cursor;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use hound::{WavReader, WavWriter};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::recording::{self, Segment};
+use crate::retry::{self, Failure};
+use crate::security;
+use crate::usage::{self, Integration};
+use crate::util::now_ms;
+use crate::{profiles, proxy, redaction, watchdog};
+
+/// Audio is uploaded in chunks so long sessions stay under the request size limit.
+const CHUNK_SECONDS: u32 = 5 * 60;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const METERED_POLL: Duration = Duration::from_secs(30);
+/// Attempts per chunk upload, including the first.
+const CHUNK_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Queued,
+    /// Waiting for the connection to stop being metered.
+    Deferred,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub session_id: String,
+    pub status: JobStatus,
+    /// Chunks transcribed so far, out of `total_chunks`.
+    pub completed_chunks: u32,
+    pub total_chunks: u32,
+    pub error: Option<String>,
+}
+
+/// Response of the worker's `POST /transcribe` route.
+#[derive(Deserialize)]
+struct TranscribeResponse {
+    segments: Vec<TranscribeSegment>,
+}
+
+#[derive(Deserialize)]
+pub struct TranscribeSegment {
+    /// Seconds from the start of the chunk.
+    pub start: f64,
+    pub text: String,
+}
+
+pub struct RetranscribeQueue {
+    jobs: Mutex<Vec<Job>>,
+    queue: Mutex<Sender<String>>,
+}
+
+/// Updates the job and emits `retranscribe:progress` with it.
+fn update_job(app: &AppHandle, session_id: &str, f: impl FnOnce(&mut Job)) {
+    let queue = app.state::<RetranscribeQueue>();
+    let mut jobs = queue.jobs.lock().unwrap();
+    if let Some(job) = jobs.iter_mut().find(|job| job.session_id == session_id) {
+        f(job);
+        let _ = app.emit("retranscribe:progress", &*job);
+    }
+}
+
+pub fn encode_chunk(spec: hound::WavSpec, samples: &[i16]) -> Result<Vec<u8>, String> {
+    let mut bytes = Cursor::new(Vec::new());
+    let mut writer = WavWriter::new(&mut bytes, spec).map_err(|e| e.to_string())?;
+    for &sample in samples {
+        writer.write_sample(sample).map_err(|e| e.to_string())?;
+    }
+    writer.finalize().map_err(|e| e.to_string())?;
+    Ok(bytes.into_inner())
+}
+
+pub fn agent(app: &AppHandle) -> ureq::Agent {
+    proxy::agent_config(app)
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into()
+}
+
+/// Sends one WAV file to the worker's batch route, retrying transient failures.
+pub fn transcribe(
+    app: &AppHandle,
+    agent: &ureq::Agent,
+    language: &str,
+    wav: &[u8],
+) -> Result<Vec<TranscribeSegment>, String> {
+    let url = profiles::worker_endpoint(app, "transcribe");
+    let response = retry::call(app, Integration::Retranscription, CHUNK_ATTEMPTS, || {
+        agent
+            .post(&url)
+            .query("language", language)
+            .header("Content-Type", "audio/wav")
+            .send(wav)
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(Failure::from)
+    })?;
+    usage::record(
+        app,
+        Integration::Retranscription,
+        wav.len() as u64,
+        response.len() as u64,
+    );
+
+    let response: TranscribeResponse =
+        serde_json::from_str(&response).map_err(|e| e.to_string())?;
+    Ok(response.segments)
+}
+
+fn run_job(app: &AppHandle, session_id: &str) -> Result<(), String> {
+    let mut session = recording::load_session(app, session_id)?;
+    if session.ended_at.is_none() {
+        return Err("Session is still being recorded".into());
+    }
+
+    let mut reader =
+        WavReader::open(recording::audio_path(app, session_id)?).map_err(|e| e.to_string())?;
+    let spec = reader.spec();
+    let samples: Vec<i16> = reader
+        .samples::<i16>()
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let chunk_len = (spec.sample_rate * CHUNK_SECONDS) as usize;
+    let chunks: Vec<&[i16]> = samples.chunks(chunk_len).collect();
+    update_job(app, session_id, |job| {
+        job.status = JobStatus::Running;
+        job.total_chunks = chunks.len() as u32;
+    });
+
+    let agent = agent(app);
+    let mut segments = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let body = encode_chunk(spec, chunk)?;
+        let chunk_segments = transcribe(app, &agent, &session.language, &body)?;
+
+        let chunk_offset_ms = index as u64 * CHUNK_SECONDS as u64 * 1000;
+        segments.extend(chunk_segments.into_iter().map(|segment| {
+            let offset_ms = chunk_offset_ms + (segment.start * 1000.0) as u64;
+            Segment {
+                text: redaction::redact(app, segment.text.trim()).into_owned(),
+                offset_ms,
+                timestamp: session.started_at + offset_ms,
+                speaker: None,
+                language: None,
+                source: None,
+                original_text: None,
+                corrected_at: None,
+            }
+        }));
+
+        update_job(app, session_id, |job| {
+            job.completed_chunks = index as u32 + 1
+        });
+    }
+
+    // Whisper does not diarize, so keep the speaker of the closest live segment.
+    for segment in &mut segments {
+        segment.speaker = session
+            .segments
+            .iter()
+            .min_by_key(|live| live.offset_ms.abs_diff(segment.offset_ms))
+            .and_then(|live| live.speaker);
+    }
+
+    session.segments = segments;
+    session.retranscribed_at = Some(now_ms());
+    recording::save_session(app, &session)
+}
+
+/// Fails the job a panic interrupted, which would otherwise stay running for good.
+fn fail_running(app: &AppHandle) {
+    let queue = app.state::<RetranscribeQueue>();
+    let mut jobs = queue.jobs.lock().unwrap();
+    for job in jobs
+        .iter_mut()
+        .filter(|job| job.status == JobStatus::Running)
+    {
+        job.status = JobStatus::Failed;
+        job.error = Some("Re-transcription crashed".into());
+        let _ = app.emit("retranscribe:progress", &*job);
+    }
+}
+
+/// Starts the background worker that processes queued sessions one at a time.
+pub fn init(app: &AppHandle) {
+    let (tx, rx) = mpsc::channel::<String>();
+    app.manage(RetranscribeQueue {
+        jobs: Mutex::default(),
+        queue: Mutex::new(tx),
+    });
+
+    watchdog::spawn_supervised_with(app, "retranscribe", fail_running, move |app| {
+        for session_id in rx.iter() {
+            // Uploads are large, so they wait out a metered connection.
+            if usage::is_metered(app) {
+                update_job(app, &session_id, |job| job.status = JobStatus::Deferred);
+                while usage::is_metered(app) {
+                    thread::sleep(METERED_POLL);
+                }
+            }
+            println!("[Retranscribe] Processing session {}", session_id);
+
+            match run_job(app, &session_id) {
+                Ok(()) => update_job(app, &session_id, |job| job.status = JobStatus::Done),
+                Err(e) => {
+                    eprintln!("[Retranscribe] Session {} failed: {}", session_id, e);
+                    update_job(app, &session_id, |job| {
+                        job.status = JobStatus::Failed;
+                        job.error = Some(e);
+                    });
+                }
+            }
+        }
+    });
+}
+
+/// Queues a stored session to be re-transcribed with the worker's batch model.
+#[tauri::command]
+pub fn retranscribe_session(app: AppHandle, webview: Webview, id: String) -> Result<Job, String> {
+    security::guard(&webview, "retranscribe_session", Some(&id))?;
+    // Fail early on unknown sessions rather than in the background.
+    recording::load_session(&app, &id)?;
+
+    let queue = app.state::<RetranscribeQueue>();
+    let mut jobs = queue.jobs.lock().unwrap();
+    if let Some(job) = jobs.iter().find(|job| {
+        job.session_id == id
+            && matches!(
+                job.status,
+                JobStatus::Queued | JobStatus::Deferred | JobStatus::Running
+            )
+    }) {
+        return Ok(job.clone());
+    }
+
+    let job = Job {
+        session_id: id.clone(),
+        status: JobStatus::Queued,
+        completed_chunks: 0,
+        total_chunks: 0,
+        error: None,
+    };
+    jobs.retain(|job| job.session_id != id);
+    jobs.push(job.clone());
+
+    queue
+        .queue
+        .lock()
+        .unwrap()
+        .send(id)
+        .map_err(|e| e.to_string())?;
+    Ok(job)
+}
+
+#[tauri::command]
+pub fn list_retranscribe_jobs(app: AppHandle) -> Vec<Job> {
+    app.state::<RetranscribeQueue>()
+        .jobs
+        .lock()
+        .unwrap()
+        .clone()
+}
@@ -0,0 +1,187 @@
+//! Retries with exponential backoff, and a circuit breaker per integration.
+//!
+//! A call that still fails after its retries counts against the integration.
+//! Once `FAILURE_THRESHOLD` calls in a row have failed the breaker trips: calls
+//! fail fast until `COOLDOWN` has passed, then one call is let through to probe
+//! the provider again. Only transient errors (timeouts, connection failures,
+//! 429 and 5xx responses) are retried or counted.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::usage::Integration;
+
+const BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(8);
+const FAILURE_THRESHOLD: u32 = 3;
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+pub enum Failure {
+    /// Worth retrying, e.g. a timeout or a 503.
+    Transient(String),
+    /// The request itself is wrong; retrying won't help.
+    Permanent(String),
+}
+
+impl From<ureq::Error> for Failure {
+    fn from(error: ureq::Error) -> Self {
+        let transient = match &error {
+            ureq::Error::StatusCode(status) => *status == 429 || *status >= 500,
+            ureq::Error::Io(_)
+            | ureq::Error::Timeout(_)
+            | ureq::Error::HostNotFound
+            | ureq::Error::ConnectionFailed
+            | ureq::Error::ConnectProxyFailed(_)
+            | ureq::Error::Protocol(_)
+            | ureq::Error::BodyStalled => true,
+            _ => false,
+        };
+        match transient {
+            true => Self::Transient(error.to_string()),
+            false => Self::Permanent(error.to_string()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Breaker {
+    failures: u32,
+    open_until: Option<Instant>,
+    /// A probe call is in flight after the cooldown.
+    probing: bool,
+}
+
+#[derive(Default)]
+pub struct RetryState(Mutex<HashMap<Integration, Breaker>>);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Degraded<'a> {
+    integration: Integration,
+    error: &'a str,
+    retry_in_ms: u64,
+}
+
+fn admit(app: &AppHandle, integration: Integration) -> Result<(), String> {
+    let state = app.state::<RetryState>();
+    let mut breakers = state.0.lock().unwrap();
+    let breaker = breakers.entry(integration).or_default();
+
+    match breaker.open_until {
+        Some(until) if breaker.probing || Instant::now() < until => Err(format!(
+            "{:?} is temporarily unavailable after repeated failures",
+            integration
+        )),
+        Some(_) => {
+            breaker.probing = true;
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+fn succeeded(app: &AppHandle, integration: Integration) {
+    let recovered = {
+        let state = app.state::<RetryState>();
+        let mut breakers = state.0.lock().unwrap();
+        let breaker = breakers.entry(integration).or_default();
+        breaker.failures = 0;
+        breaker.probing = false;
+        breaker.open_until.take().is_some()
+    };
+    if recovered {
+        println!("[Retry] {:?} recovered", integration);
+        let _ = app.emit("integrations:recovered", integration);
+    }
+}
+
+fn failed(app: &AppHandle, integration: Integration, error: &str) {
+    let tripped = {
+        let state = app.state::<RetryState>();
+        let mut breakers = state.0.lock().unwrap();
+        let breaker = breakers.entry(integration).or_default();
+        breaker.failures += 1;
+        breaker.probing = false;
+        let tripped = breaker.failures >= FAILURE_THRESHOLD;
+        if tripped {
+            breaker.open_until = Some(Instant::now() + COOLDOWN);
+        }
+        tripped
+    };
+    if tripped {
+        eprintln!(
+            "[Retry] {:?} degraded, pausing for {}s: {}",
+            integration,
+            COOLDOWN.as_secs(),
+            error
+        );
+        let _ = app.emit(
+            "integrations:degraded",
+            Degraded {
+                integration,
+                error,
+                retry_in_ms: COOLDOWN.as_millis() as u64,
+            },
+        );
+    }
+}
+
+/// Doubles per attempt up to `MAX_DELAY`, with up to 50% random jitter so
+/// callers that failed together don't retry together.
+fn backoff(attempt: u32) -> Duration {
+    let delay = BASE_DELAY
+        .saturating_mul(1 << attempt.min(16))
+        .min(MAX_DELAY);
+    let jitter = getrandom::u32().unwrap_or(0) % (delay.as_millis() as u32 / 2 + 1);
+    delay + Duration::from_millis(jitter as u64)
+}
+
+/// Runs `f` up to `attempts` times behind the integration's breaker.
+pub fn call<T>(
+    app: &AppHandle,
+    integration: Integration,
+    attempts: u32,
+    mut f: impl FnMut() -> Result<T, Failure>,
+) -> Result<T, String> {
+    admit(app, integration)?;
+
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => {
+                succeeded(app, integration);
+                return Ok(value);
+            }
+            // The provider answered, so it counts as healthy.
+            Err(Failure::Permanent(e)) => {
+                succeeded(app, integration);
+                return Err(e);
+            }
+            Err(Failure::Transient(_)) if attempt < attempts => {
+                thread::sleep(backoff(attempt - 1));
+                attempt += 1;
+            }
+            Err(Failure::Transient(e)) => {
+                failed(app, integration, &e);
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Integrations whose breaker is currently open.
+#[tauri::command]
+pub fn get_degraded_integrations(app: AppHandle) -> Vec<Integration> {
+    let state = app.state::<RetryState>();
+    let breakers = state.0.lock().unwrap();
+    breakers
+        .iter()
+        .filter(|(_, breaker)| breaker.open_until.is_some())
+        .map(|(integration, _)| *integration)
+        .collect()
+}
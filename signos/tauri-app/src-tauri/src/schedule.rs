@@ -0,0 +1,283 @@
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, Timelike};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{capture, panel, settings};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScheduleSettings {
+    pub enabled: bool,
+    pub rules: Vec<ScheduleRule>,
+}
+
+/// A single rule, e.g. `{ "name": "Work start", "cron": "0 9 * * 1-5", "action": "startCapture" }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleRule {
+    pub name: String,
+    /// Standard 5-field cron expression: minute hour day-of-month month day-of-week.
+    pub cron: String,
+    pub action: ScheduleAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduleAction {
+    ShowPanel,
+    HidePanel,
+    StartCapture,
+    StopCapture,
+}
+
+#[derive(Clone, Serialize)]
+struct ScheduleFired<'a> {
+    rule: &'a str,
+    action: ScheduleAction,
+}
+
+/// Parsed cron expression, one bitmask per field.
+#[derive(Debug, Clone, Copy)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    dom_any: bool,
+    dow_any: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("expected 5 fields, got {}", fields.len()));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7)?;
+        // Both 0 and 7 mean Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            dom_any: fields[2].starts_with('*'),
+            dow_any: fields[4].starts_with('*'),
+        })
+    }
+
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        let bit = |mask: u64, value: u32| mask & (1 << value) != 0;
+
+        let dom = bit(self.days_of_month, time.day());
+        let dow = bit(self.days_of_week, time.weekday().num_days_from_sunday());
+
+        // Cron semantics: if both day fields are restricted, either may match.
+        let day_matches = match (self.dom_any, self.dow_any) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        };
+
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && day_matches
+    }
+}
+
+/// Parses one cron field (`*`, `5`, `1-5`, `*/15`, `1,3,5`) into a bitmask.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("step must be greater than 0".into());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            (value, value)
+        };
+
+        if start > end {
+            return Err(format!("invalid range '{}'", range));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("invalid value '{}'", value))?;
+    if parsed < min || parsed > max {
+        return Err(format!("{} is out of range {}-{}", parsed, min, max));
+    }
+    Ok(parsed)
+}
+
+fn fire(app: &AppHandle, rule: &ScheduleRule) {
+    println!("[Schedule] Firing '{}' ({:?})", rule.name, rule.action);
+
+    let result = match rule.action {
+        ScheduleAction::ShowPanel => panel::show(app),
+        ScheduleAction::HidePanel => panel::hide(app),
+        ScheduleAction::StartCapture => capture::request_start(app),
+        ScheduleAction::StopCapture => capture::request_stop(app),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Schedule] Rule '{}' failed: {}", rule.name, e);
+    }
+
+    let _ = app.emit(
+        "schedule:fired",
+        ScheduleFired {
+            rule: &rule.name,
+            action: rule.action,
+        },
+    );
+}
+
+fn tick(app: &AppHandle, now: &DateTime<Local>) {
+    let schedule = settings::get(app).schedule;
+    if !schedule.enabled {
+        return;
+    }
+
+    for rule in &schedule.rules {
+        match CronExpr::parse(&rule.cron) {
+            Ok(expr) if expr.matches(now) => fire(app, rule),
+            Ok(_) => {}
+            Err(e) => eprintln!(
+                "[Schedule] Invalid cron '{}' in '{}': {}",
+                rule.cron, rule.name, e
+            ),
+        }
+    }
+}
+
+/// Starts the scheduler thread, which evaluates the rules once per minute.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || loop {
+        // Wake up right after the next minute boundary.
+        let now = Local::now();
+        thread::sleep(Duration::from_secs(60 - now.second() as u64));
+
+        tick(&app, &Local::now());
+    });
+}
+
+/// Lets the settings UI validate an expression before saving it.
+#[tauri::command]
+pub fn validate_cron(expr: String) -> Result<(), String> {
+    CronExpr::parse(&expr).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        // October 2026: the 11th is a Sunday.
+        Local
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn bits(values: &[u32]) -> u64 {
+        values.iter().fold(0, |mask, value| mask | 1 << value)
+    }
+
+    #[test]
+    fn parses_fields() {
+        assert_eq!(parse_field("5", 0, 59), Ok(bits(&[5])));
+        assert_eq!(parse_field("1-5", 0, 59), Ok(bits(&[1, 2, 3, 4, 5])));
+        assert_eq!(parse_field("1,3,5", 0, 59), Ok(bits(&[1, 3, 5])));
+        assert_eq!(parse_field("*/15", 0, 59), Ok(bits(&[0, 15, 30, 45])));
+        assert_eq!(parse_field("10-20/5", 0, 59), Ok(bits(&[10, 15, 20])));
+        assert_eq!(
+            parse_field("*", 1, 12),
+            Ok(bits(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert!(parse_field("*/0", 0, 59).is_err());
+        assert!(parse_field("60", 0, 59).is_err());
+        assert!(parse_field("0", 1, 31).is_err());
+        assert!(parse_field("5-1", 0, 59).is_err());
+        assert!(parse_field("a", 0, 59).is_err());
+        assert!(CronExpr::parse("0 9 * *").is_err());
+        assert!(CronExpr::parse("0 9 * * * *").is_err());
+    }
+
+    #[test]
+    fn matches_weekdays() {
+        let expr = CronExpr::parse("0 9 * * 1-5").unwrap();
+        assert!(expr.matches(&at(12, 9, 0)));
+        assert!(expr.matches(&at(16, 9, 0)));
+        assert!(!expr.matches(&at(12, 9, 1)));
+        assert!(!expr.matches(&at(11, 9, 0)));
+        assert!(!expr.matches(&at(17, 9, 0)));
+    }
+
+    #[test]
+    fn treats_seven_as_sunday() {
+        let sunday = CronExpr::parse("30 8 * * 7").unwrap();
+        assert!(sunday.matches(&at(11, 8, 30)));
+        assert!(sunday.matches(&at(18, 8, 30)));
+
+        // Saturday through Sunday, running over the end of the week.
+        let weekend = CronExpr::parse("0 10 * * 6-7").unwrap();
+        assert!(weekend.matches(&at(17, 10, 0)));
+        assert!(weekend.matches(&at(18, 10, 0)));
+        assert!(!weekend.matches(&at(12, 10, 0)));
+    }
+
+    #[test]
+    fn either_restricted_day_field_matches() {
+        // The 15th (a Thursday), or any Monday.
+        let expr = CronExpr::parse("0 9 15 * 1").unwrap();
+        assert!(expr.matches(&at(15, 9, 0)));
+        assert!(expr.matches(&at(12, 9, 0)));
+        assert!(!expr.matches(&at(17, 9, 0)));
+
+        // With the weekday left open, only the day of the month counts.
+        let expr = CronExpr::parse("0 9 15 * *").unwrap();
+        assert!(expr.matches(&at(15, 9, 0)));
+        assert!(!expr.matches(&at(12, 9, 0)));
+    }
+}
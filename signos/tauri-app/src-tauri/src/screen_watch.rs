@@ -0,0 +1,461 @@
+//! Watches a screen rectangle and captions text that appears in it.
+//!
+//! The region is captured at a low rate and only OCR'd when it visibly
+//! changed. Lines that weren't there on the previous pass go into the caption
+//! stream under their own source label, so apps whose text the overlay can't
+//! otherwise reach are captioned like any other input.
+
+// Frames are never captured where there is no platform backend.
+#![cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::captions::{self, Caption};
+use crate::util::now_ms;
+use crate::{secure_input, settings};
+
+const MIN_INTERVAL_MS: u64 = 250;
+/// Side of the grayscale grid compared between captures.
+const FINGERPRINT_SIZE: usize = 32;
+/// Mean difference (0-255) below which the region counts as unchanged,
+/// so a blinking cursor doesn't trigger OCR.
+const CHANGE_THRESHOLD: f64 = 1.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScreenWatchSettings {
+    /// Last region picked by the user.
+    pub region: Option<ScreenRegion>,
+    pub interval_ms: u64,
+    /// Caption source label for recognized text.
+    pub source: String,
+}
+
+impl Default for ScreenWatchSettings {
+    fn default() -> Self {
+        Self {
+            region: None,
+            interval_ms: 1000,
+            source: "Screen".into(),
+        }
+    }
+}
+
+/// Rectangle in screen coordinates: physical pixels on Windows, points on macOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenWatchStatus {
+    pub active: bool,
+    pub region: Option<ScreenRegion>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScreenText<'a> {
+    lines: &'a [String],
+    timestamp: u64,
+}
+
+/// Stop flag of the running watcher.
+#[derive(Default)]
+pub struct ScreenWatchState(Mutex<Option<Arc<AtomicBool>>>);
+
+/// A captured region, `channels` bytes per pixel.
+pub struct Frame {
+    width: usize,
+    height: usize,
+    channels: usize,
+    pixels: Vec<u8>,
+    /// The PNG the pixels were decoded from, handed to Vision as is.
+    #[cfg(target_os = "macos")]
+    encoded: Vec<u8>,
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+    use windows::Media::Ocr::OcrEngine;
+    use windows::Storage::Streams::DataWriter;
+    use windows::Win32::Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC,
+        GetDIBits, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+        SRCCOPY,
+    };
+
+    use super::{Frame, ScreenRegion};
+
+    pub fn capture(region: &ScreenRegion) -> Result<Frame, String> {
+        let (width, height) = (region.width as i32, region.height as i32);
+        let mut pixels = vec![0u8; region.width as usize * region.height as usize * 4];
+
+        unsafe {
+            let screen = GetDC(None);
+            let dc = CreateCompatibleDC(Some(screen));
+            let bitmap = CreateCompatibleBitmap(screen, width, height);
+            let previous = SelectObject(dc, bitmap.into());
+
+            let copied = BitBlt(
+                dc,
+                0,
+                0,
+                width,
+                height,
+                Some(screen),
+                region.x,
+                region.y,
+                SRCCOPY,
+            );
+
+            let mut info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    // Negative height: rows top-down.
+                    biHeight: -height,
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let rows = GetDIBits(
+                dc,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr().cast()),
+                &mut info,
+                DIB_RGB_COLORS,
+            );
+
+            SelectObject(dc, previous);
+            let _ = DeleteObject(bitmap.into());
+            let _ = DeleteDC(dc);
+            ReleaseDC(None, screen);
+
+            copied.map_err(|e| e.to_string())?;
+            if rows == 0 {
+                return Err("Failed to read the captured region".into());
+            }
+        }
+
+        Ok(Frame {
+            width: region.width as usize,
+            height: region.height as usize,
+            channels: 4,
+            pixels,
+        })
+    }
+
+    pub fn recognize(frame: &Frame) -> Result<Vec<String>, String> {
+        let writer = DataWriter::new().map_err(|e| e.to_string())?;
+        writer
+            .WriteBytes(&frame.pixels)
+            .map_err(|e| e.to_string())?;
+        let buffer = writer.DetachBuffer().map_err(|e| e.to_string())?;
+
+        let bitmap = SoftwareBitmap::CreateCopyFromBuffer(
+            &buffer,
+            BitmapPixelFormat::Bgra8,
+            frame.width as i32,
+            frame.height as i32,
+        )
+        .map_err(|e| e.to_string())?;
+
+        let engine = OcrEngine::TryCreateFromUserProfileLanguages()
+            .map_err(|_| "No OCR language is installed".to_string())?;
+        let result = engine
+            .RecognizeAsync(&bitmap)
+            .and_then(|operation| operation.get())
+            .map_err(|e| e.to_string())?;
+
+        let mut lines = Vec::new();
+        for line in result.Lines().map_err(|e| e.to_string())? {
+            if let Ok(text) = line.Text() {
+                lines.push(text.to_string());
+            }
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::io::Cursor;
+    use std::process::Command;
+
+    use objc2::rc::autoreleasepool;
+    use objc2::AllocAnyThread;
+    use objc2_foundation::{NSArray, NSData, NSDictionary};
+    use objc2_vision::{
+        VNImageRequestHandler, VNRecognizeTextRequest, VNRequest, VNRequestTextRecognitionLevel,
+    };
+
+    use super::{Frame, ScreenRegion};
+
+    /// Needs the Screen Recording permission; without it only the wallpaper is captured.
+    pub fn capture(region: &ScreenRegion) -> Result<Frame, String> {
+        let path = std::env::temp_dir().join("signos-screen-watch.png");
+        let status = Command::new("screencapture")
+            .arg("-x")
+            .arg(format!(
+                "-R{},{},{},{}",
+                region.x, region.y, region.width, region.height
+            ))
+            .args(["-t", "png"])
+            .arg(&path)
+            .status()
+            .map_err(|e| e.to_string())?;
+        if !status.success() {
+            return Err(format!("screencapture exited with {}", status));
+        }
+
+        let encoded = std::fs::read(&path).map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(&path);
+
+        let mut decoder = png::Decoder::new(Cursor::new(&encoded[..]));
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap_or(0)];
+        let info = reader.next_frame(&mut pixels).map_err(|e| e.to_string())?;
+        pixels.truncate(info.buffer_size());
+
+        Ok(Frame {
+            width: info.width as usize,
+            height: info.height as usize,
+            channels: info.color_type.samples(),
+            pixels,
+            encoded,
+        })
+    }
+
+    pub fn recognize(frame: &Frame) -> Result<Vec<String>, String> {
+        autoreleasepool(|_| {
+            let data = NSData::with_bytes(&frame.encoded);
+            let handler = VNImageRequestHandler::initWithData_options(
+                VNImageRequestHandler::alloc(),
+                &data,
+                &NSDictionary::new(),
+            );
+
+            let request = VNRecognizeTextRequest::new();
+            request.setRecognitionLevel(VNRequestTextRecognitionLevel::Accurate);
+            let base: &VNRequest = &request;
+            handler
+                .performRequests_error(&NSArray::from_slice(&[base]))
+                .map_err(|e| e.localizedDescription().to_string())?;
+
+            Ok(request
+                .results()
+                .map(|results| {
+                    results
+                        .iter()
+                        .filter_map(|observation| observation.topCandidates(1).firstObject())
+                        .map(|text| text.string().to_string())
+                        .collect()
+                })
+                .unwrap_or_default())
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::{Frame, ScreenRegion};
+
+    const UNSUPPORTED: &str = "Screen watching is not supported on this platform";
+
+    pub fn capture(_region: &ScreenRegion) -> Result<Frame, String> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub fn recognize(_frame: &Frame) -> Result<Vec<String>, String> {
+        Err(UNSUPPORTED.into())
+    }
+}
+
+/// Downscales the frame to a small grayscale grid.
+fn fingerprint(frame: &Frame) -> Vec<u8> {
+    let mut grid = Vec::with_capacity(FINGERPRINT_SIZE * FINGERPRINT_SIZE);
+    for row in 0..FINGERPRINT_SIZE {
+        let y = row * frame.height / FINGERPRINT_SIZE;
+        for column in 0..FINGERPRINT_SIZE {
+            let x = column * frame.width / FINGERPRINT_SIZE;
+            let offset = (y * frame.width + x) * frame.channels;
+            let pixel = frame
+                .pixels
+                .get(offset..offset + frame.channels.min(3))
+                .unwrap_or(&[0]);
+            let sum: u32 = pixel.iter().map(|&c| c as u32).sum();
+            grid.push((sum / pixel.len() as u32) as u8);
+        }
+    }
+    grid
+}
+
+fn difference(a: &[u8], b: &[u8]) -> f64 {
+    let total: u64 = a.iter().zip(b).map(|(a, b)| a.abs_diff(*b) as u64).sum();
+    total as f64 / a.len().max(1) as f64
+}
+
+fn watch(app: &AppHandle, region: ScreenRegion, stopped: &AtomicBool) -> Result<(), String> {
+    let mut previous: Option<Vec<u8>> = None;
+    let mut previous_lines: Vec<String> = Vec::new();
+
+    while !stopped.load(Ordering::Relaxed) {
+        let config = settings::get(app).screen_watch;
+        thread::sleep(Duration::from_millis(
+            config.interval_ms.max(MIN_INTERVAL_MS),
+        ));
+        if stopped.load(Ordering::Relaxed) {
+            break;
+        }
+
+        // Don't read the screen while a password is being typed.
+        if secure_input::is_active(app) {
+            previous = None;
+            continue;
+        }
+
+        let frame = platform::capture(&region)?;
+        let print = fingerprint(&frame);
+        if previous
+            .as_ref()
+            .is_some_and(|previous| difference(previous, &print) < CHANGE_THRESHOLD)
+        {
+            continue;
+        }
+        previous = Some(print);
+
+        let lines: Vec<String> = match platform::recognize(&frame) {
+            Ok(lines) => lines
+                .into_iter()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            Err(e) => {
+                eprintln!("[ScreenWatch] OCR failed: {}", e);
+                continue;
+            }
+        };
+
+        let new: Vec<String> = lines
+            .iter()
+            .filter(|line| !previous_lines.contains(line))
+            .cloned()
+            .collect();
+        previous_lines = lines;
+        if new.is_empty() {
+            continue;
+        }
+
+        let timestamp = now_ms();
+        captions::push(
+            app,
+            Caption {
+                text: new.join(" "),
+                is_final: true,
+                timestamp,
+                latency_ms: None,
+                language: None,
+                speaker: None,
+                source: Some(config.source),
+            },
+        );
+        let _ = app.emit(
+            "screen-watch:text",
+            ScreenText {
+                lines: &new,
+                timestamp,
+            },
+        );
+    }
+    Ok(())
+}
+
+fn is_active(app: &AppHandle) -> bool {
+    app.state::<ScreenWatchState>().0.lock().unwrap().is_some()
+}
+
+/// Starts watching `region` (or the last one) and remembers it (`screen-watch:state`).
+#[tauri::command]
+pub fn start_screen_watch(
+    app: AppHandle,
+    region: Option<ScreenRegion>,
+) -> Result<ScreenWatchStatus, String> {
+    let region = region
+        .or(settings::get(&app).screen_watch.region)
+        .ok_or("Pick a screen region to watch first")?;
+    if region.width == 0 || region.height == 0 {
+        return Err("The region is empty".into());
+    }
+    // Fails early, e.g. on unsupported platforms or without permission.
+    platform::capture(&region)?;
+    settings::update(&app, |s| s.screen_watch.region = Some(region))?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    {
+        let state = app.state::<ScreenWatchState>();
+        let mut running = state.0.lock().unwrap();
+        if let Some(previous) = running.replace(stopped.clone()) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let handle = app.clone();
+    thread::spawn(move || {
+        if let Err(e) = watch(&handle, region, &stopped) {
+            eprintln!("[ScreenWatch] Stopped: {}", e);
+        }
+        let state = handle.state::<ScreenWatchState>();
+        let mut running = state.0.lock().unwrap();
+        if running
+            .as_ref()
+            .is_some_and(|flag| Arc::ptr_eq(flag, &stopped))
+        {
+            *running = None;
+            drop(running);
+            let _ = handle.emit("screen-watch:state", get_screen_watch(handle.clone()));
+        }
+    });
+
+    println!("[ScreenWatch] Watching {:?}", region);
+    let status = get_screen_watch(app.clone());
+    let _ = app.emit("screen-watch:state", &status);
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn stop_screen_watch(app: AppHandle) -> ScreenWatchStatus {
+    let running = app.state::<ScreenWatchState>().0.lock().unwrap().take();
+    if let Some(stopped) = running {
+        stopped.store(true, Ordering::Relaxed);
+    }
+    let status = get_screen_watch(app.clone());
+    let _ = app.emit("screen-watch:state", &status);
+    status
+}
+
+#[tauri::command]
+pub fn get_screen_watch(app: AppHandle) -> ScreenWatchStatus {
+    ScreenWatchStatus {
+        active: is_active(&app),
+        region: settings::get(&app).screen_watch.region,
+    }
+}
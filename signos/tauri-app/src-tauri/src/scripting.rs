@@ -0,0 +1,253 @@
+//! User automation scripts written in Rhai.
+//!
+//! The script lives in `<app data>/automation.rhai` and may define any of:
+//! - `on_transcript(text, is_final)`
+//! - `on_focus_change(app)`, with the name of the newly frontmost app
+//! - `on_hotkey(action)`
+//!
+//! Scripts can only reach the app through the registered API: `show()`,
+//! `hide()`, `toggle()`, `notify(title, body)` and `http_post(url, body)`,
+//! which returns the HTTP status (0 if the request failed). Hooks run one at a
+//! time on a dedicated thread, with an operation limit per call.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use rhai::{Dynamic, Engine, FuncArgs, Scope, AST};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener, Manager, Webview};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::captions::Caption;
+use crate::focus::FocusChanged;
+use crate::retry::{self, Failure};
+use crate::usage::{self, Integration};
+use crate::{panel, proxy, security, settings, storage};
+
+const SCRIPT_FILE: &str = "automation.rhai";
+const MAX_OPERATIONS: u64 = 1_000_000;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Hooks waiting for a busy script; more are dropped rather than queued
+/// without bound, since a slow `http_post` holds up every caption.
+const HOOK_BACKLOG: usize = 64;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScriptingSettings {
+    pub enabled: bool,
+}
+
+enum Hook {
+    Transcript { text: String, is_final: bool },
+    FocusChange(String),
+    Hotkey(String),
+    Reload,
+}
+
+pub struct ScriptHost {
+    hooks: Mutex<SyncSender<Hook>>,
+}
+
+pub fn script_path(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(SCRIPT_FILE))
+        .map_err(|e| e.to_string())
+}
+
+fn http_post(app: &AppHandle, agent: &ureq::Agent, url: &str, body: &str) -> i64 {
+    // A single attempt: scripts run on the caption path and must not stall it,
+    // but the breaker still makes calls to an unreachable host fail fast.
+    let result = retry::call(app, Integration::Scripts, 1, || {
+        match agent
+            .post(url)
+            .header("Content-Type", "application/json")
+            .send(body)
+        {
+            Ok(response) => Ok((
+                response.status().as_u16(),
+                response.body().content_length().unwrap_or(0),
+            )),
+            Err(ureq::Error::StatusCode(status)) => Ok((status, 0)),
+            Err(e) => Err(Failure::from(e)),
+        }
+    });
+
+    match result {
+        Ok((status, received)) => {
+            usage::record(app, Integration::Scripts, body.len() as u64, received);
+            status as i64
+        }
+        Err(e) => {
+            eprintln!("[Scripting] http_post to {} failed: {}", url, e);
+            0
+        }
+    }
+}
+
+fn create_engine(app: &AppHandle) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_string_size(64 * 1024);
+    engine.on_print(|text| println!("[Scripting] {}", text));
+
+    let handle = app.clone();
+    engine.register_fn("show", move || {
+        let _ = panel::show(&handle);
+    });
+    let handle = app.clone();
+    engine.register_fn("hide", move || {
+        let _ = panel::hide(&handle);
+    });
+    let handle = app.clone();
+    engine.register_fn("toggle", move || {
+        let _ = panel::toggle(&handle);
+    });
+
+    let handle = app.clone();
+    engine.register_fn("notify", move |title: &str, body: &str| {
+        if let Err(e) = handle
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+        {
+            eprintln!("[Scripting] notify failed: {}", e);
+        }
+    });
+
+    let agent: ureq::Agent = proxy::agent_config(app)
+        .timeout_global(Some(HTTP_TIMEOUT))
+        .build()
+        .into();
+    let handle = app.clone();
+    engine.register_fn("http_post", move |url: &str, body: &str| {
+        http_post(&handle, &agent, url, body)
+    });
+
+    engine
+}
+
+/// Compiles the script and runs its top level, so it can set up globals.
+fn load(app: &AppHandle, engine: &Engine, scope: &mut Scope) -> Option<AST> {
+    if !settings::get(app).scripting.enabled {
+        return None;
+    }
+
+    let path = script_path(app).ok()?;
+    let source = fs::read_to_string(&path).ok()?;
+
+    let result = engine
+        .compile(&source)
+        .map_err(|e| e.to_string())
+        .and_then(|ast| {
+            engine
+                .run_ast_with_scope(scope, &ast)
+                .map(|_| ast)
+                .map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(ast) => {
+            println!("[Scripting] Loaded {}", path.display());
+            Some(ast)
+        }
+        Err(e) => {
+            eprintln!("[Scripting] Failed to load {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn call(engine: &Engine, scope: &mut Scope, ast: &AST, name: &str, args: impl FuncArgs) {
+    // Hooks are optional, so only call the ones the script defines.
+    if !ast.iter_functions().any(|f| f.name == name) {
+        return;
+    }
+
+    if let Err(e) = engine.call_fn::<Dynamic>(scope, ast, name, args) {
+        eprintln!("[Scripting] {} failed: {}", name, e);
+    }
+}
+
+fn send(app: &AppHandle, hook: Hook) {
+    if let Some(host) = app.try_state::<ScriptHost>() {
+        if let Err(TrySendError::Full(_)) = host.hooks.lock().unwrap().try_send(hook) {
+            eprintln!("[Scripting] Script is falling behind, dropping an event");
+        }
+    }
+}
+
+pub fn on_transcript(app: &AppHandle, caption: &Caption) {
+    send(
+        app,
+        Hook::Transcript {
+            text: caption.text.clone(),
+            is_final: caption.is_final,
+        },
+    );
+}
+
+/// Starts the script thread and routes backend events to its hooks.
+pub fn init(app: &AppHandle) {
+    let (tx, rx) = mpsc::sync_channel(HOOK_BACKLOG);
+    app.manage(ScriptHost {
+        hooks: Mutex::new(tx),
+    });
+
+    let handle = app.clone();
+    thread::spawn(move || {
+        let engine = create_engine(&handle);
+        let mut scope = Scope::new();
+        let mut ast = load(&handle, &engine, &mut scope);
+
+        for hook in rx {
+            if let Hook::Reload = hook {
+                scope = Scope::new();
+                ast = load(&handle, &engine, &mut scope);
+                continue;
+            }
+
+            let Some(ast) = &ast else {
+                continue;
+            };
+
+            match hook {
+                Hook::Transcript { text, is_final } => {
+                    call(&engine, &mut scope, ast, "on_transcript", (text, is_final))
+                }
+                Hook::FocusChange(app) => call(&engine, &mut scope, ast, "on_focus_change", (app,)),
+                Hook::Hotkey(action) => call(&engine, &mut scope, ast, "on_hotkey", (action,)),
+                Hook::Reload => {}
+            }
+        }
+    });
+
+    let handle = app.clone();
+    app.listen_any("focus:changed", move |event| {
+        if let Ok(focus) = serde_json::from_str::<FocusChanged>(event.payload()) {
+            send(&handle, Hook::FocusChange(focus.app));
+        }
+    });
+
+    // Emitted by the hotkey manager with the name of the triggered action.
+    let handle = app.clone();
+    app.listen_any("hotkey:pressed", move |event| {
+        if let Ok(action) = serde_json::from_str::<String>(event.payload()) {
+            send(&handle, Hook::Hotkey(action));
+        }
+    });
+}
+
+/// Re-reads the script, e.g. after the user edited it or toggled scripting.
+#[tauri::command]
+pub fn reload_scripts(app: AppHandle, webview: Webview) -> Result<(), String> {
+    security::guard(&webview, "reload_scripts", None)?;
+    send(&app, Hook::Reload);
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Backs off while the user types a password in another app.
+//!
+//! The frontmost app's focused element is read through the accessibility API.
+//! While it is a secure text field, screen OCR and selected-text translation
+//! are suppressed, and capture can optionally be paused. Whatever was paused
+//! here is resumed once focus leaves the field.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{capture, settings};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SecureInputSettings {
+    pub enabled: bool,
+    /// Also pause capture while a password field is focused.
+    pub pause_capture: bool,
+}
+
+impl Default for SecureInputSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pause_capture: false,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SecureInputState {
+    active: AtomicBool,
+    /// Capture was paused by us rather than by the user.
+    paused_capture: AtomicBool,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+    use std::ptr::{self, NonNull};
+
+    use objc2_core_foundation::{CFRetained, CFString, CFType};
+
+    const AX_SUCCESS: i32 = 0;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> *const c_void;
+        fn AXUIElementCopyAttributeValue(
+            element: *const c_void,
+            attribute: &CFString,
+            value: *mut *const c_void,
+        ) -> i32;
+    }
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> u8;
+    }
+
+    fn retained(value: *const c_void) -> Option<CFRetained<CFType>> {
+        NonNull::new(value as *mut CFType).map(|value| unsafe { CFRetained::from_raw(value) })
+    }
+
+    fn copy_attribute(element: &CFType, attribute: &str) -> Option<CFRetained<CFType>> {
+        let mut value = ptr::null();
+        let status = unsafe {
+            AXUIElementCopyAttributeValue(
+                element as *const CFType as *const c_void,
+                &CFString::from_str(attribute),
+                &mut value,
+            )
+        };
+        match status {
+            AX_SUCCESS => retained(value),
+            _ => None,
+        }
+    }
+
+    pub fn secure_field_focused() -> bool {
+        let Some(system) = retained(unsafe { AXUIElementCreateSystemWide() }) else {
+            return false;
+        };
+        match copy_attribute(&system, "AXFocusedUIElement") {
+            Some(focused) => copy_attribute(&focused, "AXSubrole")
+                .and_then(|subrole| subrole.downcast::<CFString>().ok())
+                .is_some_and(|subrole| subrole.to_string() == "AXSecureTextField"),
+            // Without Accessibility access the focused element can't be read;
+            // password fields also turn on secure event input, which can.
+            None => unsafe { IsSecureEventInputEnabled() != 0 },
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation};
+
+    pub fn secure_field_focused() -> bool {
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let Ok(automation) =
+                CoCreateInstance::<_, IUIAutomation>(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+            else {
+                return false;
+            };
+            automation
+                .GetFocusedElement()
+                .and_then(|element| element.CurrentIsPassword())
+                .is_ok_and(|password| password.as_bool())
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn secure_field_focused() -> bool {
+        false
+    }
+}
+
+/// Whether a password field is focused and sensitive features should stay off.
+pub fn is_active(app: &AppHandle) -> bool {
+    app.state::<SecureInputState>()
+        .active
+        .load(Ordering::SeqCst)
+}
+
+fn changed(app: &AppHandle, active: bool) {
+    let state = app.state::<SecureInputState>();
+    state.active.store(active, Ordering::SeqCst);
+    println!(
+        "[SecureInput] Password field {}",
+        if active { "focused" } else { "left" }
+    );
+    let _ = app.emit("secure-input:changed", active);
+
+    if active {
+        let status = capture::status(app);
+        if settings::get(app).secure_input.pause_capture && status.active && !status.paused {
+            state.paused_capture.store(true, Ordering::SeqCst);
+            if let Err(e) = capture::set_paused(app, true) {
+                eprintln!("[SecureInput] Failed to pause capture: {}", e);
+            }
+        }
+    } else if state.paused_capture.swap(false, Ordering::SeqCst) {
+        // Left alone if the user resumed or stopped in the meantime.
+        if capture::status(app).paused {
+            if let Err(e) = capture::set_paused(app, false) {
+                eprintln!("[SecureInput] Failed to resume capture: {}", e);
+            }
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let active = settings::get(&app).secure_input.enabled && platform::secure_field_focused();
+        if active != is_active(&app) {
+            changed(&app, active);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_secure_input_active(app: AppHandle) -> bool {
+    is_active(&app)
+}
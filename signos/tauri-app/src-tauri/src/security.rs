@@ -0,0 +1,167 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime, Url, Webview};
+
+use crate::panel::MAIN_WINDOW;
+use crate::util::now_ms;
+
+/// Entries kept in memory; older ones are dropped first.
+const AUDIT_CAPACITY: usize = 500;
+
+/// Per-command limits for expensive or sensitive commands: (command, calls, window in ms).
+const RATE_LIMITS: &[(&str, usize, u64)] = &[
+    ("install_plugin", 5, 60_000),
+    ("remove_plugin", 10, 60_000),
+    ("grant_folder_access", 5, 60_000),
+    ("reset_settings", 2, 60_000),
+    ("request_reset", 5, 60_000),
+    ("reset_app", 2, 60_000),
+    ("retranscribe_session", 3, 60_000),
+    ("share_file", 10, 60_000),
+    ("reload_scripts", 10, 60_000),
+    ("delete_session", 30, 60_000),
+    ("purge_history", 2, 60_000),
+    ("create_backup", 5, 60_000),
+    ("restore_backup", 2, 60_000),
+    ("feed_audio_file", 10, 60_000),
+];
+
+/// A privileged command invocation, allowed or not.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub command: String,
+    /// Unix timestamp (ms).
+    pub timestamp: u64,
+    pub window: String,
+    pub origin: String,
+    /// What the command acts on, e.g. a path or a session id.
+    pub detail: Option<String>,
+    pub allowed: bool,
+    /// Why the call was rejected.
+    pub reason: Option<String>,
+}
+
+#[derive(Default)]
+pub struct SecurityState {
+    audit: Mutex<VecDeque<AuditEntry>>,
+    /// Recent call times per rate-limited command.
+    calls: Mutex<HashMap<&'static str, VecDeque<u64>>>,
+}
+
+fn origin(url: &Url) -> String {
+    match url.host_str() {
+        Some(host) => format!("{}://{}", url.scheme(), host),
+        None => url.scheme().to_string(),
+    }
+}
+
+/// Only the bundled frontend (or the dev server in debug builds) may call privileged commands.
+fn is_trusted_origin<R: Runtime>(app: &AppHandle<R>, url: &Url) -> bool {
+    let bundled = match url.scheme() {
+        "tauri" => url.host_str() == Some("localhost"),
+        // Windows and Android serve the app from http(s)://tauri.localhost.
+        "http" | "https" => url.host_str() == Some("tauri.localhost"),
+        _ => false,
+    };
+
+    let dev_server = cfg!(debug_assertions)
+        && app
+            .config()
+            .build
+            .dev_url
+            .as_ref()
+            .is_some_and(|dev| origin(dev) == origin(url));
+
+    bundled || dev_server
+}
+
+fn check_rate_limit(state: &SecurityState, command: &str, now: u64) -> Result<(), String> {
+    let Some(&(name, max_calls, window_ms)) =
+        RATE_LIMITS.iter().find(|(name, _, _)| *name == command)
+    else {
+        return Ok(());
+    };
+
+    let mut calls = state.calls.lock().unwrap();
+    let recent = calls.entry(name).or_default();
+    while recent
+        .front()
+        .is_some_and(|&t| now.saturating_sub(t) >= window_ms)
+    {
+        recent.pop_front();
+    }
+
+    if recent.len() >= max_calls {
+        return Err(format!(
+            "'{}' is limited to {} calls per {}s",
+            command,
+            max_calls,
+            window_ms / 1000
+        ));
+    }
+    recent.push_back(now);
+    Ok(())
+}
+
+/// Validates the caller of a privileged command, applies its rate limit and
+/// records the attempt in the audit log. Call first thing in the command.
+pub fn guard<R: Runtime>(
+    webview: &Webview<R>,
+    command: &str,
+    detail: Option<&str>,
+) -> Result<(), String> {
+    let app = webview.app_handle();
+    let state = app.state::<SecurityState>();
+    let now = now_ms();
+
+    let url = webview.url().ok();
+    let window = webview.label().to_string();
+
+    let result = if window != MAIN_WINDOW {
+        Err(format!(
+            "'{}' may not be called from window '{}'",
+            command, window
+        ))
+    } else if !url.as_ref().is_some_and(|url| is_trusted_origin(app, url)) {
+        Err(format!("'{}' may not be called from this origin", command))
+    } else {
+        check_rate_limit(&state, command, now)
+    };
+
+    if let Err(reason) = &result {
+        eprintln!("[Security] Rejected {}: {}", command, reason);
+    }
+
+    let entry = AuditEntry {
+        command: command.to_string(),
+        timestamp: now,
+        window,
+        origin: url.as_ref().map(origin).unwrap_or_default(),
+        detail: detail.map(str::to_string),
+        allowed: result.is_ok(),
+        reason: result.as_ref().err().cloned(),
+    };
+
+    let mut audit = state.audit.lock().unwrap();
+    if audit.len() == AUDIT_CAPACITY {
+        audit.pop_front();
+    }
+    audit.push_back(entry);
+
+    result
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(SecurityState::default());
+}
+
+/// Privileged command invocations, newest first.
+#[tauri::command]
+pub fn get_security_audit(app: AppHandle) -> Vec<AuditEntry> {
+    let state = app.state::<SecurityState>();
+    let audit = state.audit.lock().unwrap();
+    audit.iter().rev().cloned().collect()
+}
@@ -0,0 +1,262 @@
+//! End-to-end check of the transcription pipeline with a known recording.
+//!
+//! The sample is fed through the same converter as the webview capture path,
+//! checked for voice activity, and sent to the worker for transcription. The
+//! report lists each stage so "captions are empty" can be narrowed down to
+//! the step that broke.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use hound::{SampleFormat, WavSpec};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::audio_feed::{self, Decoded};
+use crate::capture_format::{Resampler, TARGET_SAMPLE_RATE};
+use crate::{retranscribe, transcription};
+
+/// Resource holding the sample, next to a `.txt` file with what it says.
+const SAMPLE_RESOURCE: &str = "self-test/sample.wav";
+pub const SAMPLE_LANGUAGE: &str = "en";
+/// Input buffer size of the webview's audio worklet.
+const CAPTURE_FRAMES: usize = 1024;
+/// 30 ms at 16 kHz.
+const VAD_FRAME: usize = 480;
+/// About -40 dBFS.
+const VAD_THRESHOLD: f64 = 330.0;
+const MIN_VOICED_RATIO: f64 = 0.1;
+/// Share of the expected words the transcript has to contain.
+const MIN_WORD_MATCH: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Stage {
+    Sample,
+    Capture,
+    Vad,
+    Stt,
+    Match,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageReport {
+    pub stage: Stage,
+    pub passed: bool,
+    pub detail: String,
+    pub duration_ms: u64,
+}
+
+/// Stages run in order and stop at the first failure.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub stages: Vec<StageReport>,
+    pub expected: String,
+    pub transcript: String,
+    pub missing_words: Vec<String>,
+}
+
+pub struct Sample {
+    pub audio: Decoded,
+    pub expected: String,
+}
+
+impl SelfTestReport {
+    /// Records a stage and returns its value, or `None` if it failed.
+    fn run<T>(
+        &mut self,
+        stage: Stage,
+        f: impl FnOnce() -> Result<(T, String), String>,
+    ) -> Option<T> {
+        let started = Instant::now();
+        let result = f();
+        let (value, passed, detail) = match result {
+            Ok((value, detail)) => (Some(value), true, detail),
+            Err(detail) => (None, false, detail),
+        };
+        self.stages.push(StageReport {
+            stage,
+            passed,
+            detail,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+        value
+    }
+}
+
+pub fn sample_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .resource_dir()
+        .map(|dir| dir.join(SAMPLE_RESOURCE))
+        .map_err(|e| e.to_string())
+}
+
+pub fn load_sample(path: &Path) -> Result<Sample, String> {
+    let expected = std::fs::read_to_string(path.with_extension("txt"))
+        .map_err(|e| format!("Missing expected transcript: {}", e))?;
+    Ok(Sample {
+        audio: audio_feed::decode(path)?,
+        expected: expected.trim().to_string(),
+    })
+}
+
+/// Plays the sample through the capture converter, like a virtual input device.
+pub fn capture(app: &AppHandle, sample: &Sample) -> Vec<i16> {
+    let chunk_samples = transcription::inference(app).chunk_samples();
+    let channels = sample.audio.channels.max(1);
+    let mut resampler = Resampler::new(
+        sample.audio.sample_rate as f64,
+        channels,
+        u32::MAX,
+        chunk_samples,
+    );
+
+    sample
+        .audio
+        .samples
+        .chunks(CAPTURE_FRAMES * channels as usize)
+        .flat_map(|buffer| resampler.push(buffer))
+        .flatten()
+        .collect::<Vec<u8>>()
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect()
+}
+
+/// Share of frames whose RMS level is above the speech threshold.
+fn voiced_ratio(pcm: &[i16]) -> f64 {
+    let frames = pcm.chunks_exact(VAD_FRAME);
+    let total = frames.len().max(1) as f64;
+    let voiced = frames
+        .filter(|frame| {
+            let energy: f64 = frame.iter().map(|&s| (s as f64).powi(2)).sum();
+            (energy / frame.len() as f64).sqrt() > VAD_THRESHOLD
+        })
+        .count();
+    voiced as f64 / total
+}
+
+pub fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn run(app: &AppHandle) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+
+    let Some(sample) = report.run(Stage::Sample, || {
+        let sample = load_sample(&sample_path(app)?)?;
+        let detail = format!(
+            "{} Hz, {} channel(s), {:.1}s",
+            sample.audio.sample_rate,
+            sample.audio.channels,
+            sample.audio.samples.len() as f64
+                / sample.audio.channels.max(1) as f64
+                / sample.audio.sample_rate as f64
+        );
+        Ok((sample, detail))
+    }) else {
+        return report;
+    };
+    report.expected = sample.expected.clone();
+
+    let Some(pcm) = report.run(Stage::Capture, || {
+        let pcm = capture(app, &sample);
+        match pcm.is_empty() {
+            true => Err("The converter produced no audio".into()),
+            false => {
+                let detail = format!(
+                    "{:.1}s of {} Hz PCM",
+                    pcm.len() as f64 / TARGET_SAMPLE_RATE as f64,
+                    TARGET_SAMPLE_RATE
+                );
+                Ok((pcm, detail))
+            }
+        }
+    }) else {
+        return report;
+    };
+
+    let vad = report.run(Stage::Vad, || {
+        let ratio = voiced_ratio(&pcm);
+        let detail = format!("Speech in {:.0}% of frames", ratio * 100.0);
+        match ratio >= MIN_VOICED_RATIO {
+            true => Ok(((), detail)),
+            false => Err(detail),
+        }
+    });
+    if vad.is_none() {
+        return report;
+    }
+
+    let Some(transcript) = report.run(Stage::Stt, || {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: TARGET_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let wav = retranscribe::encode_chunk(spec, &pcm)?;
+        let agent = retranscribe::agent(app);
+        let segments = retranscribe::transcribe(app, &agent, SAMPLE_LANGUAGE, &wav)?;
+        let transcript = segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+        match transcript.is_empty() {
+            true => Err("The worker returned an empty transcript".into()),
+            false => {
+                let detail = format!("{} segment(s)", segments.len());
+                Ok((transcript, detail))
+            }
+        }
+    }) else {
+        return report;
+    };
+    report.transcript = transcript;
+
+    let heard = words(&report.transcript);
+    let expected = words(&report.expected);
+    report.missing_words = expected
+        .iter()
+        .filter(|word| !heard.contains(word))
+        .cloned()
+        .collect();
+
+    let found = expected.len() - report.missing_words.len();
+    let matched = report.run(Stage::Match, || {
+        let ratio = found as f64 / expected.len().max(1) as f64;
+        let detail = format!("{} of {} expected words", found, expected.len());
+        match ratio >= MIN_WORD_MATCH {
+            true => Ok(((), detail)),
+            false => Err(detail),
+        }
+    });
+    report.passed = matched.is_some();
+    report
+}
+
+/// Runs the bundled sample through capture, VAD and STT and reports each stage.
+#[tauri::command]
+pub async fn run_self_test(app: AppHandle) -> Result<SelfTestReport, String> {
+    let report = tauri::async_runtime::spawn_blocking(move || run(&app))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match report.stages.iter().find(|stage| !stage.passed) {
+        Some(stage) => eprintln!("[SelfTest] Failed at {:?}: {}", stage.stage, stage.detail),
+        None => println!("[SelfTest] Passed"),
+    }
+    Ok(report)
+}
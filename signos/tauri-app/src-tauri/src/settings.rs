@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::announce::AnnouncementSettings;
+use crate::avatar::AvatarSettings;
+use crate::bluetooth::BluetoothSettings;
+use crate::broadcast_delay::BroadcastDelaySettings;
+use crate::calendar::CalendarSettings;
+use crate::camera::CameraSettings;
+use crate::capture_format::CaptureFormatSettings;
+use crate::connectivity::ConnectivitySettings;
+use crate::consent::ConsentSettings;
+use crate::conversation::ConversationSettings;
+use crate::feedback::FeedbackSettings;
+use crate::file_sink::FileSinkSettings;
+use crate::fs_access::FsAccessSettings;
+use crate::fullscreen::FullscreenSettings;
+use crate::gestures::GestureSettings;
+use crate::hot_corners::HotCornerSettings;
+use crate::hotkeys::HotkeySettings;
+use crate::keywords::KeywordSettings;
+use crate::landmarks::LandmarkSettings;
+use crate::layouts::LayoutSettings;
+use crate::llm::LlmSettings;
+use crate::metrics::MetricsSettings;
+use crate::midi::MidiSettings;
+use crate::monitor::MonitorSettings;
+use crate::mqtt::MqttSettings;
+use crate::ndi::NdiSettings;
+use crate::onboarding::OnboardingSettings;
+use crate::osc::OscSettings;
+use crate::overlay::OverlaySettings;
+use crate::power::PowerSettings;
+use crate::profiles::ProfileSettings;
+use crate::proxy::ProxySettings;
+use crate::punctuation::PunctuationSettings;
+use crate::recording::RecordingSettings;
+use crate::redaction::RedactionSettings;
+use crate::relay::RelaySettings;
+use crate::schedule::ScheduleSettings;
+use crate::screen_watch::ScreenWatchSettings;
+use crate::scripting::ScriptingSettings;
+use crate::secure_input::SecureInputSettings;
+use crate::stabilizer::StabilizationSettings;
+use crate::streamdeck::StreamDeckSettings;
+use crate::summary::SummarySettings;
+use crate::sync::SyncSettings;
+use crate::system_captions::SystemCaptionSettings;
+use crate::taskbar::TaskbarSettings;
+use crate::thermal::ThermalSettings;
+use crate::transcription::TranscriptionSettings;
+use crate::usage::DataUsageSettings;
+use crate::viewer::ViewerSettings;
+use crate::{journal, security, startup, storage};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Backend settings, persisted as JSON next to the frontend's `config.json`.
+///
+/// Every section defaults on its own so older files keep loading as new
+/// options are added.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    pub schedule: ScheduleSettings,
+    pub calendar: CalendarSettings,
+    pub profiles: ProfileSettings,
+    pub stream_deck: StreamDeckSettings,
+    pub midi: MidiSettings,
+    pub transcription: TranscriptionSettings,
+    pub osc: OscSettings,
+    pub ndi: NdiSettings,
+    pub keywords: KeywordSettings,
+    pub mqtt: MqttSettings,
+    pub metrics: MetricsSettings,
+    pub scripting: ScriptingSettings,
+    pub recording: RecordingSettings,
+    pub file_sink: FileSinkSettings,
+    pub hotkeys: HotkeySettings,
+    pub taskbar: TaskbarSettings,
+    pub onboarding: OnboardingSettings,
+    pub fs_access: FsAccessSettings,
+    pub overlay: OverlaySettings,
+    pub sync: SyncSettings,
+    pub viewer: ViewerSettings,
+    pub capture_format: CaptureFormatSettings,
+    pub bluetooth: BluetoothSettings,
+    pub monitor: MonitorSettings,
+    pub stabilization: StabilizationSettings,
+    pub punctuation: PunctuationSettings,
+    pub summary: SummarySettings,
+    pub avatar: AvatarSettings,
+    pub camera: CameraSettings,
+    pub landmarks: LandmarkSettings,
+    pub gestures: GestureSettings,
+    pub connectivity: ConnectivitySettings,
+    pub proxy: ProxySettings,
+    pub data_usage: DataUsageSettings,
+    pub screen_watch: ScreenWatchSettings,
+    pub system_captions: SystemCaptionSettings,
+    pub announcements: AnnouncementSettings,
+    pub feedback: FeedbackSettings,
+    pub redaction: RedactionSettings,
+    pub consent: ConsentSettings,
+    pub secure_input: SecureInputSettings,
+    pub power: PowerSettings,
+    pub thermal: ThermalSettings,
+    pub llm: LlmSettings,
+    pub conversation: ConversationSettings,
+    pub relay: RelaySettings,
+    pub broadcast_delay: BroadcastDelaySettings,
+    pub fullscreen: FullscreenSettings,
+    pub hot_corners: HotCornerSettings,
+    pub layouts: LayoutSettings,
+}
+
+pub struct SettingsState(pub Mutex<Settings>);
+
+fn settings_path(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(storage::data_dir(app)?.join(SETTINGS_FILE))
+}
+
+/// Reads settings from disk, falling back to defaults if the file is
+/// missing or unreadable.
+pub fn load(app: &AppHandle) -> Settings {
+    let Ok(path) = settings_path(app) else {
+        return Settings::default();
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            eprintln!("[Settings] Failed to parse {}: {}", path.display(), e);
+            Settings::default()
+        }),
+        Err(_) => Settings::default(),
+    }
+}
+
+fn save(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    // Safe mode runs on defaults, which must not replace the user's file.
+    if startup::is_safe_mode(app) {
+        return Ok(());
+    }
+
+    let path = settings_path(app).map_err(|e| e.to_string())?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+pub fn delete_file(app: &AppHandle) -> Result<(), String> {
+    let path = settings_path(app).map_err(|e| e.to_string())?;
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Returns a snapshot of the current settings.
+pub fn get(app: &AppHandle) -> Settings {
+    app.state::<SettingsState>().0.lock().unwrap().clone()
+}
+
+/// Applies `f` to the settings, persists the result and notifies the frontend.
+pub fn update(app: &AppHandle, f: impl FnOnce(&mut Settings)) -> Result<Settings, String> {
+    let settings = {
+        let state = app.state::<SettingsState>();
+        let mut settings = state.0.lock().unwrap();
+        f(&mut settings);
+        settings.clone()
+    };
+
+    save(app, &settings)?;
+    let _ = app.emit("settings:changed", &settings);
+
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> Settings {
+    get(&app)
+}
+
+#[tauri::command]
+pub fn set_settings(
+    app: AppHandle,
+    webview: Webview,
+    settings: Settings,
+) -> Result<Settings, String> {
+    security::guard(&webview, "set_settings", None)?;
+    let previous = get(&app);
+    let updated = update(&app, |current| *current = settings)?;
+    journal::record_settings(&app, previous, updated.clone());
+    Ok(updated)
+}
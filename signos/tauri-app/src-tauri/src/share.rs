@@ -0,0 +1,181 @@
+use std::path::Path;
+use std::sync::mpsc;
+
+use tauri::{AppHandle, Manager, Webview, WebviewWindow};
+
+use crate::panel::MAIN_WINDOW;
+use crate::{fs_access, security};
+
+/// What to hand to the OS share UI.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+enum ShareItem {
+    Text(String),
+    File(String),
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::{NSSharingServicePicker, NSView};
+    use objc2_foundation::{NSArray, NSRectEdge, NSString, NSURL};
+    use tauri::WebviewWindow;
+
+    use super::{on_main_thread, ShareItem};
+
+    /// Shows NSSharingServicePicker anchored to the panel.
+    pub fn share(window: &WebviewWindow, item: ShareItem) -> Result<(), String> {
+        on_main_thread(window, move |window| show_picker(window, item))
+    }
+
+    fn show_picker(window: &WebviewWindow, item: ShareItem) -> Result<(), String> {
+        let mtm = MainThreadMarker::new().ok_or("Sharing must run on the main thread")?;
+
+        let object: Retained<AnyObject> = match item {
+            ShareItem::Text(text) => NSString::from_str(&text).into(),
+            ShareItem::File(path) => NSURL::fileURLWithPath(&NSString::from_str(&path)).into(),
+        };
+        let items = NSArray::from_retained_slice(&[object]);
+
+        let view = window.ns_view().map_err(|e| e.to_string())? as *const NSView;
+        let view = unsafe { view.as_ref() }.ok_or("The panel has no view")?;
+
+        let picker = unsafe { NSSharingServicePicker::initWithItems(mtm.alloc(), &items) };
+        picker.showRelativeToRect_ofView_preferredEdge(view.bounds(), view, NSRectEdge::MinY);
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::cell::RefCell;
+
+    use tauri::WebviewWindow;
+    use windows::core::{factory, AgileReference, Interface, HSTRING};
+    use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+    use windows::Foundation::TypedEventHandler;
+    use windows::Storage::{IStorageItem, StorageFile};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::IDataTransferManagerInterop;
+    use windows_collections::IIterable;
+
+    use super::{on_main_thread, ShareItem};
+
+    thread_local! {
+        /// Handler registered for the previous share, removed before the next one.
+        static HANDLER: RefCell<Option<(DataTransferManager, i64)>> = const { RefCell::new(None) };
+    }
+
+    /// Shows the Windows share flyout for the panel.
+    pub fn share(window: &WebviewWindow, item: ShareItem) -> Result<(), String> {
+        // Resolve the file off the UI thread, which must not block on WinRT operations.
+        let file = match &item {
+            ShareItem::File(path) => Some(
+                StorageFile::GetFileFromPathAsync(&HSTRING::from(path.as_str()))
+                    .and_then(|operation| operation.get())
+                    .and_then(|file| AgileReference::new(&file))
+                    .map_err(|e| e.to_string())?,
+            ),
+            ShareItem::Text(_) => None,
+        };
+
+        on_main_thread(window, move |window| show_flyout(window, item, file))
+    }
+
+    fn show_flyout(
+        window: &WebviewWindow,
+        item: ShareItem,
+        file: Option<AgileReference<StorageFile>>,
+    ) -> Result<(), String> {
+        let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+
+        let result = (|| -> windows::core::Result<()> {
+            let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+            let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+            if let Some((manager, token)) = HANDLER.take() {
+                let _ = manager.RemoveDataRequested(token);
+            }
+
+            let token = manager.DataRequested(&TypedEventHandler::new(
+                move |_, args: windows::core::Ref<DataRequestedEventArgs>| {
+                    let Some(args) = args.as_ref() else {
+                        return Ok(());
+                    };
+                    let data = args.Request()?.Data()?;
+                    data.Properties()?.SetTitle(&HSTRING::from("Signos"))?;
+
+                    match (&item, &file) {
+                        (ShareItem::Text(text), _) => data.SetText(&HSTRING::from(text.as_str())),
+                        (ShareItem::File(_), Some(file)) => {
+                            let item = file.resolve()?.cast::<IStorageItem>()?;
+                            let items: IIterable<IStorageItem> = vec![Some(item)].into();
+                            data.SetStorageItemsReadOnly(&items)
+                        }
+                        (ShareItem::File(_), None) => Ok(()),
+                    }
+                },
+            ))?;
+            HANDLER.set(Some((manager, token)));
+
+            unsafe { interop.ShowShareUIForWindow(hwnd) }
+        })();
+
+        result.map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use tauri::WebviewWindow;
+
+    use super::ShareItem;
+
+    pub fn share(_window: &WebviewWindow, _item: ShareItem) -> Result<(), String> {
+        Err("Sharing is not supported on this platform".into())
+    }
+}
+
+/// Runs `f` on the main thread and waits for its result.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn on_main_thread<T: Send + 'static>(
+    window: &WebviewWindow,
+    f: impl FnOnce(&WebviewWindow) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let (tx, rx) = mpsc::channel();
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let _ = tx.send(f(&target));
+        })
+        .map_err(|e| e.to_string())?;
+
+    rx.recv().map_err(|e| e.to_string())?
+}
+
+fn share(app: &AppHandle, item: ShareItem) -> Result<(), String> {
+    let window = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("Main window not found")?;
+    platform::share(&window, item)
+}
+
+// Async so the main thread is free to show the share UI while we wait on it.
+#[tauri::command]
+pub async fn share_text(app: AppHandle, text: String) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("Nothing to share".into());
+    }
+    share(&app, ShareItem::Text(text))
+}
+
+#[tauri::command]
+pub async fn share_file(app: AppHandle, webview: Webview, path: String) -> Result<(), String> {
+    security::guard(&webview, "share_file", Some(&path))?;
+    fs_access::check(&app, Path::new(&path))?;
+    if !Path::new(&path).is_file() {
+        return Err(format!("File not found: {}", path));
+    }
+    share(&app, ShareItem::File(path))
+}
@@ -0,0 +1,91 @@
+//! "Simplify this": plain-language rewrites of captions or selected text.
+//!
+//! The rewrite runs on the selected LLM provider in the background. Its answer
+//! streams back as `llm:token` events tagged with the request id that
+//! `simplify_text` returns, and finishes with `llm:done` or `llm:failed`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::llm::{self, Completion};
+use crate::usage::Integration;
+
+/// Longest text accepted, in characters; about a few minutes of speech.
+const MAX_TEXT_CHARS: usize = 8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SimplifyLevel {
+    /// Plain language that keeps every detail.
+    Plain,
+    /// Short sentences and everyday words.
+    Simple,
+    /// Easy read: one idea per sentence, hard words explained.
+    EasyRead,
+}
+
+#[derive(Default)]
+pub struct SimplifyState {
+    next_id: AtomicU64,
+}
+
+fn instructions(level: SimplifyLevel) -> &'static str {
+    match level {
+        SimplifyLevel::Plain => {
+            "Rewrite the text in plain language. Keep every fact, name and number, \
+             replace jargon with common words and break up long sentences."
+        }
+        SimplifyLevel::Simple => {
+            "Rewrite the text in simple language: short sentences, everyday words, \
+             active voice. Keep the important facts and leave out side remarks."
+        }
+        SimplifyLevel::EasyRead => {
+            "Rewrite the text as easy read: very short sentences with one idea each, \
+             the most common words, and a short explanation in brackets for any word \
+             that is still hard."
+        }
+    }
+}
+
+/// Rewrites `text` into plain language on the selected LLM provider. Returns
+/// the request id the `llm:` events for the rewrite are tagged with.
+#[tauri::command]
+pub fn simplify_text(app: AppHandle, text: String, level: SimplifyLevel) -> Result<String, String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Nothing to simplify".into());
+    }
+    if text.chars().count() > MAX_TEXT_CHARS {
+        return Err(format!(
+            "Text is too long to simplify (at most {} characters)",
+            MAX_TEXT_CHARS
+        ));
+    }
+
+    let next = app
+        .state::<SimplifyState>()
+        .next_id
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+    let request_id = format!("simplify:{}", next);
+    let request = Completion {
+        system: format!(
+            "{} Answer in the language of the text, with the rewrite only.",
+            instructions(level)
+        ),
+        max_tokens: (text.len() as u32 / 2).clamp(200, 2048),
+        prompt: text,
+        temperature: 0.3,
+    };
+
+    let id = request_id.clone();
+    thread::spawn(move || {
+        if llm::complete(&app, &id, Integration::Rewrites, &request).is_ok() {
+            println!("[Simplify] Finished {} ({:?})", id, level);
+        }
+    });
+    Ok(request_id)
+}
@@ -0,0 +1,136 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::settings;
+
+const MAX_PASSES: usize = 10;
+
+/// Holds back partial-result words until they stop changing, so the overlay
+/// only ever appends instead of rewriting mid-sentence. Final results pass
+/// through untouched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StabilizationSettings {
+    pub enabled: bool,
+    /// Consecutive partials a word must survive unchanged before it is shown.
+    pub passes: usize,
+}
+
+impl Default for StabilizationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            passes: 2,
+        }
+    }
+}
+
+/// Partials seen for the segment in progress.
+#[derive(Default)]
+struct Track {
+    history: VecDeque<Vec<String>>,
+    /// Words already shown, which are never taken back.
+    committed: Vec<String>,
+}
+
+/// One track per capture source, keyed by its label.
+#[derive(Default)]
+pub struct StabilizerState(Mutex<HashMap<Option<String>, Track>>);
+
+/// Words shared by every partial in `history`.
+fn stable_prefix(history: &VecDeque<Vec<String>>) -> &[String] {
+    let Some(first) = history.front() else {
+        return &[];
+    };
+    let len = history
+        .iter()
+        .map(|words| first.iter().zip(words).take_while(|(a, b)| a == b).count())
+        .min()
+        .unwrap_or(0);
+    &first[..len]
+}
+
+impl Track {
+    fn push(&mut self, text: &str, passes: usize) -> String {
+        self.history
+            .push_back(text.split_whitespace().map(String::from).collect());
+        while self.history.len() > passes {
+            self.history.pop_front();
+        }
+
+        if self.history.len() == passes {
+            let stable = stable_prefix(&self.history);
+            // A revision of an already shown word is ignored until the final result.
+            if stable.len() > self.committed.len() && stable.starts_with(&self.committed) {
+                self.committed = stable.to_vec();
+            }
+        }
+
+        self.committed.join(" ")
+    }
+}
+
+/// Replaces a partial caption's text with its stable words.
+pub fn apply(app: &AppHandle, caption: &mut Caption) {
+    let config = settings::get(app).stabilization;
+    let state = app.state::<StabilizerState>();
+    let mut tracks = state.0.lock().unwrap();
+
+    if caption.is_final || !config.enabled {
+        tracks.remove(&caption.source);
+        return;
+    }
+
+    let passes = config.passes.clamp(1, MAX_PASSES);
+    caption.text = tracks
+        .entry(caption.source.clone())
+        .or_default()
+        .push(&caption.text, passes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_words_once_they_survive_enough_partials() {
+        let mut track = Track::default();
+        assert_eq!(track.push("hello wor", 2), "");
+        assert_eq!(track.push("hello world", 2), "hello");
+        assert_eq!(track.push("hello world how", 2), "hello world");
+        assert_eq!(track.push("hello world how are", 2), "hello world how");
+    }
+
+    #[test]
+    fn never_takes_back_shown_words() {
+        let mut track = Track::default();
+        track.push("the cat", 2);
+        assert_eq!(track.push("the cat sat", 2), "the cat");
+        // A revision of shown words keeps what is on screen...
+        assert_eq!(track.push("a cat sat", 2), "the cat");
+        assert_eq!(track.push("a cat sat down", 2), "the cat");
+        // ...and so does a shorter partial.
+        assert_eq!(track.push("the", 2), "the cat");
+    }
+
+    #[test]
+    fn one_pass_shows_every_word() {
+        let mut track = Track::default();
+        assert_eq!(track.push("one two", 1), "one two");
+        assert_eq!(track.push("one two three", 1), "one two three");
+    }
+
+    #[test]
+    fn finds_the_shared_prefix() {
+        let history: VecDeque<Vec<String>> = [vec!["a", "b", "c"], vec!["a", "b", "d"]]
+            .into_iter()
+            .map(|words| words.into_iter().map(String::from).collect())
+            .collect();
+        assert_eq!(stable_prefix(&history), ["a", "b"]);
+        assert!(stable_prefix(&VecDeque::new()).is_empty());
+    }
+}
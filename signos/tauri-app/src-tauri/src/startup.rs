@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Webview};
+
+use crate::{cli, recording, security, settings, storage};
+
+const STARTUP_FILE: &str = "startup.json";
+/// Launches that never reached `frontend_ready` before safe mode kicks in.
+const MAX_FAILED_LAUNCHES: u32 = 3;
+
+/// Persisted launch bookkeeping. The counter is bumped at launch and cleared
+/// once the frontend is up, so it only keeps growing while launches crash.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct StartupRecord {
+    failed_launches: u32,
+}
+
+/// Safe mode skips plugins, scripts and integrations and runs on default
+/// settings without saving over the user's file.
+pub struct StartupState {
+    safe_mode: bool,
+    failed_launches: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeMode {
+    failed_launches: u32,
+}
+
+pub fn record_path(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(storage::data_dir(app)?.join(STARTUP_FILE))
+}
+
+fn write_record(app: &AppHandle, record: &StartupRecord) -> Result<(), String> {
+    let path = record_path(app).map_err(|e| e.to_string())?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let json = serde_json::to_string(record).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+pub fn is_safe_mode(app: &AppHandle) -> bool {
+    app.try_state::<StartupState>()
+        .is_some_and(|state| state.safe_mode)
+}
+
+/// Counts this launch as failed until the frontend reports ready. Returns whether
+/// to start in safe mode. Must run before settings are loaded.
+pub fn init(app: &AppHandle) -> bool {
+    let record: StartupRecord = record_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let failed_launches = record.failed_launches;
+    let safe_mode = failed_launches >= MAX_FAILED_LAUNCHES;
+    if safe_mode {
+        eprintln!(
+            "[Startup] {} launches failed in a row, starting in safe mode",
+            failed_launches
+        );
+    }
+
+    let next = StartupRecord {
+        failed_launches: failed_launches + 1,
+    };
+    if let Err(e) = write_record(app, &next) {
+        eprintln!("[Startup] Failed to record launch: {}", e);
+    }
+
+    app.manage(StartupState {
+        safe_mode,
+        failed_launches,
+    });
+    safe_mode
+}
+
+/// Called by the frontend once its listeners are registered, which marks the launch as successful.
+#[tauri::command]
+pub fn frontend_ready(app: AppHandle) {
+    if let Err(e) = write_record(&app, &StartupRecord::default()) {
+        eprintln!("[Startup] Failed to record successful launch: {}", e);
+    }
+
+    let state = app.state::<StartupState>();
+    if state.safe_mode {
+        let _ = app.emit(
+            "startup:safe-mode",
+            SafeMode {
+                failed_launches: state.failed_launches,
+            },
+        );
+    }
+
+    cli::run_pending(&app);
+    recording::offer_resume(&app);
+}
+
+/// Deletes the settings file and restarts, e.g. when the user accepts the safe-mode prompt.
+#[tauri::command]
+pub fn reset_settings(app: AppHandle, webview: Webview) -> Result<(), String> {
+    security::guard(&webview, "reset_settings", None)?;
+    settings::delete_file(&app)?;
+    println!("[Startup] Settings reset, restarting");
+    app.restart()
+}
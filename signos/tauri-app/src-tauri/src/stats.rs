@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::recording::{self, Session};
+
+/// Longest a single segment is assumed to last when estimating talk time.
+const MAX_SEGMENT_MS: u64 = 30_000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+    pub duration_ms: u64,
+    pub word_count: usize,
+    pub words_per_minute: f64,
+    /// Estimated talk time (ms) per speaker id, `"unknown"` when not diarized.
+    pub talk_time_ms: BTreeMap<String, u64>,
+    /// Word count per detected language, `"unknown"` when not reported.
+    pub languages: BTreeMap<String, usize>,
+}
+
+fn compute(session: &Session) -> SessionStats {
+    let duration_ms = session
+        .ended_at
+        .unwrap_or(session.started_at)
+        .saturating_sub(session.started_at);
+
+    let mut word_count = 0;
+    let mut talk_time_ms = BTreeMap::new();
+    let mut languages = BTreeMap::new();
+
+    for (index, segment) in session.segments.iter().enumerate() {
+        let words = segment.text.split_whitespace().count();
+        word_count += words;
+
+        // A segment lasts until the next one starts (or the session ends).
+        let end = session
+            .segments
+            .get(index + 1)
+            .map(|next| next.offset_ms)
+            .unwrap_or(duration_ms);
+        let talk = end.saturating_sub(segment.offset_ms).min(MAX_SEGMENT_MS);
+
+        let speaker = segment
+            .speaker
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "unknown".into());
+        *talk_time_ms.entry(speaker).or_default() += talk;
+
+        let language = segment.language.clone().unwrap_or_else(|| "unknown".into());
+        *languages.entry(language).or_default() += words;
+    }
+
+    let minutes = duration_ms as f64 / 60_000.0;
+    let words_per_minute = if minutes > 0.0 {
+        word_count as f64 / minutes
+    } else {
+        0.0
+    };
+
+    SessionStats {
+        duration_ms,
+        word_count,
+        words_per_minute,
+        talk_time_ms,
+        languages,
+    }
+}
+
+#[tauri::command]
+pub fn get_session_stats(app: AppHandle, id: String) -> Result<SessionStats, String> {
+    recording::load_session(&app, &id).map(|session| compute(&session))
+}
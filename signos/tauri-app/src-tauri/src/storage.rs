@@ -0,0 +1,79 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+
+/// Dropped next to the executable to make the install portable.
+const PORTABLE_FLAG: &str = "portable.flag";
+const PORTABLE_ARG: &str = "--portable";
+/// Folder beside the executable that replaces the app data directory.
+const PORTABLE_DIR: &str = "SignosData";
+/// Written by the frontend's store plugin.
+pub const FRONTEND_CONFIG_FILE: &str = "config.json";
+/// Downloaded landmark and language models.
+pub const MODELS_DIR: &str = "models";
+
+/// Where settings, scripts, plugins and recordings live.
+///
+/// Resolved once at startup: portable installs keep everything beside the
+/// binary (e.g. on a USB stick), others use the OS app data directory.
+pub struct StorageState {
+    portable_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInfo {
+    pub data_dir: String,
+    pub portable: bool,
+}
+
+fn portable_dir() -> Option<PathBuf> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    let requested =
+        env::args().any(|arg| arg == PORTABLE_ARG) || exe_dir.join(PORTABLE_FLAG).is_file();
+    requested.then(|| exe_dir.join(PORTABLE_DIR))
+}
+
+pub fn data_dir(app: &AppHandle) -> tauri::Result<PathBuf> {
+    match &app.state::<StorageState>().portable_dir {
+        Some(dir) => Ok(dir.clone()),
+        None => app.path().app_data_dir(),
+    }
+}
+
+pub fn is_portable(app: &AppHandle) -> bool {
+    app.state::<StorageState>().portable_dir.is_some()
+}
+
+/// Must run before anything reads from the data directory.
+pub fn init(app: &AppHandle) {
+    let portable_dir = portable_dir();
+
+    if let Some(dir) = &portable_dir {
+        println!("[Storage] Portable mode, storing data in {}", dir.display());
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("[Storage] Failed to create {}: {}", dir.display(), e);
+        }
+        // The frontend's config.json lives here too instead of $APPDATA.
+        if let Err(e) = app.fs_scope().allow_file(dir.join(FRONTEND_CONFIG_FILE)) {
+            eprintln!("[Storage] Failed to allow frontend access: {}", e);
+        }
+    }
+
+    app.manage(StorageState { portable_dir });
+}
+
+#[tauri::command]
+pub fn get_storage_info(app: AppHandle) -> Result<StorageInfo, String> {
+    Ok(StorageInfo {
+        data_dir: data_dir(&app)
+            .map_err(|e| e.to_string())?
+            .display()
+            .to_string(),
+        portable: is_portable(&app),
+    })
+}
@@ -0,0 +1,212 @@
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener};
+use tungstenite::handshake::server::{ErrorResponse, Request as Handshake, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Error as WsError, Message};
+
+use crate::{capture, lifecycle, panel, profiles, settings};
+
+const DEFAULT_PORT: u16 = 28190;
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+/// Origin the Stream Deck software loads HTML plugins from. Web pages can reach
+/// loopback too, so any other browser origin is refused.
+const PLUGIN_ORIGIN: &str = "file://";
+/// Backend events that change what the Stream Deck keys display.
+const STATE_EVENTS: [&str; 3] = ["panel:visibility", "capture:state", "profile:changed"];
+
+/// Loopback WebSocket endpoint used by the Stream Deck plugin in `signos/streamdeck/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StreamDeckSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for StreamDeckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// Messages sent by the plugin, e.g. `{ "action": "switchProfile", "profile": "meetings" }`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+enum Request {
+    TogglePanel,
+    StartCapture,
+    StopCapture,
+    ToggleCapture,
+    SwitchProfile { profile: String },
+    GetState,
+}
+
+/// Key-state feedback, pushed on connect and whenever the state changes.
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "state", rename_all = "camelCase")]
+struct KeyState {
+    panel_visible: bool,
+    capturing: bool,
+    profile: String,
+}
+
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+fn snapshot(app: &AppHandle) -> String {
+    let state = KeyState {
+        panel_visible: panel::is_visible(app),
+        capturing: capture::status(app).active,
+        profile: settings::get(app).profiles.active,
+    };
+    serde_json::to_string(&state).unwrap_or_default()
+}
+
+fn broadcast(clients: &Clients, message: &str) {
+    clients
+        .lock()
+        .unwrap()
+        .retain(|client| client.send(message.to_string()).is_ok());
+}
+
+/// Runs a plugin request, returning a direct reply if the request needs one.
+fn handle_request(app: &AppHandle, text: &str) -> Option<String> {
+    let request: Request = match serde_json::from_str(text) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("[StreamDeck] Invalid request '{}': {}", text, e);
+            return None;
+        }
+    };
+
+    let result = match request {
+        Request::TogglePanel => panel::toggle(app).map_err(|e| e.to_string()),
+        Request::ToggleCapture if capture::status(app).active => {
+            capture::request_stop(app).map_err(|e| e.to_string())
+        }
+        Request::StartCapture | Request::ToggleCapture => {
+            capture::request_start(app).map_err(|e| e.to_string())
+        }
+        Request::StopCapture => capture::request_stop(app).map_err(|e| e.to_string()),
+        Request::SwitchProfile { profile } => profiles::switch(app, &profile).map(|_| ()),
+        Request::GetState => return Some(snapshot(app)),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[StreamDeck] Request failed: {}", e);
+    }
+    None
+}
+
+fn handle_client(
+    app: &AppHandle,
+    stream: TcpStream,
+    outgoing: Receiver<String>,
+) -> Result<(), WsError> {
+    // The callback's signature is tungstenite's.
+    #[allow(clippy::result_large_err)]
+    let check_origin = |request: &Handshake, response: Response| match request
+        .headers()
+        .get("origin")
+        .map(|origin| origin.to_str())
+    {
+        None => Ok(response),
+        Some(Ok(PLUGIN_ORIGIN)) => Ok(response),
+        Some(origin) => {
+            eprintln!("[StreamDeck] Refused connection from origin {:?}", origin);
+            let mut refused = ErrorResponse::new(Some("Origin not allowed".into()));
+            *refused.status_mut() = StatusCode::FORBIDDEN;
+            Err(refused)
+        }
+    };
+    let mut ws = tungstenite::accept_hdr(stream, check_origin).map_err(|e| match e {
+        tungstenite::HandshakeError::Failure(e) => e,
+        tungstenite::HandshakeError::Interrupted(_) => WsError::ConnectionClosed,
+    })?;
+
+    // Poll for plugin messages so pushed state updates are not held up by `read`.
+    ws.get_mut().set_read_timeout(Some(READ_TIMEOUT))?;
+    ws.send(Message::text(snapshot(app)))?;
+
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(message) => ws.send(Message::text(message))?,
+                Err(TryRecvError::Empty) => break,
+                // Dropped at exit.
+                Err(TryRecvError::Disconnected) => return ws.close(None),
+            }
+        }
+
+        match ws.read() {
+            Ok(Message::Text(text)) => {
+                if let Some(reply) = handle_request(app, text.as_str()) {
+                    ws.send(Message::text(reply))?;
+                }
+            }
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(WsError::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Starts the Stream Deck endpoint if it is enabled in settings.
+pub fn init(app: &AppHandle) {
+    let config = settings::get(app).stream_deck;
+    if !config.enabled {
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[StreamDeck] Failed to bind port {}: {}", config.port, e);
+            return;
+        }
+    };
+    println!("[StreamDeck] Listening on ws://127.0.0.1:{}", config.port);
+
+    let clients: Clients = Arc::default();
+
+    for event in STATE_EVENTS {
+        let handle = app.clone();
+        let clients = clients.clone();
+        app.listen_any(event, move |_| broadcast(&clients, &snapshot(&handle)));
+    }
+
+    let closed = lifecycle::close_on_exit(app, "streamdeck", &listener);
+    let senders = clients.clone();
+    lifecycle::on_exit(app, "streamdeck", move |_, _| {
+        senders.lock().unwrap().clear()
+    });
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if closed.load(Ordering::SeqCst) {
+                break;
+            }
+            let (tx, rx) = mpsc::channel();
+            clients.lock().unwrap().push(tx);
+
+            let app = app.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_client(&app, stream, rx) {
+                    eprintln!("[StreamDeck] Client disconnected: {}", e);
+                }
+            });
+        }
+    });
+}
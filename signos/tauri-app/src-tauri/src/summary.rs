@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::llm::{self, Completion};
+use crate::recording::{self, Session};
+use crate::settings;
+use crate::usage::Integration;
+use crate::util::now_ms;
+
+/// Words ignored when ranking sentences for the local summary.
+const STOPWORDS: [&str; 48] = [
+    "the", "a", "an", "and", "or", "but", "to", "of", "in", "on", "for", "is", "are", "was", "it",
+    "that", "this", "with", "we", "you", "i", "so", "be", "have", "el", "la", "los", "las", "un",
+    "una", "y", "o", "de", "del", "en", "que", "es", "por", "para", "con", "se", "no", "lo", "al",
+    "yo", "tu", "pero", "como",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SummaryStyle {
+    Brief,
+    Bullets,
+    Detailed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SummaryProvider {
+    /// The LLM provider selected in `llm`.
+    #[default]
+    #[serde(alias = "worker")]
+    Llm,
+    /// Extractive summary built on-device from the highest-scoring segments.
+    Local,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SummarySettings {
+    pub provider: SummaryProvider,
+}
+
+/// Stored in `session.json` with the transcript it was made from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub style: SummaryStyle,
+    pub provider: SummaryProvider,
+    pub text: String,
+    /// Unix timestamp (ms).
+    pub created_at: u64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryDone<'a> {
+    session_id: &'a str,
+    summary: &'a Summary,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryFailed<'a> {
+    session_id: &'a str,
+    error: &'a str,
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+}
+
+/// Picks the segments whose words recur most across the session, in spoken order.
+fn summarize_locally(session: &Session, style: SummaryStyle) -> String {
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    for segment in &session.segments {
+        for word in words(&segment.text) {
+            *frequency.entry(word).or_default() += 1;
+        }
+    }
+
+    let mut scored: Vec<(usize, f64)> = session
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let unique: HashSet<String> = words(&segment.text).collect();
+            let score: usize = unique.iter().map(|word| frequency[word]).sum();
+            (index, score as f64 / (unique.len().max(1) as f64).sqrt())
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let count = match style {
+        SummaryStyle::Brief => 3,
+        SummaryStyle::Bullets => 5,
+        SummaryStyle::Detailed => 10,
+    };
+    let mut picked: Vec<usize> = scored.iter().take(count).map(|(index, _)| *index).collect();
+    picked.sort_unstable();
+
+    let sentences = picked
+        .into_iter()
+        .map(|index| session.segments[index].text.trim());
+    match style {
+        SummaryStyle::Bullets => sentences
+            .map(|text| format!("- {}", text))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => sentences.collect::<Vec<_>>().join(" "),
+    }
+}
+
+fn summarize_with_llm(
+    app: &AppHandle,
+    session: &Session,
+    style: SummaryStyle,
+) -> Result<String, String> {
+    let transcript = session
+        .segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let instructions = match style {
+        SummaryStyle::Brief => "Summarize the meeting transcript in two or three sentences.",
+        SummaryStyle::Bullets => {
+            "Summarize the meeting transcript as a short list of bullet points starting with \"- \"."
+        }
+        SummaryStyle::Detailed => {
+            "Write meeting notes for the transcript: the topics discussed, decisions made and open questions, in short paragraphs."
+        }
+    };
+    let language = match session.language.as_str() {
+        "en" => "English",
+        _ => "the language of the transcript",
+    };
+    let request = Completion {
+        system: format!(
+            "You write concise meeting notes. {} Answer in {} and do not add anything that is not in the transcript.",
+            instructions, language
+        ),
+        prompt: transcript,
+        max_tokens: match style {
+            SummaryStyle::Detailed => 1024,
+            _ => 400,
+        },
+        temperature: 0.3,
+    };
+
+    llm::complete(
+        app,
+        &format!("summary:{}", session.id),
+        Integration::Summaries,
+        &request,
+    )
+}
+
+fn run(app: &AppHandle, id: &str, style: SummaryStyle) -> Result<Summary, String> {
+    let mut session = recording::load_session(app, id)?;
+    if session.segments.is_empty() {
+        return Err("Session has no transcript".into());
+    }
+
+    let provider = settings::get(app).summary.provider;
+    let text = match provider {
+        SummaryProvider::Llm => summarize_with_llm(app, &session, style)?,
+        SummaryProvider::Local => summarize_locally(&session, style),
+    };
+
+    let summary = Summary {
+        style,
+        provider,
+        text,
+        created_at: now_ms(),
+    };
+    session.summary = Some(summary.clone());
+    recording::save_session(app, &session)?;
+    Ok(summary)
+}
+
+/// Summarizes a stored session in the background and saves the result with it.
+/// Completes with `summary:done` or `summary:failed`.
+#[tauri::command]
+pub fn summarize_session(app: AppHandle, id: String, style: SummaryStyle) -> Result<(), String> {
+    let session = recording::load_session(&app, &id)?;
+    if session.ended_at.is_none() {
+        return Err("Session is still being recorded".into());
+    }
+
+    thread::spawn(move || match run(&app, &id, style) {
+        Ok(summary) => {
+            println!("[Summary] Summarized session {}", id);
+            let _ = app.emit(
+                "summary:done",
+                SummaryDone {
+                    session_id: &id,
+                    summary: &summary,
+                },
+            );
+        }
+        Err(e) => {
+            eprintln!("[Summary] Session {} failed: {}", id, e);
+            let _ = app.emit(
+                "summary:failed",
+                SummaryFailed {
+                    session_id: &id,
+                    error: &e,
+                },
+            );
+        }
+    });
+    Ok(())
+}
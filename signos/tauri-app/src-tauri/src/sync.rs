@@ -0,0 +1,1049 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager, State};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::captions::Caption;
+use crate::recording::{self, Session};
+use crate::{capture, lifecycle, panel, settings};
+
+const SERVICE_TYPE: &str = "_signos._tcp.local.";
+const DEFAULT_PORT: u16 = 28191;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// For each step of a handshake, and for any single write.
+pub const IO_TIMEOUT: Duration = Duration::from_secs(30);
+/// An open connection with nothing to send pings this often...
+pub const KEEPALIVE: Duration = Duration::from_secs(20);
+/// ...and one silent for this long is dropped.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long an incoming pair request waits for the user.
+const PAIR_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+const MAX_PENDING_PAIRS: usize = 4;
+/// Connections a listener serves at once; more are closed straight away.
+pub const MAX_CONNECTIONS: usize = 16;
+/// Upper bound for a single frame; a handed-off session is the largest message.
+const MAX_FRAME: usize = 16 * 1024 * 1024;
+
+/// Opt-in LAN sync between Signos instances: discovery over mDNS, pairing by
+/// comparing a 6-digit code, then ChaCha20-Poly1305 with keys derived per
+/// connection from the pairing key.
+///
+/// The device starting a pairing commits to its public key before it sees the
+/// other one, and the code covers both keys, so a device in the middle can't
+/// search for keys that would make the two codes match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Generated on first start.
+    pub device_id: String,
+    pub device_name: String,
+    pub paired: Vec<PairedDevice>,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            device_id: String::new(),
+            device_name: default_device_name(),
+            paired: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairedDevice {
+    pub id: String,
+    pub name: String,
+    /// Hex-encoded key derived during pairing.
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDevice {
+    pub id: String,
+    pub name: String,
+    pub address: Option<String>,
+    pub paired: bool,
+    pub mirroring: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PairRequest {
+    device_id: String,
+    name: String,
+    code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Message {
+    /// Opens a pairing connection with a commitment to the initiator's key,
+    /// `SHA-256(public key || nonce)`. Sent in the clear, like the rest of pairing.
+    Hello {
+        id: String,
+        name: String,
+        commitment: String,
+    },
+    /// Answers `Hello` with the responder's key.
+    PairKey {
+        id: String,
+        name: String,
+        public_key: String,
+    },
+    /// Opens the commitment once the responder's key arrived.
+    Reveal {
+        public_key: String,
+        nonce: String,
+    },
+    /// Opens a connection between paired devices. Sent in the clear, with a
+    /// fresh nonce for the connection's keys.
+    Auth {
+        id: String,
+        nonce: String,
+    },
+    /// Answers `Auth` with the other nonce. Sent in the clear; everything after is encrypted.
+    Challenge {
+        nonce: String,
+    },
+    PairResult {
+        accepted: bool,
+    },
+    /// Keeps an idle connection from timing out.
+    Ping,
+    Caption {
+        caption: Caption,
+    },
+    Handoff {
+        session: Session,
+    },
+}
+
+struct Discovered {
+    name: String,
+    address: SocketAddr,
+}
+
+struct PendingPair {
+    name: String,
+    key: [u8; 32],
+    stream: TcpStream,
+    sealer: Sealer,
+    received: Instant,
+}
+
+#[derive(Default)]
+pub struct SyncState {
+    daemon: Mutex<Option<ServiceDaemon>>,
+    /// Devices seen on the network, by device id.
+    discovered: Mutex<HashMap<String, Discovered>>,
+    /// Incoming pair requests waiting for the user, by device id.
+    pending: Mutex<HashMap<String, PendingPair>>,
+    /// Live caption mirrors, by target device id.
+    mirrors: Mutex<HashMap<String, Sender<Message>>>,
+}
+
+fn default_device_name() -> String {
+    ["COMPUTERNAME", "HOSTNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .unwrap_or_else(|| "Signos".into())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+pub fn random<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0u8; N];
+    getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+fn parse_key(hex: &str) -> Result<[u8; 32], String> {
+    from_hex(hex)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "Invalid public key".into())
+}
+
+fn commitment(public_key: &[u8; 32], nonce: &[u8]) -> String {
+    to_hex(
+        &Sha256::new()
+            .chain_update(public_key)
+            .chain_update(nonce)
+            .finalize(),
+    )
+}
+
+/// Derives the channel key and the code both users compare before accepting.
+/// Both cover the two public keys and the initiator's nonce.
+fn derive(
+    secret: &StaticSecret,
+    initiator_key: &[u8; 32],
+    responder_key: &[u8; 32],
+    nonce: &[u8],
+    initiator: bool,
+) -> ([u8; 32], String) {
+    let peer = match initiator {
+        true => responder_key,
+        false => initiator_key,
+    };
+    let shared = secret.diffie_hellman(&PublicKey::from(*peer));
+    let transcript = [initiator_key.as_slice(), responder_key, nonce].concat();
+
+    let key: [u8; 32] = Sha256::new()
+        .chain_update(b"signos-sync-key")
+        .chain_update(shared.as_bytes())
+        .chain_update(&transcript)
+        .finalize()
+        .into();
+    let digest = Sha256::new()
+        .chain_update(b"signos-sync-code")
+        .chain_update(shared.as_bytes())
+        .chain_update(&transcript)
+        .finalize();
+    let code = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 1_000_000;
+
+    (key, format!("{:06}", code))
+}
+
+pub fn write_frame(stream: &mut TcpStream, bytes: &[u8]) -> Result<(), String> {
+    stream
+        .write_all(&(bytes.len() as u32).to_be_bytes())
+        .and_then(|_| stream.write_all(bytes))
+        .map_err(|e| e.to_string())
+}
+
+pub fn read_frame(stream: &mut TcpStream) -> Result<Vec<u8>, String> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len).map_err(|e| e.to_string())?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME {
+        return Err(format!("Frame of {} bytes is too large", len));
+    }
+
+    let mut bytes = vec![0u8; len];
+    stream.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Sending half of an encrypted connection.
+///
+/// Every connection gets its own keys, derived from the long-lived key and a
+/// nonce from each side, and frames are numbered. A frame recorded on one
+/// connection fails to decrypt on any other, and one replayed or reordered
+/// within a connection is rejected by its number.
+pub struct Sealer {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+/// Receiving half of an encrypted connection; see [`Sealer`].
+pub struct Opener {
+    cipher: ChaCha20Poly1305,
+    next: u64,
+}
+
+fn counter_nonce(counter: u64) -> Nonce {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::from(nonce)
+}
+
+fn direction_key(key: &[u8; 32], nonces: &[u8], direction: &[u8]) -> ChaCha20Poly1305 {
+    let derived: [u8; 32] = Sha256::new()
+        .chain_update(b"signos-channel-key")
+        .chain_update(key)
+        .chain_update(nonces)
+        .chain_update(direction)
+        .finalize()
+        .into();
+    ChaCha20Poly1305::new(&Key::from(derived))
+}
+
+/// Derives the keys of one connection from the long-lived `key` and both
+/// sides' nonces. Each direction has its own key, so both can count from zero.
+pub fn channel_keys(
+    key: &[u8; 32],
+    initiator_nonce: &[u8],
+    responder_nonce: &[u8],
+    initiator: bool,
+) -> (Sealer, Opener) {
+    let nonces = [initiator_nonce, responder_nonce].concat();
+    let outbound = direction_key(key, &nonces, b"initiator");
+    let inbound = direction_key(key, &nonces, b"responder");
+    let (send, receive) = match initiator {
+        true => (outbound, inbound),
+        false => (inbound, outbound),
+    };
+    (
+        Sealer {
+            cipher: send,
+            counter: 0,
+        },
+        Opener {
+            cipher: receive,
+            next: 0,
+        },
+    )
+}
+
+impl Sealer {
+    /// Frames are `counter || ciphertext`, the counter doubling as the nonce.
+    pub fn write(&mut self, stream: &mut TcpStream, plain: &[u8]) -> Result<(), String> {
+        let counter = self.counter;
+        self.counter = counter
+            .checked_add(1)
+            .ok_or("Connection used up its nonces")?;
+        let sealed = self
+            .cipher
+            .encrypt(&counter_nonce(counter), plain)
+            .map_err(|_| "Encryption failed")?;
+
+        write_frame(
+            stream,
+            &[counter.to_be_bytes().as_slice(), &sealed].concat(),
+        )
+    }
+
+    pub fn send(&mut self, stream: &mut TcpStream, message: &impl Serialize) -> Result<(), String> {
+        let plain = serde_json::to_vec(message).map_err(|e| e.to_string())?;
+        self.write(stream, &plain)
+    }
+}
+
+impl Opener {
+    pub fn read(&mut self, stream: &mut TcpStream) -> Result<Vec<u8>, String> {
+        let frame = read_frame(stream)?;
+        if frame.len() < 8 {
+            return Err("Frame too short".into());
+        }
+
+        let (counter, sealed) = frame.split_at(8);
+        let counter = u64::from_be_bytes(counter.try_into().map_err(|_| "Invalid counter")?);
+        if counter != self.next {
+            return Err("Replayed or out-of-order frame".into());
+        }
+        let plain = self
+            .cipher
+            .decrypt(&counter_nonce(counter), sealed)
+            .map_err(|_| "Decryption failed; the pairing may be stale")?;
+        self.next += 1;
+        Ok(plain)
+    }
+
+    pub fn receive<T: DeserializeOwned>(&mut self, stream: &mut TcpStream) -> Result<T, String> {
+        serde_json::from_slice(&self.read(stream)?).map_err(|e| e.to_string())
+    }
+}
+
+pub fn send_plain(stream: &mut TcpStream, message: &impl Serialize) -> Result<(), String> {
+    write_frame(
+        stream,
+        &serde_json::to_vec(message).map_err(|e| e.to_string())?,
+    )
+}
+
+pub fn read_plain<T: DeserializeOwned>(stream: &mut TcpStream) -> Result<T, String> {
+    serde_json::from_slice(&read_frame(stream)?).map_err(|e| e.to_string())
+}
+
+/// Not managed in safe mode, which skips `init`.
+fn state(app: &AppHandle) -> Result<State<'_, SyncState>, String> {
+    app.try_state::<SyncState>()
+        .ok_or_else(|| "Sync is disabled in safe mode".into())
+}
+
+fn paired_key(app: &AppHandle, id: &str) -> Option<[u8; 32]> {
+    settings::get(app)
+        .sync
+        .paired
+        .iter()
+        .find(|device| device.id == id)
+        .and_then(|device| from_hex(&device.key))
+        .and_then(|key| key.try_into().ok())
+}
+
+fn save_pairing(app: &AppHandle, id: &str, name: &str, key: &[u8; 32]) -> Result<(), String> {
+    let device = PairedDevice {
+        id: id.to_string(),
+        name: name.to_string(),
+        key: to_hex(key),
+    };
+    settings::update(app, |s| {
+        s.sync.paired.retain(|paired| paired.id != device.id);
+        s.sync.paired.push(device);
+    })
+    .map(|_| ())
+}
+
+fn connect(app: &AppHandle, id: &str) -> Result<TcpStream, String> {
+    let address = state(app)?
+        .discovered
+        .lock()
+        .unwrap()
+        .get(id)
+        .map(|device| device.address)
+        .ok_or("Device is not on the network")?;
+
+    let stream =
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    let _ = stream.set_nodelay(true);
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+    Ok(stream)
+}
+
+/// Serves `listener` on a thread per connection, at most [`MAX_CONNECTIONS`]
+/// at a time. Streams start with [`IO_TIMEOUT`] for reads and writes. At exit
+/// the listener closes and open connections are shut down.
+pub fn serve(
+    app: &AppHandle,
+    listener: TcpListener,
+    module: &'static str,
+    handle: fn(&AppHandle, TcpStream) -> Result<(), String>,
+) {
+    let open: Arc<Mutex<HashMap<usize, TcpStream>>> = Arc::default();
+    let closed = lifecycle::close_on_exit(app, module, &listener);
+    let connections = open.clone();
+    lifecycle::on_exit(app, module, move |_, _| {
+        for stream in connections.lock().unwrap().values() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    });
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for (id, stream) in listener.incoming().flatten().enumerate() {
+            if closed.load(Ordering::SeqCst) {
+                break;
+            }
+            let mut connections = open.lock().unwrap();
+            if connections.len() >= MAX_CONNECTIONS {
+                eprintln!("[{}] Too many connections, closing one", module);
+                continue;
+            }
+            let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+            let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+            match stream.try_clone() {
+                Ok(clone) => connections.insert(id, clone),
+                Err(e) => {
+                    eprintln!("[{}] Failed to accept a connection: {}", module, e);
+                    continue;
+                }
+            };
+            drop(connections);
+
+            let (app, open) = (app.clone(), open.clone());
+            thread::spawn(move || {
+                if let Err(e) = handle(&app, stream) {
+                    eprintln!("[{}] Connection closed: {}", module, e);
+                }
+                open.lock().unwrap().remove(&id);
+            });
+        }
+    });
+}
+
+/// Opens an encrypted channel to a paired device, for sending.
+fn open_channel(app: &AppHandle, id: &str) -> Result<(TcpStream, Sealer), String> {
+    let key = paired_key(app, id).ok_or("Device is not paired")?;
+    let mut stream = connect(app, id)?;
+    let nonce = random::<32>()?;
+    let own_id = settings::get(app).sync.device_id;
+    send_plain(
+        &mut stream,
+        &Message::Auth {
+            id: own_id,
+            nonce: to_hex(&nonce),
+        },
+    )?;
+
+    let Message::Challenge { nonce: theirs } = read_plain(&mut stream)? else {
+        return Err("Unexpected reply to authentication".into());
+    };
+    let theirs = from_hex(&theirs).ok_or("Invalid nonce")?;
+    let (sealer, _) = channel_keys(&key, &nonce, &theirs, true);
+    Ok((stream, sealer))
+}
+
+fn on_message(app: &AppHandle, from: &str, message: Message) {
+    match message {
+        Message::Caption { caption } => {
+            let _ = app.emit("sync:caption", &caption);
+        }
+        Message::Handoff { session } => {
+            println!("[Sync] Session {} handed off by {}", session.id, from);
+            if let Err(e) = recording::continue_handoff(app, &session) {
+                eprintln!("[Sync] Failed to continue handed-off session: {}", e);
+                return;
+            }
+            let _ = panel::show(app);
+            if !capture::status(app).active {
+                let _ = capture::request_start(app);
+            }
+            let _ = app.emit("sync:handoff", &session);
+        }
+        _ => {}
+    }
+}
+
+fn handle_pair_request(
+    app: &AppHandle,
+    mut stream: TcpStream,
+    id: String,
+    name: String,
+    commitment_hex: String,
+) -> Result<(), String> {
+    // Re-pairing under a known id would let any device replace the key.
+    if paired_key(app, &id).is_some() {
+        send_plain(&mut stream, &Message::PairResult { accepted: false })?;
+        return Err(format!("Pair request from {}, which is already paired", id));
+    }
+
+    let secret = StaticSecret::from(random::<32>()?);
+    let own_key = *PublicKey::from(&secret).as_bytes();
+    let own = settings::get(app).sync;
+    send_plain(
+        &mut stream,
+        &Message::PairKey {
+            id: own.device_id,
+            name: own.device_name,
+            public_key: to_hex(&own_key),
+        },
+    )?;
+
+    let Message::Reveal { public_key, nonce } = read_plain(&mut stream)? else {
+        return Err("Unexpected pairing message".into());
+    };
+    let peer_key = parse_key(&public_key)?;
+    let nonce = from_hex(&nonce).ok_or("Invalid nonce")?;
+    if commitment(&peer_key, &nonce) != commitment_hex {
+        return Err("Pairing key does not match its commitment".into());
+    }
+    let (key, code) = derive(&secret, &peer_key, &own_key, &nonce, false);
+    let (sealer, _) = channel_keys(&key, &peer_key, &own_key, false);
+    let request = PairRequest {
+        device_id: id.clone(),
+        name: name.clone(),
+        code,
+    };
+    let state = app.state::<SyncState>();
+    let mut pending = state.pending.lock().unwrap();
+    pending.retain(|_, pair| pair.received.elapsed() < PAIR_TIMEOUT);
+    if pending.len() >= MAX_PENDING_PAIRS && !pending.contains_key(&id) {
+        return Err("Too many pair requests waiting".into());
+    }
+    pending.insert(
+        id,
+        PendingPair {
+            name,
+            key,
+            stream,
+            sealer,
+            received: Instant::now(),
+        },
+    );
+    drop(pending);
+
+    let _ = app.emit("sync:pair-request", &request);
+    Ok(())
+}
+
+fn handle_connection(app: &AppHandle, mut stream: TcpStream) -> Result<(), String> {
+    match read_plain(&mut stream)? {
+        Message::Hello {
+            id,
+            name,
+            commitment,
+        } => handle_pair_request(app, stream, id, name, commitment),
+        Message::Auth { id, nonce: theirs } => {
+            let key = paired_key(app, &id).ok_or("Connection from an unpaired device")?;
+            let theirs = from_hex(&theirs).ok_or("Invalid nonce")?;
+            let nonce = random::<32>()?;
+            send_plain(
+                &mut stream,
+                &Message::Challenge {
+                    nonce: to_hex(&nonce),
+                },
+            )?;
+
+            let (_, mut opener) = channel_keys(&key, &theirs, &nonce, false);
+            let _ = stream.set_read_timeout(Some(IDLE_TIMEOUT));
+            loop {
+                let message = opener.receive(&mut stream)?;
+                on_message(app, &id, message);
+            }
+        }
+        _ => Err("Unexpected opening message".into()),
+    }
+}
+
+fn advertise(app: &AppHandle, daemon: &ServiceDaemon, config: &SyncSettings) -> Result<(), String> {
+    let properties = [
+        ("id", config.device_id.as_str()),
+        ("name", config.device_name.as_str()),
+    ];
+    let service = ServiceInfo::new(
+        SERVICE_TYPE,
+        &config.device_id,
+        &format!("{}.local.", config.device_id),
+        "",
+        config.port,
+        &properties[..],
+    )
+    .map_err(|e| e.to_string())?
+    .enable_addr_auto();
+    daemon.register(service).map_err(|e| e.to_string())?;
+
+    let events = daemon.browse(SERVICE_TYPE).map_err(|e| e.to_string())?;
+    let own_id = config.device_id.clone();
+    let app = app.clone();
+    thread::spawn(move || {
+        while let Ok(event) = events.recv() {
+            let state = app.state::<SyncState>();
+            match event {
+                ServiceEvent::ServiceResolved(service) => {
+                    let (Some(id), Some(ip)) = (
+                        service.get_property_val_str("id"),
+                        service.get_addresses_v4().into_iter().next(),
+                    ) else {
+                        continue;
+                    };
+                    if id == own_id {
+                        continue;
+                    }
+
+                    let device = Discovered {
+                        name: service
+                            .get_property_val_str("name")
+                            .unwrap_or(id)
+                            .to_string(),
+                        address: SocketAddr::from((ip, service.get_port())),
+                    };
+                    state
+                        .discovered
+                        .lock()
+                        .unwrap()
+                        .insert(id.to_string(), device);
+                    let _ = app.emit("sync:devices", ());
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    let id = fullname.split('.').next().unwrap_or_default();
+                    state.discovered.lock().unwrap().remove(id);
+                    let _ = app.emit("sync:devices", ());
+                }
+                _ => {}
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Mirrors captions to paired devices that are being mirrored to.
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    let Some(state) = app.try_state::<SyncState>() else {
+        return;
+    };
+
+    state.mirrors.lock().unwrap().retain(|_, mirror| {
+        mirror
+            .send(Message::Caption {
+                caption: caption.clone(),
+            })
+            .is_ok()
+    });
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(SyncState::default());
+
+    let mut config = settings::get(app).sync;
+    if config.device_id.is_empty() {
+        match random::<16>() {
+            Ok(bytes) => config.device_id = to_hex(&bytes),
+            Err(e) => {
+                eprintln!("[Sync] Failed to generate a device id: {}", e);
+                return;
+            }
+        }
+        let device_id = config.device_id.clone();
+        if let Err(e) = settings::update(app, |s| s.sync.device_id = device_id) {
+            eprintln!("[Sync] Failed to save device id: {}", e);
+        }
+    }
+
+    if !config.enabled {
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[Sync] Failed to bind port {}: {}", config.port, e);
+            return;
+        }
+    };
+
+    match ServiceDaemon::new().map_err(|e| e.to_string()) {
+        Ok(daemon) => {
+            if let Err(e) = advertise(app, &daemon, &config) {
+                eprintln!("[Sync] Failed to advertise: {}", e);
+            }
+            *app.state::<SyncState>().daemon.lock().unwrap() = Some(daemon);
+        }
+        Err(e) => eprintln!("[Sync] Failed to start mDNS: {}", e),
+    }
+    println!(
+        "[Sync] Listening on port {} as '{}'",
+        config.port, config.device_name
+    );
+
+    serve(app, listener, "Sync", handle_connection);
+
+    // Dropping the mirror senders ends their threads, which closes their connections.
+    lifecycle::on_exit(app, "sync", |app, _| {
+        let state = app.state::<SyncState>();
+        state.mirrors.lock().unwrap().clear();
+        state.pending.lock().unwrap().clear();
+        let daemon = state.daemon.lock().unwrap().take();
+        if let Some(daemon) = daemon {
+            let _ = daemon.shutdown();
+        }
+    });
+}
+
+#[tauri::command]
+pub fn list_sync_devices(app: AppHandle) -> Result<Vec<SyncDevice>, String> {
+    let state = state(&app)?;
+    let discovered = state.discovered.lock().unwrap();
+    let mirrors = state.mirrors.lock().unwrap();
+    let paired = settings::get(&app).sync.paired;
+
+    let mut devices: Vec<SyncDevice> = discovered
+        .iter()
+        .map(|(id, device)| SyncDevice {
+            id: id.clone(),
+            name: device.name.clone(),
+            address: Some(device.address.to_string()),
+            paired: paired.iter().any(|paired| &paired.id == id),
+            mirroring: mirrors.contains_key(id),
+        })
+        .collect();
+
+    // Paired devices that are currently offline.
+    devices.extend(
+        paired
+            .iter()
+            .filter(|paired| !discovered.contains_key(&paired.id))
+            .map(|paired| SyncDevice {
+                id: paired.id.clone(),
+                name: paired.name.clone(),
+                address: None,
+                paired: true,
+                mirroring: false,
+            }),
+    );
+    Ok(devices)
+}
+
+fn pair(app: AppHandle, id: String) -> Result<String, String> {
+    let mut stream = connect(&app, &id)?;
+    let secret = StaticSecret::from(random::<32>()?);
+    let own_key = *PublicKey::from(&secret).as_bytes();
+    let nonce = random::<32>()?;
+
+    let own = settings::get(&app).sync;
+    send_plain(
+        &mut stream,
+        &Message::Hello {
+            id: own.device_id,
+            name: own.device_name,
+            commitment: commitment(&own_key, &nonce),
+        },
+    )?;
+
+    let (name, peer_key) = match read_plain(&mut stream)? {
+        Message::PairKey {
+            name, public_key, ..
+        } => (name, parse_key(&public_key)?),
+        Message::PairResult { .. } => {
+            return Err("Already paired with this device; unpair it there first".into())
+        }
+        _ => return Err("Unexpected pairing reply".into()),
+    };
+    send_plain(
+        &mut stream,
+        &Message::Reveal {
+            public_key: to_hex(&own_key),
+            nonce: to_hex(&nonce),
+        },
+    )?;
+    let (key, code) = derive(&secret, &own_key, &peer_key, &nonce, true);
+    let (_, mut opener) = channel_keys(&key, &own_key, &peer_key, true);
+
+    // The other side answers once its user accepts or rejects the code.
+    let _ = stream.set_read_timeout(Some(PAIR_TIMEOUT));
+    thread::spawn(move || {
+        let accepted = matches!(
+            opener.receive(&mut stream),
+            Ok(Message::PairResult { accepted: true })
+        );
+        if accepted {
+            if let Err(e) = save_pairing(&app, &id, &name, &key) {
+                eprintln!("[Sync] Failed to save pairing: {}", e);
+            }
+        }
+        let _ = app.emit(
+            "sync:paired",
+            serde_json::json!({ "deviceId": id, "accepted": accepted }),
+        );
+    });
+
+    Ok(code)
+}
+
+/// Runs blocking network work off the async runtime's threads.
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Starts pairing with a discovered device and returns the code to compare
+/// with the one shown there. Completion is reported as `sync:paired`.
+#[tauri::command]
+pub async fn pair_device(app: AppHandle, id: String) -> Result<String, String> {
+    blocking(move || pair(app, id)).await
+}
+
+/// Answers a `sync:pair-request` after the user compared the codes.
+#[tauri::command]
+pub fn respond_to_pairing(app: AppHandle, id: String, accept: bool) -> Result<(), String> {
+    let mut pending = state(&app)?
+        .pending
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .filter(|pair| pair.received.elapsed() < PAIR_TIMEOUT)
+        .ok_or("No pending pair request from this device")?;
+
+    if accept {
+        save_pairing(&app, &id, &pending.name, &pending.key)?;
+    }
+    pending.sealer.send(
+        &mut pending.stream,
+        &Message::PairResult { accepted: accept },
+    )
+}
+
+#[tauri::command]
+pub fn unpair_device(app: AppHandle, id: String) -> Result<(), String> {
+    state(&app)?.mirrors.lock().unwrap().remove(&id);
+    settings::update(&app, |s| s.sync.paired.retain(|paired| paired.id != id)).map(|_| ())
+}
+
+/// Streams live captions to a paired device until `stop_mirroring` is called.
+#[tauri::command]
+pub async fn start_mirroring(app: AppHandle, id: String) -> Result<(), String> {
+    let state = state(&app)?;
+    let (handle, target) = (app.clone(), id.clone());
+    let (mut stream, mut sealer) = blocking(move || open_channel(&handle, &target)).await?;
+    let (tx, rx) = mpsc::channel();
+
+    let target = id.clone();
+    thread::spawn(move || loop {
+        let message = match rx.recv_timeout(KEEPALIVE) {
+            Ok(message) => message,
+            Err(RecvTimeoutError::Timeout) => Message::Ping,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if let Err(e) = sealer.send(&mut stream, &message) {
+            eprintln!("[Sync] Mirror to {} closed: {}", target, e);
+            break;
+        }
+    });
+
+    state.mirrors.lock().unwrap().insert(id, tx);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_mirroring(app: AppHandle, id: String) -> Result<(), String> {
+    // Dropping the sender ends the mirror thread.
+    state(&app)?.mirrors.lock().unwrap().remove(&id);
+    Ok(())
+}
+
+/// Sends the current session to a paired device, which continues capturing, and stops here.
+#[tauri::command]
+pub async fn hand_off_session(app: AppHandle, id: String) -> Result<(), String> {
+    let session = recording::current_session(&app)?;
+    let handle = app.clone();
+    blocking(move || {
+        let (mut stream, mut sealer) = open_channel(&handle, &id)?;
+        sealer.send(&mut stream, &Message::Handoff { session })
+    })
+    .await?;
+
+    if capture::status(&app).active {
+        capture::request_stop(&app).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (client, server)
+    }
+
+    #[test]
+    fn hex_round_trips_and_rejects_malformed_input() {
+        let bytes = [0x00, 0x0f, 0xa5, 0xff];
+        assert_eq!(to_hex(&bytes), "000fa5ff");
+        assert_eq!(from_hex("000fa5ff").unwrap(), bytes);
+        assert_eq!(from_hex("000FA5FF").unwrap(), bytes);
+        assert!(from_hex("abc").is_none());
+        assert!(from_hex("zz").is_none());
+        assert!(from_hex("aé").is_none());
+    }
+
+    #[test]
+    fn opens_frames_in_order() {
+        let key = [9u8; 32];
+        let (mut sealer, _) = channel_keys(&key, b"a", b"b", true);
+        let (_, mut opener) = channel_keys(&key, b"a", b"b", false);
+        let (mut client, mut server) = connected();
+
+        for text in [b"first".as_slice(), b"", b"third"] {
+            sealer.write(&mut client, text).unwrap();
+            assert_eq!(opener.read(&mut server).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn rejects_a_replayed_frame() {
+        let key = [9u8; 32];
+        let (mut sealer, _) = channel_keys(&key, b"a", b"b", true);
+        let (mut replayer, _) = channel_keys(&key, b"a", b"b", true);
+        let (_, mut opener) = channel_keys(&key, b"a", b"b", false);
+        let (mut client, mut server) = connected();
+
+        sealer.write(&mut client, b"pay").unwrap();
+        replayer.write(&mut client, b"pay").unwrap();
+        assert!(opener.read(&mut server).is_ok());
+        assert!(opener.read(&mut server).is_err());
+    }
+
+    #[test]
+    fn frames_only_open_on_their_own_connection_and_direction() {
+        let key = [9u8; 32];
+        let (_, other_connection) = channel_keys(&key, b"a", b"c", false);
+        let (_, same_direction) = channel_keys(&key, b"a", b"b", true);
+        let (_, other_key) = channel_keys(&[8u8; 32], b"a", b"b", false);
+        let (mut client, mut server) = connected();
+
+        // Each opener expects frame 0, so only the keys can make it fail.
+        for mut opener in [other_connection, same_direction, other_key] {
+            let (mut sealer, _) = channel_keys(&key, b"a", b"b", true);
+            sealer.write(&mut client, b"hello").unwrap();
+            assert!(opener.read(&mut server).is_err());
+        }
+    }
+
+    #[test]
+    fn both_sides_derive_the_same_code_from_both_keys() {
+        let (initiator, responder) = (StaticSecret::from([1u8; 32]), StaticSecret::from([2u8; 32]));
+        let initiator_key = *PublicKey::from(&initiator).as_bytes();
+        let responder_key = *PublicKey::from(&responder).as_bytes();
+        let nonce = [3u8; 32];
+
+        let ours = derive(&initiator, &initiator_key, &responder_key, &nonce, true);
+        let theirs = derive(&responder, &initiator_key, &responder_key, &nonce, false);
+        assert_eq!(ours, theirs);
+        assert_eq!(ours.1.len(), 6);
+
+        // A different key on either side changes the code.
+        let other_key = *PublicKey::from(&StaticSecret::from([4u8; 32])).as_bytes();
+        let swapped = derive(&responder, &other_key, &responder_key, &nonce, false);
+        assert_ne!(swapped.1, ours.1);
+    }
+
+    #[test]
+    fn commitment_only_opens_with_its_key_and_nonce() {
+        let (key, nonce) = ([5u8; 32], [6u8; 32]);
+        let committed = commitment(&key, &nonce);
+        assert_eq!(commitment(&key, &nonce), committed);
+        assert_ne!(commitment(&[7u8; 32], &nonce), committed);
+        assert_ne!(commitment(&key, &[8u8; 32]), committed);
+    }
+
+    #[test]
+    fn stores_a_handed_off_session_in_a_new_directory() {
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "id": "20260101-090000",
+            "startedAt": 1_000,
+            "endedAt": null,
+            "language": "en",
+            "profile": "default",
+            "segments": [{ "text": "hello there", "offsetMs": 500, "timestamp": 1_500 }],
+        }))
+        .unwrap();
+
+        let key = [7u8; 32];
+        let (mut sealer, _) = channel_keys(&key, b"initiator", b"responder", true);
+        let (_, mut opener) = channel_keys(&key, b"initiator", b"responder", false);
+        let (mut client, mut server) = connected();
+        sealer
+            .send(&mut client, &Message::Handoff { session })
+            .unwrap();
+        let Ok(Message::Handoff { session }) = opener.receive(&mut server) else {
+            panic!("expected a handoff");
+        };
+
+        let root = std::env::temp_dir().join(format!("signos-handoff-{}", std::process::id()));
+        let dir = root.join(&session.id);
+        recording::store_session(&dir, &session).unwrap();
+        let stored = recording::read_session(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(stored.id, "20260101-090000");
+        assert_eq!(stored.segments.len(), 1);
+        assert_eq!(stored.segments[0].text, "hello there");
+        assert_eq!(stored.segments[0].offset_ms, 500);
+    }
+}
@@ -0,0 +1,242 @@
+//! Coexistence with the OS captioner (Windows Live Captions, macOS Live Captions).
+//!
+//! The watcher notices when the system captioner is showing, so the user
+//! doesn't get the same speech captioned twice. Depending on the mode, Signos
+//! pauses its own capture while the OS captions, or (on Windows) reads the
+//! Live Captions text through UI Automation and uses it as its caption source.
+
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::captions::{self, Caption};
+use crate::util::now_ms;
+use crate::{capture, settings};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SOURCE: &str = "System";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemCaptionMode {
+    /// Only report whether the OS captioner is running.
+    #[default]
+    Coexist,
+    /// Pause capture while the OS captioner is running.
+    HandOff,
+    /// Pause capture and caption from the OS captioner's text instead.
+    Ingest,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SystemCaptionSettings {
+    pub mode: SystemCaptionMode,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemCaptionStatus {
+    /// The OS captioner is currently running.
+    pub active: bool,
+    pub mode: SystemCaptionMode,
+    pub ingest_supported: bool,
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::w;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+    };
+    use windows::Win32::System::Variant::VARIANT;
+    use windows::Win32::UI::Accessibility::{
+        CUIAutomation, IUIAutomation, TreeScope_Descendants, UIA_AutomationIdPropertyId,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::FindWindowW;
+
+    pub const INGEST_SUPPORTED: bool = true;
+
+    fn window() -> Option<windows::Win32::Foundation::HWND> {
+        unsafe { FindWindowW(w!("LiveCaptionsDesktopWindow"), None) }.ok()
+    }
+
+    pub fn is_active() -> bool {
+        window().is_some()
+    }
+
+    /// The text currently shown by Live Captions, oldest line first.
+    pub fn read_text() -> Result<String, String> {
+        let window = window().ok_or("Live Captions is not running")?;
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let automation: IUIAutomation =
+                CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+                    .map_err(|e| e.to_string())?;
+            let root = automation
+                .ElementFromHandle(window)
+                .map_err(|e| e.to_string())?;
+            let condition = automation
+                .CreatePropertyCondition(
+                    UIA_AutomationIdPropertyId,
+                    &VARIANT::from("CaptionsTextBlock"),
+                )
+                .map_err(|e| e.to_string())?;
+            let text = root
+                .FindFirst(TreeScope_Descendants, &condition)
+                .and_then(|element| element.CurrentName())
+                .map_err(|e| e.to_string())?;
+            Ok(text.to_string())
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::NSWorkspace;
+
+    pub const INGEST_SUPPORTED: bool = false;
+    /// Agent behind the Live Captions panel in Accessibility settings.
+    const LIVE_CAPTIONS: &str = "com.apple.accessibility.LiveTranscriptionAgent";
+
+    pub fn is_active() -> bool {
+        NSWorkspace::sharedWorkspace()
+            .runningApplications()
+            .iter()
+            .any(|app| {
+                app.bundleIdentifier()
+                    .is_some_and(|id| id.to_string() == LIVE_CAPTIONS)
+            })
+    }
+
+    pub fn read_text() -> Result<String, String> {
+        Err("Live Captions has no API to read its text on macOS".into())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub const INGEST_SUPPORTED: bool = false;
+
+    pub fn is_active() -> bool {
+        false
+    }
+
+    pub fn read_text() -> Result<String, String> {
+        Err("There is no system captioner on this platform".into())
+    }
+}
+
+fn status(app: &AppHandle, active: bool) -> SystemCaptionStatus {
+    SystemCaptionStatus {
+        active,
+        mode: settings::get(app).system_captions.mode,
+        ingest_supported: platform::INGEST_SUPPORTED,
+    }
+}
+
+/// Pushes the lines Live Captions finished since the last poll. The last
+/// line is still being revised, so it waits until a newer one appears.
+fn ingest(app: &AppHandle, seen: &mut Vec<String>) {
+    let text = match platform::read_text() {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("[SystemCaptions] Failed to read captions: {}", e);
+            return;
+        }
+    };
+
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.pop();
+
+    for line in lines.iter().filter(|line| !seen.contains(line)) {
+        captions::push(
+            app,
+            Caption {
+                text: line.clone(),
+                is_final: true,
+                timestamp: now_ms(),
+                latency_ms: None,
+                language: None,
+                speaker: None,
+                source: Some(SOURCE.into()),
+            },
+        );
+    }
+    *seen = lines;
+}
+
+/// Starts the watcher that emits `system-captions:state` when the OS captioner
+/// starts or stops, and applies the configured mode.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || {
+        let mut active = false;
+        // Whether the watcher paused capture, so it only resumes what it paused.
+        let mut paused = false;
+        let mut seen: Vec<String> = Vec::new();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let mode = settings::get(&app).system_captions.mode;
+            let now_active = platform::is_active();
+
+            if now_active != active {
+                active = now_active;
+                seen.clear();
+                println!(
+                    "[SystemCaptions] System captioner {}",
+                    if active { "started" } else { "stopped" }
+                );
+                let _ = app.emit("system-captions:state", status(&app, active));
+            }
+
+            let hand_off = active && mode != SystemCaptionMode::Coexist;
+            let capture = capture::status(&app);
+            if !capture.active {
+                paused = false;
+            }
+            if hand_off && capture.active && !capture.paused && !paused {
+                println!("[SystemCaptions] Pausing capture to avoid double captions");
+                paused = capture::set_paused(&app, true).is_ok();
+            } else if !hand_off && paused {
+                paused = false;
+                if capture.paused {
+                    let _ = capture::set_paused(&app, false);
+                }
+            }
+
+            if active && mode == SystemCaptionMode::Ingest && platform::INGEST_SUPPORTED {
+                ingest(&app, &mut seen);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_system_captions(app: AppHandle) -> SystemCaptionStatus {
+    status(&app, platform::is_active())
+}
+
+#[tauri::command]
+pub fn set_system_caption_mode(
+    app: AppHandle,
+    mode: SystemCaptionMode,
+) -> Result<SystemCaptionStatus, String> {
+    if mode == SystemCaptionMode::Ingest && !platform::INGEST_SUPPORTED {
+        return Err("Reading system captions is not supported on this platform".into());
+    }
+    settings::update(&app, |s| s.system_captions.mode = mode)?;
+
+    let status = status(&app, platform::is_active());
+    let _ = app.emit("system-captions:state", &status);
+    Ok(status)
+}
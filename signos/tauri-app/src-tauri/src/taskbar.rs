@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::panel::MAIN_WINDOW;
+use crate::settings;
+
+/// The panel stays out of the taskbar by default. With a taskbar button,
+/// Windows also gets jump-list tasks and thumbnail toolbar buttons.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaskbarSettings {
+    pub show_in_taskbar: bool,
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::sync::OnceLock;
+
+    use tauri::{AppHandle, WebviewWindow};
+    use windows::core::{w, Interface, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DefSubclassProc, DestinationList, EnumerableObjectCollection, ICustomDestinationList,
+        IShellLinkW, ITaskbarList3, SHGetStockIconInfo, SetWindowSubclass, ShellLink, TaskbarList,
+        SHGSI_ICON, SHGSI_SMALLICON, SHSTOCKICONID, SHSTOCKICONINFO, SIID_APPLICATION,
+        SIID_AUDIOFILES, SIID_DOCASSOC, THBF_ENABLED, THBN_CLICKED, THB_FLAGS, THB_ICON,
+        THB_TOOLTIP, THUMBBUTTON,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{RegisterWindowMessageW, WM_COMMAND};
+
+    use crate::capture;
+    use crate::intents::{self, Intent};
+
+    /// Jump-list tasks start the app with a `signos://` link (see `intents`).
+    const TASKS: [(&str, &str); 3] = [
+        ("signos://show", "Show Signos"),
+        ("signos://start-capture", "Start Capture"),
+        ("signos://open-transcript", "Open Last Transcript"),
+    ];
+
+    const BUTTON_TOGGLE_PANEL: u32 = 1;
+    const BUTTON_TOGGLE_CAPTURE: u32 = 2;
+    const BUTTON_OPEN_TRANSCRIPT: u32 = 3;
+    const SUBCLASS_ID: usize = 1;
+
+    static SUBCLASSED: OnceLock<()> = OnceLock::new();
+
+    fn create<T: Interface>(clsid: &windows::core::GUID) -> windows::core::Result<T> {
+        unsafe { CoCreateInstance(clsid, None, CLSCTX_INPROC_SERVER) }
+    }
+
+    pub fn set_jump_list(enabled: bool) -> Result<(), String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let exe = HSTRING::from(exe.as_path());
+
+        let result = (|| -> windows::core::Result<()> {
+            let list: ICustomDestinationList = create(&DestinationList)?;
+            if !enabled {
+                return unsafe { list.DeleteList(PCWSTR::null()) };
+            }
+
+            let mut slots = 0;
+            let _removed: IObjectArray = unsafe { list.BeginList(&mut slots)? };
+            let tasks: IObjectCollection = create(&EnumerableObjectCollection)?;
+
+            for (link, title) in TASKS {
+                let shell_link: IShellLinkW = create(&ShellLink)?;
+                unsafe {
+                    shell_link.SetPath(&exe)?;
+                    shell_link.SetArguments(&HSTRING::from(link))?;
+                    shell_link.SetIconLocation(&exe, 0)?;
+
+                    let properties: IPropertyStore = shell_link.cast()?;
+                    properties.SetValue(&PKEY_Title, &PROPVARIANT::from(title))?;
+                    properties.Commit()?;
+
+                    tasks.AddObject(&shell_link)?;
+                }
+            }
+
+            unsafe {
+                list.AddUserTasks(&tasks.cast::<IObjectArray>()?)?;
+                list.CommitList()
+            }
+        })();
+
+        result.map_err(|e| e.to_string())
+    }
+
+    fn button(id: u32, icon: SHSTOCKICONID, tip: &str) -> THUMBBUTTON {
+        let mut info = SHSTOCKICONINFO {
+            cbSize: size_of::<SHSTOCKICONINFO>() as u32,
+            ..Default::default()
+        };
+        let _ = unsafe { SHGetStockIconInfo(icon, SHGSI_ICON | SHGSI_SMALLICON, &mut info) };
+
+        let mut button = THUMBBUTTON {
+            dwMask: THB_ICON | THB_TOOLTIP | THB_FLAGS,
+            iId: id,
+            hIcon: info.hIcon,
+            dwFlags: THBF_ENABLED,
+            ..Default::default()
+        };
+        // Leave room for the terminating NUL.
+        let len = button.szTip.len() - 1;
+        for (dst, src) in button.szTip.iter_mut().zip(tip.encode_utf16().take(len)) {
+            *dst = src;
+        }
+        button
+    }
+
+    fn buttons(app: &AppHandle) -> [THUMBBUTTON; 3] {
+        let capture_tip = match capture::status(app).active {
+            true => "Stop capture",
+            false => "Start capture",
+        };
+
+        [
+            button(BUTTON_TOGGLE_PANEL, SIID_APPLICATION, "Show/hide Signos"),
+            button(BUTTON_TOGGLE_CAPTURE, SIID_AUDIOFILES, capture_tip),
+            button(
+                BUTTON_OPEN_TRANSCRIPT,
+                SIID_DOCASSOC,
+                "Open last transcript",
+            ),
+        ]
+    }
+
+    fn taskbar() -> windows::core::Result<ITaskbarList3> {
+        let taskbar: ITaskbarList3 = create(&TaskbarList)?;
+        unsafe { taskbar.HrInit()? };
+        Ok(taskbar)
+    }
+
+    fn add_buttons(app: &AppHandle, hwnd: HWND) -> windows::core::Result<()> {
+        unsafe { taskbar()?.ThumbBarAddButtons(hwnd, &buttons(app)) }
+    }
+
+    /// Refreshes the capture button's tooltip.
+    pub fn update_buttons(app: &AppHandle, window: &WebviewWindow) -> Result<(), String> {
+        let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+        unsafe { taskbar().and_then(|taskbar| taskbar.ThumbBarUpdateButtons(hwnd, &buttons(app))) }
+            .map_err(|e| e.to_string())
+    }
+
+    fn on_button(app: &AppHandle, id: u32) {
+        let result = match id {
+            BUTTON_TOGGLE_PANEL => {
+                intents::run(app, Intent::Toggle);
+                Ok(())
+            }
+            BUTTON_TOGGLE_CAPTURE if capture::status(app).active => capture::request_stop(app),
+            BUTTON_TOGGLE_CAPTURE => capture::request_start(app),
+            BUTTON_OPEN_TRANSCRIPT => {
+                intents::run(app, Intent::OpenTranscript);
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+
+        if let Err(e) = result {
+            eprintln!("[Taskbar] Thumbnail button failed: {}", e);
+        }
+    }
+
+    /// Explorer sends this once the window's taskbar button exists (and again if it restarts).
+    fn taskbar_button_created() -> u32 {
+        static MESSAGE: OnceLock<u32> = OnceLock::new();
+        *MESSAGE.get_or_init(|| unsafe { RegisterWindowMessageW(w!("TaskbarButtonCreated")) })
+    }
+
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: usize,
+        data: usize,
+    ) -> LRESULT {
+        let app = &*(data as *const AppHandle);
+
+        if message == WM_COMMAND && (wparam.0 >> 16) as u32 & 0xFFFF == THBN_CLICKED {
+            on_button(app, (wparam.0 & 0xFFFF) as u32);
+            return LRESULT(0);
+        }
+        if message == taskbar_button_created() {
+            if let Err(e) = add_buttons(app, hwnd) {
+                eprintln!("[Taskbar] Failed to add thumbnail buttons: {}", e);
+            }
+        }
+
+        DefSubclassProc(hwnd, message, wparam, lparam)
+    }
+
+    /// Subclasses the panel so it receives thumbnail button clicks. Runs on the UI thread.
+    pub fn install_buttons(app: &AppHandle, window: &WebviewWindow) -> Result<(), String> {
+        let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+
+        if SUBCLASSED.set(()).is_ok() {
+            // The subclass lives as long as the window, so the handle is never freed.
+            let data = Box::into_raw(Box::new(app.clone())) as usize;
+            let _ = taskbar_button_created();
+            if !unsafe { SetWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID, data) }.as_bool()
+            {
+                return Err("Failed to subclass the panel window".into());
+            }
+        }
+
+        // Fails harmlessly if the taskbar button does not exist yet.
+        let _ = add_buttons(app, hwnd);
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use tauri::{AppHandle, WebviewWindow};
+
+    pub fn set_jump_list(_enabled: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn update_buttons(_app: &AppHandle, _window: &WebviewWindow) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn install_buttons(_app: &AppHandle, _window: &WebviewWindow) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Shows or hides the taskbar button, along with the jump list and thumbnail toolbar.
+fn apply(app: &AppHandle) -> Result<(), String> {
+    let enabled = settings::get(app).taskbar.show_in_taskbar;
+
+    let window = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("Main window not found")?;
+
+    let handle = app.clone();
+    app.run_on_main_thread(move || {
+        // Mobile has no taskbar, only the jump list and buttons (no-ops there) remain.
+        #[cfg(desktop)]
+        let shown = window.set_skip_taskbar(!enabled).map_err(|e| e.to_string());
+        #[cfg(mobile)]
+        let shown = Ok(());
+
+        let result = shown
+            .and_then(|_| platform::set_jump_list(enabled))
+            .and_then(|_| match enabled {
+                true => platform::install_buttons(&handle, &window),
+                false => Ok(()),
+            });
+
+        if let Err(e) = result {
+            eprintln!("[Taskbar] Failed to update taskbar presence: {}", e);
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+pub fn init(app: &AppHandle) {
+    if let Err(e) = apply(app) {
+        eprintln!("[Taskbar] {}", e);
+    }
+
+    let handle = app.clone();
+    app.listen_any("capture:state", move |_| {
+        if !settings::get(&handle).taskbar.show_in_taskbar {
+            return;
+        }
+        let Some(window) = handle.get_webview_window(MAIN_WINDOW) else {
+            return;
+        };
+
+        let app = handle.clone();
+        let _ = handle.run_on_main_thread(move || {
+            if let Err(e) = platform::update_buttons(&app, &window) {
+                eprintln!("[Taskbar] Failed to update thumbnail buttons: {}", e);
+            }
+        });
+    });
+}
+
+#[tauri::command]
+pub fn set_show_in_taskbar(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.taskbar.show_in_taskbar = enabled)?;
+    apply(&app)
+}
@@ -0,0 +1,159 @@
+//! Backs off under thermal pressure, mostly for fanless Apple Silicon Macs.
+//!
+//! The system's thermal state is followed through its change notifications.
+//! At serious or critical pressure hand landmarks switch to the lite model and
+//! the camera is paused until the machine cools down. `thermal:changed` tells
+//! the UI why recognition got worse.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{camera, settings};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ThermalSettings {
+    pub enabled: bool,
+    /// Pause the camera at serious pressure, not just use the lite model.
+    pub pause_camera: bool,
+}
+
+impl Default for ThermalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pause_camera: true,
+        }
+    }
+}
+
+// Only Apple platforms report anything but nominal.
+#[cfg_attr(not(any(target_os = "macos", target_os = "ios")), allow(dead_code))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThermalLevel {
+    #[default]
+    Nominal,
+    Fair,
+    Serious,
+    Critical,
+}
+
+#[derive(Default)]
+pub struct ThermalState {
+    level: Mutex<ThermalLevel>,
+    /// The camera was paused by us rather than by the user.
+    paused_camera: AtomicBool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThermalStatus {
+    pub level: ThermalLevel,
+    /// Quality is being reduced because of it.
+    pub degraded: bool,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+    use std::ptr::NonNull;
+
+    use block2::RcBlock;
+    use objc2_foundation::{
+        NSNotification, NSNotificationCenter, NSProcessInfo, NSProcessInfoThermalState,
+        NSProcessInfoThermalStateDidChangeNotification,
+    };
+
+    use super::ThermalLevel;
+
+    pub fn level() -> ThermalLevel {
+        match NSProcessInfo::processInfo().thermalState() {
+            NSProcessInfoThermalState::Fair => ThermalLevel::Fair,
+            NSProcessInfoThermalState::Serious => ThermalLevel::Serious,
+            NSProcessInfoThermalState::Critical => ThermalLevel::Critical,
+            _ => ThermalLevel::Nominal,
+        }
+    }
+
+    /// Calls `on_change` with the new level, from whichever thread posted the notification.
+    pub fn observe(on_change: impl Fn(ThermalLevel) + Send + 'static) {
+        let block = RcBlock::new(move |_notification: NonNull<NSNotification>| on_change(level()));
+        let observer = unsafe {
+            NSNotificationCenter::defaultCenter().addObserverForName_object_queue_usingBlock(
+                Some(NSProcessInfoThermalStateDidChangeNotification),
+                None,
+                None,
+                &block,
+            )
+        };
+        // Observes for as long as the app runs.
+        std::mem::forget(observer);
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+mod platform {
+    use super::ThermalLevel;
+
+    pub fn level() -> ThermalLevel {
+        ThermalLevel::Nominal
+    }
+
+    pub fn observe(_on_change: impl Fn(ThermalLevel) + Send + 'static) {}
+}
+
+pub fn status(app: &AppHandle) -> ThermalStatus {
+    let level = *app.state::<ThermalState>().level.lock().unwrap();
+    ThermalStatus {
+        level,
+        degraded: settings::get(app).thermal.enabled && level >= ThermalLevel::Serious,
+    }
+}
+
+/// Whether quality should be reduced for thermal reasons.
+pub fn degraded(app: &AppHandle) -> bool {
+    status(app).degraded
+}
+
+fn changed(app: &AppHandle, level: ThermalLevel) {
+    let state = app.state::<ThermalState>();
+    {
+        let mut current = state.level.lock().unwrap();
+        if *current == level {
+            return;
+        }
+        *current = level;
+    }
+
+    let status = status(app);
+    println!("[Thermal] Thermal state is now {:?}", level);
+    let _ = app.emit("thermal:changed", &status);
+
+    if status.degraded {
+        if settings::get(app).thermal.pause_camera && camera::is_active(app) {
+            state.paused_camera.store(true, Ordering::SeqCst);
+            if let Err(e) = camera::request_stop(app) {
+                eprintln!("[Thermal] Failed to pause camera: {}", e);
+            }
+        }
+    } else if state.paused_camera.swap(false, Ordering::SeqCst) && !camera::is_active(app) {
+        if let Err(e) = camera::request_start(app) {
+            eprintln!("[Thermal] Failed to resume camera: {}", e);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    changed(app, platform::level());
+
+    let handle = app.clone();
+    platform::observe(move |level| changed(&handle, level));
+}
+
+#[tauri::command]
+pub fn get_thermal_status(app: AppHandle) -> ThermalStatus {
+    status(&app)
+}
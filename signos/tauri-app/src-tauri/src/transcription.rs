@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{power, settings};
+
+/// Nova-3 streaming only accepts `multi` (Spanish/English code-switching) or `en`.
+pub const SUPPORTED_LANGUAGES: [&str; 2] = ["multi", "en"];
+/// Streaming models the worker accepts as `?model=`. Flux is English-only.
+pub const SUPPORTED_MODELS: [&str; 2] = ["nova-3", "flux"];
+const ENGLISH_ONLY_MODELS: [&str; 1] = ["flux"];
+
+const CHUNK_MS: std::ops::RangeInclusive<u32> = 20..=2000;
+const CONTEXT_SECONDS: std::ops::RangeInclusive<f32> = 0.0..=10.0;
+const BEAM_SIZE: std::ops::RangeInclusive<u32> = 1..=10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TranscriptionSettings {
+    /// Passed to the worker as `?language=` when the WebSocket connects.
+    pub language: String,
+    /// Passed to the worker as `?model=`.
+    pub model: String,
+    pub inference: InferenceParams,
+}
+
+/// Latency/accuracy trade-offs, e.g. small chunks for calls and a long
+/// context for lectures. The defaults match the worker's own.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InferenceParams {
+    /// Audio per chunk sent to the worker.
+    pub chunk_ms: u32,
+    /// Trailing silence before a segment is finalized, so a segment can carry
+    /// more context. 0 leaves it to the model.
+    pub context_seconds: f32,
+    /// Candidate transcripts the model weighs per result. 1 is greedy.
+    pub beam_size: u32,
+}
+
+impl Default for InferenceParams {
+    fn default() -> Self {
+        Self {
+            chunk_ms: 256,
+            context_seconds: 0.0,
+            beam_size: 1,
+        }
+    }
+}
+
+impl InferenceParams {
+    /// Chunk length in samples at the 16 kHz pipeline rate.
+    pub fn chunk_samples(&self) -> usize {
+        self.chunk_ms as usize * 16
+    }
+}
+
+impl Default for TranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            language: SUPPORTED_LANGUAGES[0].into(),
+            model: SUPPORTED_MODELS[0].into(),
+            inference: InferenceParams::default(),
+        }
+    }
+}
+
+/// Parameters the pipeline should use right now, low-power mode included.
+pub fn inference(app: &AppHandle) -> InferenceParams {
+    power::inference(app, settings::get(app).transcription.inference)
+}
+
+pub fn set_language(app: &AppHandle, language: &str) -> Result<String, String> {
+    if !SUPPORTED_LANGUAGES.contains(&language) {
+        return Err(format!("Unsupported language '{}'", language));
+    }
+
+    // An English-only model can't follow, so it goes back to the default.
+    let previous = settings::get(app).transcription.model;
+    let settings = settings::update(app, |s| {
+        s.transcription.language = language.into();
+        if language != "en" && ENGLISH_ONLY_MODELS.contains(&s.transcription.model.as_str()) {
+            s.transcription.model = SUPPORTED_MODELS[0].into();
+        }
+    })?;
+    let _ = app.emit("language:changed", language);
+    if settings.transcription.model != previous {
+        let _ = app.emit("model:changed", &settings.transcription.model);
+    }
+
+    Ok(language.into())
+}
+
+/// Switches to the next supported language.
+pub fn cycle_language(app: &AppHandle) -> Result<String, String> {
+    let current = settings::get(app).transcription.language;
+    let index = SUPPORTED_LANGUAGES
+        .iter()
+        .position(|language| *language == current)
+        .map_or(0, |index| (index + 1) % SUPPORTED_LANGUAGES.len());
+
+    set_language(app, SUPPORTED_LANGUAGES[index])
+}
+
+#[tauri::command]
+pub fn get_language(app: AppHandle) -> String {
+    settings::get(&app).transcription.language
+}
+
+#[tauri::command]
+pub fn set_transcription_language(app: AppHandle, language: String) -> Result<String, String> {
+    set_language(&app, &language)
+}
+
+#[tauri::command]
+pub fn get_model(app: AppHandle) -> String {
+    settings::get(&app).transcription.model
+}
+
+/// Switches the streaming model (`model:changed`). A running capture keeps its
+/// connection while the frontend opens one for the new model, and swaps over at
+/// the next final segment.
+#[tauri::command]
+pub fn set_model(app: AppHandle, model_id: String) -> Result<String, String> {
+    if !SUPPORTED_MODELS.contains(&model_id.as_str()) {
+        return Err(format!("Unsupported model '{}'", model_id));
+    }
+    if ENGLISH_ONLY_MODELS.contains(&model_id.as_str())
+        && settings::get(&app).transcription.language != "en"
+    {
+        return Err(format!("'{}' only transcribes English", model_id));
+    }
+
+    settings::update(&app, |s| s.transcription.model = model_id.clone())?;
+    let _ = app.emit("model:changed", &model_id);
+
+    Ok(model_id)
+}
+
+#[tauri::command]
+pub fn get_inference_params(app: AppHandle) -> InferenceParams {
+    inference(&app)
+}
+
+/// Applied live (`inference:changed`): the chunk size right away, the worker
+/// parameters by reconnecting at the next final segment.
+#[tauri::command]
+pub fn set_inference_params(
+    app: AppHandle,
+    chunk_ms: u32,
+    context_seconds: f32,
+    beam_size: u32,
+) -> Result<InferenceParams, String> {
+    if !CHUNK_MS.contains(&chunk_ms) {
+        return Err(format!(
+            "Chunk length must be between {} and {} ms",
+            CHUNK_MS.start(),
+            CHUNK_MS.end()
+        ));
+    }
+    if !CONTEXT_SECONDS.contains(&context_seconds) {
+        return Err(format!(
+            "Context must be between {} and {} seconds",
+            CONTEXT_SECONDS.start(),
+            CONTEXT_SECONDS.end()
+        ));
+    }
+    if !BEAM_SIZE.contains(&beam_size) {
+        return Err(format!(
+            "Beam size must be between {} and {}",
+            BEAM_SIZE.start(),
+            BEAM_SIZE.end()
+        ));
+    }
+
+    let params = InferenceParams {
+        chunk_ms,
+        context_seconds,
+        beam_size,
+    };
+    settings::update(&app, |s| s.transcription.inference = params)?;
+    let _ = app.emit("inference:changed", inference(&app));
+
+    Ok(params)
+}
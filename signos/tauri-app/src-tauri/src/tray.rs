@@ -0,0 +1,233 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::capture::{self, AudioDevices};
+use crate::util::now_ms;
+use crate::{panel, profiles, recording, settings};
+
+pub const TRAY_ID: &str = "signos";
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+const TOGGLE_CAPTURE: &str = "toggle-capture";
+const TOGGLE_PANEL: &str = "toggle-panel";
+const MARK_MOMENT: &str = "mark-moment";
+const QUIT: &str = "quit";
+const PROFILE_PREFIX: &str = "profile:";
+const DEVICE_PREFIX: &str = "device:";
+
+/// Backend state the menu is built from. The menu is only rebuilt when this
+/// changes; the elapsed capture time is updated in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MenuSnapshot {
+    capturing: bool,
+    paused: bool,
+    recording: bool,
+    active_profile: String,
+    profiles: Vec<String>,
+    devices: AudioDevices,
+}
+
+#[derive(Default)]
+struct TrayState {
+    snapshot: Mutex<Option<MenuSnapshot>>,
+    capture_item: Mutex<Option<MenuItem<Wry>>>,
+}
+
+fn snapshot(app: &AppHandle) -> MenuSnapshot {
+    let status = capture::status(app);
+    let profiles = settings::get(app).profiles;
+
+    MenuSnapshot {
+        capturing: status.active,
+        paused: status.paused,
+        recording: recording::is_recording(app),
+        active_profile: profiles.active,
+        profiles: profiles.profiles.into_iter().map(|p| p.name).collect(),
+        devices: capture::devices(app),
+    }
+}
+
+/// `12:34`, or `1:02:34` past the hour.
+fn format_elapsed(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{}:{:02}:{:02}", hours, secs / 60 % 60, secs % 60),
+    }
+}
+
+fn capture_label(app: &AppHandle) -> String {
+    let status = capture::status(app);
+    let elapsed = status
+        .started_at
+        .map(|started_at| format_elapsed(now_ms().saturating_sub(started_at)))
+        .unwrap_or_default();
+
+    match (status.active, status.paused) {
+        (false, _) => "Start Capture".into(),
+        (true, false) => format!("Capturing… {}", elapsed),
+        (true, true) => format!("Paused {}", elapsed),
+    }
+}
+
+fn build_menu(
+    app: &AppHandle,
+    snapshot: &MenuSnapshot,
+) -> tauri::Result<(Menu<Wry>, MenuItem<Wry>)> {
+    let capture_item =
+        MenuItem::with_id(app, TOGGLE_CAPTURE, capture_label(app), true, None::<&str>)?;
+
+    let profile_menu = Submenu::new(app, format!("Profile: {}", snapshot.active_profile), true)?;
+    for name in &snapshot.profiles {
+        profile_menu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", PROFILE_PREFIX, name),
+            name,
+            true,
+            *name == snapshot.active_profile,
+            None::<&str>,
+        )?)?;
+    }
+
+    let device_menu = Submenu::new(app, "Input Device", true)?;
+    if snapshot.devices.devices.is_empty() {
+        device_menu.append(&MenuItem::new(app, "No inputs found", false, None::<&str>)?)?;
+    }
+    for device in &snapshot.devices.devices {
+        device_menu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{}{}", DEVICE_PREFIX, device.id),
+            &device.label,
+            true,
+            snapshot.devices.selected.as_ref() == Some(&device.id),
+            None::<&str>,
+        )?)?;
+    }
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &capture_item,
+            &MenuItem::with_id(app, TOGGLE_PANEL, "Show/Hide Signos", true, None::<&str>)?,
+            &MenuItem::with_id(
+                app,
+                MARK_MOMENT,
+                "Mark This Moment",
+                snapshot.recording,
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &profile_menu,
+            &device_menu,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, QUIT, "Quit Signos", true, None::<&str>)?,
+        ],
+    )?;
+
+    Ok((menu, capture_item))
+}
+
+/// Rebuilds the menu if the backend state changed, otherwise refreshes the elapsed time.
+///
+/// Menu calls block until the main thread runs them, and menu events lock the
+/// same state there, so no lock is held across them.
+fn refresh(app: &AppHandle) -> tauri::Result<()> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    let state = app.state::<TrayState>();
+    let current = snapshot(app);
+
+    let changed = {
+        let mut last = state.snapshot.lock().unwrap();
+        let changed = last.as_ref() != Some(&current);
+        if changed {
+            *last = Some(current.clone());
+        }
+        changed
+    };
+    if !changed {
+        let item = state.capture_item.lock().unwrap().clone();
+        if let Some(item) = item {
+            item.set_text(capture_label(app))?;
+        }
+        return Ok(());
+    }
+
+    let result = build_menu(app, &current)
+        .and_then(|(menu, capture_item)| tray.set_menu(Some(menu)).map(|_| capture_item));
+    match result {
+        Ok(capture_item) => {
+            *state.capture_item.lock().unwrap() = Some(capture_item);
+            Ok(())
+        }
+        Err(e) => {
+            // Try again on the next tick.
+            state.snapshot.lock().unwrap().take();
+            Err(e)
+        }
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    let result = match id {
+        TOGGLE_CAPTURE if capture::status(app).active => {
+            capture::request_stop(app).map_err(|e| e.to_string())
+        }
+        TOGGLE_CAPTURE => capture::request_start(app).map_err(|e| e.to_string()),
+        TOGGLE_PANEL => panel::toggle(app).map_err(|e| e.to_string()),
+        MARK_MOMENT => recording::mark(app, None).map(|_| ()),
+        QUIT => {
+            app.exit(0);
+            Ok(())
+        }
+        _ => match (
+            id.strip_prefix(PROFILE_PREFIX),
+            id.strip_prefix(DEVICE_PREFIX),
+        ) {
+            (Some(profile), _) => profiles::switch(app, profile).map(|_| ()),
+            (_, Some(device)) => capture::request_device(app, device).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Tray] '{}' failed: {}", id, e);
+    }
+
+    // Check items toggle themselves when clicked, so rebuild to restore the real state.
+    app.state::<TrayState>().snapshot.lock().unwrap().take();
+}
+
+/// Creates the tray icon and starts the watcher that keeps its menu in sync.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    app.manage(TrayState::default());
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Signos")
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+    refresh(app)?;
+
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(WATCH_INTERVAL);
+
+        if let Err(e) = refresh(&app) {
+            eprintln!("[Tray] Failed to update menu: {}", e);
+        }
+    });
+
+    Ok(())
+}
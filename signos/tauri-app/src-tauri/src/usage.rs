@@ -0,0 +1,166 @@
+//! Bytes sent and received per integration, and the metered connection mode
+//! that holds back large transfers.
+//!
+//! HTTP clients record their own requests; the webview reports its worker
+//! WebSocket traffic in batches. Totals are kept in `data_usage.json` until
+//! reset.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::retry::{self, Failure};
+use crate::util::now_ms;
+use crate::{settings, storage};
+
+const USAGE_FILE: &str = "data_usage.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DataUsageSettings {
+    /// Defers large transfers such as re-transcription uploads.
+    pub metered: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Integration {
+    /// Streaming transcription over the worker WebSocket.
+    CloudStt,
+    /// Batch uploads of recorded sessions.
+    Retranscription,
+    Summaries,
+    /// Plain-language rewrites from `simplify_text`.
+    Rewrites,
+    /// Sign lookups for the avatar and the share intent.
+    Signs,
+    /// `http_post` calls from user scripts.
+    Scripts,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+    pub sent: u64,
+    pub received: u64,
+    /// HTTP requests, or batches reported by the webview for streamed traffic.
+    pub requests: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataUsage {
+    /// Unix timestamp (ms) the totals started from.
+    pub since: u64,
+    pub integrations: BTreeMap<Integration, Usage>,
+    #[serde(skip_deserializing)]
+    pub metered: bool,
+}
+
+pub struct UsageState(Mutex<DataUsage>);
+
+fn empty() -> DataUsage {
+    DataUsage {
+        since: now_ms(),
+        integrations: BTreeMap::new(),
+        metered: false,
+    }
+}
+
+pub fn usage_path(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_dir(app)
+        .map(|dir| dir.join(USAGE_FILE))
+        .map_err(|e| e.to_string())
+}
+
+fn save(app: &AppHandle, usage: &DataUsage) {
+    let result = usage_path(app).and_then(|path| {
+        let json = serde_json::to_string_pretty(usage).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        eprintln!("[Usage] Failed to save data usage: {}", e);
+    }
+}
+
+pub fn is_metered(app: &AppHandle) -> bool {
+    settings::get(app).data_usage.metered
+}
+
+pub fn record(app: &AppHandle, integration: Integration, sent: u64, received: u64) {
+    let state = app.state::<UsageState>();
+    let mut usage = state.0.lock().unwrap();
+    let entry = usage.integrations.entry(integration).or_default();
+    entry.sent += sent;
+    entry.received += received;
+    entry.requests += 1;
+    save(app, &usage);
+}
+
+/// Attempts per JSON request, including the first.
+const ATTEMPTS: u32 = 3;
+
+/// POSTs `body` as JSON and parses the JSON response, recording both sizes.
+/// Transient failures are retried through the integration's breaker.
+pub fn post_json<R: DeserializeOwned>(
+    app: &AppHandle,
+    integration: Integration,
+    agent: &ureq::Agent,
+    url: &str,
+    body: &impl Serialize,
+) -> Result<R, String> {
+    let json = serde_json::to_string(body).map_err(|e| e.to_string())?;
+    let response = retry::call(app, integration, ATTEMPTS, || {
+        agent
+            .post(url)
+            .header("Content-Type", "application/json")
+            .send(&json)
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(Failure::from)
+    })?;
+
+    record(app, integration, json.len() as u64, response.len() as u64);
+    serde_json::from_str(&response).map_err(|e| e.to_string())
+}
+
+pub fn init(app: &AppHandle) {
+    let usage = usage_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_else(empty);
+    app.manage(UsageState(Mutex::new(usage)));
+}
+
+#[tauri::command]
+pub fn get_data_usage(app: AppHandle) -> DataUsage {
+    let mut usage = app.state::<UsageState>().0.lock().unwrap().clone();
+    usage.metered = is_metered(&app);
+    usage
+}
+
+#[tauri::command]
+pub fn reset_data_usage(app: AppHandle) -> DataUsage {
+    let usage = empty();
+    save(&app, &usage);
+    *app.state::<UsageState>().0.lock().unwrap() = usage;
+    get_data_usage(app)
+}
+
+#[tauri::command]
+pub fn set_metered(app: AppHandle, metered: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.data_usage.metered = metered)?;
+    let _ = app.emit("data-usage:metered", metered);
+    Ok(())
+}
+
+/// WebSocket traffic counted by the webview since its last report.
+#[tauri::command]
+pub fn report_data_usage(app: AppHandle, sent: u64, received: u64) {
+    record(&app, Integration::CloudStt, sent, received);
+}
@@ -0,0 +1,214 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use qrcode::{Color, QrCode};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Response;
+use tauri::{AppHandle, Manager};
+
+use crate::captions::Caption;
+use crate::{settings, sync};
+
+const DEFAULT_PORT: u16 = 28192;
+const PAGE: &str = include_str!("viewer.html");
+/// Comment lines keep idle connections from being dropped by phones and proxies.
+const KEEPALIVE: Duration = Duration::from_secs(15);
+const MAX_REQUEST: usize = 8 * 1024;
+/// Events queued for one client; a client this far behind has stalled and is dropped.
+const CLIENT_BACKLOG: usize = 64;
+/// QR rendering: pixels per module and the quiet zone in modules required around the code.
+const QR_SCALE: usize = 8;
+const QR_QUIET_ZONE: usize = 4;
+
+/// Read-only caption page for the audience, served on the LAN at `/` with
+/// live updates over server-sent events at `/events`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ViewerSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ViewerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ViewerState {
+    clients: Mutex<Vec<SyncSender<String>>>,
+}
+
+/// The address other devices reach us on. Connecting a UDP socket sends nothing.
+pub fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9)).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+/// `http://<lan ip>:<port>/`, or an error if the viewer is off.
+pub fn url(app: &AppHandle) -> Result<String, String> {
+    let config = settings::get(app).viewer;
+    if !config.enabled {
+        return Err("The caption viewer is disabled".into());
+    }
+
+    let ip = lan_ip().ok_or("No network connection")?;
+    Ok(format!("http://{}:{}/", ip, config.port))
+}
+
+pub fn send_caption(app: &AppHandle, caption: &Caption) {
+    let Some(state) = app.try_state::<ViewerState>() else {
+        return;
+    };
+    let Ok(json) = serde_json::to_string(caption) else {
+        return;
+    };
+
+    let event = format!("event: caption\ndata: {}\n\n", json);
+    state
+        .clients
+        .lock()
+        .unwrap()
+        .retain(|client| match client.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                eprintln!("[Viewer] Dropping a client that stopped reading");
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+}
+
+/// Reads the request head and returns its path. `sync::serve` gives the
+/// stream a read timeout, so a client that never finishes it is dropped.
+fn read_path(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 || request.len() > MAX_REQUEST {
+            return None;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&request);
+    let mut parts = head.lines().next()?.split_whitespace();
+    match (parts.next()?, parts.next()?) {
+        ("GET", path) => Some(path.split('?').next().unwrap_or(path).to_string()),
+        _ => None,
+    }
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+}
+
+fn stream_events(app: &AppHandle, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+
+    let (tx, rx) = mpsc::sync_channel(CLIENT_BACKLOG);
+    app.state::<ViewerState>().clients.lock().unwrap().push(tx);
+
+    loop {
+        match rx.recv_timeout(KEEPALIVE) {
+            Ok(event) => stream.write_all(event.as_bytes())?,
+            Err(RecvTimeoutError::Timeout) => stream.write_all(b": keepalive\n\n")?,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        stream.flush()?;
+    }
+}
+
+fn handle_client(app: &AppHandle, mut stream: TcpStream) -> Result<(), String> {
+    match read_path(&mut stream).as_deref() {
+        Some("/") => respond(&mut stream, "200 OK", "text/html; charset=utf-8", PAGE),
+        // The client disconnecting is the normal way out.
+        Some("/events") => {
+            let _ = stream_events(app, stream);
+        }
+        Some(_) => respond(&mut stream, "404 Not Found", "text/plain", "Not found"),
+        None => respond(&mut stream, "400 Bad Request", "text/plain", "Bad request"),
+    }
+    Ok(())
+}
+
+/// Starts the viewer server if it is enabled in settings.
+pub fn init(app: &AppHandle) {
+    app.manage(ViewerState::default());
+
+    let config = settings::get(app).viewer;
+    if !config.enabled {
+        return;
+    }
+
+    let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[Viewer] Failed to bind port {}: {}", config.port, e);
+            return;
+        }
+    };
+    if let Ok(url) = url(app) {
+        println!("[Viewer] Serving captions at {}", url);
+    }
+
+    sync::serve(app, listener, "Viewer", handle_client);
+}
+
+#[tauri::command]
+pub fn get_viewer_url(app: AppHandle) -> Result<String, String> {
+    url(&app)
+}
+
+/// Renders `text` as a black-on-white grayscale PNG QR code.
+fn qr_png(text: &str) -> Result<Vec<u8>, String> {
+    let code = QrCode::new(text).map_err(|e| e.to_string())?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + 2 * QR_QUIET_ZONE) * QR_SCALE;
+
+    let mut pixels = vec![0xFF; size * size];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let (x, y) = (i % modules + QR_QUIET_ZONE, i / modules + QR_QUIET_ZONE);
+        for row in y * QR_SCALE..(y + 1) * QR_SCALE {
+            pixels[row * size + x * QR_SCALE..row * size + (x + 1) * QR_SCALE].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+/// QR code PNG (raw bytes) for the viewer URL, for presenters to put on screen.
+#[tauri::command]
+pub fn get_viewer_qr(app: AppHandle) -> Result<Response, String> {
+    qr_png(&url(&app)?).map(Response::new)
+}
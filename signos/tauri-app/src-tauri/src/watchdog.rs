@@ -0,0 +1,202 @@
+//! Supervision for the capture and inference pipeline.
+//!
+//! Backend worker threads run under `spawn_supervised` and are restarted if
+//! they panic, unless they keep panicking. Capture runs in the webview, so the
+//! frontend sends a heartbeat while audio is flowing; if it stops while
+//! capture is active, capture is restarted, which reopens the devices. If the
+//! frontend itself stops beating, its webview has crashed or hung and is
+//! reloaded instead (see `replay`). Either way `pipeline:recovered` is emitted
+//! so the user knows captions may have a gap.
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::capture;
+use crate::panel::MAIN_WINDOW;
+use crate::util::now_ms;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Time without a capture heartbeat before capture counts as stalled.
+const STALL_TIMEOUT_MS: u64 = 6000;
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+/// A task that panics this many times within `RESTART_WINDOW` is given up on.
+const MAX_RESTARTS: u32 = 5;
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+/// Time without a frontend heartbeat, while capturing, before the webview is reloaded.
+const FRONTEND_TIMEOUT_MS: u64 = 10000;
+const CAPTURE_STOP_TIMEOUT: Duration = Duration::from_secs(3);
+pub const CAPTURE_TASK: &str = "capture";
+pub const FRONTEND_TASK: &str = "frontend";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecoveryReason {
+    Panicked,
+    Stalled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recovery {
+    pub task: String,
+    pub reason: RecoveryReason,
+    /// Recoveries of this task since launch.
+    pub restarts: u32,
+}
+
+#[derive(Default)]
+pub struct WatchdogState {
+    /// Last heartbeat per task (Unix ms).
+    beats: Mutex<HashMap<String, u64>>,
+    restarts: Mutex<HashMap<String, u32>>,
+}
+
+fn recovered(app: &AppHandle, task: &str, reason: RecoveryReason) {
+    let restarts = {
+        let state = app.state::<WatchdogState>();
+        let mut restarts = state.restarts.lock().unwrap();
+        let count = restarts.entry(task.to_string()).or_default();
+        *count += 1;
+        *count
+    };
+
+    let recovery = Recovery {
+        task: task.to_string(),
+        reason,
+        restarts,
+    };
+    println!("[Watchdog] Recovered {} ({:?})", task, reason);
+    let _ = app.emit("pipeline:recovered", &recovery);
+}
+
+/// Runs `body` on its own thread and runs it again if it panics. A normal
+/// return ends the task, and so do [`MAX_RESTARTS`] panics within
+/// [`RESTART_WINDOW`].
+pub fn spawn_supervised(
+    app: &AppHandle,
+    name: &'static str,
+    body: impl FnMut(&AppHandle) + Send + 'static,
+) {
+    spawn_supervised_with(app, name, |_| {}, body)
+}
+
+/// Like [`spawn_supervised`], with `on_panic` run before each restart to
+/// clean up what the panic left half done.
+pub fn spawn_supervised_with(
+    app: &AppHandle,
+    name: &'static str,
+    on_panic: impl Fn(&AppHandle) + Send + 'static,
+    mut body: impl FnMut(&AppHandle) + Send + 'static,
+) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut window = (Instant::now(), 0);
+        loop {
+            if panic::catch_unwind(AssertUnwindSafe(|| body(&app))).is_ok() {
+                return;
+            }
+            on_panic(&app);
+
+            if window.0.elapsed() > RESTART_WINDOW {
+                window = (Instant::now(), 0);
+            }
+            window.1 += 1;
+            if window.1 >= MAX_RESTARTS {
+                eprintln!("[Watchdog] {} keeps panicking, giving up", name);
+                return;
+            }
+            eprintln!("[Watchdog] {} panicked, restarting", name);
+            thread::sleep(RESTART_DELAY);
+            recovered(&app, name, RecoveryReason::Panicked);
+        }
+    });
+}
+
+pub fn heartbeat(app: &AppHandle, task: &str) {
+    app.state::<WatchdogState>()
+        .beats
+        .lock()
+        .unwrap()
+        .insert(task.to_string(), now_ms());
+}
+
+/// Stops and starts capture through the frontend, so it reopens its devices.
+fn restart_capture(app: &AppHandle) -> tauri::Result<()> {
+    capture::request_stop(app)?;
+    let deadline = Instant::now() + CAPTURE_STOP_TIMEOUT;
+    while capture::status(app).active && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    capture::request_start(app)
+}
+
+/// Whether `task` missed its heartbeat for `timeout_ms` during the current capture.
+fn stalled(app: &AppHandle, task: &str, timeout_ms: u64) -> bool {
+    let status = capture::status(app);
+    let Some(started_at) = status.started_at.filter(|_| status.active) else {
+        return false;
+    };
+
+    let last_beat = app
+        .state::<WatchdogState>()
+        .beats
+        .lock()
+        .unwrap()
+        .get(task)
+        .copied()
+        .unwrap_or(0);
+    // A capture that just started hasn't had time to beat yet.
+    now_ms().saturating_sub(last_beat.max(started_at)) > timeout_ms
+}
+
+fn reload_frontend(app: &AppHandle) -> tauri::Result<()> {
+    match app.get_webview_window(MAIN_WINDOW) {
+        Some(window) => window.reload(),
+        None => Ok(()),
+    }
+}
+
+/// Starts the thread that watches the capture and frontend heartbeats.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+
+        if stalled(&app, FRONTEND_TASK, FRONTEND_TIMEOUT_MS) {
+            eprintln!("[Watchdog] Frontend stopped responding, reloading it");
+            heartbeat(&app, FRONTEND_TASK);
+            heartbeat(&app, CAPTURE_TASK);
+            match reload_frontend(&app) {
+                Ok(()) => recovered(&app, FRONTEND_TASK, RecoveryReason::Stalled),
+                Err(e) => eprintln!("[Watchdog] Failed to reload frontend: {}", e),
+            }
+            continue;
+        }
+        if !stalled(&app, CAPTURE_TASK, STALL_TIMEOUT_MS) {
+            continue;
+        }
+        eprintln!(
+            "[Watchdog] No audio for {} ms, restarting capture",
+            STALL_TIMEOUT_MS
+        );
+        // Counts as a beat so the restart gets a full timeout to come up.
+        heartbeat(&app, CAPTURE_TASK);
+        match restart_capture(&app) {
+            Ok(()) => recovered(&app, CAPTURE_TASK, RecoveryReason::Stalled),
+            Err(e) => eprintln!("[Watchdog] Failed to restart capture: {}", e),
+        }
+    });
+}
+
+/// Sent by the frontend on a timer (`frontend`) and while audio chunks are arriving (`capture`).
+#[tauri::command]
+pub fn pipeline_heartbeat(app: AppHandle, task: String) {
+    heartbeat(&app, &task);
+}
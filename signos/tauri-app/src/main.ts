@@ -1,5 +1,8 @@
 // Signos - Minimal STT Client
 
+import { Channel, invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { join } from '@tauri-apps/api/path';
 import { exit } from '@tauri-apps/plugin-process';
 import { BaseDirectory, exists, readTextFile, writeTextFile, mkdir } from '@tauri-apps/plugin-fs';
 
@@ -9,6 +12,9 @@ interface TranscriptMessage {
     is_final: boolean;
     timestamp: number;
     latency_ms?: number;
+    language?: string;
+    speaker?: number;
+    model?: string;
 }
 
 interface ImagePath {
@@ -44,6 +50,12 @@ interface ErrorMessage {
     timestamp: number;
 }
 
+interface Profile {
+    name: string;
+    workerUrl?: string | null;
+    audioDeviceId?: string | null;
+}
+
 type ServerMessage = TranscriptMessage | SignsMessage | StatsMessage | ErrorMessage;
 type AppView = 'menu' | 'settings' | 'recording';
 
@@ -54,7 +66,186 @@ interface AppConfig {
     animationSpeed: 'slow' | 'normal' | 'fast';
 }
 
+interface AudioDevice {
+    id: string;
+    label: string;
+}
+
+interface LowQualityInput {
+    device: AudioDevice;
+    fallback: AudioDevice | null;
+    switched: boolean;
+}
+
+interface MonitorSettings {
+    enabled: boolean;
+    outputDevice: string | null;
+    gain: number;
+}
+
+// `setSinkId` is missing from some webviews (e.g. WKWebView)
+type SinkAudioElement = HTMLAudioElement & { setSinkId?: (sinkId: string) => Promise<void> };
+
+interface InferenceParams {
+    chunkMs: number;
+    contextSeconds: number;
+    beamSize: number;
+}
+
+interface CameraSettings {
+    deviceId: string | null;
+    fps: number;
+}
+
+interface CaptureFormat {
+    sampleRate: number;
+    channels: number;
+    channelMask: number;
+}
+
+interface AudioFileInfo {
+    path: string;
+    sampleRate: number;
+    channels: number;
+    durationMs: number;
+    speed: number;
+}
+
+interface CaptureSource {
+    label: string;
+    deviceId: string;
+}
+
+// An extra input with its own worker connection (see `add_source`)
+interface SourcePipeline {
+    ws: WebSocket;
+    mediaStream: MediaStream;
+    audioContext: AudioContext;
+    workletNode: AudioWorkletNode;
+}
+
+type SttProvider = 'cloud' | 'browser';
+
+interface ProviderChange {
+    provider: SttProvider;
+    reason: string;
+}
+
+// Minimal Web Speech API surface; not part of the DOM typings
+interface BrowserRecognitionResult {
+    isFinal: boolean;
+    0: { transcript: string };
+}
+
+interface BrowserRecognition {
+    lang: string;
+    continuous: boolean;
+    interimResults: boolean;
+    onresult: ((event: { resultIndex: number; results: ArrayLike<BrowserRecognitionResult> }) => void) | null;
+    onend: (() => void) | null;
+    start(): void;
+    stop(): void;
+}
+
+type BrowserRecognitionConstructor = new () => BrowserRecognition;
+
+interface OrphanedSession {
+    id: string;
+    startedAt: number;
+    endedAt: number | null;
+    segments: number;
+    durationMs: number;
+}
+
+interface ReplaySnapshot {
+    reloaded: boolean;
+    captions: { text: string; source: string | null }[];
+    capture: { active: boolean; paused: boolean; startedAt: number | null };
+    recording: boolean;
+}
+
+interface PipelineRecovery {
+    task: string;
+    reason: 'panicked' | 'stalled';
+    restarts: number;
+}
+
+interface AccessibilityPrefs {
+    increaseContrast: boolean;
+    reduceMotion: boolean;
+    reduceTransparency: boolean;
+    textScale: number;
+}
+
+interface CaptureEnding {
+    endsAt: number;
+    remainingMs: number;
+}
+
+interface PowerStatus {
+    lowPower: boolean;
+    manual: boolean;
+    system: boolean;
+}
+
+interface ThermalStatus {
+    level: 'nominal' | 'fair' | 'serious' | 'critical';
+    degraded: boolean;
+}
+
+interface SpeakRequest {
+    reply: number;
+    text: string;
+    voice?: string;
+    rate: number;
+}
+
+interface RelayStatus {
+    active: string[];
+    audio: boolean;
+}
+
+interface RelayCorrection {
+    interpreterId: string;
+    interpreter: string;
+    timestamp: number;
+    text: string;
+}
+
+interface Occlusion {
+    occluded: boolean;
+    mode?: 'exclusive' | 'borderless';
+    app?: string;
+    monitor?: number;
+    action?: 'raised' | 'moved' | 'hidden';
+}
+
+interface ResetScope {
+    settings: boolean;
+    models: boolean;
+    sessions: boolean;
+    credentials: boolean;
+}
+
+interface ConsentStatus {
+    required: boolean;
+    granted: boolean;
+}
+
+interface StorageInfo {
+    dataDir: string;
+    portable: boolean;
+}
+
 const CONFIG_FILE = 'config.json';
+const METRICS_FLUSH_INTERVAL_MS = 5000;
+const HEARTBEAT_INTERVAL_MS = 1000;
+// Square frames matching the hand landmark model input
+const CAMERA_FRAME_SIZE = 224;
+// Longest wait for a segment boundary before a loaded model is swapped in anyway
+const MODEL_SWAP_TIMEOUT_MS = 10000;
+// Keeps the old connection open briefly so signs for its last segment still arrive
+const MODEL_SWAP_GRACE_MS = 3000;
 const DEFAULT_CONFIG: AppConfig = {
     workerUrl: 'ws://localhost:8787',
     selectedDeviceId: '',
@@ -65,17 +256,38 @@ const DEFAULT_CONFIG: AppConfig = {
 class SignosClient {
     private currentView: AppView = 'menu';
     private ws: WebSocket | null = null;
+    private pendingWs: WebSocket | null = null;
+    private browserRecognizer: BrowserRecognition | null = null;
+    private modelSwapTimer: number | null = null;
+    private modelSwapGeneration = 0;
     private audioContext: AudioContext | null = null;
     private mediaStream: MediaStream | null = null;
     private audioWorkletNode: AudioWorkletNode | null = null;
+    private isNativeCapture = false;
+    private isFileFeed = false;
+    private sourcePipelines = new Map<string, SourcePipeline>();
+    private monitorGain: GainNode | null = null;
+    private monitorAudio: SinkAudioElement | null = null;
+    private cameraStream: MediaStream | null = null;
+    private cameraTimer: number | null = null;
     private isRecording: boolean = false;
+    private isPaused: boolean = false;
+    private droppedFrames: number = 0;
+    private bytesSent = 0;
+    private lastHeartbeatAt = 0;
+    private bytesReceived = 0;
+    private isSavingAudio: boolean = false;
+    private isRelayingAudio: boolean = false;
     private workerUrl: string = 'ws://localhost:8787';
     private selectedDeviceId: string = '';
+    private audioDevices: AudioDevice[] = [];
 
     // Sign display state
     private signDisplayQueue: SignInfo[] = [];
     private isDisplayingSign: boolean = false;
     private config: AppConfig = DEFAULT_CONFIG;
+    private configPath: string = CONFIG_FILE;
+    private configOptions: { baseDir?: BaseDirectory } = { baseDir: BaseDirectory.AppData };
 
     private views: {
         menu: HTMLElement;
@@ -87,6 +299,7 @@ class SignosClient {
         startBtn: HTMLButtonElement;
         settingsBtn: HTMLButtonElement;
         exitBtn: HTMLButtonElement;
+        resetSettingsBtn: HTMLButtonElement;
         stopBtn: HTMLButtonElement;
         toggleDebugBtn: HTMLButtonElement;
         workerUrlInput: HTMLInputElement;
@@ -118,6 +331,7 @@ class SignosClient {
             startBtn: document.getElementById('startBtn') as HTMLButtonElement,
             settingsBtn: document.getElementById('settingsBtn') as HTMLButtonElement,
             exitBtn: document.getElementById('exitBtn') as HTMLButtonElement,
+            resetSettingsBtn: document.getElementById('resetSettingsBtn') as HTMLButtonElement,
             stopBtn: document.getElementById('stopBtn') as HTMLButtonElement,
             toggleDebugBtn: document.getElementById('toggleDebugBtn') as HTMLButtonElement,
             workerUrlInput: document.getElementById('workerUrl') as HTMLInputElement,
@@ -143,17 +357,358 @@ class SignosClient {
     private async initialize(): Promise<void> {
         await this.loadConfig();
         await this.loadAudioDevices();
-        this.showView('menu');
+        await this.listenToBackend();
+        await this.resumeAfterReload();
+        // Lets the backend run startup actions (CLI flags) that need the listeners above
+        invoke('frontend_ready')
+            .catch((error) => console.error('[Startup] Failed to report ready:', error));
+        setInterval(() => this.flushMetrics(), METRICS_FLUSH_INTERVAL_MS);
+        // Without these the backend assumes the webview hung and reloads it
+        setInterval(() => {
+            invoke('pipeline_heartbeat', { task: 'frontend' })
+                .catch((error) => console.error('[Watchdog] Failed to send heartbeat:', error));
+        }, HEARTBEAT_INTERVAL_MS);
+        if (!this.isRecording) {
+            this.showView('menu');
+        }
+    }
+
+    // After a reload or webview crash the backend still holds the live session
+    private async resumeAfterReload(): Promise<void> {
+        const snapshot = await invoke<ReplaySnapshot>('get_replay_snapshot');
+        if (!snapshot.reloaded) {
+            return;
+        }
+        this.log('Reconnected after a reload', 'info');
+        for (const caption of snapshot.captions) {
+            this.log(caption.source ? `📝 [${caption.source}] "${caption.text}"` : `📝 "${caption.text}"`, 'success');
+        }
+        this.isSavingAudio = snapshot.recording;
+        if (!snapshot.capture.active) {
+            return;
+        }
+
+        await this.start();
+        if (!this.isRecording) {
+            // Capture couldn't come back, so the backend shouldn't think it's running
+            await this.reportCaptureState(false);
+            return;
+        }
+        this.isPaused = snapshot.capture.paused;
+    }
+
+    // Dropped frames and worker traffic are counted locally and reported in batches
+    private flushMetrics(): void {
+        if (this.bytesSent > 0 || this.bytesReceived > 0) {
+            invoke('report_data_usage', { sent: this.bytesSent, received: this.bytesReceived })
+                .catch((error) => console.error('[Usage] Failed to report data usage:', error));
+            this.bytesSent = 0;
+            this.bytesReceived = 0;
+        }
+        if (this.droppedFrames === 0) {
+            return;
+        }
+        invoke('report_dropped_frames', { count: this.droppedFrames })
+            .catch((error) => console.error('[Metrics] Failed to report dropped frames:', error));
+        this.droppedFrames = 0;
+    }
+
+    private reportError(source: string): void {
+        invoke('report_error', { source })
+            .catch((error) => console.error('[Metrics] Failed to report error:', error));
+        invoke('play_feedback', { kind: 'error' })
+            .catch((error) => console.error('[Feedback] Failed to play error cue:', error));
+    }
+
+    // Capture can also be started/stopped by the backend (scheduler, tray, hotkeys...)
+    private async listenToBackend(): Promise<void> {
+        await listen('capture:start', () => {
+            if (!this.isRecording) {
+                this.start();
+            }
+        });
+
+        await listen('capture:stop', () => {
+            if (this.isRecording) {
+                this.stop();
+            }
+        });
+
+        await listen<string>('model:changed', async () => {
+            if (this.isRecording) {
+                await this.prepareModelSwap();
+            }
+        });
+
+        await listen<InferenceParams>('inference:changed', async (event) => {
+            this.audioWorkletNode?.port.postMessage({ chunkSamples: event.payload.chunkMs * 16 });
+            if (this.isRecording) {
+                await this.prepareModelSwap();
+            }
+        });
+
+        // Chunk size and camera rate follow through inference:changed and camera:start
+        await listen<PowerStatus>('power:changed', (event) => {
+            const { lowPower, system } = event.payload;
+            this.log(`Low-power mode ${lowPower ? 'on' : 'off'}${lowPower && system ? ' (system)' : ''}`, 'info');
+        });
+
+        await listen<ThermalStatus>('thermal:changed', (event) => {
+            if (event.payload.degraded) {
+                this.log(`The system is running hot (${event.payload.level}): sign recognition is reduced until it cools down`, 'error');
+            } else {
+                this.log(`Thermal state: ${event.payload.level}`, 'info');
+            }
+        });
+
+        // Conversation mode voices the user's replies; the backend hands the floor back once done
+        await listen<SpeakRequest>('conversation:speak', (event) => {
+            const { reply, text, voice, rate } = event.payload;
+            const utterance = new SpeechSynthesisUtterance(text);
+            utterance.rate = rate;
+            utterance.voice = speechSynthesis.getVoices().find((v) => v.name === voice) ?? null;
+            const finish = () => {
+                invoke('finish_speaking', { reply })
+                    .catch((error) => console.error('[Conversation] Failed to report reply spoken:', error));
+            };
+            utterance.onend = finish;
+            utterance.onerror = finish;
+            speechSynthesis.speak(utterance);
+            this.log(`🗣️ "${text}"`, 'info');
+        });
+
+        // Audio goes to the backend while an interpreter relay is allowed to hear it
+        await listen<RelayStatus>('relay:state', (event) => {
+            this.isRelayingAudio = event.payload.audio;
+        });
+
+        await listen<RelayCorrection>('relay:correction', (event) => {
+            const { interpreter, text } = event.payload;
+            this.log(`✏️ ${interpreter}: "${text}"`, 'info');
+        });
+
+        // Exclusive fullscreen games cover topmost windows; say so instead of failing silently
+        await listen<Occlusion>('display:occluded', (event) => {
+            const { action, app, monitor } = event.payload;
+            if (action === 'hidden') {
+                this.log(`Captions can't be shown over ${app ?? 'the fullscreen app'}; switch it to borderless or windowed`, 'error');
+            } else if (action === 'moved') {
+                this.log(`Panel moved off monitor ${monitor} while ${app ?? 'a fullscreen app'} runs`, 'info');
+            }
+        });
+
+        // Layout presets set the panel's opacity; the window itself is transparent
+        await listen<number>('layout:opacity', (event) => {
+            document.body.style.opacity = String(event.payload);
+        });
+
+        await listen<ResetScope>('app:reset', (event) => {
+            const parts = Object.entries(event.payload).filter(([, selected]) => selected).map(([part]) => part);
+            this.log(`Removed ${parts.join(', ')}`, 'success');
+        });
+
+        await listen<CaptureFormat>('capture:format', async (event) => {
+            this.log(`Capture format: ${event.payload.sampleRate} Hz, ${event.payload.channels} channel(s)`, 'info');
+            if (this.isRecording && !this.isNativeCapture && !this.isFileFeed) {
+                await this.stop();
+                await this.start();
+            }
+        });
+
+        await listen<CameraSettings>('camera:start', async (event) => {
+            try {
+                await this.startCamera(event.payload);
+            } catch (error: unknown) {
+                const message = error instanceof Error ? error.message : String(error);
+                this.log(`Failed to start camera: ${message}`, 'error');
+            }
+        });
+
+        await listen('camera:stop', async () => {
+            await this.stopCamera();
+        });
+
+        await listen<MonitorSettings>('capture:monitor', async (event) => {
+            if (this.isRecording && !this.isNativeCapture && !this.isFileFeed) {
+                await this.applyMonitor(event.payload);
+            }
+        });
+
+        await listen<CaptureSource[]>('capture:sources', async (event) => {
+            if (this.isRecording) {
+                await this.syncSources(event.payload);
+            }
+        });
+
+        // While paused the connection stays open but no audio is sent
+        await listen<boolean>('capture:paused', (event) => {
+            this.isPaused = event.payload;
+            this.log(this.isPaused ? 'Capture paused' : 'Capture resumed', 'info');
+        });
+
+        // The language is read on connect, so reconnect to apply it
+        await listen<string>('language:changed', async (event) => {
+            this.log(`Language switched to ${event.payload}`, 'info');
+            if (this.isRecording) {
+                await this.stop();
+                await this.start();
+            }
+        });
+
+        // While a session recording is active, audio is also sent to the backend
+        await listen<boolean>('recording:state', (event) => {
+            this.isSavingAudio = event.payload;
+            this.log(this.isSavingAudio ? 'Session recording started' : 'Session recording saved', 'info');
+        });
+
+        // Profile overrides apply on top of the saved config
+        await listen<Profile>('profile:changed', (event) => {
+            const profile = event.payload;
+            this.workerUrl = profile.workerUrl || this.config.workerUrl;
+            this.selectedDeviceId = profile.audioDeviceId || this.config.selectedDeviceId;
+            console.log(`[Profile] Switched to ${profile.name}`);
+            this.reportAudioDevices();
+        });
+
+        // Signs for text sent from OS automation (Services, Shortcuts links)
+        await listen<SignsMessage>('signs:received', async (event) => {
+            await this.handleSigns(event.payload);
+        });
+
+        // Repeated failed launches: integrations are off and settings are defaults
+        await listen<{ failedLaunches: number }>('startup:safe-mode', (event) => {
+            this.log(`Safe mode after ${event.payload.failedLaunches} failed launches`, 'error');
+            this.elements.resetSettingsBtn.classList.remove('hidden');
+        });
+
+        await listen<LowQualityInput>('capture:low-quality-input', (event) => {
+            const { device, fallback, switched } = event.payload;
+            this.log(`"${device.label}" is a Bluetooth headset in hands-free mode; transcription accuracy will suffer`, 'error');
+            if (switched && fallback) {
+                this.log(`Switched to "${fallback.label}"`, 'info');
+            } else if (fallback) {
+                this.log(`Select "${fallback.label}" for better accuracy`, 'info');
+            }
+        });
+
+        // Worker unreachable or back: the backend picks the recognizer
+        await listen<ProviderChange>('transcription:provider', async (event) => {
+            await this.applyProvider(event.payload);
+        });
+
+        // A capture started with start_capture_for is about to stop itself
+        await listen<CaptureEnding>('capture:ending', (event) => {
+            const seconds = Math.round(event.payload.remainingMs / 1000);
+            this.log(`Capture time limit: stopping in ${seconds}s`, 'info');
+        });
+
+        // A start was held back, or capture came up and was stopped, for lack of consent
+        await listen('consent:required', async () => {
+            this.log('Consent is required before capturing', 'info');
+            if (!this.isRecording) {
+                await this.start();
+            }
+        });
+
+        // The previous run was killed mid-session; its transcript was already recovered
+        await listen<OrphanedSession>('recording:orphaned', async (event) => {
+            const session = event.payload;
+            const minutes = Math.round(session.durationMs / 60000);
+            this.log(`Recovered interrupted session ${session.id} (${session.segments} segments)`, 'info');
+            const resume = window.confirm(`Signos closed unexpectedly during a ${minutes} min session. Resume capturing into it?`);
+            try {
+                if (resume) {
+                    await invoke('resume_session', { id: session.id });
+                } else {
+                    await invoke('dismiss_orphaned_session');
+                }
+            } catch (error) {
+                this.log(`Failed to resume session: ${error}`, 'error');
+            }
+        });
+
+        await listen<PipelineRecovery>('pipeline:recovered', (event) => {
+            const { task, reason } = event.payload;
+            this.log(`Recovered ${task} after it ${reason === 'stalled' ? 'stalled' : 'crashed'}`, 'error');
+        });
+
+        // OS contrast, motion and text size preferences
+        await listen<AccessibilityPrefs>('accessibility:changed', (event) => {
+            this.applyAccessibility(event.payload);
+        });
+        invoke<AccessibilityPrefs>('get_accessibility_prefs')
+            .then((prefs) => this.applyAccessibility(prefs))
+            .catch((error) => console.error('[Accessibility] Failed to read preferences:', error));
+
+        // Input picked from the tray menu
+        await listen<string>('capture:device', async (event) => {
+            this.elements.audioInputSelect.value = event.payload;
+            await this.saveConfig();
+            await this.reportAudioDevices();
+            this.log('Audio input changed', 'info');
+            if (this.isRecording) {
+                await this.stop();
+                await this.start();
+            }
+        });
+    }
+
+    private applyAccessibility(prefs: AccessibilityPrefs): void {
+        const root = document.documentElement;
+        root.classList.toggle('high-contrast', prefs.increaseContrast);
+        root.classList.toggle('reduce-motion', prefs.reduceMotion);
+        root.classList.toggle('reduce-transparency', prefs.reduceTransparency);
+        root.style.setProperty('--text-scale', String(prefs.textScale));
+    }
+
+    // Onboarding progress lives in the backend so the wizard survives restarts
+    private completeOnboardingStep(step: 'permissions' | 'device' | 'model' | 'hotkey'): void {
+        invoke('complete_step', { step })
+            .catch((error) => console.error(`[Onboarding] Failed to complete ${step}:`, error));
+    }
+
+    // The tray lists the inputs, but only the webview can enumerate them
+    private async reportAudioDevices(): Promise<void> {
+        try {
+            await invoke('report_audio_devices', {
+                devices: { devices: this.audioDevices, selected: this.selectedDeviceId || null },
+            });
+        } catch (error) {
+            console.error('[AudioDevices] Failed to report devices:', error);
+        }
+    }
+
+    private async reportCaptureState(active: boolean): Promise<void> {
+        try {
+            await invoke('report_capture_state', { active });
+        } catch (error) {
+            console.error('[Capture] Failed to report state:', error);
+        }
+    }
+
+    // Portable installs keep config.json beside the executable instead of in AppData
+    private async resolveConfigPath(): Promise<void> {
+        try {
+            const storage = await invoke<StorageInfo>('get_storage_info');
+            if (storage.portable) {
+                this.configPath = await join(storage.dataDir, CONFIG_FILE);
+                this.configOptions = {};
+                console.log('[Config] Portable mode, using', this.configPath);
+            }
+        } catch (error) {
+            console.error('[Config] Failed to resolve storage:', error);
+        }
     }
 
     private async loadConfig(): Promise<void> {
+        await this.resolveConfigPath();
         try {
             console.log('[Config] Checking if config exists...');
-            const configExists: boolean = await exists(CONFIG_FILE, { baseDir: BaseDirectory.AppData });
+            const configExists: boolean = await exists(this.configPath, this.configOptions);
             console.log('[Config] Config exists:', configExists);
 
             if (configExists) {
-                const configJson: string = await readTextFile(CONFIG_FILE, { baseDir: BaseDirectory.AppData });
+                const configJson: string = await readTextFile(this.configPath, this.configOptions);
                 this.config = { ...DEFAULT_CONFIG, ...JSON.parse(configJson) };
                 console.log('[Config] Loaded config:', this.config);
 
@@ -187,16 +742,16 @@ class SignosClient {
 
             console.log('[Config] Saving config:', this.config);
 
-            // Ensure directory exists
-            try {
-                await mkdir('', { baseDir: BaseDirectory.AppData, recursive: true });
-            } catch (e) {
-                // Directory might already exist, ignore error
+            // Ensure directory exists (the backend creates the portable one)
+            if (this.configOptions.baseDir !== undefined) {
+                try {
+                    await mkdir('', { baseDir: this.configOptions.baseDir, recursive: true });
+                } catch (e) {
+                    // Directory might already exist, ignore error
+                }
             }
 
-            await writeTextFile(CONFIG_FILE, JSON.stringify(this.config, null, 2), {
-                baseDir: BaseDirectory.AppData,
-            });
+            await writeTextFile(this.configPath, JSON.stringify(this.config, null, 2), this.configOptions);
             console.log('[Config] Config saved successfully');
         } catch (error: unknown) {
             const message = error instanceof Error ? error.message : String(error);
@@ -208,10 +763,17 @@ class SignosClient {
         this.elements.startBtn.addEventListener('click', () => this.start());
         this.elements.settingsBtn.addEventListener('click', () => this.openSettings());
         this.elements.exitBtn.addEventListener('click', () => this.exitApp());
+        this.elements.resetSettingsBtn.addEventListener('click', () => this.resetSettings());
         this.elements.stopBtn.addEventListener('click', () => this.stop());
         this.elements.toggleDebugBtn.addEventListener('click', () => this.toggleDebug());
         this.elements.saveSettingsBtn.addEventListener('click', async () => await this.saveSettings());
         this.elements.cancelSettingsBtn.addEventListener('click', () => this.showView('menu'));
+        // The native menu stays usable in captions-only layouts without buttons
+        document.addEventListener('contextmenu', (event) => {
+            event.preventDefault();
+            invoke('show_context_menu', { x: event.clientX, y: event.clientY })
+                .catch((error) => console.error('[ContextMenu] Failed to show menu:', error));
+        });
     }
 
     private toggleDebug(): void {
@@ -243,6 +805,7 @@ class SignosClient {
             // Request microphone permission first to get device labels
             const stream: MediaStream = await navigator.mediaDevices.getUserMedia({ audio: true });
             console.log('[AudioDevices] Permission granted, stream:', stream);
+            this.completeOnboardingStep('permissions');
             stream.getTracks().forEach((track: MediaStreamTrack) => track.stop());
 
             const devices: MediaDeviceInfo[] = await navigator.mediaDevices.enumerateDevices();
@@ -298,6 +861,12 @@ class SignosClient {
             }
 
             console.log('[AudioDevices] Load complete, total inputs:', audioInputs.length);
+
+            this.audioDevices = audioInputs.map((device: MediaDeviceInfo, index: number) => ({
+                id: device.deviceId,
+                label: device.label || `Audio Input ${index + 1}`,
+            }));
+            await this.reportAudioDevices();
         } catch (error: unknown) {
             const message = error instanceof Error ? error.message : String(error);
             console.error('[AudioDevices] Failed to enumerate devices:', message, error);
@@ -329,15 +898,34 @@ class SignosClient {
         console.log(`[${type.toUpperCase()}] ${message}`);
     }
 
+    // With the consent policy on, capture waits until the user confirms everyone agreed
+    private async ensureConsent(): Promise<boolean> {
+        const status = await invoke<ConsentStatus>('get_consent_status');
+        if (!status.required || status.granted) {
+            return true;
+        }
+        if (!window.confirm('Everyone present must agree to being captured. Have they all consented?')) {
+            this.log('Capture not started: consent was not confirmed', 'info');
+            return false;
+        }
+        await invoke('confirm_consent');
+        return true;
+    }
+
     private async start(): Promise<void> {
         try {
+            if (!(await this.ensureConsent())) {
+                return;
+            }
             this.showView('recording');
             this.log('Starting recording...', 'info');
 
             await this.connectWebSocket();
             await this.startAudioCapture();
+            await this.syncSources(await invoke<CaptureSource[]>('list_sources'));
 
             this.isRecording = true;
+            await this.reportCaptureState(true);
             this.log('Recording started', 'success');
         } catch (error: unknown) {
             const message = error instanceof Error ? error.message : String(error);
@@ -350,6 +938,23 @@ class SignosClient {
         this.log('Stopping recording...', 'info');
 
         this.isRecording = false;
+        this.isPaused = false;
+        await this.reportCaptureState(false);
+
+        await this.syncSources([]);
+        this.stopMonitor();
+
+        if (this.isNativeCapture) {
+            await invoke('stop_native_capture')
+                .catch((error) => console.error('[Capture] Failed to stop native capture:', error));
+            this.isNativeCapture = false;
+        }
+
+        if (this.isFileFeed) {
+            await invoke('stop_audio_feed')
+                .catch((error) => console.error('[Capture] Failed to stop audio feed:', error));
+            this.isFileFeed = false;
+        }
 
         if (this.audioWorkletNode) {
             this.audioWorkletNode.disconnect();
@@ -370,6 +975,8 @@ class SignosClient {
             this.ws.close();
             this.ws = null;
         }
+        this.cancelModelSwap();
+        this.stopBrowserRecognizer();
 
         // Clear displays
         this.signDisplayQueue = [];
@@ -390,27 +997,60 @@ class SignosClient {
         this.workerUrl = this.elements.workerUrlInput.value.trim();
         this.selectedDeviceId = this.elements.audioInputSelect.value;
         await this.saveConfig();
+        await this.reportAudioDevices();
+        if (this.selectedDeviceId) {
+            this.completeOnboardingStep('device');
+        }
         this.showView('menu');
     }
 
+    // Deletes the backend settings and restarts the app
+    private async resetSettings(): Promise<void> {
+        try {
+            await invoke('reset_settings');
+        } catch (error) {
+            this.log(`Failed to reset settings: ${error}`, 'error');
+        }
+    }
+
     private async exitApp(): Promise<void> {
         if (this.isRecording) {
             await this.stop();
         }
-        await exit(0);
+        try {
+            await invoke('quit_app', { save: true });
+        } catch (error) {
+            console.error('[Lifecycle] Failed to quit cleanly:', error);
+            await exit(0);
+        }
+    }
+
+    // Worker URL with the language, model and inference parameters from the backend
+    private async workerSocketUrl(): Promise<string> {
+        const language = await invoke<string>('get_language');
+        const model = await invoke<string>('get_model');
+        const inference = await invoke<InferenceParams>('get_inference_params');
+
+        const url = new URL(this.workerUrl);
+        url.searchParams.set('language', language);
+        url.searchParams.set('model', model);
+        url.searchParams.set('context_seconds', String(inference.contextSeconds));
+        url.searchParams.set('beam_size', String(inference.beamSize));
+        return url.toString();
     }
 
     private async connectWebSocket(): Promise<void> {
-        return new Promise((resolve: (value: void) => void, reject: (reason?: Error) => void) => {
-            if (!this.workerUrl) {
-                reject(new Error('Please configure Worker URL in settings'));
-                return;
-            }
+        if (!this.workerUrl) {
+            throw new Error('Please configure Worker URL in settings');
+        }
+        const url = await this.workerSocketUrl();
 
+        return new Promise((resolve: (value: void) => void, reject: (reason?: Error) => void) => {
             this.log(`Connecting to ${this.workerUrl}...`, 'info');
 
             try {
-                this.ws = new WebSocket(this.workerUrl);
+                const ws = new WebSocket(url);
+                this.ws = ws;
 
                 this.ws.onopen = () => {
                     this.log('WebSocket connected', 'success');
@@ -423,11 +1063,13 @@ class SignosClient {
 
                 this.ws.onerror = () => {
                     this.log('WebSocket error', 'error');
+                    this.reportError('websocket');
                     reject(new Error('WebSocket connection failed'));
                 };
 
                 this.ws.onclose = () => {
                     this.log('WebSocket disconnected', 'info');
+                    this.handleSocketClosed(ws);
                 };
             } catch (error) {
                 reject(error as Error);
@@ -435,13 +1077,179 @@ class SignosClient {
         });
     }
 
+    // ========================================
+    // Offline Fallback
+    // ========================================
+
+    // Only an unexpected close of the socket in use counts as a drop
+    private handleSocketClosed(ws: WebSocket): void {
+        if (this.isRecording && this.ws === ws) {
+            this.ws = null;
+            invoke('report_stt_dropped')
+                .catch((error) => console.error('[Connectivity] Failed to report dropped connection:', error));
+        }
+    }
+
+    private async applyProvider(change: ProviderChange): Promise<void> {
+        if (!this.isRecording) {
+            return;
+        }
+
+        if (change.provider === 'browser') {
+            this.log(`Worker unreachable (${change.reason}); using the browser speech recognizer`, 'error');
+            this.cancelModelSwap();
+            if (this.ws) {
+                const ws = this.ws;
+                this.ws = null;
+                ws.close();
+            }
+            await this.startBrowserRecognizer();
+            return;
+        }
+
+        this.stopBrowserRecognizer();
+        this.log('Worker reachable again; switching back', 'info');
+        try {
+            await this.connectWebSocket();
+        } catch (error: unknown) {
+            const message = error instanceof Error ? error.message : String(error);
+            this.log(`Failed to reconnect: ${message}`, 'error');
+            invoke('report_stt_dropped')
+                .catch((error) => console.error('[Connectivity] Failed to report dropped connection:', error));
+        }
+    }
+
+    private async startBrowserRecognizer(): Promise<void> {
+        if (this.browserRecognizer) {
+            return;
+        }
+        const speechWindow = window as unknown as {
+            SpeechRecognition?: BrowserRecognitionConstructor;
+            webkitSpeechRecognition?: BrowserRecognitionConstructor;
+        };
+        const Recognition = speechWindow.SpeechRecognition ?? speechWindow.webkitSpeechRecognition;
+        if (!Recognition) {
+            this.log('Speech recognition is not available in this webview', 'error');
+            return;
+        }
+
+        // `multi` has no Web Speech equivalent, so use the system language
+        const language = await invoke<string>('get_language');
+        const recognizer = new Recognition();
+        recognizer.lang = language === 'en' ? 'en-US' : navigator.language;
+        recognizer.continuous = true;
+        recognizer.interimResults = true;
+
+        recognizer.onresult = (event) => {
+            for (let i = event.resultIndex; i < event.results.length; i++) {
+                const result = event.results[i];
+                this.handleTranscript({
+                    type: 'transcript',
+                    text: result[0].transcript.trim(),
+                    is_final: result.isFinal,
+                    timestamp: Date.now(),
+                    model: 'browser',
+                });
+            }
+        };
+        // Continuous recognition still ends on long silences
+        recognizer.onend = () => {
+            if (this.browserRecognizer === recognizer) {
+                recognizer.start();
+            }
+        };
+
+        this.browserRecognizer = recognizer;
+        recognizer.start();
+    }
+
+    private stopBrowserRecognizer(): void {
+        const recognizer = this.browserRecognizer;
+        this.browserRecognizer = null;
+        recognizer?.stop();
+    }
+
+    // ========================================
+    // Model Swapping
+    // ========================================
+
+    // Connects with the new model or parameters while the current connection keeps transcribing
+    private async prepareModelSwap(): Promise<void> {
+        this.cancelModelSwap();
+        const generation = ++this.modelSwapGeneration;
+        const model = await invoke<string>('get_model');
+        this.log(`Loading model ${model}...`, 'info');
+
+        const ws = new WebSocket(await this.workerSocketUrl());
+        try {
+            await new Promise<void>((resolve, reject) => {
+                ws.onopen = () => resolve();
+                ws.onerror = () => reject(new Error('WebSocket connection failed'));
+            });
+        } catch (error: unknown) {
+            const message = error instanceof Error ? error.message : String(error);
+            this.log(`Failed to load model ${model}: ${message}`, 'error');
+            return;
+        }
+
+        // Capture may have stopped, or another model been picked, while connecting
+        if (!this.isRecording || generation !== this.modelSwapGeneration) {
+            ws.close();
+            return;
+        }
+
+        ws.onmessage = (event: MessageEvent) => this.handleMessage(event.data);
+        ws.onerror = () => this.reportError('websocket');
+        ws.onclose = () => {
+            this.log('WebSocket disconnected', 'info');
+            this.handleSocketClosed(ws);
+        };
+
+        this.pendingWs = ws;
+        this.modelSwapTimer = window.setTimeout(() => this.swapModel(), MODEL_SWAP_TIMEOUT_MS);
+        this.log(`Model ${model} loaded; switching at the next segment`, 'info');
+    }
+
+    // Routes audio to the loaded model; called at a segment boundary
+    private swapModel(): void {
+        if (!this.pendingWs) {
+            return;
+        }
+        if (this.modelSwapTimer !== null) {
+            window.clearTimeout(this.modelSwapTimer);
+            this.modelSwapTimer = null;
+        }
+
+        const previous = this.ws;
+        this.ws = this.pendingWs;
+        this.pendingWs = null;
+        window.setTimeout(() => previous?.close(), MODEL_SWAP_GRACE_MS);
+
+        this.log('Switched model', 'success');
+    }
+
+    private cancelModelSwap(): void {
+        if (this.modelSwapTimer !== null) {
+            window.clearTimeout(this.modelSwapTimer);
+            this.modelSwapTimer = null;
+        }
+        if (this.pendingWs) {
+            this.pendingWs.close();
+            this.pendingWs = null;
+        }
+    }
+
     private handleMessage(data: string): void {
+        this.bytesReceived += data.length;
         try {
             const message: ServerMessage = JSON.parse(data);
 
             switch (message.type) {
                 case 'transcript':
                     this.handleTranscript(message);
+                    if (message.is_final && this.pendingWs) {
+                        this.swapModel();
+                    }
                     break;
                 case 'signs':
                     this.handleSigns(message);
@@ -461,12 +1269,31 @@ class SignosClient {
         }
     }
 
-    private handleTranscript(message: TranscriptMessage): void {
+    private async handleTranscript(message: TranscriptMessage, source: string | null = null): Promise<void> {
+        // The backend runs the text plugins and fans captions out to its outputs (OSC, ...)
+        let text = message.text;
+        try {
+            const caption = await invoke<{ text: string }>('push_caption', {
+                caption: {
+                    text: message.text,
+                    isFinal: message.is_final,
+                    timestamp: message.timestamp,
+                    latencyMs: message.latency_ms ?? null,
+                    language: message.language ?? null,
+                    speaker: message.speaker ?? null,
+                    source,
+                },
+            });
+            text = caption.text;
+        } catch (error) {
+            console.error('[Captions] Failed to forward caption:', error);
+        }
+
         if (message.is_final) {
-            this.log(`📝 "${message.text}"`, 'success');
+            this.log(source ? `📝 [${source}] "${text}"` : `📝 "${text}"`, 'success');
         } else {
             // Log interim transcript
-            console.log(`[Transcript] Interim: "${message.text}"`);
+            console.log(`[Transcript] Interim: "${text}"`);
         }
     }
 
@@ -489,6 +1316,7 @@ class SignosClient {
 
     private handleError(message: ErrorMessage): void {
         this.log(`Server error: ${message.error}`, 'error');
+        this.reportError('worker');
     }
 
     // ========================================
@@ -550,51 +1378,312 @@ class SignosClient {
     // ========================================
 
     private async startAudioCapture(): Promise<void> {
+        // A file queued with feed_audio_file stands in for the microphone.
+        if (await invoke<boolean>('audio_feed_pending')) {
+            const onAudio = new Channel<ArrayBuffer>();
+            onAudio.onmessage = (pcmData) => this.handleAudioChunk(pcmData);
+            const file = await invoke<AudioFileInfo>('start_audio_feed', { onAudio });
+            this.isFileFeed = true;
+            this.log(`Streaming ${file.path} at ${file.speed}x`, 'success');
+            return;
+        }
+
         this.log('Requesting audio access...', 'info');
 
+        // iOS captures through AVAudioEngine so audio keeps flowing in the background.
+        if (await invoke<boolean>('native_capture_available')) {
+            const onAudio = new Channel<ArrayBuffer>();
+            onAudio.onmessage = (pcmData) => this.handleAudioChunk(pcmData);
+            await invoke('start_native_capture', { onAudio });
+            this.isNativeCapture = true;
+            this.log('Native audio pipeline initialized', 'success');
+            return;
+        }
+
+        // Anything but 16 kHz mono is captured raw and converted by the backend
+        const format = await invoke<CaptureFormat>('get_capture_format');
+        const inference = await invoke<InferenceParams>('get_inference_params');
+        const raw = format.sampleRate !== 16000 || format.channels !== 1;
+
+        // Voice processing downmixes to mono, so it is off for multi-channel interfaces
+        const processing = format.channels === 1;
         const constraints: MediaStreamConstraints = {
-            audio: this.selectedDeviceId
-                ? {
-                    deviceId: { exact: this.selectedDeviceId },
-                    channelCount: 1,
-                    sampleRate: 16000,
-                    echoCancellation: true,
-                    noiseSuppression: true,
-                    autoGainControl: true,
-                }
-                : {
-                    channelCount: 1,
-                    sampleRate: 16000,
-                    echoCancellation: true,
-                    noiseSuppression: true,
-                    autoGainControl: true,
-                }
+            audio: {
+                ...(this.selectedDeviceId ? { deviceId: { exact: this.selectedDeviceId } } : {}),
+                channelCount: format.channels,
+                sampleRate: format.sampleRate,
+                echoCancellation: processing,
+                noiseSuppression: processing,
+                autoGainControl: processing,
+            }
         };
 
         this.mediaStream = await navigator.mediaDevices.getUserMedia(constraints);
 
         this.log('Audio access granted', 'success');
 
-        this.audioContext = new AudioContext({ sampleRate: 16000 });
+        this.audioContext = new AudioContext({ sampleRate: format.sampleRate });
 
         await this.audioContext.audioWorklet.addModule('/audio-processor.js');
 
         const source = this.audioContext.createMediaStreamSource(this.mediaStream);
 
-        this.audioWorkletNode = new AudioWorkletNode(this.audioContext, 'pcm-processor');
+        this.audioWorkletNode = new AudioWorkletNode(this.audioContext, 'pcm-processor', {
+            channelCount: format.channels,
+            channelCountMode: 'explicit',
+            channelInterpretation: 'discrete',
+            processorOptions: { raw, channels: format.channels, chunkSamples: inference.chunkMs * 16 },
+        });
 
-        this.audioWorkletNode.port.onmessage = (event: MessageEvent<ArrayBuffer>) => {
-            if (this.ws && this.ws.readyState === WebSocket.OPEN) {
-                const pcmData = event.data;
-                this.ws.send(pcmData);
-            }
-        };
+        if (raw) {
+            const headers = {
+                'sample-rate': String(this.audioContext.sampleRate),
+                channels: String(format.channels),
+            };
+            this.audioWorkletNode.port.onmessage = (event: MessageEvent<ArrayBuffer>) => {
+                invoke<ArrayBuffer>('convert_audio', event.data, { headers })
+                    .then((pcmData) => {
+                        if (pcmData.byteLength > 0) {
+                            this.handleAudioChunk(pcmData);
+                        }
+                    })
+                    .catch((error) => console.error('[Capture] Failed to convert audio:', error));
+            };
+            this.log(`Capturing ${format.channels} channel(s) at ${this.audioContext.sampleRate} Hz`, 'info');
+        } else {
+            this.audioWorkletNode.port.onmessage = (event: MessageEvent<ArrayBuffer>) => {
+                this.handleAudioChunk(event.data);
+            };
+        }
 
         source.connect(this.audioWorkletNode);
         this.audioWorkletNode.connect(this.audioContext.destination);
 
+        await this.applyMonitor(await invoke<MonitorSettings>('get_monitor'));
+
         this.log('Audio pipeline initialized', 'success');
     }
+
+    // ========================================
+    // Camera
+    // ========================================
+
+    // Sends downscaled RGBA frames to the backend for hand landmark inference
+    private async startCamera(camera: CameraSettings): Promise<void> {
+        await this.stopCamera();
+
+        this.cameraStream = await navigator.mediaDevices.getUserMedia({
+            video: camera.deviceId ? { deviceId: { exact: camera.deviceId } } : true,
+        });
+
+        const video = document.createElement('video');
+        video.muted = true;
+        video.playsInline = true;
+        video.srcObject = this.cameraStream;
+        await video.play();
+
+        const canvas = new OffscreenCanvas(CAMERA_FRAME_SIZE, CAMERA_FRAME_SIZE);
+        const context = canvas.getContext('2d', { willReadFrequently: true })!;
+        const headers = { width: String(CAMERA_FRAME_SIZE), height: String(CAMERA_FRAME_SIZE) };
+
+        this.cameraTimer = window.setInterval(() => {
+            // Center square crop, so hands keep their proportions
+            const side = Math.min(video.videoWidth, video.videoHeight);
+            if (side === 0) {
+                return;
+            }
+            context.drawImage(
+                video,
+                (video.videoWidth - side) / 2,
+                (video.videoHeight - side) / 2,
+                side,
+                side,
+                0,
+                0,
+                CAMERA_FRAME_SIZE,
+                CAMERA_FRAME_SIZE,
+            );
+            const frame = context.getImageData(0, 0, CAMERA_FRAME_SIZE, CAMERA_FRAME_SIZE);
+            invoke('push_video_frame', frame.data.buffer, { headers })
+                .catch((error) => console.error('[Camera] Failed to push frame:', error));
+        }, 1000 / Math.max(camera.fps, 1));
+
+        await invoke('report_camera_state', { active: true });
+        this.log('Camera started', 'success');
+    }
+
+    private async stopCamera(): Promise<void> {
+        if (!this.cameraStream) {
+            return;
+        }
+        if (this.cameraTimer !== null) {
+            window.clearInterval(this.cameraTimer);
+            this.cameraTimer = null;
+        }
+        this.cameraStream.getTracks().forEach((track: MediaStreamTrack) => track.stop());
+        this.cameraStream = null;
+
+        await invoke('report_camera_state', { active: false });
+        this.log('Camera stopped', 'info');
+    }
+
+    // ========================================
+    // Monitor Output
+    // ========================================
+
+    // Plays the processed input on the chosen output so users hear what the worker hears
+    private async applyMonitor(monitor: MonitorSettings): Promise<void> {
+        if (!monitor.enabled || !this.audioContext || !this.mediaStream) {
+            this.stopMonitor();
+            return;
+        }
+
+        if (!this.monitorGain || !this.monitorAudio) {
+            const destination = this.audioContext.createMediaStreamDestination();
+            this.monitorGain = this.audioContext.createGain();
+            this.audioContext.createMediaStreamSource(this.mediaStream).connect(this.monitorGain);
+            this.monitorGain.connect(destination);
+
+            this.monitorAudio = new Audio() as SinkAudioElement;
+            this.monitorAudio.srcObject = destination.stream;
+        }
+
+        this.monitorGain.gain.value = monitor.gain;
+
+        if (this.monitorAudio.setSinkId) {
+            await this.monitorAudio.setSinkId(monitor.outputDevice ?? '')
+                .catch((error) => this.log(`Monitor output unavailable: ${error}`, 'error'));
+        } else if (monitor.outputDevice) {
+            this.log('Choosing a monitor output is not supported here; using the default output', 'info');
+        }
+
+        await this.monitorAudio.play();
+        this.log(`Monitoring input at ${Math.round(monitor.gain * 100)}% gain`, 'info');
+    }
+
+    private stopMonitor(): void {
+        if (this.monitorAudio) {
+            this.monitorAudio.pause();
+            this.monitorAudio.srcObject = null;
+            this.monitorAudio = null;
+        }
+        if (this.monitorGain) {
+            this.monitorGain.disconnect();
+            this.monitorGain = null;
+        }
+    }
+
+    // ========================================
+    // Extra Capture Sources
+    // ========================================
+
+    // Starts pipelines for new sources and stops the ones that were removed
+    private async syncSources(sources: CaptureSource[]): Promise<void> {
+        const labels = new Set(sources.map((source) => source.label));
+
+        for (const label of [...this.sourcePipelines.keys()]) {
+            if (!labels.has(label)) {
+                await this.stopSource(label);
+            }
+        }
+
+        for (const source of sources) {
+            if (this.sourcePipelines.has(source.label)) {
+                continue;
+            }
+            try {
+                await this.startSource(source);
+                this.log(`Source "${source.label}" started`, 'success');
+            } catch (error: unknown) {
+                const message = error instanceof Error ? error.message : String(error);
+                this.log(`Failed to start source "${source.label}": ${message}`, 'error');
+            }
+        }
+    }
+
+    private async startSource(source: CaptureSource): Promise<void> {
+        const ws = new WebSocket(await this.workerSocketUrl());
+        await new Promise<void>((resolve, reject) => {
+            ws.onopen = () => resolve();
+            ws.onerror = () => reject(new Error('WebSocket connection failed'));
+        });
+        ws.onerror = () => this.reportError('websocket');
+        ws.onmessage = (event: MessageEvent) => {
+            try {
+                const message: ServerMessage = JSON.parse(event.data);
+                if (message.type === 'transcript') {
+                    this.handleTranscript(message, source.label);
+                }
+            } catch (error) {
+                console.error(`[Sources] Bad message from "${source.label}":`, error);
+            }
+        };
+
+        const mediaStream = await navigator.mediaDevices.getUserMedia({
+            audio: {
+                deviceId: { exact: source.deviceId },
+                channelCount: 1,
+                sampleRate: 16000,
+                echoCancellation: true,
+                noiseSuppression: true,
+                autoGainControl: true,
+            },
+        });
+
+        const audioContext = new AudioContext({ sampleRate: 16000 });
+        await audioContext.audioWorklet.addModule('/audio-processor.js');
+        const workletNode = new AudioWorkletNode(audioContext, 'pcm-processor');
+
+        // Only the main input is recorded to disk; extra sources are transcribed only
+        workletNode.port.onmessage = (event: MessageEvent<ArrayBuffer>) => {
+            if (!this.isPaused && ws.readyState === WebSocket.OPEN) {
+                ws.send(event.data);
+                this.bytesSent += event.data.byteLength;
+            }
+        };
+
+        audioContext.createMediaStreamSource(mediaStream).connect(workletNode);
+        workletNode.connect(audioContext.destination);
+
+        this.sourcePipelines.set(source.label, { ws, mediaStream, audioContext, workletNode });
+    }
+
+    private async stopSource(label: string): Promise<void> {
+        const pipeline = this.sourcePipelines.get(label);
+        if (!pipeline) {
+            return;
+        }
+        this.sourcePipelines.delete(label);
+
+        pipeline.workletNode.disconnect();
+        pipeline.mediaStream.getTracks().forEach((track: MediaStreamTrack) => track.stop());
+        await pipeline.audioContext.close();
+        pipeline.ws.close();
+        this.log(`Source "${label}" stopped`, 'info');
+    }
+
+    private handleAudioChunk(pcmData: ArrayBuffer): void {
+        // The backend restarts capture if these stop while it is active
+        const now = Date.now();
+        if (now - this.lastHeartbeatAt >= HEARTBEAT_INTERVAL_MS) {
+            this.lastHeartbeatAt = now;
+            invoke('pipeline_heartbeat', { task: 'capture' })
+                .catch((error) => console.error('[Watchdog] Failed to send heartbeat:', error));
+        }
+        if (this.isPaused) {
+            return;
+        }
+        if (this.isSavingAudio || this.isRelayingAudio) {
+            invoke('push_audio', pcmData)
+                .catch((error) => console.error('[Recording] Failed to save audio:', error));
+        }
+        if (this.ws && this.ws.readyState === WebSocket.OPEN) {
+            this.ws.send(pcmData);
+            this.bytesSent += pcmData.byteLength;
+        } else {
+            this.droppedFrames++;
+        }
+    }
 }
 
 // Initialize when DOM is ready
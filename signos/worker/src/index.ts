@@ -10,9 +10,46 @@
  * 5. Worker sends sign sequence to client
  */
 
-import type { Env, ClientMessage, SignMatch, ImagePath, ErrorMessage, StatsMessage } from './types';
+import type {
+	Env,
+	ClientMessage,
+	SignMatch,
+	SignInfo,
+	SignsMessage,
+	SignsRequest,
+	ImagePath,
+	ErrorMessage,
+	StatsMessage,
+	TranscribeResponse,
+	Nova3Response,
+	SummarizeRequest,
+	SummarizeResponse,
+	SummaryStyle,
+	CompleteRequest,
+} from './types';
 import { SignMatcher } from './services/sign-matcher';
 
+/**
+ * Format matches for the client: parse image JSON and structure properly
+ */
+function toSignInfos(signs: SignMatch[]): SignInfo[] {
+	return signs.map((sign) => {
+		let images: ImagePath[] = [];
+		try {
+			images = JSON.parse(sign.images);
+		} catch (e) {
+			console.error('[SignMatcher] Failed to parse images for', sign.glosa, ':', e);
+		}
+
+		return {
+			glosa: sign.glosa,
+			images: images,
+			definition: sign.definition,
+			confidence: sign.score,
+		};
+	});
+}
+
 /**
  * Translate transcript to signs and send to client
  */
@@ -36,28 +73,11 @@ async function translateAndSendSigns(
 			return;
 		}
 
-		// Format for client: parse image JSON and structure properly
-		const signInfos = signs.map((sign) => {
-			let images: ImagePath[] = [];
-			try {
-				images = JSON.parse(sign.images);
-			} catch (e) {
-				console.error('[SignMatcher] Failed to parse images for', sign.glosa, ':', e);
-			}
-
-			return {
-				glosa: sign.glosa,
-				images: images,
-				definition: sign.definition,
-				confidence: sign.score,
-			};
-		});
-
 		// Send to client
 		const signsMessage: ClientMessage = {
 			type: 'signs',
 			text: text,
-			signs: signInfos,
+			signs: toSignInfos(signs),
 			timestamp: Date.now(),
 		};
 
@@ -71,6 +91,26 @@ async function translateAndSendSigns(
 	}
 }
 
+/**
+ * Speaker who said most of the words in a diarized result
+ */
+function dominantSpeaker(words?: Array<{ speaker?: number }>): number | undefined {
+	const counts = new Map<number, number>();
+	for (const word of words ?? []) {
+		if (word.speaker !== undefined) {
+			counts.set(word.speaker, (counts.get(word.speaker) ?? 0) + 1);
+		}
+	}
+
+	let best: number | undefined;
+	for (const [speaker, count] of counts) {
+		if (best === undefined || count > counts.get(best)!) {
+			best = speaker;
+		}
+	}
+	return best;
+}
+
 /**
  * Mock STT for testing when AI is unavailable
  */
@@ -85,6 +125,50 @@ function mockTranscribe(): string {
 	return mockPhrases[Math.floor(Math.random() * mockPhrases.length)];
 }
 
+/**
+ * Streaming models the client may pick with `?model=`. Flux is English-only
+ * and reports turns instead of speech_final.
+ */
+const STREAMING_MODELS: Record<string, string> = {
+	'nova-3': '@cf/deepgram/nova-3',
+	flux: '@cf/deepgram/flux',
+};
+const DEFAULT_MODEL = 'nova-3';
+
+/**
+ * Client tuning knobs (`?context_seconds=&beam_size=`); zero/one keep the model defaults
+ */
+interface InferenceOptions {
+	contextSeconds: number;
+	beamSize: number;
+}
+
+function inferenceOptions(url: URL): InferenceOptions {
+	const contextSeconds = Number(url.searchParams.get('context_seconds') ?? 0);
+	const beamSize = Number(url.searchParams.get('beam_size') ?? 1);
+	return {
+		contextSeconds: Number.isFinite(contextSeconds) ? Math.min(Math.max(contextSeconds, 0), 10) : 0,
+		beamSize: Number.isInteger(beamSize) ? Math.min(Math.max(beamSize, 1), 10) : 1,
+	};
+}
+
+/**
+ * Model parameters for the inference options: the context becomes the
+ * trailing silence before a segment is finalized, the beam the number of
+ * alternatives the best one is chosen from
+ */
+function inferenceParams(model: string, options: InferenceOptions): Record<string, string> {
+	const params: Record<string, string> = {};
+	const contextMs = String(Math.round(options.contextSeconds * 1000));
+	if (model === 'flux') {
+		if (options.contextSeconds > 0) params.eot_timeout_ms = contextMs;
+		return params;
+	}
+	if (options.contextSeconds > 0) params.endpointing = contextMs;
+	if (options.beamSize > 1) params.alternatives = String(options.beamSize);
+	return params;
+}
+
 /**
  * Handle WebSocket connection with nova-3 WebSocket streaming
  * Uses direct fetch() to Cloudflare AI API for true WebSocket streaming
@@ -92,11 +176,14 @@ function mockTranscribe(): string {
 async function handleWebSocket(
 	clientWs: WebSocket,
 	serverWs: WebSocket,
-	env: Env
+	env: Env,
+	language: string,
+	model: string,
+	options: InferenceOptions
 ): Promise<void> {
 	const useMock = env.MOCK_MODE === 'true';
 
-	console.log(`New WebSocket connection (mock_mode: ${useMock})`);
+	console.log(`New WebSocket connection (model: ${model}, mock_mode: ${useMock})`);
 
 	serverWs.accept();
 
@@ -115,16 +202,22 @@ async function handleWebSocket(
 		// NOTE: Nova-3 WebSocket only supports language=multi or language=en
 		// Use 'multi' for Spanish - it enables multilingual code-switching
 		const params = new URLSearchParams({
-			encoding: 'linear16',
-			sample_rate: '16000',
-			language: 'multi', // Multilingual mode (supports Spanish + 9 other languages)
-			smart_format: 'true',
-			punctuate: 'true',
-			interim_results: 'true', // Enable real-time partial results (reduces latency)
-			vad_events: 'true', // Voice Activity Detection events
+			...(model === 'flux'
+				? { encoding: 'linear16', sample_rate: '16000' }
+				: {
+						encoding: 'linear16',
+						sample_rate: '16000',
+						language, // 'multi' enables Spanish + 9 other languages
+						smart_format: 'true',
+						punctuate: 'true',
+						interim_results: 'true', // Enable real-time partial results (reduces latency)
+						vad_events: 'true', // Voice Activity Detection events
+						diarize: 'true', // Per-word speaker ids, used for talk-time stats
+					}),
+			...inferenceParams(model, options),
 		});
 
-		const url = `https://api.cloudflare.com/client/v4/accounts/${env.CF_ACCOUNT}/ai/run/@cf/deepgram/nova-3?${params.toString()}`;
+		const url = `https://api.cloudflare.com/client/v4/accounts/${env.CF_ACCOUNT}/ai/run/${STREAMING_MODELS[model]}?${params.toString()}`;
 
 		console.log('[SETUP] Connecting to nova-3 WebSocket...');
 		console.log('[SETUP] URL:', url);
@@ -199,8 +292,14 @@ async function handleWebSocket(
 
 				// Extract transcript from nova-3 WebSocket response
 				// Actual response format: { type: "Results", channel: { alternatives: [{ transcript }] }, speech_final: bool }
-				const transcript = novaMessage?.channel?.alternatives?.[0]?.transcript || '';
-				const speechFinal = novaMessage?.speech_final || false; // True when speech segment ends
+				// Flux instead sends { type: "TurnInfo", event: "Update" | "EndOfTurn", transcript, words }
+				const alternatives = (novaMessage as Nova3Response)?.channel?.alternatives ?? [];
+				const alternative = alternatives.reduce<(typeof alternatives)[number] | undefined>(
+					(best, candidate) => (!best || (candidate.confidence ?? 0) > (best.confidence ?? 0) ? candidate : best),
+					undefined
+				);
+				const transcript = alternative?.transcript ?? novaMessage?.transcript ?? '';
+				const speechFinal = novaMessage?.speech_final || novaMessage?.event === 'EndOfTurn'; // True when speech segment ends
 				const isFinal = speechFinal; // Use speech_final for is_final flag
 
 				console.log(`[NOVA→WORKER] Extracted transcript: "${transcript}" (speech_final: ${speechFinal})`);
@@ -212,6 +311,9 @@ async function handleWebSocket(
 					is_final: isFinal,
 					timestamp: Date.now(),
 					latency_ms: latency,
+					language: alternative?.languages?.[0],
+					speaker: dominantSpeaker(alternative?.words),
+					model,
 				};
 
 				console.log(`[WORKER→CLIENT] Sending transcript:`, JSON.stringify(message));
@@ -337,6 +439,171 @@ function handleMockMode(clientWs: WebSocket, serverWs: WebSocket, signMatcher: S
 	});
 }
 
+/**
+ * Batch transcription of a recorded WAV file with a larger model.
+ * Used by the app to re-transcribe stored sessions.
+ */
+async function handleTranscribe(request: Request, env: Env): Promise<Response> {
+	const audio = new Uint8Array(await request.arrayBuffer());
+	if (audio.byteLength === 0) {
+		return new Response('Expected WAV audio in the request body', { status: 400 });
+	}
+
+	// The model takes base64 audio
+	let binary = '';
+	for (let i = 0; i < audio.length; i += 0x8000) {
+		binary += String.fromCharCode(...audio.subarray(i, i + 0x8000));
+	}
+
+	const language = new URL(request.url).searchParams.get('language');
+
+	try {
+		const result = await env.AI.run('@cf/openai/whisper-large-v3-turbo', {
+			audio: btoa(binary),
+			...(language && language !== 'multi' ? { language } : {}),
+		});
+
+		const response: TranscribeResponse = {
+			text: result.text ?? '',
+			segments: (result.segments ?? []).map((segment) => ({
+				start: segment.start ?? 0,
+				end: segment.end ?? 0,
+				text: segment.text ?? '',
+			})),
+		};
+
+		return Response.json(response, {
+			headers: { 'Access-Control-Allow-Origin': '*' },
+		});
+	} catch (error) {
+		console.error('[Transcribe] Batch transcription failed:', error);
+		return new Response('Transcription failed', { status: 502 });
+	}
+}
+
+/**
+ * Translate arbitrary text (e.g. the clipboard) to signs outside of a session.
+ */
+async function handleSignsRequest(request: Request, env: Env): Promise<Response> {
+	let text = '';
+	try {
+		text = ((await request.json()) as SignsRequest).text?.trim() ?? '';
+	} catch {
+		// Fall through to the empty-text error
+	}
+	if (!text) {
+		return new Response('Expected JSON body with a "text" field', { status: 400 });
+	}
+
+	try {
+		const signMatcher = new SignMatcher(env, env.AI);
+		const response: SignsMessage = {
+			type: 'signs',
+			text,
+			signs: toSignInfos(await signMatcher.translateToSigns(text)),
+			timestamp: Date.now(),
+		};
+
+		return Response.json(response, {
+			headers: { 'Access-Control-Allow-Origin': '*' },
+		});
+	} catch (error) {
+		console.error('[SignMatcher] Text translation failed:', error);
+		return new Response('Translation failed', { status: 502 });
+	}
+}
+
+const SUMMARY_INSTRUCTIONS: Record<SummaryStyle, string> = {
+	brief: 'Summarize the meeting transcript in two or three sentences.',
+	bullets: 'Summarize the meeting transcript as a short list of bullet points starting with "- ".',
+	detailed:
+		'Write meeting notes for the transcript: the topics discussed, decisions made and open questions, in short paragraphs.',
+};
+
+/**
+ * Summarize a stored session's transcript. Used by the app's `summarize_session`.
+ */
+async function handleSummarize(request: Request, env: Env): Promise<Response> {
+	let body: SummarizeRequest | null = null;
+	try {
+		body = (await request.json()) as SummarizeRequest;
+	} catch {
+		// Fall through to the empty-text error
+	}
+	const text = body?.text?.trim() ?? '';
+	if (!text) {
+		return new Response('Expected JSON body with a "text" field', { status: 400 });
+	}
+
+	const style: SummaryStyle = body?.style && body.style in SUMMARY_INSTRUCTIONS ? body.style : 'brief';
+	const language = body?.language === 'en' ? 'English' : 'the language of the transcript';
+
+	try {
+		const result = await env.AI.run('@cf/meta/llama-3.1-8b-instruct', {
+			messages: [
+				{
+					role: 'system',
+					content: `You write concise meeting notes. ${SUMMARY_INSTRUCTIONS[style]} Answer in ${language} and do not add anything that is not in the transcript.`,
+				},
+				{ role: 'user', content: text },
+			],
+			max_tokens: style === 'detailed' ? 1024 : 400,
+			temperature: 0.3,
+		});
+
+		const response: SummarizeResponse = {
+			summary: (result as { response?: string }).response?.trim() ?? '',
+		};
+
+		return Response.json(response, {
+			headers: { 'Access-Control-Allow-Origin': '*' },
+		});
+	} catch (error) {
+		console.error('[Summarize] Summarization failed:', error);
+		return new Response('Summarization failed', { status: 502 });
+	}
+}
+
+/**
+ * Run a prompt and stream the answer back as server-sent events. Used by the
+ * app's worker LLM provider for summaries, rewrites and glossing.
+ */
+async function handleComplete(request: Request, env: Env): Promise<Response> {
+	let body: CompleteRequest | null = null;
+	try {
+		body = (await request.json()) as CompleteRequest;
+	} catch {
+		// Fall through to the empty-prompt error
+	}
+	const prompt = body?.prompt?.trim() ?? '';
+	if (!prompt) {
+		return new Response('Expected JSON body with a "prompt" field', { status: 400 });
+	}
+
+	try {
+		const stream = await env.AI.run('@cf/meta/llama-3.1-8b-instruct', {
+			messages: [
+				{ role: 'system', content: body?.system ?? '' },
+				{ role: 'user', content: prompt },
+			],
+			max_tokens: Math.min(Math.max(body?.max_tokens ?? 512, 1), 2048),
+			temperature: body?.temperature ?? 0.3,
+			stream: true,
+		});
+
+		// Workers AI already answers with `data: {"response": ...}` events and a final `data: [DONE]`
+		return new Response(stream as ReadableStream, {
+			headers: {
+				'Content-Type': 'text/event-stream',
+				'Access-Control-Allow-Origin': '*',
+			},
+		});
+	} catch (error) {
+		console.error('[Complete] Completion failed:', error);
+		return new Response('Completion failed', { status: 502 });
+	}
+}
+
 /**
  * Main fetch handler
  */
@@ -353,18 +620,46 @@ export default {
 			});
 		}
 
+		if (request.method === 'POST' && new URL(request.url).pathname === '/transcribe') {
+			return handleTranscribe(request, env);
+		}
+
+		if (request.method === 'POST' && new URL(request.url).pathname === '/signs') {
+			return handleSignsRequest(request, env);
+		}
+
+		if (request.method === 'POST' && new URL(request.url).pathname === '/summarize') {
+			return handleSummarize(request, env);
+		}
+
+		if (request.method === 'POST' && new URL(request.url).pathname === '/complete') {
+			return handleComplete(request, env);
+		}
+
+		// Reachability probe used by the app's connectivity watcher
+		if (request.method === 'GET' && new URL(request.url).pathname === '/health') {
+			return Response.json({ ok: true }, { headers: { 'Access-Control-Allow-Origin': '*' } });
+		}
+
 		// Check for WebSocket upgrade
 		const upgradeHeader = request.headers.get('Upgrade');
 		if (upgradeHeader !== 'websocket') {
 			return new Response('Expected WebSocket upgrade', { status: 426 });
 		}
 
+		// Nova-3 only supports 'multi' or 'en'; anything else falls back to 'multi'
+		const requested = new URL(request.url).searchParams.get('language');
+		const language = requested === 'en' ? 'en' : 'multi';
+
+		const requestedModel = new URL(request.url).searchParams.get('model') ?? DEFAULT_MODEL;
+		const model = Object.hasOwn(STREAMING_MODELS, requestedModel) ? requestedModel : DEFAULT_MODEL;
+
 		// Create WebSocket pair for client connection
 		const pair = new WebSocketPair();
 		const [client, server] = Object.values(pair);
 
 		// Handle WebSocket in background
-		ctx.waitUntil(handleWebSocket(client, server, env));
+		ctx.waitUntil(handleWebSocket(client, server, env, language, model, inferenceOptions(new URL(request.url))));
 
 		// Return client WebSocket to user
 		return new Response(null, {
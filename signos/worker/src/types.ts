@@ -26,6 +26,8 @@ export interface TranscriptMessage {
 	timestamp: number;
 	latency_ms?: number;
 	language?: string;
+	speaker?: number; // Diarized speaker id, when available
+	model?: string; // Streaming model that produced the result
 }
 
 export interface SignsMessage {
@@ -87,8 +89,60 @@ export interface Nova3Response {
 		alternatives?: Array<{
 			transcript: string;
 			confidence?: number;
+			languages?: string[];
+			words?: Array<{ word: string; speaker?: number }>;
 		}>;
 	};
 	speech_final?: boolean;
 	is_final?: boolean;
 }
+
+// ========================================
+// Batch Transcription (POST /transcribe)
+// ========================================
+
+export interface TranscribeSegment {
+	start: number; // Seconds from the start of the uploaded audio
+	end: number;
+	text: string;
+}
+
+export interface TranscribeResponse {
+	text: string;
+	segments: TranscribeSegment[];
+}
+
+// ========================================
+// Text Translation (POST /signs)
+// ========================================
+
+export interface SignsRequest {
+	text: string;
+}
+
+// ========================================
+// Session Summaries (POST /summarize)
+// ========================================
+
+export type SummaryStyle = 'brief' | 'bullets' | 'detailed';
+
+export interface SummarizeRequest {
+	text: string; // Transcript, one segment per line
+	style?: SummaryStyle;
+	language?: string;
+}
+
+export interface SummarizeResponse {
+	summary: string;
+}
+
+// ========================================
+// Text Completions (POST /complete)
+// ========================================
+
+export interface CompleteRequest {
+	system: string; // Instructions for the model
+	prompt: string; // The text to work on
+	max_tokens?: number;
+	temperature?: number;
+}